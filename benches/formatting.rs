@@ -0,0 +1,136 @@
+//! Benchmarks of the formatter on large generated shaders.
+//!
+//! The shaders are generated and formatted once, then each iteration runs the formatter
+//! (built binary) in the "only scan" mode on an already formatted shader.
+//! Use the `--bench` option of the formatter to see timings of each stage for your shaders.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Number of repeated code blocks (functions, structs, macros) in each generated shader.
+const BLOCK_COUNT: usize = 1500;
+
+/// Shader with a lot of functions with branches, loops and brace initializers.
+fn generate_functions_shader() -> String {
+    let mut code = String::new();
+    for i in 0..BLOCK_COUNT {
+        write!(
+            code,
+            "/// Calculates some value.\n\
+             float calculate{i}(float a, float b)\n\
+             {{\n\
+             float weights[3] = {{0.2, 0.6, 0.2}};\n\
+             float result = a * b + {i}.0; // some comment\n\
+             for (int index = 0; index < 3; index++)\n\
+             {{\n\
+             if (result > weights[index])\n\
+             {{\n\
+             result -= weights[index];\n\
+             }}\n\
+             }}\n\
+             return result;\n\
+             }}\n\n"
+        )
+        .unwrap();
+    }
+    code
+}
+
+/// Shader with a lot of documented structs and resources.
+fn generate_structs_shader() -> String {
+    let mut code = String::new();
+    for i in 0..BLOCK_COUNT {
+        write!(
+            code,
+            "/** Data of an object. */\n\
+             struct ObjectData{i}\n\
+             {{\n\
+             /** World matrix. */\n\
+             float4x4 world;\n\
+             /** Color of the object. */\n\
+             float3 color;\n\
+             }};\n\
+             StructuredBuffer<ObjectData{i}> objects{i} : register(t{i});\n\n"
+        )
+        .unwrap();
+    }
+    code
+}
+
+/// Shader with a lot of preprocessor directives and multi-line macros.
+fn generate_macros_shader() -> String {
+    let mut code = String::new();
+    for i in 0..BLOCK_COUNT {
+        write!(
+            code,
+            "#ifdef FEATURE_{i}\n\
+             #define APPLY_{i}(value) \\\n\
+             value *= {i}.0; \\\n\
+             value += 1.0\n\
+             #else\n\
+             #define APPLY_{i}(value)\n\
+             #endif\n\n"
+        )
+        .unwrap();
+    }
+    code
+}
+
+/// Runs the specified command of the formatter and makes sure it succeeded.
+fn run_formatter(command: &str, path: &Path) {
+    let output = Command::new(env!("CARGO_BIN_EXE_shader-formatter"))
+        .arg(command)
+        .arg(path)
+        .output()
+        .expect("failed to run the formatter");
+    assert!(
+        output.status.success(),
+        "failed to format \"{}\": {}",
+        path.display(),
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
+
+/// Writes and formats a shader in the temporary directory.
+///
+/// # Return
+/// Path to the written shader.
+fn prepare_shader(name: &str, code: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join("shader-formatter-bench");
+    std::fs::create_dir_all(&directory).unwrap();
+
+    // Allow large files.
+    std::fs::write(
+        directory.join("shader-formatter.toml"),
+        "MaxFileSize = 104857600\n",
+    )
+    .unwrap();
+
+    let path = directory.join(name);
+    std::fs::write(&path, code).unwrap();
+    run_formatter("format", &path);
+
+    path
+}
+
+fn formatting(c: &mut Criterion) {
+    let shaders = [
+        ("functions.hlsl", generate_functions_shader()),
+        ("structs.hlsl", generate_structs_shader()),
+        ("macros.hlsl", generate_macros_shader()),
+    ];
+
+    let mut group = c.benchmark_group("formatting");
+    group.sample_size(10);
+    for (name, code) in shaders {
+        let path = prepare_shader(name, &code);
+        group.bench_function(name, |b| b.iter(|| run_formatter("check", &path)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, formatting);
+criterion_main!(benches);
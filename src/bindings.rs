@@ -0,0 +1,305 @@
+use crate::parser::{Span, Token};
+
+/// Slot that a resource is bound to (like `t0` in `register(t0)` or `binding = 0`
+/// in `layout(binding = 0)`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BindingSlot {
+    /// HLSL register type (like `t` in `register(t0)`), `None` for GLSL bindings.
+    pub register_type: Option<char>,
+    pub index: i64,
+    /// HLSL register space or GLSL descriptor set.
+    pub space: i64,
+}
+
+impl std::fmt::Display for BindingSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.register_type {
+            Some(register_type) => write!(
+                f,
+                "register({}{}, space{})",
+                register_type, self.index, self.space
+            ),
+            None => write!(f, "set = {}, binding = {}", self.space, self.index),
+        }
+    }
+}
+
+/// Resource with an explicitly specified binding.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceBinding<'src> {
+    pub name: &'src str,
+    pub name_span: Span,
+    /// Span of the resource type (like `RWTexture2D<float4>`, `cbuffer` or `sampler2D`),
+    /// for GLSL blocks points to the storage qualifier (like `uniform` or `buffer`).
+    pub type_span: Span,
+    pub slot: BindingSlot,
+}
+
+/// GLSL `in`/`out` variable or block with an explicitly specified location.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterfaceLocation<'src> {
+    pub name: &'src str,
+    pub name_span: Span,
+    /// `in` or `out`.
+    pub direction: &'src str,
+    pub location: i64,
+}
+
+/// Collects resources that have explicit bindings: HLSL resources with `register(...)`
+/// and GLSL variables or blocks with `binding` layout qualifier.
+pub fn collect_bindings<'src>(tokens: &[(Token<'src>, Span)]) -> Vec<ResourceBinding<'src>> {
+    let mut bindings = Vec::new();
+
+    for (i, (token, _)) in tokens.iter().enumerate() {
+        if !matches!(tokens.get(i + 1), Some((Token::Ctrl('('), _))) {
+            continue;
+        }
+
+        let binding = match token {
+            Token::Ident("register") => get_register_binding(tokens, i),
+            Token::Ident("layout") => get_layout_binding(tokens, i),
+            _ => None,
+        };
+        bindings.extend(binding);
+    }
+
+    bindings
+}
+
+/// Looks for two resources that are bound to the same slot.
+///
+/// # Return
+/// The resource that was bound to the slot first and the resource that uses the same slot.
+pub fn find_duplicate_binding<'a, 'src>(
+    bindings: &'a [ResourceBinding<'src>],
+) -> Option<(&'a ResourceBinding<'src>, &'a ResourceBinding<'src>)> {
+    bindings.iter().enumerate().find_map(|(i, duplicate)| {
+        bindings[..i]
+            .iter()
+            .find(|binding| binding.slot == duplicate.slot)
+            .map(|binding| (binding, duplicate))
+    })
+}
+
+/// Collects GLSL `in`/`out` variables and blocks that have the `location` layout qualifier.
+pub fn collect_locations<'src>(tokens: &[(Token<'src>, Span)]) -> Vec<InterfaceLocation<'src>> {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(i, (token, _))| {
+            *token == Token::Ident("layout")
+                && matches!(tokens.get(i + 1), Some((Token::Ctrl('('), _)))
+        })
+        .filter_map(|(i, _)| get_layout_location(tokens, i))
+        .collect()
+}
+
+/// Looks for two variables of the same direction (`in` or `out`) that use the same location.
+///
+/// # Return
+/// The variable that was declared with the location first and the variable that uses the same location.
+pub fn find_duplicate_location<'a, 'src>(
+    locations: &'a [InterfaceLocation<'src>],
+) -> Option<(&'a InterfaceLocation<'src>, &'a InterfaceLocation<'src>)> {
+    locations.iter().enumerate().find_map(|(i, duplicate)| {
+        locations[..i]
+            .iter()
+            .find(|location| {
+                location.direction == duplicate.direction && location.location == duplicate.location
+            })
+            .map(|location| (location, duplicate))
+    })
+}
+
+/// Looks for a variable that is declared after a variable of the same direction (`in` or `out`)
+/// with a bigger location.
+///
+/// # Return
+/// The previous variable of the same direction and the variable that breaks the order.
+pub fn find_unordered_location<'a, 'src>(
+    locations: &'a [InterfaceLocation<'src>],
+) -> Option<(&'a InterfaceLocation<'src>, &'a InterfaceLocation<'src>)> {
+    locations.iter().enumerate().find_map(|(i, current)| {
+        locations[..i]
+            .iter()
+            .rev()
+            .find(|previous| previous.direction == current.direction)
+            .filter(|previous| previous.location > current.location)
+            .map(|previous| (previous, current))
+    })
+}
+
+/// Returns arguments of a keyword like `register(t0, space1)` (tokens inside of the parentheses).
+fn get_arguments<'a, 'src>(
+    tokens: &'a [(Token<'src>, Span)],
+    keyword_index: usize,
+) -> Option<&'a [(Token<'src>, Span)]> {
+    let start = keyword_index + 2;
+    let length = tokens
+        .get(start..)?
+        .iter()
+        .position(|(token, _)| *token == Token::Ctrl(')'))?;
+
+    Some(&tokens[start..start + length])
+}
+
+/// Returns span of a type that is written before a name (like `RWTexture2D<float4>`).
+fn get_type_span(tokens: &[(Token<'_>, Span)], name_index: usize) -> Option<Span> {
+    let type_end = tokens.get(name_index.checked_sub(1)?)?;
+    let type_start = if type_end.0 == Token::Ctrl('>') {
+        let template_start = tokens[..name_index - 1]
+            .iter()
+            .rposition(|(token, _)| *token == Token::Ctrl('<'))?;
+        tokens.get(template_start.checked_sub(1)?)?
+    } else {
+        type_end
+    };
+
+    match type_start.0 {
+        Token::Ident(_) | Token::TypeName(_) => {
+            Some(Span::from(type_start.1.start..type_end.1.end))
+        }
+        _ => None,
+    }
+}
+
+/// Parses an HLSL binding like `name : register(t0, space1)`.
+fn get_register_binding<'src>(
+    tokens: &[(Token<'src>, Span)],
+    register_index: usize,
+) -> Option<ResourceBinding<'src>> {
+    let mut register_type = None;
+    let mut index = None;
+    let mut space = 0;
+    for (token, _) in get_arguments(tokens, register_index)? {
+        let Token::Ident(argument) = token else {
+            continue;
+        };
+        let argument = argument.to_ascii_lowercase();
+
+        if let Some(value) = argument.strip_prefix("space") {
+            space = value.parse().ok()?;
+        } else {
+            let mut chars = argument.chars();
+            register_type = chars.next();
+            index = Some(chars.as_str().parse().ok()?);
+        }
+    }
+
+    // Find the name (like `textures` in `Texture2D textures[2] : register(t0);`).
+    let mut name_index = register_index.checked_sub(2)?;
+    if tokens.get(register_index - 1)?.0 != Token::Ctrl(':') {
+        return None;
+    }
+    if tokens[name_index].0 == Token::Ctrl(']') {
+        name_index = tokens[..name_index]
+            .iter()
+            .rposition(|(token, _)| *token == Token::Ctrl('['))?
+            .checked_sub(1)?;
+    }
+    let (Token::Ident(name), name_span) = tokens[name_index] else {
+        return None;
+    };
+
+    Some(ResourceBinding {
+        name,
+        name_span,
+        type_span: get_type_span(tokens, name_index)?,
+        slot: BindingSlot {
+            register_type: Some(register_type?),
+            index: index?,
+            space,
+        },
+    })
+}
+
+/// Returns the value of a layout qualifier like `binding = 0` from `layout(...)` arguments.
+fn get_layout_qualifier_value(arguments: &[(Token<'_>, Span)], qualifier: &str) -> Option<i64> {
+    arguments.windows(3).find_map(|window| match window {
+        [(Token::Ident(name), _), (Token::Op("="), _), (Token::Integer(value), _)]
+            if *name == qualifier =>
+        {
+            Some(*value)
+        }
+        _ => None,
+    })
+}
+
+/// Returns index of the name in a declaration that follows `layout(...)` (the last identifier
+/// before the body of a block or the end of a declaration).
+fn get_layout_declaration_name(
+    tokens: &[(Token<'_>, Span)],
+    declaration_start: usize,
+) -> Option<usize> {
+    let declaration_length = tokens
+        .get(declaration_start..)?
+        .iter()
+        .position(|(token, _)| {
+            matches!(
+                token,
+                Token::Ctrl('{') | Token::Ctrl(';') | Token::Ctrl('[')
+            )
+        })?;
+
+    tokens[declaration_start..declaration_start + declaration_length]
+        .iter()
+        .rposition(|(token, _)| matches!(token, Token::Ident(_)))
+        .map(|index| index + declaration_start)
+}
+
+/// Parses a GLSL binding like `layout(set = 1, binding = 0) uniform Name`.
+fn get_layout_binding<'src>(
+    tokens: &[(Token<'src>, Span)],
+    layout_index: usize,
+) -> Option<ResourceBinding<'src>> {
+    let arguments = get_arguments(tokens, layout_index)?;
+    let index = get_layout_qualifier_value(arguments, "binding")?;
+    let space = get_layout_qualifier_value(arguments, "set").unwrap_or_default();
+
+    let name_index = get_layout_declaration_name(tokens, layout_index + arguments.len() + 3)?;
+    let (Token::Ident(name), name_span) = tokens[name_index] else {
+        return None;
+    };
+
+    Some(ResourceBinding {
+        name,
+        name_span,
+        type_span: get_type_span(tokens, name_index)?,
+        slot: BindingSlot {
+            register_type: None,
+            index,
+            space,
+        },
+    })
+}
+
+/// Parses a GLSL location like `layout(location = 0) in vec2 name`.
+fn get_layout_location<'src>(
+    tokens: &[(Token<'src>, Span)],
+    layout_index: usize,
+) -> Option<InterfaceLocation<'src>> {
+    let arguments = get_arguments(tokens, layout_index)?;
+    let location = get_layout_qualifier_value(arguments, "location")?;
+
+    // Look for the storage qualifier between the layout and the name
+    // (like `in` in `layout(location = 0) flat in vec2 name`).
+    let declaration_start = layout_index + arguments.len() + 3;
+    let name_index = get_layout_declaration_name(tokens, declaration_start)?;
+    let direction =
+        tokens[declaration_start..name_index]
+            .iter()
+            .find_map(|(token, _)| match token {
+                Token::Ident(qualifier @ ("in" | "out")) => Some(*qualifier),
+                _ => None,
+            })?;
+    let (Token::Ident(name), name_span) = tokens[name_index] else {
+        return None;
+    };
+
+    Some(InterfaceLocation {
+        name,
+        name_span,
+        direction,
+        location,
+    })
+}
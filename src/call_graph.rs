@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+
+use chumsky::{input::Input, Parser};
+
+use crate::{
+    error::FormatError,
+    formatter::Formatter,
+    parser::{self, ComplexToken, Span, Token},
+};
+
+/// Name of the GLSL entry point function.
+const GLSL_ENTRY_POINT_NAME: &str = "main";
+
+/// Information about a function defined in some file.
+struct FunctionDefinition {
+    name: String,
+    name_span: Span,
+    /// Index of the file (in the specified files) that defines the function.
+    file_index: usize,
+}
+
+/// Builds a call graph from all specified files and looks for functions that are never
+/// referenced in any of the files (shader entry points are ignored).
+///
+/// # Arguments
+/// - `files` Paths and contents of the files.
+///
+/// # Return
+/// Path to the file with an unused function and an error that describes the function.
+pub fn find_unused_functions(files: &[(PathBuf, String)]) -> Vec<(PathBuf, FormatError)> {
+    let mut definitions = Vec::new();
+    let mut referenced_names = Vec::new();
+
+    for (file_index, (_, content)) in files.iter().enumerate() {
+        let Some(tokens) = parser::token_parser().parse(content.as_str()).into_output() else {
+            continue;
+        };
+        let tokens = parser::split_template_closing_shifts(tokens);
+        let Some(complex_tokens) =
+            parser::complex_token_parser(parser::collect_type_names(&tokens))
+                .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+                .into_output()
+        else {
+            continue;
+        };
+
+        // Collect defined functions.
+        let mut definition_spans = Vec::new();
+        for (token, _) in &complex_tokens {
+            let ComplexToken::Function(info) = token else {
+                continue;
+            };
+            definition_spans.push(info.name_span);
+
+            if info.is_declaration
+                || info.name == GLSL_ENTRY_POINT_NAME
+                || Formatter::is_entry_point(info)
+            {
+                continue;
+            }
+            definitions.push(FunctionDefinition {
+                name: info.name.to_owned(),
+                name_span: info.name_span,
+                file_index,
+            });
+        }
+
+        // Collect names of called functions.
+        for (i, (token, span)) in tokens.iter().enumerate() {
+            match token {
+                Token::Ident(name)
+                    if matches!(tokens.get(i + 1), Some((Token::Ctrl('('), _)))
+                        && !definition_spans.contains(span) =>
+                {
+                    referenced_names.push(*name);
+                }
+                Token::Preprocessor(text) => {
+                    // Functions can also be called from macros.
+                    referenced_names.extend(
+                        text.split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                            .filter(|word| !word.is_empty()),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    definitions
+        .into_iter()
+        .filter(|definition| !referenced_names.contains(&definition.name.as_str()))
+        .map(|definition| {
+            let (path, content) = &files[definition.file_index];
+            (
+                path.clone(),
+                FormatError::rule_violation(
+                    "ReportUnusedFunctions",
+                    &definition.name,
+                    None,
+                    format!("function \"{}\" is never used", definition.name),
+                    definition.name_span,
+                )
+                .locate(content),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,218 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::rules::{IndentationRule, LineEnding};
+
+/// Formats shader files and checks them for rule violations.
+#[derive(Parser)]
+#[command(name = "shader-formatter", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Format the specified file or all files in the specified directory.
+    Format(FormatArgs),
+    /// Only check if formatting is needed (files are not changed), exits with a failure
+    /// if some file needs formatting.
+    Check(CheckArgs),
+    /// Create a config file with all rules in the current directory.
+    Init {
+        /// Name of the preset to take rule values from.
+        preset: Option<String>,
+    },
+    /// Print description, allowed values, default value and an example of a config rule.
+    Explain {
+        /// Name of the rule (as written in the config file).
+        rule: Option<String>,
+    },
+    /// Print JSON schema of the config file.
+    DumpConfigSchema,
+    /// Print resources with explicit bindings (registers), files are not formatted.
+    ReportBindings {
+        #[command(flatten)]
+        files: FileArgs,
+        /// Print a JSON array.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print parsed tokens with their locations as a JSON array, files are not formatted.
+    PrintTokens {
+        #[command(flatten)]
+        files: FileArgs,
+    },
+    /// Print parsed structs, enums, functions and other statements with their locations
+    /// as a JSON array, files are not formatted.
+    PrintAst {
+        #[command(flatten)]
+        files: FileArgs,
+    },
+    /// Print how much time tokenizing, parsing and formatting of each file took, files
+    /// are not changed.
+    Bench {
+        #[command(flatten)]
+        files: FileArgs,
+    },
+    /// Format staged shader files and stage the result (used as a git pre-commit hook).
+    Hook {
+        /// How to print errors and warnings.
+        #[arg(long, value_enum, default_value_t)]
+        output_format: OutputFormat,
+    },
+    /// Create a git pre-commit hook that runs the "hook" command.
+    InstallHook,
+    /// Keep running and handle JSON-RPC format requests from stdin (one per line).
+    Daemon,
+}
+
+/// Defines which files to process.
+#[derive(Args)]
+pub struct FileArgs {
+    /// Path to a file or a directory.
+    pub path: PathBuf,
+    /// Visit symbolic links when looking for files in a directory.
+    #[arg(long)]
+    pub follow_symlinks: bool,
+    /// Visit hidden directories (like ".git") when looking for files in a directory.
+    #[arg(long)]
+    pub hidden: bool,
+}
+
+#[derive(Args)]
+pub struct FormatArgs {
+    #[command(flatten)]
+    pub files: FileArgs,
+    /// Print the formatted code of the specified file instead of changing the file.
+    #[arg(long, conflicts_with = "emit_patch")]
+    pub stdout: bool,
+    /// Don't change files, print a unified diff of all changes (can be applied using "git apply").
+    #[arg(long)]
+    pub emit_patch: bool,
+    /// Print parsed tokens (used for debugging).
+    #[arg(long)]
+    pub print_tokens: bool,
+    /// Before formatting rename global symbols (functions, structs, global variables) to names
+    /// suggested by naming rules and update references in all processed and included files.
+    #[arg(long, conflicts_with_all = ["stdout", "emit_patch", "format_only"])]
+    pub fix_names: bool,
+    /// Only print symbols that "--fix-names" would rename and files that would be changed.
+    #[arg(long, requires = "fix_names")]
+    pub dry_run: bool,
+    /// Don't format files, write names that violate naming rules (with suggested names and
+    /// locations of all references) to the specified JSON file.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["stdout", "emit_patch", "fix_names", "format_only"])]
+    pub export_renames: Option<PathBuf>,
+    #[command(flatten)]
+    pub rule_set: RuleSetArgs,
+    #[command(flatten)]
+    pub editor_settings: EditorSettingsArgs,
+    #[command(flatten)]
+    pub report: ReportArgs,
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    #[command(flatten)]
+    pub files: FileArgs,
+    #[command(flatten)]
+    pub rule_set: RuleSetArgs,
+    #[command(flatten)]
+    pub editor_settings: EditorSettingsArgs,
+    #[command(flatten)]
+    pub report: ReportArgs,
+}
+
+/// Defines which rules are applied.
+#[derive(Args)]
+pub struct RuleSetArgs {
+    /// Only check lint rules (like naming and documentation rules), don't change the code.
+    #[arg(long, conflicts_with = "format_only")]
+    pub lint_only: bool,
+    /// Only apply formatting rules, don't check lint rules (like naming and documentation rules).
+    #[arg(long)]
+    pub format_only: bool,
+}
+
+/// Defines how results of processing files are reported.
+#[derive(Args)]
+pub struct ReportArgs {
+    /// How to print errors and warnings.
+    #[arg(long, value_enum, default_value_t)]
+    pub output_format: OutputFormat,
+    /// How to show progress of processing files (printed to stderr).
+    #[arg(long, value_enum, default_value_t)]
+    pub progress: ProgressMode,
+    /// Measure how much time tokenizing, parsing and formatting of each file took and print
+    /// the N slowest files at the end (to stderr, 10 files if N is not specified).
+    #[arg(
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "10"
+    )]
+    pub profile: Option<usize>,
+}
+
+/// Rules that take precedence over the config file (used by editors to pass their settings).
+#[derive(Args)]
+pub struct EditorSettingsArgs {
+    /// Characters that are used to indent lines of code ("Tab", "TwoSpaces", "FourSpaces" or "Auto").
+    #[arg(long, value_parser = parse_indentation)]
+    pub indent: Option<IndentationRule>,
+    /// Characters that are used to end lines ("Lf" or "CrLf").
+    #[arg(long, value_parser = parse_line_ending)]
+    pub line_ending: Option<LineEnding>,
+    /// Whether a file must end with a line break.
+    #[arg(long)]
+    pub final_newline: Option<bool>,
+}
+
+/// Parses value of the `--indent` option (uses the same names as the config file).
+fn parse_indentation(value: &str) -> Result<IndentationRule, String> {
+    match value {
+        "Tab" => Ok(IndentationRule::Tab),
+        "TwoSpaces" => Ok(IndentationRule::TwoSpaces),
+        "FourSpaces" => Ok(IndentationRule::FourSpaces),
+        "Auto" => Ok(IndentationRule::Auto),
+        _ => Err("expected \"Tab\", \"TwoSpaces\", \"FourSpaces\" or \"Auto\"".to_owned()),
+    }
+}
+
+/// Parses value of the `--line-ending` option (uses the same names as the config file).
+fn parse_line_ending(value: &str) -> Result<LineEnding, String> {
+    match value {
+        "Lf" => Ok(LineEnding::Lf),
+        "CrLf" => Ok(LineEnding::CrLf),
+        _ => Err("expected \"Lf\" or \"CrLf\"".to_owned()),
+    }
+}
+
+/// Defines how errors and warnings are printed.
+#[derive(Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable messages.
+    #[default]
+    Human,
+    /// `file:line:col: rule: message` (understood by Vim's quickfix, Emacs compilation-mode
+    /// and many CI log parsers).
+    Compact,
+}
+
+/// Defines how progress of processing multiple files is shown.
+#[derive(Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum ProgressMode {
+    /// A progress bar if stdout and stderr are terminals and multiple files are processed,
+    /// nothing otherwise.
+    #[default]
+    Auto,
+    /// A progress bar with the number of processed files and the current file.
+    Bar,
+    /// A line with the number of processed files and the current file per file.
+    Plain,
+    /// Progress is not shown.
+    None,
+}
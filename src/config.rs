@@ -1,67 +1,700 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use regex::Regex;
+use serde_json::json;
 use toml::Value;
 
-use crate::rules::*;
+use crate::{error::FormatError, rules::*};
 
 /// Name of the file that stores formatting rules.
 const CONFIG_FILE_NAME: &str = "shader-formatter.toml";
 
+/// Names of files that store formatting rules in other formats (used if the TOML file does not exist).
+const OTHER_CONFIG_FILE_NAMES: [&str; 2] = ["shader-formatter.json", "shader-formatter.yaml"];
+
+/// Name of the rule that selects a set of rules that other rules are applied on top of.
+const PRESET_KEY: &str = "Preset";
+
+/// Name of the config section with values of preprocessor macros.
+const DEFINES_SECTION_NAME: &str = "Defines";
+
+/// Name of the config section that specifies severities of lint rules.
+const SEVERITY_SECTION_NAME: &str = "Severity";
+
 /// Represents a config file with formatting rules, deserialized from the disk.
 #[derive(Clone)]
 pub struct Config {
     pub new_line_around_braces: NewLineOnOpenBrace,
+    pub empty_body_style: EmptyBodyStyle,
     pub indentation: IndentationRule,
     pub max_empty_lines: usize,
+    pub max_line_length: Option<usize>,
+    pub max_function_lines: Option<usize>,
+    pub max_nesting_depth: Option<usize>,
+    /// Maximum size (in bytes) of push constant blocks.
+    pub max_push_constant_size: Option<usize>,
+    /// Maximum depth of subdirectories to look for files in (when formatting a directory).
+    pub max_depth: Option<usize>,
+    /// If specified, files larger than this size (in bytes) are skipped.
+    pub max_file_size: Option<usize>,
+    /// Files that take longer than this time (in milliseconds) to format are skipped.
+    pub max_format_time: Option<usize>,
+    /// If specified, files larger than `max_file_size` are formatted in chunks of about this size
+    /// (in bytes) instead of being skipped.
+    pub streaming_chunk_size: Option<usize>,
+    pub comment_banner_char: Option<char>,
+    pub comment_banner_width: Option<usize>,
+    pub align_initializer_columns: bool,
+    pub argument_wrap: Option<ArgumentWrap>,
+    pub bin_pack_parameters: bool,
+    pub break_before_binary_operators: Option<bool>,
+    pub statement_attribute_placement: Option<StatementAttributePlacement>,
+    pub require_braces_on_if: Option<RequireBraces>,
+    pub require_braces_on_else: Option<RequireBraces>,
+    pub require_braces_on_for: Option<RequireBraces>,
+    pub require_braces_on_while: Option<RequireBraces>,
+    pub trailing_comma_in_initializers: Option<TrailingComma>,
+    pub line_ending: Option<LineEnding>,
+    pub insert_final_newline: Option<bool>,
+    pub space_before_constructor_parenthesis: Option<bool>,
+    pub space_after_cast: Option<bool>,
     pub spaces_in_brackets: bool,
+    pub spaces_in_angle_brackets: bool,
+    pub space_before_inheritance_colon: bool,
+    pub keep_user_line_breaks: bool,
+    pub collapse_whitespace: bool,
+    pub remove_space_after_unary_operators: bool,
+    pub spaces_around_ternary_operator: bool,
+    pub remove_spaces_around_member_access: bool,
+    pub remove_redundant_semicolons: bool,
+    pub normalize_local_size_layout: bool,
+    pub sort_qualifiers: bool,
+    pub normalize_return_spacing: bool,
+    pub normalize_declaration_spacing: bool,
+    pub normalize_default_value_spacing: bool,
+    pub empty_line_before_doc_comments: bool,
     pub variable_case: Option<Case>,
+    pub local_variable_case: Option<Case>,
+    pub global_variable_case: Option<Case>,
+    pub static_variable_case: Option<Case>,
     pub function_case: Option<Case>,
+    pub entry_point_name_pattern: Option<Regex>,
+    pub forbidden_identifiers: Vec<String>,
+    pub forbidden_identifier_pattern: Option<Regex>,
+    pub min_identifier_length: Option<usize>,
+    pub max_identifier_length: Option<usize>,
+    /// Names that are not checked by `MinIdentifierLength` and `MaxIdentifierLength`.
+    pub identifier_length_allowlist: Vec<String>,
     pub struct_case: Option<Case>,
-    pub bool_prefix: Option<String>,
-    pub int_prefix: Option<String>,
-    pub float_prefix: Option<String>,
-    pub global_variable_prefix: Option<String>,
+    pub enum_case: Option<Case>,
+    pub enum_value_case: Option<Case>,
+    /// Abbreviations that case rules treat as single words (like `RGB` in `lightRGB`).
+    pub acronyms: Vec<String>,
+    pub enum_value_layout: Option<EnumValueLayout>,
+    pub enum_trailing_comma: bool,
+    pub bool_prefix: Option<Vec<String>>,
+    pub int_prefix: Option<Vec<String>>,
+    pub float_prefix: Option<Vec<String>>,
+    pub bool_prefix_scope: PrefixScope,
+    pub int_prefix_scope: PrefixScope,
+    pub float_prefix_scope: PrefixScope,
+    pub exempt_loop_counters: bool,
+    pub global_variable_prefix: Option<Vec<String>>,
+    pub constant_buffer_field_prefix: Option<Vec<String>>,
     pub require_docs_on_functions: bool,
+    pub function_docs_placement: Option<FunctionDocsPlacement>,
     pub require_docs_on_structs: bool,
     pub require_docs_on_fields: bool,
     pub indent_preprocessor: bool,
     pub preprocessor_if_creates_nesting: bool,
+    pub version_directive_first: bool,
+    pub require_default_precision: bool,
+    pub require_local_size: bool,
+    pub require_numthreads: bool,
+    pub validate_semantics: bool,
+    /// Semantics (without indices) that are allowed in addition to system-value semantics.
+    pub custom_semantics: Vec<String>,
+    /// Directories to look for included files.
+    pub include_dirs: Vec<PathBuf>,
+    pub require_existing_includes: bool,
+    pub forbid_circular_includes: bool,
+    pub forbid_unused_variables: bool,
+    pub forbid_unused_parameters: bool,
+    pub report_unused_functions: bool,
+    pub warn_on_shadowing: bool,
+    pub warn_on_uninitialized_variables: bool,
+    pub forbid_duplicate_bindings: bool,
+    pub forbid_duplicate_locations: bool,
+    pub forbid_mixed_indentation: bool,
+    pub require_brief_in_docs: bool,
+    pub require_ordered_param_docs: bool,
+    pub require_param_docs_direction: bool,
+    pub require_ordered_locations: bool,
+    pub forbid_macro_redefinition: bool,
+    pub require_default_in_switch: bool,
+    pub forbid_dead_branches: bool,
+    pub validate_nolint_comments: bool,
+    /// Values of preprocessor macros (from the `[Defines]` section).
+    pub defines: Vec<(String, i64)>,
+    /// Severities of lint rules that don't use the default (error) severity.
+    pub severities: Vec<(&'static str, Severity)>,
+    /// Rules from stage-specific sections (like `[Compute]`).
+    pub stage_configs: Vec<(ShaderStage, Config)>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             max_empty_lines: 1,
+            max_line_length: None,
+            max_function_lines: None,
+            max_nesting_depth: None,
+            max_push_constant_size: None,
+            max_depth: None,
+            max_file_size: None,
+            max_format_time: None,
+            streaming_chunk_size: None,
+            comment_banner_char: None,
+            comment_banner_width: None,
+            align_initializer_columns: false,
+            argument_wrap: None,
+            bin_pack_parameters: true,
+            break_before_binary_operators: None,
+            statement_attribute_placement: None,
+            require_braces_on_if: None,
+            require_braces_on_else: None,
+            require_braces_on_for: None,
+            require_braces_on_while: None,
+            trailing_comma_in_initializers: None,
+            line_ending: None,
+            insert_final_newline: None,
+            space_before_constructor_parenthesis: None,
+            space_after_cast: None,
             new_line_around_braces: NewLineOnOpenBrace::After,
+            empty_body_style: EmptyBodyStyle::Separate,
             indentation: IndentationRule::FourSpaces,
             spaces_in_brackets: false,
+            spaces_in_angle_brackets: false,
+            space_before_inheritance_colon: true,
+            keep_user_line_breaks: false,
+            collapse_whitespace: false,
+            remove_space_after_unary_operators: false,
+            spaces_around_ternary_operator: false,
+            remove_spaces_around_member_access: false,
+            remove_redundant_semicolons: false,
+            normalize_local_size_layout: false,
+            sort_qualifiers: false,
+            normalize_return_spacing: false,
+            normalize_declaration_spacing: false,
+            normalize_default_value_spacing: false,
+            empty_line_before_doc_comments: false,
             variable_case: None,
+            local_variable_case: None,
+            global_variable_case: None,
+            static_variable_case: None,
             function_case: None,
+            entry_point_name_pattern: None,
+            forbidden_identifiers: Vec::new(),
+            forbidden_identifier_pattern: None,
+            min_identifier_length: None,
+            max_identifier_length: None,
+            identifier_length_allowlist: ["i", "j", "k", "n", "x", "y", "z", "uv"]
+                .map(str::to_owned)
+                .to_vec(),
             struct_case: None,
+            enum_case: None,
+            enum_value_case: None,
+            acronyms: Vec::new(),
+            enum_value_layout: None,
+            enum_trailing_comma: false,
             bool_prefix: None,
             int_prefix: None,
             float_prefix: None,
+            bool_prefix_scope: PrefixScope::All,
+            int_prefix_scope: PrefixScope::All,
+            float_prefix_scope: PrefixScope::All,
+            exempt_loop_counters: false,
             global_variable_prefix: None,
+            constant_buffer_field_prefix: None,
             require_docs_on_functions: false,
+            function_docs_placement: None,
             require_docs_on_structs: false,
             require_docs_on_fields: false,
             indent_preprocessor: false,
             preprocessor_if_creates_nesting: false,
+            version_directive_first: false,
+            require_default_precision: false,
+            require_local_size: false,
+            require_numthreads: false,
+            validate_semantics: false,
+            custom_semantics: Vec::new(),
+            include_dirs: Vec::new(),
+            require_existing_includes: false,
+            forbid_circular_includes: false,
+            forbid_unused_variables: false,
+            forbid_unused_parameters: false,
+            report_unused_functions: false,
+            warn_on_shadowing: false,
+            warn_on_uninitialized_variables: false,
+            forbid_duplicate_bindings: false,
+            forbid_duplicate_locations: false,
+            forbid_mixed_indentation: false,
+            require_brief_in_docs: false,
+            require_ordered_param_docs: false,
+            require_param_docs_direction: false,
+            require_ordered_locations: false,
+            forbid_macro_redefinition: false,
+            require_default_in_switch: false,
+            forbid_dead_branches: false,
+            validate_nolint_comments: false,
+            defines: Vec::new(),
+            severities: Vec::new(),
+            stage_configs: Vec::new(),
         }
     }
 }
 
 impl Config {
+    /// Returns default rules with changes from the specified preset.
+    pub fn from_preset(preset: Preset) -> Config {
+        let default = Config::default();
+
+        match preset {
+            Preset::Unreal => Config {
+                indentation: IndentationRule::Tab,
+                new_line_around_braces: NewLineOnOpenBrace::Before,
+                function_case: Some(Case::Pascal),
+                struct_case: Some(Case::Pascal),
+                enum_case: Some(Case::Pascal),
+                variable_case: Some(Case::Pascal),
+                bool_prefix: Some(vec!["b".to_owned()]),
+                ..default
+            },
+            Preset::Unity => Config {
+                new_line_around_braces: NewLineOnOpenBrace::Before,
+                function_case: Some(Case::Pascal),
+                struct_case: Some(Case::Pascal),
+                enum_case: Some(Case::Pascal),
+                variable_case: Some(Case::Camel),
+                ..default
+            },
+            Preset::Google => Config {
+                indentation: IndentationRule::TwoSpaces,
+                max_line_length: Some(80),
+                argument_wrap: Some(ArgumentWrap::AlignWithParenthesis),
+                function_case: Some(Case::Pascal),
+                struct_case: Some(Case::Pascal),
+                enum_case: Some(Case::Pascal),
+                variable_case: Some(Case::Snake),
+                ..default
+            },
+            Preset::Allman => Config {
+                new_line_around_braces: NewLineOnOpenBrace::Before,
+                ..default
+            },
+        }
+    }
+
+    /// Returns value of a rule (in TOML syntax) that uses the specified name,
+    /// `None` if the rule is not specified or the name is unknown.
+    pub fn rule_value(&self, name: &str) -> Option<String> {
+        fn case_name(case: Option<Case>) -> Option<String> {
+            case.map(|case| {
+                match case {
+                    Case::Camel => "\"Camel\"",
+                    Case::Pascal => "\"Pascal\"",
+                    Case::Snake => "\"Snake\"",
+                    Case::UpperSnake => "\"UpperSnake\"",
+                }
+                .to_owned()
+            })
+        }
+        fn require_braces(value: Option<RequireBraces>) -> Option<String> {
+            value.map(|value| {
+                match value {
+                    RequireBraces::Always => "\"Always\"",
+                    RequireBraces::MultiLine => "\"MultiLine\"",
+                }
+                .to_owned()
+            })
+        }
+        fn string(value: Option<&str>) -> Option<String> {
+            value.map(|value| format!("{:?}", value))
+        }
+        fn prefixes(value: &Option<Vec<String>>) -> Option<String> {
+            match value.as_deref()? {
+                [prefix] => string(Some(prefix)),
+                prefixes => Some(format!(
+                    "[{}]",
+                    prefixes
+                        .iter()
+                        .map(|prefix| format!("{:?}", prefix))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            }
+        }
+        fn prefix_scope(value: PrefixScope) -> Option<String> {
+            string(Some(match value {
+                PrefixScope::All => "All",
+                PrefixScope::GlobalsOnly => "GlobalsOnly",
+            }))
+        }
+        fn number(value: Option<usize>) -> Option<String> {
+            value.map(|value| value.to_string())
+        }
+        fn boolean(value: bool) -> Option<String> {
+            Some(value.to_string())
+        }
+
+        match name {
+            "Indentation" => string(Some(match self.indentation {
+                IndentationRule::Tab => "Tab",
+                IndentationRule::TwoSpaces => "TwoSpaces",
+                IndentationRule::FourSpaces => "FourSpaces",
+                IndentationRule::Auto => "Auto",
+            })),
+            "NewLineOnOpenBrace" => string(Some(match self.new_line_around_braces {
+                NewLineOnOpenBrace::Before => "Before",
+                NewLineOnOpenBrace::After => "After",
+                NewLineOnOpenBrace::Auto => "Auto",
+            })),
+            "EmptyBodyStyle" => string(Some(match self.empty_body_style {
+                EmptyBodyStyle::Collapsed => "Collapsed",
+                EmptyBodyStyle::Separate => "Separate",
+            })),
+            "MaxEmptyLines" => number(Some(self.max_empty_lines)),
+            "SpacesInBrackets" => boolean(self.spaces_in_brackets),
+            "SpacesInAngleBrackets" => boolean(self.spaces_in_angle_brackets),
+            "SpaceBeforeInheritanceColon" => boolean(self.space_before_inheritance_colon),
+            "KeepUserLineBreaks" => boolean(self.keep_user_line_breaks),
+            "CollapseWhitespace" => boolean(self.collapse_whitespace),
+            "RemoveSpaceAfterUnaryOperators" => boolean(self.remove_space_after_unary_operators),
+            "SpacesAroundTernaryOperator" => boolean(self.spaces_around_ternary_operator),
+            "RemoveSpacesAroundMemberAccess" => boolean(self.remove_spaces_around_member_access),
+            "RemoveRedundantSemicolons" => boolean(self.remove_redundant_semicolons),
+            "NormalizeLocalSizeLayout" => boolean(self.normalize_local_size_layout),
+            "SortQualifiers" => boolean(self.sort_qualifiers),
+            "NormalizeReturnSpacing" => boolean(self.normalize_return_spacing),
+            "NormalizeDeclarationSpacing" => boolean(self.normalize_declaration_spacing),
+            "NormalizeDefaultValueSpacing" => boolean(self.normalize_default_value_spacing),
+            "EmptyLineBeforeDocComments" => boolean(self.empty_line_before_doc_comments),
+            "BinPackParameters" => boolean(self.bin_pack_parameters),
+            "AlignInitializerColumns" => boolean(self.align_initializer_columns),
+            "CommentBannerChar" => self
+                .comment_banner_char
+                .map(|banner_char| format!("{:?}", banner_char.to_string())),
+            "CommentBannerWidth" => number(self.comment_banner_width),
+            "IndentPreprocessor" => boolean(self.indent_preprocessor),
+            "PreprocessorIfCreatesNesting" => boolean(self.preprocessor_if_creates_nesting),
+            "RequireDocsOnFunctions" => boolean(self.require_docs_on_functions),
+            "FunctionDocsPlacement" => {
+                string(
+                    self.function_docs_placement
+                        .map(|placement| match placement {
+                            FunctionDocsPlacement::Declaration => "Declaration",
+                            FunctionDocsPlacement::Definition => "Definition",
+                        }),
+                )
+            }
+            "RequireDocsOnStructs" => boolean(self.require_docs_on_structs),
+            "RequireDocsOnFields" => boolean(self.require_docs_on_fields),
+            "VersionDirectiveFirst" => boolean(self.version_directive_first),
+            "IncludeDirs" => {
+                let dirs = self
+                    .include_dirs
+                    .iter()
+                    .map(|dir| format!("{:?}", dir.to_string_lossy()))
+                    .collect::<Vec<_>>();
+                Some(format!("[{}]", dirs.join(", ")))
+            }
+            "RequireExistingIncludes" => boolean(self.require_existing_includes),
+            "ForbidCircularIncludes" => boolean(self.forbid_circular_includes),
+            "ForbidUnusedVariables" => boolean(self.forbid_unused_variables),
+            "ForbidUnusedParameters" => boolean(self.forbid_unused_parameters),
+            "WarnOnShadowing" => boolean(self.warn_on_shadowing),
+            "WarnOnUninitializedVariables" => boolean(self.warn_on_uninitialized_variables),
+            "ReportUnusedFunctions" => boolean(self.report_unused_functions),
+            "ForbidDuplicateBindings" => boolean(self.forbid_duplicate_bindings),
+            "ForbidDuplicateLocations" => boolean(self.forbid_duplicate_locations),
+            "ForbidMixedIndentation" => boolean(self.forbid_mixed_indentation),
+            "RequireBriefInDocs" => boolean(self.require_brief_in_docs),
+            "RequireOrderedParamDocs" => boolean(self.require_ordered_param_docs),
+            "RequireParamDocsDirection" => boolean(self.require_param_docs_direction),
+            "RequireOrderedLocations" => boolean(self.require_ordered_locations),
+            "ForbidMacroRedefinition" => boolean(self.forbid_macro_redefinition),
+            "RequireDefaultInSwitch" => boolean(self.require_default_in_switch),
+            "ForbidDeadBranches" => boolean(self.forbid_dead_branches),
+            "ValidateNolintComments" => boolean(self.validate_nolint_comments),
+            "RequireDefaultPrecision" => boolean(self.require_default_precision),
+            "RequireLocalSize" => boolean(self.require_local_size),
+            "RequireNumthreads" => boolean(self.require_numthreads),
+            "ValidateSemantics" => boolean(self.validate_semantics),
+            "CustomSemantics" => {
+                let semantics = self
+                    .custom_semantics
+                    .iter()
+                    .map(|semantic| format!("{:?}", semantic))
+                    .collect::<Vec<_>>();
+                Some(format!("[{}]", semantics.join(", ")))
+            }
+            "VariableCase" => case_name(self.variable_case),
+            "LocalVariableCase" => case_name(self.local_variable_case),
+            "GlobalVariableCase" => case_name(self.global_variable_case),
+            "StaticVariableCase" => case_name(self.static_variable_case),
+            "FunctionCase" => case_name(self.function_case),
+            "EntryPointNamePattern" => {
+                string(self.entry_point_name_pattern.as_ref().map(Regex::as_str))
+            }
+            "ForbiddenIdentifiers" => {
+                let names = self
+                    .forbidden_identifiers
+                    .iter()
+                    .map(|name| format!("{:?}", name))
+                    .collect::<Vec<_>>();
+                Some(format!("[{}]", names.join(", ")))
+            }
+            "ForbiddenIdentifierPattern" => string(
+                self.forbidden_identifier_pattern
+                    .as_ref()
+                    .map(Regex::as_str),
+            ),
+            "MinIdentifierLength" => number(self.min_identifier_length),
+            "MaxIdentifierLength" => number(self.max_identifier_length),
+            "IdentifierLengthAllowlist" => {
+                let names = self
+                    .identifier_length_allowlist
+                    .iter()
+                    .map(|name| format!("{:?}", name))
+                    .collect::<Vec<_>>();
+                Some(format!("[{}]", names.join(", ")))
+            }
+            "StructCase" => case_name(self.struct_case),
+            "EnumCase" => case_name(self.enum_case),
+            "EnumValueCase" => case_name(self.enum_value_case),
+            "Acronyms" => {
+                let acronyms = self
+                    .acronyms
+                    .iter()
+                    .map(|acronym| format!("{:?}", acronym))
+                    .collect::<Vec<_>>();
+                Some(format!("[{}]", acronyms.join(", ")))
+            }
+            "EnumValueLayout" => string(self.enum_value_layout.map(|layout| match layout {
+                EnumValueLayout::OnePerLine => "OnePerLine",
+                EnumValueLayout::Packed => "Packed",
+            })),
+            "EnumTrailingComma" => boolean(self.enum_trailing_comma),
+            "MaxLineLength" => number(self.max_line_length),
+            "ArgumentWrap" => string(self.argument_wrap.map(|wrap| match wrap {
+                ArgumentWrap::AllOnOneLine => "AllOnOneLine",
+                ArgumentWrap::AlignWithParenthesis => "AlignWithParenthesis",
+                ArgumentWrap::OnePerLine => "OnePerLine",
+            })),
+            "BreakBeforeBinaryOperators" => self
+                .break_before_binary_operators
+                .map(|value| value.to_string()),
+            "StatementAttributePlacement" => string(self.statement_attribute_placement.map(
+                |placement| match placement {
+                    StatementAttributePlacement::SeparateLine => "SeparateLine",
+                    StatementAttributePlacement::SameLine => "SameLine",
+                },
+            )),
+            "RequireBracesOnIf" => require_braces(self.require_braces_on_if),
+            "RequireBracesOnElse" => require_braces(self.require_braces_on_else),
+            "RequireBracesOnFor" => require_braces(self.require_braces_on_for),
+            "RequireBracesOnWhile" => require_braces(self.require_braces_on_while),
+            "TrailingCommaInInitializers" => string(self.trailing_comma_in_initializers.map(
+                |trailing_comma| match trailing_comma {
+                    TrailingComma::Always => "Always",
+                    TrailingComma::Never => "Never",
+                    TrailingComma::Multiline => "Multiline",
+                },
+            )),
+            "LineEnding" => string(self.line_ending.map(|line_ending| match line_ending {
+                LineEnding::Lf => "Lf",
+                LineEnding::CrLf => "CrLf",
+            })),
+            "InsertFinalNewline" => self.insert_final_newline.map(|value| value.to_string()),
+            "SpaceBeforeConstructorParenthesis" => self
+                .space_before_constructor_parenthesis
+                .map(|value| value.to_string()),
+            "SpaceAfterCast" => self.space_after_cast.map(|value| value.to_string()),
+            "MaxFunctionLines" => number(self.max_function_lines),
+            "MaxNestingDepth" => number(self.max_nesting_depth),
+            "MaxPushConstantSize" => number(self.max_push_constant_size),
+            "MaxDepth" => number(self.max_depth),
+            "MaxFileSize" => number(self.max_file_size),
+            "MaxFormatTime" => number(self.max_format_time),
+            "StreamingChunkSize" => number(self.streaming_chunk_size),
+            "BoolPrefix" => prefixes(&self.bool_prefix),
+            "IntPrefix" => prefixes(&self.int_prefix),
+            "FloatPrefix" => prefixes(&self.float_prefix),
+            "BoolPrefixScope" => prefix_scope(self.bool_prefix_scope),
+            "IntPrefixScope" => prefix_scope(self.int_prefix_scope),
+            "FloatPrefixScope" => prefix_scope(self.float_prefix_scope),
+            "ExemptLoopCounters" => boolean(self.exempt_loop_counters),
+            "GlobalVariablePrefix" => prefixes(&self.global_variable_prefix),
+            "ConstantBufferFieldPrefix" => prefixes(&self.constant_buffer_field_prefix),
+            _ => None,
+        }
+    }
+
+    /// Returns text of a config file that has all rules with their current values (rules without
+    /// a value are commented out), descriptions and allowed values.
+    pub fn to_commented_toml(&self) -> String {
+        let mut output = String::new();
+        for rule in RULES {
+            output += &format!(
+                "# {}\n# Allowed values: {}.\n",
+                rule.description,
+                rule.allowed_values()
+            );
+            match self.rule_value(rule.id) {
+                Some(value) => output += &format!("{} = {}\n\n", rule.id, value),
+                None => output += &format!("# {} = {}\n\n", rule.id, rule.example_value),
+            }
+        }
+
+        output += &format!(
+            "# Values of macros used to find dead preprocessor branches.\n# [{}]\n# USE_SHADOWS = 1\n",
+            DEFINES_SECTION_NAME
+        );
+
+        output
+    }
+
+    /// Returns a JSON schema that describes keys, sections and values of the config file.
+    pub fn json_schema() -> serde_json::Value {
+        // Describe rules.
+        let mut rules = serde_json::Map::new();
+        for rule in RULES {
+            let mut schema = match rule.value_type {
+                RuleValueType::Bool => json!({ "type": "boolean" }),
+                RuleValueType::UnsignedInteger => json!({ "type": "integer", "minimum": 0 }),
+                RuleValueType::Char => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+                RuleValueType::Regex => json!({ "type": "string", "format": "regex" }),
+                RuleValueType::StringArray => {
+                    json!({ "type": "array", "items": { "type": "string" } })
+                }
+                RuleValueType::StringOrStringArray => json!({
+                    "anyOf": [
+                        { "type": "string" },
+                        { "type": "array", "items": { "type": "string" }, "minItems": 1 },
+                    ]
+                }),
+                RuleValueType::Enum(names) => json!({ "type": "string", "enum": names }),
+            };
+            schema["description"] = rule.description.into();
+            if let Some(default) = rule
+                .default
+                .and_then(|value| serde_json::from_str(value).ok())
+            {
+                schema["default"] = default;
+            }
+            rules.insert(rule.id.to_owned(), schema);
+        }
+
+        // Describe severities of lint rules.
+        let severities = RULES
+            .iter()
+            .filter(|rule| rule.category == RuleCategory::Lint)
+            .map(|rule| {
+                (
+                    rule.id.to_owned(),
+                    json!({ "type": "string", "enum": Severity::NAMES }),
+                )
+            })
+            .collect::<serde_json::Map<_, _>>();
+
+        let mut properties = rules.clone();
+        properties.insert(
+            PRESET_KEY.to_owned(),
+            json!({
+                "type": "string",
+                "enum": Preset::NAMES,
+                "description": "Set of rules that other rules are applied on top of.",
+            }),
+        );
+        properties.insert(
+            DEFINES_SECTION_NAME.to_owned(),
+            json!({
+                "type": "object",
+                "description": "Values of macros used to find dead preprocessor branches.",
+                "additionalProperties": { "type": ["integer", "boolean"] },
+            }),
+        );
+        properties.insert(
+            SEVERITY_SECTION_NAME.to_owned(),
+            json!({
+                "type": "object",
+                "description": "Severities of lint rules.",
+                "properties": severities,
+                "additionalProperties": false,
+            }),
+        );
+        for name in ShaderStage::CONFIG_SECTION_NAMES {
+            properties.insert(
+                name.to_owned(),
+                json!({
+                    "type": "object",
+                    "description": format!("Rules for {} shaders.", name.to_lowercase()),
+                    "properties": rules,
+                    "additionalProperties": false,
+                }),
+            );
+        }
+
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "shader-formatter config",
+            "type": "object",
+            "properties": properties,
+            "additionalProperties": false,
+        })
+    }
+
+    /// Creates a new config file (with all rules and their descriptions) in the specified directory.
+    ///
+    /// # Arguments
+    /// - `directory` Directory to create the config file in.
+    /// - `preset` Optional preset to take values of the rules from.
+    ///
+    /// # Return
+    /// Path to the created file.
+    pub fn create_file(directory: &Path, preset: Option<Preset>) -> Result<PathBuf, FormatError> {
+        let path_to_config = directory.join(CONFIG_FILE_NAME);
+        if path_to_config.exists() {
+            return Err(FormatError::Io(format!(
+                "config file already exists at {}",
+                path_to_config.display()
+            )));
+        }
+
+        let config = preset.map(Config::from_preset).unwrap_or_default();
+        if let Err(e) = std::fs::write(&path_to_config, config.to_commented_toml()) {
+            return Err(FormatError::Io(format!(
+                "failed to write the file at {}, error: {}",
+                path_to_config.display(),
+                e
+            )));
+        }
+
+        Ok(path_to_config)
+    }
+
     /// Looks for a config file in the specified directory or in parent directories.
-    /// If not found returns an empty config as `Ok`, otherwise an error message.
-    pub fn get(config_directory: &Path) -> Result<Config, String> {
+    /// If not found returns an empty config as `Ok`, otherwise an error.
+    pub fn get(config_directory: &Path) -> Result<Config, FormatError> {
         let mut current_dir = config_directory.to_path_buf();
 
         loop {
             // Check if config exists in this directory.
-            let path_to_config = current_dir.join(CONFIG_FILE_NAME);
-            if path_to_config.exists() {
-                return Self::load_from_file(path_to_config.as_path());
+            for file_name in std::iter::once(CONFIG_FILE_NAME).chain(OTHER_CONFIG_FILE_NAMES) {
+                let path_to_config = current_dir.join(file_name);
+                if path_to_config.exists() {
+                    return Self::load_from_file(path_to_config.as_path());
+                }
             }
 
             // Go to parent directory.
@@ -72,165 +705,873 @@ impl Config {
         }
     }
 
-    fn load_from_file(path_to_file: &std::path::Path) -> Result<Config, String> {
+    fn load_from_file(path_to_file: &std::path::Path) -> Result<Config, FormatError> {
         // Read file.
         let file_content = match std::fs::read_to_string(path_to_file) {
             Ok(v) => v,
             Err(e) => {
-                return Err(format!(
+                return Err(FormatError::Io(format!(
                     "failed to read the file at {}, error: {}",
                     path_to_file.display(),
                     e
-                ))
+                )))
             }
         };
 
-        // Parse TOML.
-        let table = match file_content.parse::<toml::Table>() {
+        // Parse the file (JSON and YAML files are converted to TOML tables).
+        let table = match path_to_file
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("json") => {
+                serde_json::from_str::<toml::Table>(&file_content).map_err(|e| e.to_string())
+            }
+            Some("yaml") => {
+                serde_yaml::from_str::<toml::Table>(&file_content).map_err(|e| e.to_string())
+            }
+            _ => file_content
+                .parse::<toml::Table>()
+                .map_err(|e| e.to_string()),
+        };
+        let table = match table {
             Ok(t) => t,
             Err(e) => {
-                return Err(format!(
+                return Err(FormatError::Config(format!(
                     "failed to parse config file at {}, error: {}",
                     path_to_file.display(),
                     e
-                ))
+                )))
             }
         };
 
-        let mut config = Config::default();
-        for (key, value) in table {
-            match key.as_str() {
-                "Indentation" => {
-                    config.indentation = match Self::toml_value_to_string(&key, &value)? {
-                        "Tab" => IndentationRule::Tab,
-                        "TwoSpaces" => IndentationRule::TwoSpaces,
-                        "FourSpaces" => IndentationRule::FourSpaces,
-                        other => {
-                            return Err(format!(
-                                "found unknown value \"{}\" for rule \"{}\"",
-                                other, key
-                            ))
-                        }
-                    };
-                }
-                "VariableCase" => {
-                    config.variable_case = Some(Self::toml_value_to_case(&key, &value)?)
+        // Start from a preset (if specified).
+        let mut config = match table.get(PRESET_KEY) {
+            Some(value) => {
+                let name = Self::toml_value_to_string(PRESET_KEY, value)?;
+                match Preset::from_name(name) {
+                    Some(preset) => Config::from_preset(preset),
+                    None => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            name, PRESET_KEY
+                        )))
+                    }
                 }
-                "FunctionCase" => {
-                    config.function_case = Some(Self::toml_value_to_case(&key, &value)?)
+            }
+            None => Config::default(),
+        };
+
+        // Apply rules (rules of stage-specific sections are applied later on top of them).
+        let config_dir = path_to_file.parent().unwrap_or(Path::new(""));
+        for (key, value) in &table {
+            if !value.is_table() && key != PRESET_KEY {
+                config.apply_rule(key, value)?;
+            }
+        }
+        config.make_include_dirs_relative_to(config_dir);
+
+        // Read values of macros.
+        if let Some(section) = table.get(DEFINES_SECTION_NAME) {
+            let Some(section) = section.as_table() else {
+                return Err(FormatError::Config(format!(
+                    "expected \"{}\" to be a section",
+                    DEFINES_SECTION_NAME
+                )));
+            };
+            for (name, value) in section {
+                let value = match value {
+                    Value::Integer(value) => *value,
+                    Value::Boolean(value) => *value as i64,
+                    _ => {
+                        return Err(FormatError::Config(format!(
+                            "expected value of macro \"{}\" to be an integer or a boolean",
+                            name
+                        )))
+                    }
+                };
+                config.defines.push((name.clone(), value));
+            }
+        }
+
+        // Read severities of lint rules.
+        if let Some(section) = table.get(SEVERITY_SECTION_NAME) {
+            let Some(section) = section.as_table() else {
+                return Err(FormatError::Config(format!(
+                    "expected \"{}\" to be a section",
+                    SEVERITY_SECTION_NAME
+                )));
+            };
+            for (id, value) in section {
+                let rule = match find_rule(id) {
+                    Some(rule) if rule.category == RuleCategory::Lint => rule,
+                    _ => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown lint rule \"{}\" in section \"{}\"",
+                            id, SEVERITY_SECTION_NAME
+                        )))
+                    }
+                };
+                let name = Self::toml_value_to_string(id, value)?;
+                let Some(severity) = Severity::from_name(name) else {
+                    return Err(FormatError::Config(format!(
+                        "found unknown severity \"{}\" for rule \"{}\"",
+                        name, id
+                    )));
+                };
+                config.severities.push((rule.id, severity));
+            }
+        }
+
+        // Prepare rules for shader stages.
+        for (key, value) in &table {
+            let Some(section) = value.as_table() else {
+                continue;
+            };
+            if key == DEFINES_SECTION_NAME || key == SEVERITY_SECTION_NAME {
+                continue;
+            }
+            let Some(stage) = ShaderStage::from_config_section_name(key) else {
+                return Err(FormatError::Config(format!(
+                    "found unknown section \"{}\"",
+                    key
+                )));
+            };
+
+            // Inherited include directories are already relative to the config directory.
+            let mut stage_config = config.clone();
+            for (key, value) in section {
+                stage_config.apply_rule(key, value)?;
+            }
+            if section.contains_key("IncludeDirs") {
+                stage_config.make_include_dirs_relative_to(config_dir);
+            }
+            stage_config.disable_rules_with_severity_off();
+            config.stage_configs.push((stage, stage_config));
+        }
+        config.disable_rules_with_severity_off();
+
+        Ok(config)
+    }
+
+    /// Returns severity of the rule that uses the specified identifier.
+    pub fn severity(&self, id: &str) -> Severity {
+        self.severities
+            .iter()
+            .find(|(rule, _)| *rule == id)
+            .map(|(_, severity)| *severity)
+            .unwrap_or(Severity::Error)
+    }
+
+    /// Changes values of rules in this config and in stage-specific configs (used to apply
+    /// settings that take precedence over the config file, like editor settings).
+    pub fn override_rules(&mut self, apply: &impl Fn(&mut Config)) {
+        apply(self);
+        for (_, stage_config) in &mut self.stage_configs {
+            stage_config.override_rules(apply);
+        }
+    }
+
+    /// Disables all lint rules (rules that report code that needs to be changed manually),
+    /// only formatting rules will be applied.
+    pub fn disable_lint_rules(&mut self) {
+        for rule in RULES {
+            if rule.category == RuleCategory::Lint {
+                self.disable_rule(rule.id);
+            }
+        }
+        for (_, stage_config) in &mut self.stage_configs {
+            stage_config.disable_lint_rules();
+        }
+    }
+
+    /// Disables a lint rule that uses the specified identifier.
+    ///
+    /// # Return
+    /// `false` if the rule was not enabled or can't be disabled.
+    pub fn disable_rule(&mut self, id: &str) -> bool {
+        fn disable_flag(flag: &mut bool) -> bool {
+            std::mem::take(flag)
+        }
+        fn disable_option<T>(option: &mut Option<T>) -> bool {
+            option.take().is_some()
+        }
+        fn disable_list<T>(list: &mut Vec<T>) -> bool {
+            !std::mem::take(list).is_empty()
+        }
+
+        match id {
+            "VariableCase" => disable_option(&mut self.variable_case),
+            "LocalVariableCase" => disable_option(&mut self.local_variable_case),
+            "GlobalVariableCase" => disable_option(&mut self.global_variable_case),
+            "StaticVariableCase" => disable_option(&mut self.static_variable_case),
+            "FunctionCase" => disable_option(&mut self.function_case),
+            "EntryPointNamePattern" => disable_option(&mut self.entry_point_name_pattern),
+            "ForbiddenIdentifiers" => disable_list(&mut self.forbidden_identifiers),
+            "ForbiddenIdentifierPattern" => disable_option(&mut self.forbidden_identifier_pattern),
+            "MinIdentifierLength" => disable_option(&mut self.min_identifier_length),
+            "MaxIdentifierLength" => disable_option(&mut self.max_identifier_length),
+            "StructCase" => disable_option(&mut self.struct_case),
+            "EnumCase" => disable_option(&mut self.enum_case),
+            "EnumValueCase" => disable_option(&mut self.enum_value_case),
+            "BoolPrefix" => disable_option(&mut self.bool_prefix),
+            "IntPrefix" => disable_option(&mut self.int_prefix),
+            "FloatPrefix" => disable_option(&mut self.float_prefix),
+            "GlobalVariablePrefix" => disable_option(&mut self.global_variable_prefix),
+            "ConstantBufferFieldPrefix" => disable_option(&mut self.constant_buffer_field_prefix),
+            "MaxFunctionLines" => disable_option(&mut self.max_function_lines),
+            "MaxNestingDepth" => disable_option(&mut self.max_nesting_depth),
+            "MaxPushConstantSize" => disable_option(&mut self.max_push_constant_size),
+            "RequireDocsOnFunctions" => disable_flag(&mut self.require_docs_on_functions),
+            "RequireDocsOnStructs" => disable_flag(&mut self.require_docs_on_structs),
+            "RequireDocsOnFields" => disable_flag(&mut self.require_docs_on_fields),
+            "VersionDirectiveFirst" => disable_flag(&mut self.version_directive_first),
+            "RequireExistingIncludes" => disable_flag(&mut self.require_existing_includes),
+            "ForbidCircularIncludes" => disable_flag(&mut self.forbid_circular_includes),
+            "ForbidUnusedVariables" => disable_flag(&mut self.forbid_unused_variables),
+            "ForbidUnusedParameters" => disable_flag(&mut self.forbid_unused_parameters),
+            "WarnOnShadowing" => disable_flag(&mut self.warn_on_shadowing),
+            "WarnOnUninitializedVariables" => {
+                disable_flag(&mut self.warn_on_uninitialized_variables)
+            }
+            "ReportUnusedFunctions" => disable_flag(&mut self.report_unused_functions),
+            "ForbidDuplicateBindings" => disable_flag(&mut self.forbid_duplicate_bindings),
+            "ForbidDuplicateLocations" => disable_flag(&mut self.forbid_duplicate_locations),
+            "ForbidMixedIndentation" => disable_flag(&mut self.forbid_mixed_indentation),
+            "RequireBriefInDocs" => disable_flag(&mut self.require_brief_in_docs),
+            "RequireOrderedParamDocs" => disable_flag(&mut self.require_ordered_param_docs),
+            "RequireParamDocsDirection" => disable_flag(&mut self.require_param_docs_direction),
+            "RequireOrderedLocations" => disable_flag(&mut self.require_ordered_locations),
+            "ForbidMacroRedefinition" => disable_flag(&mut self.forbid_macro_redefinition),
+            "RequireDefaultInSwitch" => disable_flag(&mut self.require_default_in_switch),
+            "ForbidDeadBranches" => disable_flag(&mut self.forbid_dead_branches),
+            "ValidateNolintComments" => disable_flag(&mut self.validate_nolint_comments),
+            "RequireDefaultPrecision" => disable_flag(&mut self.require_default_precision),
+            "RequireLocalSize" => disable_flag(&mut self.require_local_size),
+            "RequireNumthreads" => disable_flag(&mut self.require_numthreads),
+            "ValidateSemantics" => disable_flag(&mut self.validate_semantics),
+            _ => false,
+        }
+    }
+
+    /// Disables lint rules that have `Off` severity.
+    fn disable_rules_with_severity_off(&mut self) {
+        for (id, severity) in self.severities.clone() {
+            if severity == Severity::Off {
+                self.disable_rule(id);
+            }
+        }
+    }
+
+    /// Returns rules that should be used for the specified shader stage.
+    pub fn get_stage_config(&self, stage: Option<ShaderStage>) -> Config {
+        match self
+            .stage_configs
+            .iter()
+            .find(|(section_stage, _)| Some(*section_stage) == stage)
+        {
+            Some((_, stage_config)) => stage_config.clone(),
+            None => self.clone(),
+        }
+    }
+
+    /// Converts relative paths of include directories to paths relative to the specified directory.
+    fn make_include_dirs_relative_to(&mut self, dir: &Path) {
+        for include_dir in &mut self.include_dirs {
+            *include_dir = dir.join(&include_dir);
+        }
+    }
+
+    /// Parses the specified rule and saves its value.
+    fn apply_rule(&mut self, key: &str, value: &Value) -> Result<(), FormatError> {
+        match key {
+            "Indentation" => {
+                self.indentation = match Self::toml_value_to_string(key, value)? {
+                    "Tab" => IndentationRule::Tab,
+                    "TwoSpaces" => IndentationRule::TwoSpaces,
+                    "FourSpaces" => IndentationRule::FourSpaces,
+                    "Auto" => IndentationRule::Auto,
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
+                };
+            }
+            "VariableCase" => self.variable_case = Some(Self::toml_value_to_case(key, value)?),
+            "LocalVariableCase" => {
+                self.local_variable_case = Some(Self::toml_value_to_case(key, value)?)
+            }
+            "GlobalVariableCase" => {
+                self.global_variable_case = Some(Self::toml_value_to_case(key, value)?)
+            }
+            "StaticVariableCase" => {
+                self.static_variable_case = Some(Self::toml_value_to_case(key, value)?)
+            }
+            "FunctionCase" => self.function_case = Some(Self::toml_value_to_case(key, value)?),
+            "EntryPointNamePattern" => {
+                let pattern = Self::toml_value_to_string(key, value)?;
+                self.entry_point_name_pattern = match Regex::new(pattern) {
+                    Ok(regex) => Some(regex),
+                    Err(e) => {
+                        return Err(FormatError::Config(format!(
+                            "found invalid value for rule \"{}\", error: {}",
+                            key, e
+                        )))
+                    }
+                };
+            }
+            "ForbiddenIdentifiers" => {
+                let Some(names) = value.as_array() else {
+                    return Err(FormatError::Config(format!(
+                        "expected value for key \"{}\" to be an array",
+                        key
+                    )));
+                };
+                self.forbidden_identifiers = names
+                    .iter()
+                    .map(|name| Self::toml_value_to_string(key, name).map(str::to_owned))
+                    .collect::<Result<_, _>>()?;
+            }
+            "MinIdentifierLength" => {
+                self.min_identifier_length = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "MaxIdentifierLength" => {
+                self.max_identifier_length = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "IdentifierLengthAllowlist" => {
+                let Some(names) = value.as_array() else {
+                    return Err(FormatError::Config(format!(
+                        "expected value for key \"{}\" to be an array",
+                        key
+                    )));
+                };
+                self.identifier_length_allowlist = names
+                    .iter()
+                    .map(|name| Self::toml_value_to_string(key, name).map(str::to_owned))
+                    .collect::<Result<_, _>>()?;
+            }
+            "ForbiddenIdentifierPattern" => {
+                let pattern = Self::toml_value_to_string(key, value)?;
+                self.forbidden_identifier_pattern = match Regex::new(pattern) {
+                    Ok(regex) => Some(regex),
+                    Err(e) => {
+                        return Err(FormatError::Config(format!(
+                            "found invalid value for rule \"{}\", error: {}",
+                            key, e
+                        )))
+                    }
+                };
+            }
+            "StructCase" => self.struct_case = Some(Self::toml_value_to_case(key, value)?),
+            "EnumCase" => self.enum_case = Some(Self::toml_value_to_case(key, value)?),
+            "EnumValueCase" => self.enum_value_case = Some(Self::toml_value_to_case(key, value)?),
+            "Acronyms" => {
+                let Some(acronyms) = value.as_array() else {
+                    return Err(FormatError::Config(format!(
+                        "expected value for key \"{}\" to be an array",
+                        key
+                    )));
+                };
+                self.acronyms = acronyms
+                    .iter()
+                    .map(|acronym| Self::toml_value_to_string(key, acronym).map(str::to_uppercase))
+                    .collect::<Result<_, _>>()?;
+            }
+            "EnumValueLayout" => {
+                self.enum_value_layout = match Self::toml_value_to_string(key, value)? {
+                    "OnePerLine" => Some(EnumValueLayout::OnePerLine),
+                    "Packed" => Some(EnumValueLayout::Packed),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "StructCase" => config.struct_case = Some(Self::toml_value_to_case(&key, &value)?),
-                "NewLineOnOpenBrace" => {
-                    config.new_line_around_braces = match Self::toml_value_to_string(&key, &value)?
-                    {
-                        "After" => NewLineOnOpenBrace::After,
-                        "Before" => NewLineOnOpenBrace::Before,
-                        other => {
-                            return Err(format!(
-                                "found unknown value \"{}\" for rule \"{}\"",
-                                other, key
-                            ))
-                        }
+            }
+            "EnumTrailingComma" => {
+                self.enum_trailing_comma = Self::toml_value_to_bool(key, value)?;
+            }
+            "NewLineOnOpenBrace" => {
+                self.new_line_around_braces = match Self::toml_value_to_string(key, value)? {
+                    "After" => NewLineOnOpenBrace::After,
+                    "Before" => NewLineOnOpenBrace::Before,
+                    "Auto" => NewLineOnOpenBrace::Auto,
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
                     }
                 }
-                "MaxEmptyLines" => {
-                    config.max_empty_lines = Self::toml_value_to_usize(&key, &value)?;
+            }
+            "EmptyBodyStyle" => {
+                self.empty_body_style = match Self::toml_value_to_string(key, value)? {
+                    "Collapsed" => EmptyBodyStyle::Collapsed,
+                    "Separate" => EmptyBodyStyle::Separate,
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "SpacesInBrackets" => {
-                    config.spaces_in_brackets = Self::toml_value_to_bool(&key, &value)?;
+            }
+            "MaxEmptyLines" => {
+                self.max_empty_lines = Self::toml_value_to_usize(key, value)?;
+            }
+            "MaxLineLength" => {
+                self.max_line_length = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "CommentBannerChar" => {
+                let text = Self::toml_value_to_string(key, value)?;
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(_char), None) => self.comment_banner_char = Some(_char),
+                    _ => {
+                        return Err(FormatError::Config(format!(
+                            "expected value for key \"{}\" to be a single character",
+                            key
+                        )))
+                    }
                 }
-                "BoolPrefix" => {
-                    config.bool_prefix =
-                        Some(Self::toml_value_to_string(&key, &value)?.to_string());
+            }
+            "CommentBannerWidth" => {
+                self.comment_banner_width = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "MaxFunctionLines" => {
+                self.max_function_lines = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "MaxNestingDepth" => {
+                self.max_nesting_depth = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "MaxPushConstantSize" => {
+                self.max_push_constant_size = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "MaxDepth" => {
+                self.max_depth = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "MaxFileSize" => {
+                self.max_file_size = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "MaxFormatTime" => {
+                self.max_format_time = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "StreamingChunkSize" => {
+                self.streaming_chunk_size = Some(Self::toml_value_to_usize(key, value)?);
+            }
+            "ArgumentWrap" => {
+                self.argument_wrap = match Self::toml_value_to_string(key, value)? {
+                    "AllOnOneLine" => Some(ArgumentWrap::AllOnOneLine),
+                    "AlignWithParenthesis" => Some(ArgumentWrap::AlignWithParenthesis),
+                    "OnePerLine" => Some(ArgumentWrap::OnePerLine),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "IntPrefix" => {
-                    config.int_prefix = Some(Self::toml_value_to_string(&key, &value)?.to_string());
+            }
+            "BinPackParameters" => {
+                self.bin_pack_parameters = Self::toml_value_to_bool(key, value)?;
+            }
+            "BreakBeforeBinaryOperators" => {
+                self.break_before_binary_operators = Some(Self::toml_value_to_bool(key, value)?);
+            }
+            "StatementAttributePlacement" => {
+                self.statement_attribute_placement = match Self::toml_value_to_string(key, value)? {
+                    "SeparateLine" => Some(StatementAttributePlacement::SeparateLine),
+                    "SameLine" => Some(StatementAttributePlacement::SameLine),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "FloatPrefix" => {
-                    config.float_prefix =
-                        Some(Self::toml_value_to_string(&key, &value)?.to_string());
+            }
+            "RequireBracesOnIf" => {
+                self.require_braces_on_if = match Self::toml_value_to_string(key, value)? {
+                    "Always" => Some(RequireBraces::Always),
+                    "MultiLine" => Some(RequireBraces::MultiLine),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "GlobalVariablePrefix" => {
-                    config.global_variable_prefix =
-                        Some(Self::toml_value_to_string(&key, &value)?.to_string());
+            }
+            "RequireBracesOnElse" => {
+                self.require_braces_on_else = match Self::toml_value_to_string(key, value)? {
+                    "Always" => Some(RequireBraces::Always),
+                    "MultiLine" => Some(RequireBraces::MultiLine),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "RequireDocsOnFunctions" => {
-                    config.require_docs_on_functions = Self::toml_value_to_bool(&key, &value)?;
+            }
+            "RequireBracesOnFor" => {
+                self.require_braces_on_for = match Self::toml_value_to_string(key, value)? {
+                    "Always" => Some(RequireBraces::Always),
+                    "MultiLine" => Some(RequireBraces::MultiLine),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "RequireDocsOnStructs" => {
-                    config.require_docs_on_structs = Self::toml_value_to_bool(&key, &value)?;
+            }
+            "RequireBracesOnWhile" => {
+                self.require_braces_on_while = match Self::toml_value_to_string(key, value)? {
+                    "Always" => Some(RequireBraces::Always),
+                    "MultiLine" => Some(RequireBraces::MultiLine),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "RequireDocsOnFields" => {
-                    config.require_docs_on_fields = Self::toml_value_to_bool(&key, &value)?;
+            }
+            "TrailingCommaInInitializers" => {
+                self.trailing_comma_in_initializers = match Self::toml_value_to_string(key, value)?
+                {
+                    "Always" => Some(TrailingComma::Always),
+                    "Never" => Some(TrailingComma::Never),
+                    "Multiline" => Some(TrailingComma::Multiline),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "IndentPreprocessor" => {
-                    config.indent_preprocessor = Self::toml_value_to_bool(&key, &value)?;
+            }
+            "LineEnding" => {
+                self.line_ending = match Self::toml_value_to_string(key, value)? {
+                    "Lf" => Some(LineEnding::Lf),
+                    "CrLf" => Some(LineEnding::CrLf),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                "PreprocessorIfCreatesNesting" => {
-                    config.preprocessor_if_creates_nesting =
-                        Self::toml_value_to_bool(&key, &value)?;
+            }
+            "InsertFinalNewline" => {
+                self.insert_final_newline = Some(Self::toml_value_to_bool(key, value)?);
+            }
+            "SpaceBeforeConstructorParenthesis" => {
+                self.space_before_constructor_parenthesis =
+                    Some(Self::toml_value_to_bool(key, value)?);
+            }
+            "SpaceAfterCast" => {
+                self.space_after_cast = Some(Self::toml_value_to_bool(key, value)?);
+            }
+            "AlignInitializerColumns" => {
+                self.align_initializer_columns = Self::toml_value_to_bool(key, value)?;
+            }
+            "SpacesInBrackets" => {
+                self.spaces_in_brackets = Self::toml_value_to_bool(key, value)?;
+            }
+            "SpacesInAngleBrackets" => {
+                self.spaces_in_angle_brackets = Self::toml_value_to_bool(key, value)?;
+            }
+            "SpaceBeforeInheritanceColon" => {
+                self.space_before_inheritance_colon = Self::toml_value_to_bool(key, value)?;
+            }
+            "KeepUserLineBreaks" => {
+                self.keep_user_line_breaks = Self::toml_value_to_bool(key, value)?;
+            }
+            "CollapseWhitespace" => {
+                self.collapse_whitespace = Self::toml_value_to_bool(key, value)?;
+            }
+            "RemoveSpaceAfterUnaryOperators" => {
+                self.remove_space_after_unary_operators = Self::toml_value_to_bool(key, value)?;
+            }
+            "SpacesAroundTernaryOperator" => {
+                self.spaces_around_ternary_operator = Self::toml_value_to_bool(key, value)?;
+            }
+            "RemoveSpacesAroundMemberAccess" => {
+                self.remove_spaces_around_member_access = Self::toml_value_to_bool(key, value)?;
+            }
+            "RemoveRedundantSemicolons" => {
+                self.remove_redundant_semicolons = Self::toml_value_to_bool(key, value)?;
+            }
+            "NormalizeLocalSizeLayout" => {
+                self.normalize_local_size_layout = Self::toml_value_to_bool(key, value)?;
+            }
+            "SortQualifiers" => {
+                self.sort_qualifiers = Self::toml_value_to_bool(key, value)?;
+            }
+            "NormalizeReturnSpacing" => {
+                self.normalize_return_spacing = Self::toml_value_to_bool(key, value)?;
+            }
+            "NormalizeDeclarationSpacing" => {
+                self.normalize_declaration_spacing = Self::toml_value_to_bool(key, value)?;
+            }
+            "NormalizeDefaultValueSpacing" => {
+                self.normalize_default_value_spacing = Self::toml_value_to_bool(key, value)?;
+            }
+            "EmptyLineBeforeDocComments" => {
+                self.empty_line_before_doc_comments = Self::toml_value_to_bool(key, value)?;
+            }
+            "BoolPrefix" => {
+                self.bool_prefix = Some(Self::toml_value_to_prefixes(key, value)?);
+            }
+            "IntPrefix" => {
+                self.int_prefix = Some(Self::toml_value_to_prefixes(key, value)?);
+            }
+            "FloatPrefix" => {
+                self.float_prefix = Some(Self::toml_value_to_prefixes(key, value)?);
+            }
+            "BoolPrefixScope" => {
+                self.bool_prefix_scope = Self::toml_value_to_prefix_scope(key, value)?;
+            }
+            "IntPrefixScope" => {
+                self.int_prefix_scope = Self::toml_value_to_prefix_scope(key, value)?;
+            }
+            "FloatPrefixScope" => {
+                self.float_prefix_scope = Self::toml_value_to_prefix_scope(key, value)?;
+            }
+            "ExemptLoopCounters" => {
+                self.exempt_loop_counters = Self::toml_value_to_bool(key, value)?;
+            }
+            "GlobalVariablePrefix" => {
+                self.global_variable_prefix = Some(Self::toml_value_to_prefixes(key, value)?);
+            }
+            "ConstantBufferFieldPrefix" => {
+                self.constant_buffer_field_prefix = Some(Self::toml_value_to_prefixes(key, value)?);
+            }
+            "RequireDocsOnFunctions" => {
+                self.require_docs_on_functions = Self::toml_value_to_bool(key, value)?;
+            }
+            "FunctionDocsPlacement" => {
+                self.function_docs_placement = match Self::toml_value_to_string(key, value)? {
+                    "Declaration" => Some(FunctionDocsPlacement::Declaration),
+                    "Definition" => Some(FunctionDocsPlacement::Definition),
+                    other => {
+                        return Err(FormatError::Config(format!(
+                            "found unknown value \"{}\" for rule \"{}\"",
+                            other, key
+                        )))
+                    }
                 }
-                _ => return Err(format!("found unknown rule \"{}\"", key)),
+            }
+            "RequireDocsOnStructs" => {
+                self.require_docs_on_structs = Self::toml_value_to_bool(key, value)?;
+            }
+            "RequireDocsOnFields" => {
+                self.require_docs_on_fields = Self::toml_value_to_bool(key, value)?;
+            }
+            "IndentPreprocessor" => {
+                self.indent_preprocessor = Self::toml_value_to_bool(key, value)?;
+            }
+            "PreprocessorIfCreatesNesting" => {
+                self.preprocessor_if_creates_nesting = Self::toml_value_to_bool(key, value)?;
+            }
+            "VersionDirectiveFirst" => {
+                self.version_directive_first = Self::toml_value_to_bool(key, value)?;
+            }
+            "RequireDefaultPrecision" => {
+                self.require_default_precision = Self::toml_value_to_bool(key, value)?;
+            }
+            "RequireLocalSize" => {
+                self.require_local_size = Self::toml_value_to_bool(key, value)?;
+            }
+            "RequireNumthreads" => {
+                self.require_numthreads = Self::toml_value_to_bool(key, value)?;
+            }
+            "ValidateSemantics" => {
+                self.validate_semantics = Self::toml_value_to_bool(key, value)?;
+            }
+            "CustomSemantics" => {
+                let Some(semantics) = value.as_array() else {
+                    return Err(FormatError::Config(format!(
+                        "expected value for key \"{}\" to be an array",
+                        key
+                    )));
+                };
+                self.custom_semantics = semantics
+                    .iter()
+                    .map(|semantic| Self::toml_value_to_string(key, semantic).map(str::to_owned))
+                    .collect::<Result<_, _>>()?;
+            }
+            "RequireExistingIncludes" => {
+                self.require_existing_includes = Self::toml_value_to_bool(key, value)?;
+            }
+            "ForbidCircularIncludes" => {
+                self.forbid_circular_includes = Self::toml_value_to_bool(key, value)?;
+            }
+            "ForbidUnusedVariables" => {
+                self.forbid_unused_variables = Self::toml_value_to_bool(key, value)?;
+            }
+            "ForbidUnusedParameters" => {
+                self.forbid_unused_parameters = Self::toml_value_to_bool(key, value)?;
+            }
+            "ReportUnusedFunctions" => {
+                self.report_unused_functions = Self::toml_value_to_bool(key, value)?;
+            }
+            "WarnOnShadowing" => {
+                self.warn_on_shadowing = Self::toml_value_to_bool(key, value)?;
+            }
+            "ForbidDuplicateBindings" => {
+                self.forbid_duplicate_bindings = Self::toml_value_to_bool(key, value)?;
+            }
+            "ForbidDuplicateLocations" => {
+                self.forbid_duplicate_locations = Self::toml_value_to_bool(key, value)?;
+            }
+            "ForbidMixedIndentation" => {
+                self.forbid_mixed_indentation = Self::toml_value_to_bool(key, value)?;
+            }
+            "RequireBriefInDocs" => {
+                self.require_brief_in_docs = Self::toml_value_to_bool(key, value)?;
+            }
+            "RequireOrderedParamDocs" => {
+                self.require_ordered_param_docs = Self::toml_value_to_bool(key, value)?;
+            }
+            "RequireParamDocsDirection" => {
+                self.require_param_docs_direction = Self::toml_value_to_bool(key, value)?;
+            }
+            "RequireOrderedLocations" => {
+                self.require_ordered_locations = Self::toml_value_to_bool(key, value)?;
+            }
+            "ForbidMacroRedefinition" => {
+                self.forbid_macro_redefinition = Self::toml_value_to_bool(key, value)?;
+            }
+            "RequireDefaultInSwitch" => {
+                self.require_default_in_switch = Self::toml_value_to_bool(key, value)?;
+            }
+            "ForbidDeadBranches" => {
+                self.forbid_dead_branches = Self::toml_value_to_bool(key, value)?;
+            }
+            "ValidateNolintComments" => {
+                self.validate_nolint_comments = Self::toml_value_to_bool(key, value)?;
+            }
+            "WarnOnUninitializedVariables" => {
+                self.warn_on_uninitialized_variables = Self::toml_value_to_bool(key, value)?;
+            }
+            "IncludeDirs" => {
+                let Some(dirs) = value.as_array() else {
+                    return Err(FormatError::Config(format!(
+                        "expected value for key \"{}\" to be an array",
+                        key
+                    )));
+                };
+                self.include_dirs = dirs
+                    .iter()
+                    .map(|dir| Self::toml_value_to_string(key, dir).map(PathBuf::from))
+                    .collect::<Result<_, _>>()?;
+            }
+            _ => {
+                return Err(FormatError::Config(format!(
+                    "found unknown rule \"{}\"",
+                    key
+                )))
             }
         }
 
-        Ok(config)
+        Ok(())
     }
 
     /// Tries to convert a TOML value to a case type and returns a meaningful error message
     /// if we failed.
-    fn toml_value_to_case(key: &str, value: &Value) -> Result<Case, String> {
+    fn toml_value_to_case(key: &str, value: &Value) -> Result<Case, FormatError> {
         match Self::toml_value_to_string(key, value)? {
             "Camel" => Ok(Case::Camel),
             "Pascal" => Ok(Case::Pascal),
             "Snake" => Ok(Case::Snake),
             "UpperSnake" => Ok(Case::UpperSnake),
-            other => Err(format!(
+            other => Err(FormatError::Config(format!(
                 "found unknown value \"{}\" for rule \"{}\"",
                 other, key
-            )),
+            ))),
+        }
+    }
+
+    /// Tries to convert a TOML value to a prefix scope and returns a meaningful error message
+    /// in case we failed.
+    fn toml_value_to_prefix_scope(key: &str, value: &Value) -> Result<PrefixScope, FormatError> {
+        match Self::toml_value_to_string(key, value)? {
+            "All" => Ok(PrefixScope::All),
+            "GlobalsOnly" => Ok(PrefixScope::GlobalsOnly),
+            other => Err(FormatError::Config(format!(
+                "found unknown value \"{}\" for rule \"{}\"",
+                other, key
+            ))),
         }
     }
 
     /// Tries to convert a TOML value to a string and returns a meaningful error message
     /// in case we failed.
-    fn toml_value_to_string<'a>(key: &str, value: &'a Value) -> Result<&'a str, String> {
+    fn toml_value_to_string<'a>(key: &str, value: &'a Value) -> Result<&'a str, FormatError> {
         match value.as_str() {
             Some(v) => Ok(v),
-            None => Err(format!("expected value for key \"{}\" to be a string", key)),
+            None => Err(FormatError::Config(format!(
+                "expected value for key \"{}\" to be a string",
+                key
+            ))),
         }
     }
 
+    /// Tries to convert a TOML value (a string or an array of strings) to a list of prefixes
+    /// and returns a meaningful error message in case we failed.
+    fn toml_value_to_prefixes(key: &str, value: &Value) -> Result<Vec<String>, FormatError> {
+        let Some(values) = value.as_array() else {
+            return Ok(vec![Self::toml_value_to_string(key, value)?.to_owned()]);
+        };
+
+        if values.is_empty() {
+            return Err(FormatError::Config(format!(
+                "expected value for key \"{}\" to have at least one prefix",
+                key
+            )));
+        }
+
+        values
+            .iter()
+            .map(|value| Self::toml_value_to_string(key, value).map(str::to_owned))
+            .collect()
+    }
+
     /// Tries to convert a TOML value to a `usize` and returns a meaningful error message
     /// in case we failed.
-    fn toml_value_to_usize(key: &str, value: &Value) -> Result<usize, String> {
+    fn toml_value_to_usize(key: &str, value: &Value) -> Result<usize, FormatError> {
         match value.as_integer() {
             Some(v) => {
                 if v.is_negative() {
-                    return Err(format!(
+                    return Err(FormatError::Config(format!(
                         "expected value for key \"{}\" to be an unsigned integer",
                         key
-                    ));
+                    )));
                 }
 
                 Ok(v as usize)
             }
-            None => Err(format!(
+            None => Err(FormatError::Config(format!(
                 "expected value for key \"{}\" to be an integer",
                 key
-            )),
+            ))),
         }
     }
 
     /// Tries to convert a TOML value to a boolean and returns a meaningful error message
     /// in case we failed.
-    fn toml_value_to_bool(key: &str, value: &Value) -> Result<bool, String> {
+    fn toml_value_to_bool(key: &str, value: &Value) -> Result<bool, FormatError> {
         match value.as_bool() {
             Some(v) => Ok(v),
-            None => Err(format!(
+            None => Err(FormatError::Config(format!(
                 "expected value for key \"{}\" to be a boolean",
                 key
-            )),
+            ))),
         }
     }
 }
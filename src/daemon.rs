@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::{config::Config, error::FormatError, formatter::Formatter};
+
+/// JSON-RPC error code for requests that are not valid JSON.
+const PARSE_ERROR_CODE: i32 = -32700;
+/// JSON-RPC error code for unknown methods.
+const METHOD_NOT_FOUND_CODE: i32 = -32601;
+/// JSON-RPC error code for requests with missing or invalid parameters.
+const INVALID_PARAMS_CODE: i32 = -32602;
+
+/// Formats the specified content and returns formatted text or diagnostics.
+///
+/// Params: `path` (used to find the config and to resolve includes) and `content`.
+const FORMAT_METHOD: &str = "format";
+/// Forgets loaded configs so that changed config files are read again.
+const RELOAD_CONFIGS_METHOD: &str = "reloadConfigs";
+/// Stops the daemon.
+const SHUTDOWN_METHOD: &str = "shutdown";
+
+/// Handles format requests of a long-running process (used by editor plugins to avoid
+/// paying the startup and config discovery cost on each request).
+///
+/// Each request and response is a JSON-RPC 2.0 object written on a single line.
+#[derive(Default)]
+pub struct Daemon {
+    /// Loaded configs per directory of a formatted file.
+    configs: HashMap<PathBuf, Config>,
+}
+
+impl Daemon {
+    /// Reads requests from the specified input (one per line) and writes responses to
+    /// the specified output until the input is closed or a shutdown request is received.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (response, shutdown) = self.handle_request(&line);
+            if let Some(response) = response {
+                writeln!(output, "{}", response)?;
+                output.flush()?;
+            }
+            if shutdown {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handles a single request.
+    ///
+    /// # Return
+    /// Response (`None` for notifications, i.e. requests without an ID) and `true` if
+    /// the daemon should stop.
+    pub fn handle_request(&mut self, request: &str) -> (Option<Value>, bool) {
+        let request: Value = match serde_json::from_str(request) {
+            Ok(v) => v,
+            Err(e) => {
+                return (
+                    Some(Self::error_response(
+                        Value::Null,
+                        PARSE_ERROR_CODE,
+                        &format!("failed to parse the request, error: {}", e),
+                    )),
+                    false,
+                );
+            }
+        };
+        let id = request.get("id").cloned();
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        let (result, shutdown) = match request.get("method").and_then(Value::as_str) {
+            Some(FORMAT_METHOD) => (self.format(&params), false),
+            Some(RELOAD_CONFIGS_METHOD) => {
+                self.configs.clear();
+                (Ok(Value::Null), false)
+            }
+            Some(SHUTDOWN_METHOD) => (Ok(Value::Null), true),
+            Some(method) => (
+                Err((
+                    METHOD_NOT_FOUND_CODE,
+                    format!("unknown method \"{}\"", method),
+                )),
+                false,
+            ),
+            None => (
+                Err((METHOD_NOT_FOUND_CODE, "expected a method name".to_owned())),
+                false,
+            ),
+        };
+
+        let Some(id) = id else {
+            return (None, shutdown);
+        };
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => Self::error_response(id, code, &message),
+        };
+
+        (Some(response), shutdown)
+    }
+
+    /// Formats content from the params of a format request.
+    ///
+    /// # Return
+    /// `{ "output": "..." }` if formatted successfully, `{ "diagnostics": [...] }` if the code
+    /// needs manual changes or `Err` with an error code and a message if params are invalid.
+    fn format(&mut self, params: &Value) -> Result<Value, (i32, String)> {
+        let (Some(path), Some(content)) = (
+            params.get("path").and_then(Value::as_str),
+            params.get("content").and_then(Value::as_str),
+        ) else {
+            return Err((
+                INVALID_PARAMS_CODE,
+                "expected \"path\" and \"content\" strings".to_owned(),
+            ));
+        };
+        let path = Path::new(path);
+
+        let config = match self.get_config(path) {
+            Ok(config) => config,
+            Err(error) => return Ok(json!({ "diagnostics": [error.to_json()] })),
+        };
+
+        let mut formatter = Formatter::new(config);
+        formatter.set_path_to_shader(path);
+        match formatter.format(content, false) {
+            Ok(output) => Ok(json!({ "output": output })),
+            Err(error) => Ok(json!({ "diagnostics": [error.to_json()] })),
+        }
+    }
+
+    /// Returns config for the specified file (loads it if it's not loaded yet).
+    fn get_config(&mut self, path: &Path) -> Result<Config, FormatError> {
+        let directory = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        if let Some(config) = self.configs.get(&directory) {
+            return Ok(config.clone());
+        }
+
+        let config = Config::get(&directory)?;
+        self.configs.insert(directory, config.clone());
+
+        Ok(config)
+    }
+
+    /// Creates a JSON-RPC error response.
+    fn error_response(id: Value, code: i32, message: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        })
+    }
+}
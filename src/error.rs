@@ -0,0 +1,210 @@
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::{helpers, parser::Span};
+
+/// Name used instead of a rule name when printing parser errors in the compact format.
+const PARSE_ERROR_RULE_NAME: &str = "ParseError";
+
+/// Text that we append to the beginning of an error message if manual changes (in the code) are required
+/// (like changing a variable's case).
+pub const CHANGES_REQUIRED_ERR_MSG: &str = "changes required";
+
+/// Lines and columns of the first and the last characters of some code.
+///
+/// Uses `u32` to keep `FormatError` small.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Location {
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+impl Location {
+    /// Converts the specified span to lines and columns (starting from 1) of the specified text.
+    pub fn new(span: Span, text: &str) -> Self {
+        let line_index = helpers::LineIndex::new(text);
+        let (start_line, start_column) = line_index.line_and_column(span.start);
+
+        // Span end is exclusive, point to the last character instead.
+        let last_char_offset = match text.get(span.start..span.end) {
+            Some(code) => match code.char_indices().next_back() {
+                Some((offset, _)) => span.start + offset,
+                None => span.start,
+            },
+            None => span.start,
+        };
+        let (end_line, end_column) = line_index.line_and_column(last_char_offset);
+
+        Self {
+            start_line: start_line as u32,
+            start_column: start_column as u32,
+            end_line: end_line as u32,
+            end_column: end_column as u32,
+        }
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} column {} - line {} column {}",
+            self.start_line, self.start_column, self.end_line, self.end_column
+        )
+    }
+}
+
+/// Describes why formatting of a file failed.
+///
+/// Spans point to the text that was checked (the text after formatting) and are converted
+/// to a `Location` before the error is returned from the formatter.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormatError {
+    /// Failed to parse the code.
+    ParseError {
+        span: Span,
+        location: Location,
+        reason: String,
+    },
+    /// The code does not follow some rule and needs to be changed manually.
+    RuleViolation {
+        /// Name of the rule (as written in the config file).
+        rule: &'static str,
+        /// Name of the variable/function/struct that violates the rule.
+        name: String,
+        /// The correct name (if known).
+        suggestion: Option<String>,
+        /// Human-readable description of the problem.
+        message: String,
+        span: Span,
+        location: Location,
+    },
+    /// Failed to read or write a file.
+    Io(String),
+    /// Config file is invalid.
+    Config(String),
+}
+
+impl FormatError {
+    /// Creates a new `RuleViolation` error, its location is resolved later using `locate`.
+    pub fn rule_violation(
+        rule: &'static str,
+        name: &str,
+        suggestion: Option<String>,
+        message: String,
+        span: Span,
+    ) -> Self {
+        Self::RuleViolation {
+            rule,
+            name: name.to_owned(),
+            suggestion,
+            message,
+            span,
+            location: Location::default(),
+        }
+    }
+
+    /// Converts span of the error to a `Location` in the specified text.
+    pub fn locate(mut self, text: &str) -> Self {
+        match &mut self {
+            Self::ParseError { span, location, .. }
+            | Self::RuleViolation { span, location, .. } => *location = Location::new(*span, text),
+            Self::Io(_) | Self::Config(_) => {}
+        }
+
+        self
+    }
+
+    /// Moves location of the error by the specified number of lines (used when the checked text
+    /// is a part of a file).
+    pub fn offset_lines(mut self, line_count: u32) -> Self {
+        match &mut self {
+            Self::ParseError { location, .. } | Self::RuleViolation { location, .. } => {
+                location.start_line += line_count;
+                location.end_line += line_count;
+            }
+            Self::Io(_) | Self::Config(_) => {}
+        }
+
+        self
+    }
+
+    /// Formats the error as `file:line:col: rule: message` (understood by Vim's quickfix,
+    /// Emacs compilation-mode and many CI log parsers).
+    ///
+    /// # Arguments
+    /// - `path` Path to the file that was checked.
+    pub fn to_compact(&self, path: &Path) -> String {
+        let (rule, message, location) = match self {
+            Self::ParseError {
+                location, reason, ..
+            } => (PARSE_ERROR_RULE_NAME, reason, location),
+            Self::RuleViolation {
+                rule,
+                message,
+                location,
+                ..
+            } => (*rule, message, location),
+            Self::Io(message) | Self::Config(message) => {
+                return format!("{}: {}", path.display(), message)
+            }
+        };
+
+        format!(
+            "{}:{}:{}: {}: {}",
+            path.display(),
+            location.start_line,
+            location.start_column,
+            rule,
+            message
+        )
+    }
+
+    /// Converts the error to a JSON object with the rule name, message and location of the error.
+    pub fn to_json(&self) -> Value {
+        let (rule, message, location, suggestion) = match self {
+            Self::ParseError {
+                location, reason, ..
+            } => (PARSE_ERROR_RULE_NAME, reason, location, None),
+            Self::RuleViolation {
+                rule,
+                message,
+                location,
+                suggestion,
+                ..
+            } => (*rule, message, location, suggestion.as_deref()),
+            Self::Io(message) | Self::Config(message) => return json!({ "message": message }),
+        };
+
+        json!({
+            "rule": rule,
+            "message": message,
+            "line": location.start_line,
+            "column": location.start_column,
+            "endLine": location.end_line,
+            "endColumn": location.end_column,
+            "suggestion": suggestion,
+        })
+    }
+}
+
+impl std::fmt::Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseError {
+                location, reason, ..
+            } => write!(f, "parser error at {}, reason: {}", location, reason),
+            Self::RuleViolation {
+                message, location, ..
+            } => write!(
+                f,
+                "{}: {} (at {})",
+                CHANGES_REQUIRED_ERR_MSG, message, location
+            ),
+            Self::Io(message) | Self::Config(message) => write!(f, "{}", message),
+        }
+    }
+}
@@ -1,35 +1,284 @@
+use std::path::{Path, PathBuf};
+
 use chumsky::prelude::*;
 use chumsky::span::SimpleSpan;
 use convert_case::Casing;
 
 use crate::{
+    bindings,
     config::Config,
-    helpers,
-    parser::{self, ComplexToken::*, FunctionInfo, StructInfo, Token, Type},
-    rules::{Case, IndentationRule, NewLineOnOpenBrace},
+    error::{FormatError, Location},
+    helpers, includes,
+    parser::{
+        self, ComplexToken::*, ConstantBufferInfo, EnumInfo, FunctionInfo, LayoutToken, Span,
+        StructField, StructInfo, Token, Type,
+    },
+    preprocessor, progress,
+    rules::{
+        self, ArgumentWrap, Case, EmptyBodyStyle, EnumValueLayout, FunctionDocsPlacement,
+        IndentationRule, LineEnding, NewLineOnOpenBrace, PrefixScope, RequireBraces, Severity,
+        ShaderStage, StatementAttributePlacement, TrailingComma,
+    },
 };
 
-/// Text that we append to the beginning of an error message if manual changes (in the code) are required
-/// (like changing a variable's case).
-pub const CHANGES_REQUIRED_ERR_MSG: &str = "changes required";
-
 /// Comments used to tell the formatter to don't format (ignore) some lines of code.
-const NOFORMAT_BEGIN_COMMENT: &str = " NOFORMATBEGIN";
-const NOFORMAT_END_COMMENT: &str = " NOFORMATEND";
+pub const NOFORMAT_BEGIN_COMMENT: &str = " NOFORMATBEGIN";
+pub const NOFORMAT_END_COMMENT: &str = " NOFORMATEND";
+
+/// Characters that can be used in "section divider" comments (like `//-----`).
+const COMMENT_BANNER_CHARS: [char; 8] = ['-', '=', '*', '#', '~', '_', '+', '/'];
+
+/// Minimum number of repeated characters in a comment to consider it a "section divider".
+const MIN_COMMENT_BANNER_LENGTH: usize = 4;
 
 #[cfg(windows)]
 const LINE_ENDING: &str = "\r\n";
 #[cfg(not(windows))]
 const LINE_ENDING: &str = "\n";
 
+/// Binary operators that long expressions can be split at (longer operators go first).
+const BINARY_OPERATORS: [&str; 23] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<<", ">>", "+=", "-=", "*=", "/=", "+", "-", "*", "/",
+    "%", "&", "|", "^", "<", ">", "=",
+];
+
+/// Names of HLSL scalar types that are also used in names of vector and matrix types
+/// (like `float3` or `float3x4`).
+const HLSL_NUMERIC_TYPE_NAMES: [&str; 14] = [
+    "bool",
+    "int",
+    "uint",
+    "dword",
+    "half",
+    "float",
+    "double",
+    "min16float",
+    "min10float",
+    "min16int",
+    "min12int",
+    "min16uint",
+    "int64_t",
+    "uint64_t",
+];
+
+/// Groups of qualifiers of declarations in their canonical order (see `SortQualifiers` rule).
+const QUALIFIER_ORDER: [&[&str]; 7] = [
+    &["invariant"],
+    &[
+        "flat",
+        "smooth",
+        "noperspective",
+        "nointerpolation",
+        "linear",
+    ],
+    &["centroid", "sample", "patch"],
+    &[
+        "in",
+        "out",
+        "inout",
+        "uniform",
+        "buffer",
+        "shared",
+        "static",
+        "groupshared",
+        "attribute",
+        "varying",
+    ],
+    &["precise"],
+    &["const"],
+    &["highp", "mediump", "lowp"],
+];
+
+/// Names of HLSL attributes of statements (like `unroll` in `[unroll] for (...)`).
+const HLSL_STATEMENT_ATTRIBUTE_NAMES: [&str; 8] = [
+    "unroll",
+    "loop",
+    "fastopt",
+    "allow_uav_condition",
+    "branch",
+    "flatten",
+    "forcecase",
+    "call",
+];
+
+/// Prefix of GLSL built-in names (like `gl_Position` or `gl_PerVertex`) that can't be changed.
+const GLSL_BUILTIN_PREFIX: &str = "gl_";
+
+/// Prefix of HLSL system-value semantics (like `SV_Position`).
+const SYSTEM_VALUE_SEMANTIC_PREFIX: &str = "SV_";
+
+/// Names of HLSL system-value semantics (without indices like `0` in `SV_Target0`).
+const HLSL_SYSTEM_VALUE_SEMANTICS: [&str; 32] = [
+    "SV_Barycentrics",
+    "SV_ClipDistance",
+    "SV_Coverage",
+    "SV_CullDistance",
+    "SV_CullPrimitive",
+    "SV_Depth",
+    "SV_DepthGreaterEqual",
+    "SV_DepthLessEqual",
+    "SV_DispatchThreadID",
+    "SV_DomainLocation",
+    "SV_GroupID",
+    "SV_GroupIndex",
+    "SV_GroupThreadID",
+    "SV_GSInstanceID",
+    "SV_InnerCoverage",
+    "SV_InsideTessFactor",
+    "SV_InstanceID",
+    "SV_IsFrontFace",
+    "SV_OutputControlPointID",
+    "SV_Position",
+    "SV_PrimitiveID",
+    "SV_RenderTargetArrayIndex",
+    "SV_SampleIndex",
+    "SV_ShadingRate",
+    "SV_StartInstanceLocation",
+    "SV_StartVertexLocation",
+    "SV_StencilRef",
+    "SV_Target",
+    "SV_TessFactor",
+    "SV_VertexID",
+    "SV_ViewID",
+    "SV_ViewportArrayIndex",
+];
+
+/// Names of semantics, attributes, functions and built-in variables that are only used
+/// in a specific shader stage.
+const SHADER_STAGE_MARKERS: [(&str, ShaderStage); 36] = [
+    ("SV_VertexID", ShaderStage::Vertex),
+    ("SV_InstanceID", ShaderStage::Vertex),
+    ("gl_VertexID", ShaderStage::Vertex),
+    ("gl_VertexIndex", ShaderStage::Vertex),
+    ("gl_InstanceID", ShaderStage::Vertex),
+    ("gl_InstanceIndex", ShaderStage::Vertex),
+    ("SV_Target", ShaderStage::Fragment),
+    ("SV_Depth", ShaderStage::Fragment),
+    ("SV_IsFrontFace", ShaderStage::Fragment),
+    ("discard", ShaderStage::Fragment),
+    ("gl_FragCoord", ShaderStage::Fragment),
+    ("gl_FragColor", ShaderStage::Fragment),
+    ("gl_FragData", ShaderStage::Fragment),
+    ("gl_FragDepth", ShaderStage::Fragment),
+    ("gl_FrontFacing", ShaderStage::Fragment),
+    ("gl_PointCoord", ShaderStage::Fragment),
+    ("numthreads", ShaderStage::Compute),
+    ("SV_DispatchThreadID", ShaderStage::Compute),
+    ("SV_GroupID", ShaderStage::Compute),
+    ("SV_GroupThreadID", ShaderStage::Compute),
+    ("SV_GroupIndex", ShaderStage::Compute),
+    ("local_size_x", ShaderStage::Compute),
+    ("gl_GlobalInvocationID", ShaderStage::Compute),
+    ("gl_LocalInvocationID", ShaderStage::Compute),
+    ("gl_WorkGroupID", ShaderStage::Compute),
+    ("maxvertexcount", ShaderStage::Geometry),
+    ("EmitVertex", ShaderStage::Geometry),
+    ("patchconstantfunc", ShaderStage::Hull),
+    ("outputcontrolpoints", ShaderStage::Hull),
+    ("SV_OutputControlPointID", ShaderStage::Hull),
+    ("SV_DomainLocation", ShaderStage::Domain),
+    ("gl_TessCoord", ShaderStage::Domain),
+    ("SetMeshOutputCounts", ShaderStage::Mesh),
+    ("SetMeshOutputsEXT", ShaderStage::Mesh),
+    ("DispatchMesh", ShaderStage::Amplification),
+    ("EmitMeshTasksEXT", ShaderStage::Amplification),
+];
+
+/// Place where a checked variable is declared (used to pick naming rules).
+#[derive(Clone, Copy, PartialEq)]
+enum VariableScope {
+    /// Variable in the global scope.
+    Global,
+    /// Local variable or a function argument.
+    Local,
+    /// Local variable declared in the header of a `for` loop (like `i` in `for (int i = 0; ...)`).
+    LoopCounter,
+    /// `static` variable in the global or a local scope.
+    Static { is_global: bool },
+    /// Field of a struct.
+    Field,
+    /// Field of a constant buffer with `ConstantBufferFieldPrefix` (the prefix is already removed).
+    ConstantBufferField,
+}
+
+impl VariableScope {
+    /// Returns `true` if the variable is declared in the global scope.
+    fn is_global(self) -> bool {
+        matches!(self, Self::Global | Self::Static { is_global: true })
+    }
+
+    /// Returns `true` if the variable is checked by a type prefix rule with the specified scope.
+    fn is_in_prefix_scope(self, prefix_scope: PrefixScope) -> bool {
+        match prefix_scope {
+            PrefixScope::All => true,
+            PrefixScope::GlobalsOnly => self.is_global() || self == Self::ConstantBufferField,
+        }
+    }
+}
+
+/// A line of formatted code.
+struct CodeLine<'a> {
+    /// Text of the line without a line ending.
+    code: &'a str,
+    /// Line ending (empty for the last line).
+    ending: &'a str,
+    /// `false` if the line is a comment, a preprocessor directive or should not be formatted.
+    is_code: bool,
+}
+
+impl<'a> CodeLine<'a> {
+    /// Returns code of the line without a comment at the end of the line.
+    fn code_without_comment(&self) -> &'a str {
+        match self.code.find("//") {
+            Some(comment_start) => &self.code[..comment_start],
+            None => self.code,
+        }
+    }
+}
+
 /// Applies rules on files.
 pub struct Formatter {
     config: Config,
+    /// Path to the file that is being formatted (if known).
+    path_to_shader: Option<PathBuf>,
+    /// Stage of the shader that is being formatted (if known).
+    shader_stage: Option<ShaderStage>,
+    /// Whether to print warnings as `file:line:col: rule: message`.
+    compact_output: bool,
+    /// Whether to only check lint rules without changing the code.
+    lint_only: bool,
 }
 
 impl Formatter {
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            path_to_shader: None,
+            shader_stage: None,
+            compact_output: false,
+            lint_only: false,
+        }
+    }
+
+    /// Sets path to the file that will be formatted, used to resolve relative includes and
+    /// to determine the shader stage by the file extension (if not determined the stage is
+    /// detected using the code of the shader).
+    pub fn set_path_to_shader(&mut self, path: &Path) {
+        self.path_to_shader = Some(path.to_path_buf());
+        self.shader_stage = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(ShaderStage::from_file_extension);
+    }
+
+    /// Sets whether warnings are printed in the compact `file:line:col: rule: message` format.
+    pub fn set_compact_output(&mut self, compact_output: bool) {
+        self.compact_output = compact_output;
+    }
+
+    /// Sets whether only lint rules are checked (the code is returned unchanged).
+    pub fn set_lint_only(&mut self, lint_only: bool) {
+        self.lint_only = lint_only;
     }
 
     /// Formats the specified content according to the formatting rules from config.
@@ -39,941 +288,4852 @@ impl Formatter {
     /// - `print_tokens` Defines whether or not to print parsed token to stdout (used for debugging).
     ///
     /// # Return
-    /// `Ok(String)` if successful with formatted content, otherise `Err(String)` with a meaningful
-    /// error message.
-    pub fn format(&self, content: &str, print_tokens: bool) -> Result<String, String> {
+    /// `Ok(String)` if successful with formatted content, otherise `Err(FormatError)` that describes
+    /// the problem.
+    pub fn format(&self, content: &str, print_tokens: bool) -> Result<String, FormatError> {
+        let mut warnings = Vec::new();
+        let result = self.format_collecting_warnings(content, print_tokens, &mut warnings);
+
+        // Show skipped code and violations of rules with `Warning` severity.
+        for warning in &warnings {
+            let description = match warning {
+                FormatError::ParseError { .. } => "skipped unparsable code, ",
+                _ => "",
+            };
+            self.print_warning(description, warning);
+        }
+
+        result
+    }
+
+    /// Same as `format` but instead of printing warnings (skipped unparsable code and violations
+    /// of rules with `Warning` severity) adds them to the specified array.
+    pub fn format_collecting_warnings(
+        &self,
+        content: &str,
+        print_tokens: bool,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<String, FormatError> {
+        // Use rules of the shader stage.
+        let shader_stage = self
+            .shader_stage
+            .or_else(|| Self::detect_shader_stage(content));
+        let mut formatter = Self {
+            config: self.config.get_stage_config(shader_stage),
+            path_to_shader: self.path_to_shader.clone(),
+            shader_stage,
+            compact_output: self.compact_output,
+            lint_only: self.lint_only,
+        };
+        if formatter.config.indentation == IndentationRule::Auto {
+            formatter.config.indentation = Self::detect_indentation(content);
+        }
+        if formatter.config.new_line_around_braces == NewLineOnOpenBrace::Auto {
+            formatter.config.new_line_around_braces = Self::detect_brace_placement(content);
+        }
+
+        match formatter.format_shader(content, print_tokens, warnings) {
+            Ok(output) if !formatter.lint_only => Ok(formatter.apply_line_ending_rules(output)),
+            result => result,
+        }
+    }
+
+    /// Formats the specified content according to the formatting rules from config without
+    /// looking for stage-specific rules.
+    ///
+    /// # Arguments
+    /// - `content` Text to format.
+    /// - `print_tokens` Defines whether or not to print parsed token to stdout (used for debugging).
+    /// - `located_warnings` Collects warnings (with locations in the checked text).
+    fn format_shader(
+        &self,
+        content: &str,
+        print_tokens: bool,
+        located_warnings: &mut Vec<FormatError>,
+    ) -> Result<String, FormatError> {
         // Exit on empty input.
         if content.is_empty() {
             return Ok(content.to_owned());
         }
 
-        // Apply rules that don't need tokens.
-        let output = self.apply_simple_rules(content);
-        if let Err(msg) = output {
-            return Err(format!("{}: {}", CHANGES_REQUIRED_ERR_MSG, msg));
-        }
-        let output = output.unwrap();
+        let output = if self.lint_only {
+            content.to_owned()
+        } else {
+            self.apply_formatting_rules(content)?
+        };
 
         // Parse tokens.
         let (tokens, errors) = parser::token_parser()
             .parse(output.as_str())
             .into_output_errors();
 
-        // Show any errors.
-        if !errors.is_empty() {
-            if let Some(error) = errors.into_iter().next() {
-                let (line, column) =
-                    helpers::span_offset_to_line_and_column(error.span().start, output.as_str());
-                let reason = error.reason();
-
-                return Err(format!(
-                    "token parser error at line {} column {}, reason: {}",
-                    line, column, reason
-                ));
+        // Parts of the code that we failed to parse and skipped.
+        let mut warnings = Vec::new();
+
+        // Show any errors (only fail if we were unable to recover).
+        for error in errors {
+            let error = FormatError::ParseError {
+                span: *error.span(),
+                location: Location::default(),
+                reason: error.reason().to_string(),
+            };
+            if tokens.is_none() {
+                return Err(error.locate(&output));
             }
+            warnings.push(error);
         }
 
         // Exit of no tokens returned (not an error).
         if tokens.is_none() {
             return Ok(output);
         }
-        let tokens: Vec<(parser::Token<'_>, SimpleSpan)> = tokens.unwrap();
+        let tokens: Vec<(parser::Token<'_>, SimpleSpan)> =
+            parser::split_template_closing_shifts(tokens.unwrap());
 
         // Print tokens if needed.
         if print_tokens {
             println!("parsed tokens:");
+            let line_index = helpers::LineIndex::new(&output);
             for token in &tokens {
-                let (line, column) =
-                    helpers::span_offset_to_line_and_column(token.1.start, content);
+                let (line, column) = line_index.line_and_column(token.1.start);
                 println!("[line {}, column {}] {}", line, column, token.0);
             }
             println!("------------------------------------\n");
         }
 
+        // Add missing braces and format the code again (to indent the bodies).
+        if !self.lint_only {
+            let edits = self.get_missing_braces(&output, &tokens);
+            if !edits.is_empty() {
+                return self.format_shader(
+                    &Self::apply_edits(&output, edits),
+                    false,
+                    located_warnings,
+                );
+            }
+        }
+
+        // Collect custom types defined in this file and in included files.
+        let mut known_types = parser::collect_type_names(&tokens);
+        let mut visited_files: Vec<PathBuf> = self
+            .path_to_shader
+            .iter()
+            .map(|path| path.canonicalize().unwrap_or(path.clone()))
+            .collect();
+        known_types.extend(includes::collect_included_types(
+            &tokens,
+            self.path_to_shader.as_ref().and_then(|path| path.parent()),
+            &self.config.include_dirs,
+            &mut visited_files,
+        ));
+
         // Parse statements.
-        let (complex_tokens, errors) = parser::complex_token_parser()
+        let (complex_tokens, errors) = parser::complex_token_parser(known_types)
             .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
             .into_output_errors();
 
-        // Show any errors.
-        if !errors.is_empty() {
-            if let Some(error) = errors.into_iter().next() {
-                let (line, column) =
-                    helpers::span_offset_to_line_and_column(error.span().start, output.as_str());
-                let reason = error.reason();
-                return Err(format!(
-                    "statement parser error at line {} column {}, reason: {}",
-                    line, column, reason
-                ));
+        // Show any errors (only fail if we were unable to recover).
+        for error in errors {
+            let error = FormatError::ParseError {
+                span: *error.span(),
+                location: Location::default(),
+                reason: error.reason().to_string(),
+            };
+            if complex_tokens.is_none() {
+                return Err(error.locate(&output));
             }
+            warnings.push(error);
         }
 
-        match complex_tokens {
-            None => Ok(output), // nothing to do here
-            Some(tokens) => {
+        // Changes to apply to the output (if all checks will pass).
+        let mut edits: Vec<(Span, String)> = Vec::new();
+
+        let check_result = match complex_tokens {
+            None => Ok(()), // nothing to do here
+            Some(complex_tokens) => {
                 // Print tokens if needed.
                 if print_tokens {
                     println!("parsed complex tokens:");
-                    for token in &tokens {
-                        let (line, column) =
-                            helpers::span_offset_to_line_and_column(token.1.start, content);
+                    let line_index = helpers::LineIndex::new(&output);
+                    for token in &complex_tokens {
+                        let (line, column) = line_index.line_and_column(token.1.start);
                         println!("[line {}, column {}] {}", line, column, token.0);
                     }
                     println!("------------------------------------\n");
                 }
 
-                // Check rules.
-                match self.check_complex_rules(tokens) {
-                    Ok(_) => Ok(output), // everything is fine
-                    Err(msg) => Err(format!("{}: {}", CHANGES_REQUIRED_ERR_MSG, msg)),
-                }
-            }
-        }
-    }
-
-    /// Applies the most simplest formatting rules that do not require
-    /// any prior parsing (no tokens required).
-    ///
-    /// # Return
-    /// `Ok` with formatted code or `Err` with an error message.
-    fn apply_simple_rules(&self, content: &str) -> Result<String, String> {
-        // Prepare indentation text.
-        let indentation_text = match self.config.indentation {
-            IndentationRule::Tab => "\t",
-            IndentationRule::TwoSpaces => "  ",
-            IndentationRule::FourSpaces => "    ",
-        };
-
-        let mut output = String::with_capacity(content.len());
-
-        // Prepare some handy variables...
-
-        // For nesting.
-        let mut nesting_count: usize = 0;
-
-        // For new lines.
-        let mut consecutive_empty_new_line_count: usize = 0;
-        let mut is_on_new_line = true;
-        let mut ignore_until_text = false;
-        let mut stop_ignoring_if_end_of_line = false;
-
-        // For comments.
-        let mut inside_c_comment_count: usize = 0;
-        let mut inside_comment = false;
-        let mut last_comment_line = String::new(); // contains last found line of comment
-
-        // For preprocessor directives.
-        let mut preproc_add_nesting_on_next_line = false;
-        let mut line_started_with_preprocessor = false;
-
-        // For macros.
-        let mut last_non_space_char_is_backslash = false;
-        let mut prev_line_ended_with_backslash = false;
+                // Format enum values and inheritance colons.
+                if !self.lint_only {
+                    for (token, _) in &complex_tokens {
+                        match token {
+                            Enum(info) => {
+                                if let Some(layout) = self.config.enum_value_layout {
+                                    if let Some(text) =
+                                        self.format_enum_values(&output, info, layout)
+                                    {
+                                        edits.push((info.body_span, text));
+                                    }
+                                }
+                            }
+                            Struct(info) => {
+                                self.format_inheritance_colons(&output, info, &mut edits);
+                                if self.config.require_ordered_param_docs {
+                                    Self::reorder_method_param_docs(&output, info, &mut edits);
+                                }
+                                if self.config.normalize_default_value_spacing {
+                                    Self::normalize_method_default_value_spacing(
+                                        &output, &tokens, info, &mut edits,
+                                    );
+                                }
+                            }
+                            Function(info) => {
+                                if self.config.require_ordered_param_docs {
+                                    edits.extend(
+                                        Self::reorder_param_docs(&output, info).unwrap_or_default(),
+                                    );
+                                }
+                                if self.config.normalize_default_value_spacing {
+                                    edits.extend(Self::normalize_default_value_spacing(
+                                        &output, &tokens, info,
+                                    ));
+                                }
+                            }
+                            ConstantBuffer(info) => {
+                                for nested_info in &info.nested {
+                                    self.format_inheritance_colons(
+                                        &output,
+                                        nested_info,
+                                        &mut edits,
+                                    );
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
 
-        // Other.
-        let mut last_3_chars = [' '; 3];
-        let mut inside_no_format = false;
+                    if self.config.remove_redundant_semicolons {
+                        edits.extend(Self::remove_redundant_semicolons(&output, &tokens));
+                    }
 
-        for _char in content.chars() {
-            // Just ignore '\r's.
-            if _char == '\r' {
-                if inside_no_format {
-                    output.push(_char);
-                }
-                continue;
-            }
+                    if self.config.normalize_local_size_layout {
+                        edits.extend(Self::normalize_local_size_layouts(&output, &tokens));
+                    }
 
-            // Handle new line.
-            if _char == '\n' {
-                let current_line_is_empty = is_on_new_line;
-                is_on_new_line = true;
+                    if self.config.sort_qualifiers {
+                        edits.extend(Self::sort_qualifiers(&output, &tokens));
+                    }
 
-                if !inside_no_format {
-                    if preproc_add_nesting_on_next_line {
-                        nesting_count += 1;
-                        preproc_add_nesting_on_next_line = false;
+                    if self.config.normalize_return_spacing {
+                        edits.extend(Self::normalize_return_spacing(&output, &tokens));
                     }
 
-                    if (!ignore_until_text || stop_ignoring_if_end_of_line)
-                        && consecutive_empty_new_line_count <= self.config.max_empty_lines
-                    {
-                        // Reset state for new lines.
-                        ignore_until_text = false;
-                        stop_ignoring_if_end_of_line = false;
-
-                        if current_line_is_empty {
-                            // Remove indentation that we added.
-                            for _ in 0..nesting_count * indentation_text.chars().count() {
-                                output.pop();
-                            }
-                        }
+                    if self.config.normalize_declaration_spacing {
+                        edits.extend(Self::normalize_declaration_spacing(&output, &tokens));
+                    }
 
-                        // Add a new line.
-                        output += LINE_ENDING;
-                        output += &indentation_text.repeat(nesting_count);
+                    if self.config.empty_line_before_doc_comments {
+                        edits.extend(Self::add_empty_lines_before_doc_comments(&output, &tokens));
+                    }
 
-                        consecutive_empty_new_line_count += 1;
+                    if let Some(trailing_comma) = self.config.trailing_comma_in_initializers {
+                        edits.extend(Self::format_initializer_trailing_commas(
+                            &output,
+                            &tokens,
+                            trailing_comma,
+                        ));
                     }
-                } else {
-                    output.push(_char);
                 }
 
-                continue;
+                // Check rules.
+                self.check_includes(&tokens, &mut warnings)
+                    .and_then(|_| self.check_duplicate_bindings(&tokens, &mut warnings))
+                    .and_then(|_| self.check_locations(&output, &tokens, &mut warnings))
+                    .and_then(|_| self.check_mixed_indentation(&output, &mut warnings))
+                    .and_then(|_| self.check_dead_branches(&tokens, &mut warnings))
+                    .and_then(|_| self.check_macro_redefinitions(&output, &tokens, &mut warnings))
+                    .and_then(|_| self.check_default_precision(&complex_tokens, &mut warnings))
+                    .and_then(|_| self.check_local_size(&tokens, &complex_tokens, &mut warnings))
+                    .and_then(|_| {
+                        self.check_param_docs_order(&output, &complex_tokens, &mut warnings)
+                    })
+                    .and_then(|_| {
+                        self.check_complex_rules(&output, &tokens, complex_tokens, &mut warnings)
+                    })
             }
+        };
 
-            if is_on_new_line {
-                inside_comment = false;
-
-                prev_line_ended_with_backslash = last_non_space_char_is_backslash;
+        located_warnings.extend(warnings.into_iter().map(|warning| warning.locate(&output)));
 
-                // Find where text starts.
-                if _char != ' ' && _char != '\t' {
-                    is_on_new_line = false;
-                    ignore_until_text = false;
-                    stop_ignoring_if_end_of_line = false;
-                    consecutive_empty_new_line_count = 0;
+        if let Err(error) = check_result {
+            return Err(error.locate(&output));
+        }
 
-                    if !inside_no_format {
-                        if _char == '#' {
-                            line_started_with_preprocessor = true;
+        if edits.is_empty() {
+            return Ok(output);
+        }
 
-                            if !self.config.indent_preprocessor {
-                                // Remove everything until the beginning of the line.
-                                let mut chars_to_remove = 0;
-                                for check in output.chars().rev() {
-                                    if check != ' ' && check != '\t' {
-                                        break;
-                                    }
-                                    chars_to_remove += 1;
-                                }
-                                for _ in 0..chars_to_remove {
-                                    output.pop();
-                                }
-                            }
-                        } else {
-                            line_started_with_preprocessor = false;
-
-                            if _char == '=' {
-                                // Since this is the first character on the line,
-                                // add an additional indentation because this line is probably too long
-                                // and was split into 2 lines, example:
-                                // int some_long_variable_name
-                                //     = ...;
-                                output += indentation_text;
-                            }
-                        }
+        Ok(Self::apply_edits(&output, edits))
+    }
 
-                        if inside_c_comment_count > 0 && _char == '*' {
-                            // Add a single space for C-style comments to look good.
-                            output.push(' ');
-                        }
-                    }
-                } else {
-                    if inside_no_format {
-                        output.push(_char);
-                    }
-                    continue;
-                }
+    /// Changes line endings and the line break at the end of the text (if specified in the config).
+    fn apply_line_ending_rules(&self, mut text: String) -> String {
+        if let Some(line_ending) = self.config.line_ending {
+            text = text.replace("\r\n", "\n");
+            if line_ending == LineEnding::CrLf {
+                text = text.replace('\n', "\r\n");
             }
+        }
 
-            if ignore_until_text {
-                if _char != ' ' && _char != '\t' {
-                    ignore_until_text = false;
-                    stop_ignoring_if_end_of_line = false;
-                } else {
-                    continue;
-                }
+        match self.config.insert_final_newline {
+            Some(true) if !text.is_empty() && !text.ends_with('\n') => {
+                text += self
+                    .config
+                    .line_ending
+                    .map_or(LINE_ENDING, |ending| ending.as_str());
             }
+            Some(false) => text.truncate(text.trim_end_matches(['\r', '\n']).len()),
+            _ => {}
+        }
 
-            // Detect a C-style comment.
-            if last_3_chars[1] == '/' && last_3_chars[2] == '*' && (_char == '*' || _char == '!') {
-                inside_c_comment_count += 1;
-            } else if last_3_chars[1] == '*' && last_3_chars[2] == '/' {
-                inside_c_comment_count = inside_c_comment_count.saturating_sub(1);
-            }
+        text
+    }
 
-            if self.config.preprocessor_if_creates_nesting
-                && self.config.indent_preprocessor
-                && !inside_comment
-            {
-                if last_3_chars[1] == '#' && last_3_chars[2] == 'i' && _char == 'f' {
-                    preproc_add_nesting_on_next_line = true;
-                } else if (last_3_chars[0] == '#' && last_3_chars[1] == 'e')
-                    && ((last_3_chars[2] == 'n' && _char == 'd')
-                        || (last_3_chars[2] == 'l' && _char == 'i')
-                        || (last_3_chars[2] == 'l' && _char == 's'))
-                {
-                    // Remove everything until the beginning of the line.
-                    let mut chars_to_remove = last_3_chars.len(); // skip already added chars
-                    for check in output.chars().rev().skip(last_3_chars.len()) {
-                        if check != ' ' && check != '\t' {
-                            break;
-                        }
-                        chars_to_remove += 1;
-                    }
-                    for _ in 0..chars_to_remove {
-                        output.pop();
-                    }
+    /// Applies rules that don't need parsed statements (like indentation and spacing).
+    fn apply_formatting_rules(&self, content: &str) -> Result<String, FormatError> {
+        // Apply rules that don't need tokens.
+        let mut output = self
+            .apply_simple_rules(content)
+            .map_err(|error| error.locate(content))?;
 
-                    // Decrease nesting.
-                    nesting_count = nesting_count.saturating_sub(1);
+        // Normalize "section divider" comments.
+        if let Some(banner_char) = self.config.comment_banner_char {
+            output = self.normalize_comment_banners(&output, banner_char);
+        }
 
-                    // Add new nesting.
-                    output += &indentation_text.repeat(nesting_count);
+        // Wrap long lines.
+        if let Some(max_line_length) = self.config.max_line_length {
+            output = self.wrap_long_lines(&output, max_line_length);
+        }
 
-                    // Add removed chars.
-                    output += &last_3_chars.iter().collect::<String>();
+        // Place operators of multi-line expressions.
+        if let Some(break_before) = self.config.break_before_binary_operators {
+            output = self.place_binary_operators(&output, break_before);
+        }
 
-                    if _char == 'i' || _char == 's' {
-                        // #elif or #else
-                        preproc_add_nesting_on_next_line = true;
-                    }
-                }
-            }
+        Ok(output)
+    }
 
-            // Determine if we are inside of a comment.
-            if last_3_chars[1] == '/' && last_3_chars[2] == '/' {
-                inside_comment = true;
-                last_comment_line = String::new();
+    /// Returns the specified rule violation as an error or (if the rule has `Warning` severity)
+    /// saves it to `warnings` so that checks continue and all violations of the rule are reported.
+    fn report(
+        &self,
+        error: FormatError,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if let FormatError::RuleViolation { rule, .. } = &error {
+            if self.config.severity(rule) == Severity::Warning {
+                warnings.push(error);
+                return Ok(());
             }
+        }
 
-            // Update last input chars.
-            last_3_chars[0] = last_3_chars[1];
-            last_3_chars[1] = last_3_chars[2];
-            last_3_chars[2] = _char;
-
-            if inside_comment || inside_c_comment_count > 0 {
-                // Just copy the char, don't do anything else.
-                output.push(_char);
-                last_comment_line.push(_char);
+        Err(error)
+    }
 
-                // Check if we don't need to format code.
-                if last_comment_line == NOFORMAT_BEGIN_COMMENT {
-                    inside_no_format = true;
-                } else if last_comment_line == NOFORMAT_END_COMMENT {
-                    inside_no_format = false;
-                }
+    /// Prints the specified error as a warning to stderr.
+    ///
+    /// # Arguments
+    /// - `description` Text to print before the error (ignored in the compact format).
+    /// - `error` Located error.
+    fn print_warning(&self, description: &str, error: &FormatError) {
+        // Don't print on the same line as the progress bar.
+        progress::clear_bar();
+
+        if self.compact_output {
+            let path = self.path_to_shader.as_deref().unwrap_or(Path::new(""));
+            eprintln!("{}", error.to_compact(path));
+        } else {
+            eprintln!("warning: {}{}", description, error);
+        }
+    }
 
-                continue;
-            } else if inside_no_format {
-                // Just copy the char, don't run any additional logic.
-                output.push(_char);
+    /// Replaces the specified spans of the text in a single pass (instead of shifting the rest
+    /// of the text on each change).
+    ///
+    /// Insertions (empty spans) are applied before replacements that start at the same position,
+    /// edits that overlap an already applied edit are ignored.
+    fn apply_edits(text: &str, mut edits: Vec<(Span, String)>) -> String {
+        edits.sort_by_key(|(span, _)| (span.start, span.end));
+
+        let mut output = String::with_capacity(text.len());
+        let mut last_edit_end = 0;
+        for (span, new_text) in edits {
+            if span.start < last_edit_end {
                 continue;
             }
+            output += &text[last_edit_end..span.start];
+            output += &new_text;
+            last_edit_end = span.end;
+        }
+        output += &text[last_edit_end..];
 
-            if _char != ' ' {
-                last_non_space_char_is_backslash = _char == '\\';
-            }
+        output
+    }
 
-            if _char == '{' {
-                // Remove everything until text.
-                let mut chars_to_remove = 0;
-                let mut text_starts_with_backslash = false;
-                for check in output.chars().rev() {
-                    if check != ' ' && check != '\t' && check != '\n' && check != '\r' {
-                        text_starts_with_backslash = check == '\\';
-                        break;
+    /// Splits lines that are longer than the specified limit according to the wrapping rules.
+    ///
+    /// # Arguments
+    /// - `text` Formatted text.
+    /// - `max_line_length` Maximum length of a line.
+    fn wrap_long_lines(&self, text: &str, max_line_length: usize) -> String {
+        let mut output = String::with_capacity(text.len());
+
+        let mut paren_depth: usize = 0; // to skip lines that were already wrapped
+
+        for line in Self::split_code_lines(text) {
+            let is_continuation_line = paren_depth > 0;
+            if line.is_code {
+                for _char in line.code_without_comment().chars() {
+                    match _char {
+                        '(' => paren_depth += 1,
+                        ')' => paren_depth = paren_depth.saturating_sub(1),
+                        _ => {}
                     }
+                }
+            }
+            if !line.is_code || is_continuation_line || line.code.chars().count() <= max_line_length
+            {
+                output += line.code;
+                output += line.ending;
+                continue;
+            }
 
-                    chars_to_remove += 1;
+            let mut wrapped = None;
+            if self.config.spaces_around_ternary_operator {
+                wrapped = self.wrap_ternary_expression(line.code);
+            }
+            if wrapped.is_none() && !self.config.bin_pack_parameters {
+                wrapped = self.wrap_arguments(line.code, true, ArgumentWrap::AlignWithParenthesis);
+            }
+            if wrapped.is_none() {
+                if let Some(style) = self.config.argument_wrap {
+                    wrapped = self.wrap_arguments(line.code, false, style);
                 }
-                for _ in 0..chars_to_remove {
-                    output.pop();
+            }
+            if wrapped.is_none() {
+                if let Some(break_before) = self.config.break_before_binary_operators {
+                    wrapped = self.wrap_binary_expression(line.code, max_line_length, break_before);
                 }
+            }
+            match wrapped {
+                Some(wrapped) => output += &wrapped,
+                None => output += line.code,
+            }
+            output += line.ending;
+        }
 
-                // Handle new line.
-                match self.config.new_line_around_braces {
-                    NewLineOnOpenBrace::After => {
-                        if prev_line_ended_with_backslash && text_starts_with_backslash {
-                            // Most likelly we got here from this code:
-                            // #define MACRO \
-                            // ...           \
-                            // {
-                            // and now we have:
-                            // #define MACRO \
-                            // ...           \{
+        output
+    }
 
-                            // Remove backslash.
-                            output.pop();
+    /// Replaces characters of "section divider" comments (like `//-----` or `//=====`) with
+    /// the specified character and changes their width (if specified in the config).
+    fn normalize_comment_banners(&self, text: &str, banner_char: char) -> String {
+        let mut output = String::with_capacity(text.len());
+
+        for line in text.split_inclusive('\n') {
+            let code = line.trim_end();
+            let indentation = &code[..code.len() - code.trim_start().len()];
+
+            let banner_length = code.trim_start().strip_prefix("//").and_then(|comment| {
+                let first_char = comment.chars().next()?;
+                let length = comment.chars().count();
+                let is_banner = COMMENT_BANNER_CHARS.contains(&first_char)
+                    && length >= MIN_COMMENT_BANNER_LENGTH
+                    && comment.chars().all(|_char| _char == first_char);
+                is_banner.then_some(length)
+            });
+            let Some(mut banner_length) = banner_length else {
+                output += line;
+                continue;
+            };
+            if Self::is_inside_no_format(text, output.len()) {
+                output += line;
+                continue;
+            }
 
-                            // Remove everything until text.
-                            let mut chars_to_remove = 0;
-                            for check in output.chars().rev() {
-                                if check != ' ' && check != '\t' && check != '\n' && check != '\r' {
-                                    break;
-                                }
+            if let Some(width) = self.config.comment_banner_width {
+                banner_length = width.saturating_sub(indentation.chars().count() + 2).max(1);
+            }
 
-                                chars_to_remove += 1;
-                            }
-                            for _ in 0..chars_to_remove {
-                                output.pop();
-                            }
-                        }
+            output += indentation;
+            output += "//";
+            output.extend(std::iter::repeat_n(banner_char, banner_length));
+            output += &line[code.len()..];
+        }
 
-                        // Make sure previous line is not a comment otherwise our stuff will be inside of a comment:
-                        // struct Foo // comment
-                        // {
-                        // can become this:
-                        // struct Foo // comment {
-                        let mut found_comment = false;
-
-                        // Read the previous line.
-                        let mut line_before = String::new();
-                        for check in output.chars().rev() {
-                            if check == '\n' {
-                                break;
-                            }
-                            line_before.push(check);
-                        }
-                        line_before = line_before.chars().rev().collect();
-
-                        if !Self::is_text_starts_with_comment(&line_before) {
-                            // See if it has a comment.
-                            let line_before_chars_count = line_before.chars().count();
-                            let mut skipped_chars_count: usize = 0;
-                            let mut copy_chars = false;
-                            let mut last_empty_chars_count: usize = 0;
-                            let mut line_before_iter = line_before.chars().peekable();
-                            while let Some(prev_line_char) = line_before_iter.next() {
-                                if copy_chars {
-                                    output.push(prev_line_char);
-                                }
+        output
+    }
 
-                                if prev_line_char == '/' {
-                                    if let Some('/') = line_before_iter.peek() {
-                                        found_comment = true;
-
-                                        // Remove everything until this comment.
-                                        #[cfg(windows)]
-                                        {
-                                            // `-1` to also consider `\r`
-                                            chars_to_remove +=
-                                                line_before_chars_count - skipped_chars_count - 1;
-                                        }
-                                        #[cfg(not(windows))]
-                                        {
-                                            chars_to_remove +=
-                                                line_before_chars_count - skipped_chars_count;
-                                        }
-
-                                        // Also remove empty text before this comment.
-                                        chars_to_remove += last_empty_chars_count.saturating_sub(1);
-
-                                        for _ in 0..chars_to_remove {
-                                            output.pop();
-                                        }
-
-                                        // Add a brace.
-                                        output.push(' ');
-                                        output.push('{');
-                                        output.push(' ');
-
-                                        // Now copy everything until end of line.
-                                        output.push('/');
-                                        copy_chars = true;
-                                    }
-                                }
+    /// Splits the specified text into lines and marks lines that can be formatted
+    /// (not comments, preprocessor directives or lines between `NOFORMAT` comments).
+    fn split_code_lines(text: &str) -> Vec<CodeLine<'_>> {
+        let mut lines = Vec::new();
 
-                                if prev_line_char == ' ' || prev_line_char == '\t' {
-                                    last_empty_chars_count += 1;
-                                } else {
-                                    last_empty_chars_count = 0;
-                                }
+        let mut inside_c_comment = false;
+        let mut inside_no_format = false;
 
-                                skipped_chars_count += 1;
-                            }
+        for line in text.split_inclusive('\n') {
+            // Separate the line ending.
+            let line_ending_len = if line.ends_with("\r\n") {
+                2
+            } else if line.ends_with('\n') {
+                1
+            } else {
+                0
+            };
+            let (code, ending) = line.split_at(line.len() - line_ending_len);
+
+            let trimmed_code = code.trim_start();
+            let was_inside_c_comment = inside_c_comment;
+            if code.contains(NOFORMAT_BEGIN_COMMENT) {
+                inside_no_format = true;
+            } else if code.contains(NOFORMAT_END_COMMENT) {
+                inside_no_format = false;
+            }
+            let code_before_line_comment = match code.find("//") {
+                Some(comment_start) if !was_inside_c_comment => &code[..comment_start],
+                _ => code,
+            };
+            if let Some(comment_start) = code_before_line_comment.rfind("/*") {
+                inside_c_comment = !code[comment_start..].contains("*/");
+            } else if code.contains("*/") {
+                inside_c_comment = false;
+            }
 
-                            if !found_comment {
-                                // Add a space and a brace.
-                                output.push(' ');
-                                output.push(_char);
-                            }
-                        } else {
-                            // Just put bracket to a new line.
-                            output += LINE_ENDING;
-                            output += &indentation_text.repeat(nesting_count);
-                            output.push(_char);
-                        }
+            lines.push(CodeLine {
+                code,
+                ending,
+                is_code: !inside_no_format
+                    && !was_inside_c_comment
+                    && !trimmed_code.starts_with("//")
+                    && !trimmed_code.starts_with("/*")
+                    && !trimmed_code.starts_with('#'),
+            });
+        }
 
-                        // Increase nesting.
-                        nesting_count += 1;
+        lines
+    }
 
-                        // Before inserting a new line check if we are inside of a multi-line macro.
-                        if !prev_line_ended_with_backslash {
-                            // Insert a new line.
-                            is_on_new_line = true;
-                            output += LINE_ENDING;
-                            output += &indentation_text.repeat(nesting_count);
-                            consecutive_empty_new_line_count += 1;
-                        }
-                    }
-                    NewLineOnOpenBrace::Before => {
-                        // Before inserting a new line check if we are inside of a multi-line macro.
-                        if prev_line_ended_with_backslash {
-                            if let Some(last_char) = output.chars().next_back() {
-                                if last_char != '\\' {
-                                    output.push('\\');
-                                }
+    /// Splits the specified line (without a line ending) at binary operators (like `+` or `&&`)
+    /// so that lines will not exceed the specified limit (if possible).
+    ///
+    /// # Return
+    /// `None` if the line does not have binary operators, otherwise wrapped line.
+    fn wrap_binary_expression(
+        &self,
+        line: &str,
+        max_line_length: usize,
+        break_before: bool,
+    ) -> Option<String> {
+        let line_indentation: String = line
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+        let continuation_indentation = line_indentation + self.get_indentation_text();
+
+        let mut wrapped = String::new();
+        let mut rest = line;
+        loop {
+            // Find binary operators that are not inside of brackets.
+            let code = match rest.find("//") {
+                Some(comment_start) => &rest[..comment_start],
+                None => rest,
+            };
+            let mut candidates: Vec<(usize, &str)> = Vec::new(); // position of the space before operator
+            let mut depth: usize = 0;
+            for (offset, _char) in code.char_indices() {
+                match _char {
+                    '(' | '[' | '{' => depth += 1,
+                    ')' | ']' | '}' => depth = depth.saturating_sub(1),
+                    ' ' if depth == 0 => {
+                        let after_space = &code[offset + 1..];
+                        if let Some(operator) = Self::get_binary_operator_at_start(after_space) {
+                            // Don't split at the beginning of the line.
+                            if !code[..offset].trim().is_empty() {
+                                candidates.push((offset, operator));
                             }
                         }
-
-                        // Insert a new line.
-                        is_on_new_line = true;
-                        output += LINE_ENDING;
-                        output += &indentation_text.repeat(nesting_count);
-
-                        // Add brace.
-                        output.push(_char);
-
-                        if prev_line_ended_with_backslash {
-                            output.push('\\');
-                        }
-
-                        // Add new line with increased nesting.
-                        nesting_count += 1;
-                        output += LINE_ENDING;
-                        output += &indentation_text.repeat(nesting_count);
-                        consecutive_empty_new_line_count += 1;
-                    }
-                }
-
-                // Ignore everything until we find some text.
-                ignore_until_text = true;
-            } else if _char == '}' {
-                // Decrease nesting.
-                nesting_count = nesting_count.saturating_sub(1);
-
-                // Remove everything until text.
-                let mut chars_to_remove = 0;
-                for check in output.chars().rev() {
-                    if check != ' ' && check != '\t' && check != '\n' && check != '\r' {
-                        break;
                     }
-                    chars_to_remove += 1;
-                }
-                for _ in 0..chars_to_remove {
-                    output.pop();
+                    _ => {}
                 }
+            }
 
-                // Don't add a new line if this line was started with `#`
-                // we likelly need to keep the code on the same line.
-                if !line_started_with_preprocessor {
-                    // Add a new line.
-                    output += LINE_ENDING;
-                    output += &indentation_text.repeat(nesting_count);
+            // Take the last operator that fits into the limit.
+            let line_start_length = match wrapped.rfind('\n') {
+                Some(line_start) => wrapped[line_start + 1..].chars().count(),
+                None => 0,
+            };
+            let first_line_length = |(offset, operator): &(usize, &str)| {
+                let length = line_start_length + rest[..*offset].chars().count();
+                if break_before {
+                    length
                 } else {
-                    output.push(' '); // just add a space after text
+                    length + 1 + operator.len()
                 }
+            };
+            let split = candidates
+                .iter()
+                .rev()
+                .find(|candidate| first_line_length(candidate) <= max_line_length)
+                .or(candidates.first());
+            let (offset, operator) = match split {
+                Some(split) => *split,
+                None => break,
+            };
+
+            // Split the line.
+            let continuation = rest[offset + 1 + operator.len()..].trim_start();
+            wrapped += &rest[..offset];
+            if !break_before {
+                wrapped.push(' ');
+                wrapped += operator;
+            }
+            wrapped += LINE_ENDING;
+            wrapped += &continuation_indentation;
+            if break_before {
+                wrapped += operator;
+                wrapped.push(' ');
+            }
+            rest = continuation;
+
+            let line_start_length = match wrapped.rfind('\n') {
+                Some(line_start) => wrapped[line_start + 1..].chars().count(),
+                None => 0,
+            };
+            if line_start_length + rest.chars().count() <= max_line_length {
+                break;
+            }
+        }
 
-                // Copy brace.
-                output.push(_char);
+        if wrapped.is_empty() {
+            return None;
+        }
+        wrapped += rest;
 
-                // struct Foo{
-                // Don't insert a new line here, here is an example why:
-                // };
-                // The `;` will be on the new line if we insert one.
-            } else if _char == '[' || _char == '(' {
-                output.push(_char);
+        Some(wrapped)
+    }
 
-                // Add space if needed.
-                if self.config.spaces_in_brackets {
-                    output.push(' ');
+    /// Splits the first ternary expression (the least nested one) in the specified line (without
+    /// a line ending) so that `?` and `:` start continuation lines.
+    ///
+    /// # Return
+    /// `None` if the line does not have ternary expressions, otherwise wrapped line.
+    fn wrap_ternary_expression(&self, line: &str) -> Option<String> {
+        let code = CodeLine {
+            code: line,
+            ending: "",
+            is_code: true,
+        }
+        .code_without_comment();
+
+        // Find `?` with the smallest nesting.
+        let mut question_mark: Option<(usize, usize)> = None; // offset and depth
+        let mut depth: usize = 0;
+        for (offset, _char) in code.char_indices() {
+            match _char {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth = depth.saturating_sub(1),
+                '?' if question_mark.is_none_or(|(_, min_depth)| depth < min_depth) => {
+                    question_mark = Some((offset, depth));
                 }
-
-                // Wait for text or an end of line.
-                ignore_until_text = true;
-                stop_ignoring_if_end_of_line = true;
-
-                // Increase nesting if will be on new line (while inside braces).
-                nesting_count += 1;
-            } else if _char == ']' || _char == ')' {
-                // Remove everything until text.
-                let mut chars_to_remove = 0;
-                for check in output.chars().rev() {
-                    if check != ' ' && check != '\t' && check != '\n' && check != '\r' {
+                _ => {}
+            }
+        }
+        let (question_offset, question_depth) = question_mark?;
+
+        // Find the matching `:` (skip `::` and nested ternary expressions).
+        let mut colon_offset = None;
+        let mut depth = question_depth;
+        let mut nested_count: usize = 0;
+        let bytes = code.as_bytes();
+        for (offset, _char) in code
+            .char_indices()
+            .skip_while(|(offset, _)| *offset <= question_offset)
+        {
+            match _char {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => {
+                    if depth == question_depth {
                         break;
                     }
-                    chars_to_remove += 1;
+                    depth -= 1;
                 }
-                for _ in 0..chars_to_remove {
-                    output.pop();
+                '?' if depth == question_depth => nested_count += 1,
+                ':' if depth == question_depth
+                    && bytes.get(offset + 1) != Some(&b':')
+                    && bytes[offset - 1] != b':' =>
+                {
+                    if nested_count == 0 {
+                        colon_offset = Some(offset);
+                        break;
+                    }
+                    nested_count -= 1;
                 }
+                _ => {}
+            }
+        }
+        let colon_offset = colon_offset?;
+
+        let line_indentation: String = line
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+        let continuation_indentation = line_indentation + self.get_indentation_text();
+
+        let condition = line[..question_offset].trim_end();
+        let if_true = line[question_offset + 1..colon_offset].trim();
+        let if_false = line[colon_offset + 1..].trim_start();
+        if condition.trim().is_empty() || if_true.is_empty() {
+            return None;
+        }
 
-                // Add space if needed.
-                let nothing_in_brackets = match output.chars().last() {
-                    None => false,
-                    Some(c) => c == '<' || c == '[' || c == '(',
-                };
-                if self.config.spaces_in_brackets && !nothing_in_brackets {
-                    output.push(' ');
-                }
+        Some(format!(
+            "{}{}{}? {}{}{}: {}",
+            condition,
+            LINE_ENDING,
+            continuation_indentation,
+            if_true,
+            LINE_ENDING,
+            continuation_indentation,
+            if_false
+        ))
+    }
 
-                output.push(_char);
+    /// Moves binary operators (like `+` or `&&`) of expressions that are split into multiple lines
+    /// to the beginning of continuation lines (if `break_before` is `true`) or to the end
+    /// of previous lines (if `break_before` is `false`) and indents continuation lines.
+    fn place_binary_operators(&self, text: &str, break_before: bool) -> String {
+        let lines = Self::split_code_lines(text);
+        let mut codes: Vec<String> = lines.iter().map(|line| line.code.to_owned()).collect();
 
-                // Decrease nesting if will be on new line.
-                nesting_count = nesting_count.saturating_sub(1);
-            } else {
-                if _char == ')' {
-                    // Check if we have spaces like `(    )` to remove them.
-                    let mut chars_to_remove = 0;
-                    for check in output.chars().rev() {
-                        if check != ' ' && check != '\t' {
-                            break;
-                        }
-                        chars_to_remove += 1;
-                    }
-                    for _ in 0..chars_to_remove {
-                        output.pop();
-                    }
-                }
+        // Move operators.
+        for index in 1..lines.len() {
+            if !lines[index - 1].is_code || !lines[index].is_code {
+                continue;
+            }
 
-                // Just copy the char.
-                output.push(_char);
+            let next_line = codes[index].trim_start().to_owned();
+            if next_line.is_empty() {
+                continue;
+            }
+            let prev_line = codes[index - 1].clone();
+            let prev_code = CodeLine {
+                code: &prev_line,
+                ending: "",
+                is_code: true,
+            }
+            .code_without_comment()
+            .trim_end();
+
+            if break_before {
+                if let Some(operator) = Self::get_binary_operator_at_end(prev_code) {
+                    // Move the operator to the next line.
+                    let comment = &prev_line[prev_code.len()..];
+                    codes[index - 1] = format!(
+                        "{}{}",
+                        prev_code[..prev_code.len() - operator.len()].trim_end(),
+                        comment
+                    );
+                    codes[index] = format!("{} {}", operator, next_line);
+                }
+            } else if let Some(operator) = Self::get_binary_operator_at_start(&next_line) {
+                let can_move = prev_code.len() == prev_line.trim_end().len()
+                    && !prev_code.trim().is_empty()
+                    && !prev_code.ends_with([';', '{', '}', ',']);
+                if can_move {
+                    // Move the operator to the previous line.
+                    codes[index - 1] = format!("{} {}", prev_code, operator);
+                    codes[index] = next_line[operator.len()..].trim_start().to_owned();
+                }
             }
         }
 
-        if inside_no_format {
-            return Err(format!(
-                "{} was found but no matching{} detected",
-                NOFORMAT_BEGIN_COMMENT, NOFORMAT_END_COMMENT
-            ));
+        // Indent continuation lines.
+        let mut output = String::with_capacity(text.len());
+        let mut expression_indentation = String::new();
+        for (index, line) in lines.iter().enumerate() {
+            let code = &codes[index];
+            let trimmed_code = code.trim_start();
+            let indentation = &code[..code.len() - trimmed_code.len()];
+
+            let is_continuation = line.is_code
+                && index > 0
+                && lines[index - 1].is_code
+                && !trimmed_code.is_empty()
+                && (if break_before {
+                    Self::get_binary_operator_at_start(trimmed_code).is_some()
+                } else {
+                    let prev_line = CodeLine {
+                        code: &codes[index - 1],
+                        ending: "",
+                        is_code: true,
+                    };
+                    Self::get_binary_operator_at_end(prev_line.code_without_comment().trim_end())
+                        .is_some()
+                });
+
+            if is_continuation {
+                output += &expression_indentation;
+                output += self.get_indentation_text();
+                output += trimmed_code;
+            } else {
+                expression_indentation = indentation.to_owned();
+                output += code;
+            }
+            output += line.ending;
         }
 
-        Ok(output)
+        output
     }
 
-    /// Checks complex formatting rules that require prior parsing (tokens required).
-    fn check_complex_rules(
-        &self,
-        complex_tokens: Vec<(parser::ComplexToken<'_>, SimpleSpan)>,
-    ) -> Result<(), String> {
-        // Prepare some variables to determine if we are inside of a global scope or inside of some function.
-        let mut is_global_scope = true;
-        let mut is_inside_nolint = false;
-        let mut scope_nesting_count = 0;
+    /// Returns a binary operator (like `+` or `&&`) that the specified text starts with
+    /// (the operator should be followed by a whitespace).
+    fn get_binary_operator_at_start(text: &str) -> Option<&'static str> {
+        BINARY_OPERATORS.into_iter().find(|operator| {
+            text.starts_with(operator) && text[operator.len()..].starts_with([' ', '\t'])
+        })
+    }
 
-        let mut token_iter = complex_tokens.iter().peekable();
-        while let Some((complex_token, _)) = token_iter.next() {
-            // Check for nolint section.
-            if let Other(Token::Comment(text)) = *complex_token {
-                if text.starts_with("NOLINTBEGIN") {
-                    is_inside_nolint = true;
-                } else if text.starts_with("NOLINTEND") {
-                    is_inside_nolint = false;
+    /// Returns a binary operator (like `+` or `&&`) that the specified text ends with
+    /// (the operator should be preceded by a whitespace).
+    fn get_binary_operator_at_end(text: &str) -> Option<&'static str> {
+        BINARY_OPERATORS.into_iter().find(|operator| {
+            text.ends_with(operator) && text[..text.len() - operator.len()].ends_with([' ', '\t'])
+        })
+    }
+
+    /// Splits arguments of the first function call (or parameters of the first function declaration)
+    /// in the specified line (without a line ending) according to the specified style.
+    ///
+    /// # Return
+    /// `None` if the line does not have a call (declaration) with multiple arguments,
+    /// otherwise wrapped line.
+    fn wrap_arguments(
+        &self,
+        line: &str,
+        is_declaration: bool,
+        style: ArgumentWrap,
+    ) -> Option<String> {
+        let (open_paren, close_paren) = Self::find_parentheses(line, is_declaration)?;
+
+        // Split arguments by commas that are not inside of nested brackets.
+        let mut args = Vec::new();
+        let mut depth: usize = 0;
+        let mut arg_start = open_paren + 1;
+        for (offset, _char) in line[open_paren..close_paren].char_indices() {
+            let offset = open_paren + offset;
+            match _char {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth = depth.saturating_sub(1),
+                ',' if depth == 1 => {
+                    args.push(line[arg_start..offset].trim());
+                    arg_start = offset + 1;
                 }
+                _ => {}
             }
+        }
+        args.push(line[arg_start..close_paren].trim());
+        if args.len() < 2 {
+            return None;
+        }
 
-            // Skip this token if nolint.
-            if is_inside_nolint {
-                continue;
+        let line_indentation: String = line
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+        let hanging_indentation = line_indentation.clone() + self.get_indentation_text();
+
+        let mut wrapped = String::from(&line[..=open_paren]);
+        match style {
+            ArgumentWrap::AllOnOneLine => {
+                wrapped += LINE_ENDING;
+                wrapped += &hanging_indentation;
+                wrapped += &args.join(", ");
             }
-            // Check if next token is a nolint.
-            else if let Some((Other(Token::Comment(text)), _)) = token_iter.peek() {
-                if text.starts_with("NOLINT") {
-                    continue;
+            ArgumentWrap::AlignWithParenthesis => {
+                let align_indentation = line_indentation.clone()
+                    + &" ".repeat(line[line_indentation.len()..=open_paren].chars().count());
+                for (index, arg) in args.iter().enumerate() {
+                    if index != 0 {
+                        wrapped.push(',');
+                        wrapped += LINE_ENDING;
+                        wrapped += &align_indentation;
+                    }
+                    wrapped += arg;
                 }
             }
-
-            match complex_token {
-                VariableDeclaration(_type, name) => {
-                    self.check_variable_name(name, *_type, is_global_scope)?;
+            ArgumentWrap::OnePerLine => {
+                for (index, arg) in args.iter().enumerate() {
+                    if index != 0 {
+                        wrapped.push(',');
+                    }
+                    wrapped += LINE_ENDING;
+                    wrapped += &hanging_indentation;
+                    wrapped += arg;
                 }
-                Struct(info) => {
-                    is_global_scope = false;
+            }
+        }
+        wrapped += &line[close_paren..];
 
-                    // Check docs.
-                    if self.config.require_docs_on_structs {
-                        Self::check_struct_docs(info)?;
-                    }
+        Some(wrapped)
+    }
 
-                    // Check name case.
-                    if let Some(case) = self.config.struct_case {
-                        Self::check_name_case(info.name, case)?;
+    /// Looks for the first function call (or a function declaration) in the specified line
+    /// (not a keyword like `if`) and returns positions of its parentheses.
+    fn find_parentheses(line: &str, is_declaration: bool) -> Option<(usize, usize)> {
+        let mut open_parens: Vec<usize> = Vec::new();
+        let mut calls: Vec<(usize, usize)> = Vec::new();
+
+        for (offset, _char) in line.char_indices() {
+            match _char {
+                '(' => open_parens.push(offset),
+                ')' => {
+                    let open_paren = open_parens.pop()?;
+                    let text_before = &line[..open_paren];
+                    let is_expected_kind = if is_declaration {
+                        Self::is_declaration_parenthesis(text_before)
+                    } else {
+                        Self::is_call_parenthesis(text_before)
+                    };
+                    if is_expected_kind {
+                        calls.push((open_paren, offset));
                     }
+                }
+                '/' if line[offset..].starts_with("//") || line[offset..].starts_with("/*") => {
+                    break;
+                }
+                _ => {}
+            }
+        }
 
-                    // Check fields.
-                    for field_info in &info.fields {
-                        self.check_variable_name(
-                            field_info.name,
-                            field_info._type,
-                            is_global_scope,
-                        )?;
-                    }
+        // Take the leftmost (outermost) call.
+        calls.into_iter().min_by_key(|(open_paren, _)| *open_paren)
+    }
 
-                    // Check field docs.
-                    if self.config.require_docs_on_fields {
-                        Self::check_struct_field_docs(info)?;
-                    }
+    /// Tells if `(` that goes after the specified text opens arguments of a function call.
+    fn is_call_parenthesis(text_before: &str) -> bool {
+        matches!(
+            Self::get_name_before_parenthesis(text_before),
+            Some((_, false))
+        )
+    }
 
-                    is_global_scope = true;
-                }
-                Function(info) => {
-                    is_global_scope = false;
-                    scope_nesting_count = 0;
+    /// Tells if `(` that goes after the specified text opens parameters of a function declaration
+    /// (like `float foo(`).
+    fn is_declaration_parenthesis(text_before: &str) -> bool {
+        matches!(
+            Self::get_name_before_parenthesis(text_before),
+            Some((_, true))
+        )
+    }
 
-                    // Check docs.
-                    if self.config.require_docs_on_functions {
-                        Self::check_function_docs(info)?;
-                    }
+    /// Returns a name (of a function) that goes right before `(` (which goes after the specified text)
+    /// and whether the name goes after a type (like in `float foo(`).
+    ///
+    /// # Return
+    /// `None` if `(` does not go after a name or the name is a keyword like `if`.
+    fn get_name_before_parenthesis(text_before: &str) -> Option<(&str, bool)> {
+        if text_before.ends_with(char::is_whitespace) {
+            return None;
+        }
 
-                    // Check name case.
-                    if let Some(case) = self.config.function_case {
-                        Self::check_name_case(info.name, case)?;
-                    }
+        let name = text_before
+            .rsplit(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .filter(|name| !name.is_empty())?;
+        if matches!(name, "if" | "for" | "while" | "switch" | "return") {
+            return None;
+        }
 
-                    // Check args.
-                    for info in &info.args {
-                        self.check_variable_name(info.name, info._type, is_global_scope)?;
-                    }
-                }
-                Other(token) => {
-                    if !is_global_scope {
-                        if *token == Token::Ctrl('{') {
-                            scope_nesting_count += 1;
-                        } else if *token == Token::Ctrl('}') {
-                            if scope_nesting_count == 0 {
-                                // Unexpected, we probably have something wrong in other place.
-                                return Err("found '}' but scope nesting counter is already zero"
-                                    .to_owned());
-                            } else {
-                                scope_nesting_count -= 1;
-                                if scope_nesting_count == 0 {
-                                    is_global_scope = true;
-                                }
+        let text_before_name = text_before[..text_before.len() - name.len()].trim_end();
+        let is_after_type = text_before_name
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '>')
+            && !text_before_name.ends_with("return");
+
+        Some((name, is_after_type))
+    }
+
+    /// Returns text used for one level of indentation.
+    fn get_indentation_text(&self) -> &'static str {
+        match self.config.indentation {
+            IndentationRule::Tab => "\t",
+            IndentationRule::TwoSpaces => "  ",
+            // `Auto` is replaced with the detected indentation before formatting.
+            IndentationRule::FourSpaces | IndentationRule::Auto => "    ",
+        }
+    }
+
+    /// Formats the body of an enum (from `{` to `}`) according to the specified layout.
+    ///
+    /// # Return
+    /// `None` if the body should not be changed (already formatted or contains something
+    /// that we can't move safely like comments after values), otherwise formatted body.
+    fn format_enum_values(
+        &self,
+        text: &str,
+        info: &EnumInfo,
+        layout: EnumValueLayout,
+    ) -> Option<String> {
+        let body = &text[info.body_span.start..info.body_span.end];
+        if info.values.is_empty() || Self::is_inside_no_format(text, info.body_span.start) {
+            return None;
+        }
+
+        // Don't join values that were placed on separate lines by the author.
+        if layout == EnumValueLayout::Packed
+            && self.config.keep_user_line_breaks
+            && body.contains('\n')
+        {
+            return None;
+        }
+
+        // Make sure the body only has values, commas and whitespace.
+        let mut last_value_end = info.body_span.start + 1;
+        for value in &info.values {
+            if !text[last_value_end..value.span.start]
+                .chars()
+                .all(|c| c == ',' || c.is_whitespace())
+            {
+                return None;
+            }
+            last_value_end = value.span.end;
+        }
+        if !text[last_value_end..info.body_span.end - 1]
+            .chars()
+            .all(|c| c == ',' || c.is_whitespace())
+        {
+            return None;
+        }
+
+        let mut output = String::from("{");
+        match layout {
+            EnumValueLayout::OnePerLine => {
+                // Find indentation of the line with the enum body.
+                let line_start = text[..info.body_span.start]
+                    .rfind('\n')
+                    .map_or(0, |offset| offset + 1);
+                let line_indentation: String = text[line_start..]
+                    .chars()
+                    .take_while(|c| *c == ' ' || *c == '\t')
+                    .collect();
+                let value_indentation = line_indentation.clone() + self.get_indentation_text();
+
+                for (index, value) in info.values.iter().enumerate() {
+                    output += LINE_ENDING;
+                    output += &value_indentation;
+
+                    // Re-indent every line (values can have multi-line docs).
+                    let value_text = &text[value.span.start..value.span.end];
+                    for (line_index, line) in value_text.lines().enumerate() {
+                        let line = line.trim();
+                        if line_index != 0 {
+                            output += LINE_ENDING;
+                            output += &value_indentation;
+                            if line.starts_with('*') {
+                                output.push(' '); // for C-style comments to look good
                             }
                         }
+                        output += line;
+                    }
+
+                    if index + 1 != info.values.len() || self.config.enum_trailing_comma {
+                        output.push(',');
                     }
                 }
+
+                output += LINE_ENDING;
+                output += &line_indentation;
+            }
+            EnumValueLayout::Packed => {
+                // Comments can't be placed on a single line with other values.
+                if info.values.iter().any(|value| !value.docs.is_empty()) {
+                    return None;
+                }
+
+                let values: Vec<&str> = info
+                    .values
+                    .iter()
+                    .map(|value| text[value.span.start..value.span.end].trim())
+                    .collect();
+
+                output.push(' ');
+                output += &values.join(", ");
+                output.push(' ');
             }
         }
+        output.push('}');
 
-        if is_inside_nolint {
-            return Err("`NOLINTBEGIN` was found but no matching `NOLINTEND` detected".to_owned());
+        if output == body {
+            return None;
         }
 
-        Ok(())
+        Some(output)
     }
 
-    /// Checks various complex formatting rules on the specified variable.
+    /// Looks for bodies of `if`, `else`, `for` and `while` statements that are not enclosed
+    /// in braces but should be according to the config.
     ///
     /// # Return
-    /// `Ok` if the name is correct (according to the rules), otherwise `Err` that container
-    /// an error message with suggestions according to the rules.
-    fn check_variable_name(
-        &self,
-        mut name: &str,
-        _type: Type,
-        is_global_scope: bool,
-    ) -> Result<(), String> {
-        // Check global variable prefix.
-        if let Some(global_prefix) = &self.config.global_variable_prefix {
-            // TODO: rework this branch into a single one when Rust's #53667 is resolved
-            if is_global_scope {
-                if !name.starts_with(global_prefix) {
-                    return Err(format!(
-                        "\"{}\" has incorrect prefix because it's a global variable, the correct name is probably \"{}\"",
-                        name, global_prefix.to_owned() + name
-                    ));
+    /// Edits that insert `{` before and `}` after the bodies (the result needs to be formatted again).
+    fn get_missing_braces(&self, text: &str, tokens: &[(Token<'_>, Span)]) -> Vec<(Span, String)> {
+        let mut edits = Vec::new();
+
+        for (index, (token, span)) in tokens.iter().enumerate() {
+            let rule = match token {
+                Token::Ident("if") => self.config.require_braces_on_if,
+                Token::Ident("else") => self.config.require_braces_on_else,
+                Token::Ident("for") => self.config.require_braces_on_for,
+                Token::Ident("while") => self.config.require_braces_on_while,
+                _ => continue,
+            };
+            let Some(rule) = rule else {
+                continue;
+            };
+
+            // Skip the condition.
+            let body_start = if *token == Token::Ident("else") {
+                if matches!(tokens.get(index + 1), Some((Token::Ident("if"), _))) {
+                    continue; // `else if`
+                }
+                index + 1
+            } else {
+                match Self::find_closing_bracket(tokens, index + 1) {
+                    Some(close_index) => close_index + 1,
+                    None => continue,
                 }
+            };
+
+            // Skip bodies with braces and the end of `do-while`.
+            if matches!(
+                tokens.get(body_start),
+                None | Some((Token::Ctrl('{' | ';'), _))
+            ) {
+                continue;
+            }
+
+            let Some(mut body_end) = Self::get_statement_end(tokens, body_start) else {
+                continue;
+            };
+            if rule == RequireBraces::MultiLine
+                && !text[span.start..tokens[body_end].1.end].contains('\n')
+            {
+                continue;
+            }
+            if Self::is_inside_no_format(text, span.start) {
+                continue;
+            }
 
-                // Make sure the name is in ASCII because we will create a new slice using bytes not chars.
-                if !name.is_ascii() && !global_prefix.is_ascii() {
-                    return Err(format!(
-                        "expected global prefix rule \"{}\" and \"{}\" to have an ASCII-only name",
-                        global_prefix, name
-                    ));
+            // Keep a comment that follows the body on the same line before `}`.
+            if let Some((Token::Comment(_), comment_span)) = tokens.get(body_end + 1) {
+                if !text[tokens[body_end].1.end..comment_span.start].contains('\n') {
+                    body_end += 1;
                 }
+            }
 
-                // Remove global prefix from further checks.
-                name = &name[global_prefix.len()..];
+            let header_end = tokens[body_start - 1].1.end;
+            edits.push((Span::from(header_end..header_end), " {".to_owned()));
+            let body_end = tokens[body_end].1.start
+                + text[tokens[body_end].1.start..tokens[body_end].1.end]
+                    .trim_end()
+                    .len();
+            edits.push((Span::from(body_end..body_end), "\n}".to_owned()));
+        }
+
+        edits
+    }
+
+    /// Returns index of the last token of a statement that starts at the specified index
+    /// (like `;` of `foo();` or `}` of the last `else` body of an `if` statement).
+    fn get_statement_end(tokens: &[(Token<'_>, Span)], start_index: usize) -> Option<usize> {
+        match tokens.get(start_index)?.0 {
+            Token::Comment(_) => Self::get_statement_end(tokens, start_index + 1),
+            Token::Preprocessor(_) => None,
+            Token::Ctrl('{') => Self::find_closing_bracket(tokens, start_index),
+            Token::Ident("if") => {
+                let close_index = Self::find_closing_bracket(tokens, start_index + 1)?;
+                let body_end = Self::get_statement_end(tokens, close_index + 1)?;
+                match tokens.get(body_end + 1) {
+                    Some((Token::Ident("else"), _)) => {
+                        Self::get_statement_end(tokens, body_end + 2)
+                    }
+                    _ => Some(body_end),
+                }
+            }
+            Token::Ident("for" | "while" | "switch") => {
+                let close_index = Self::find_closing_bracket(tokens, start_index + 1)?;
+                Self::get_statement_end(tokens, close_index + 1)
+            }
+            Token::Ident("do") => {
+                let body_end = Self::get_statement_end(tokens, start_index + 1)?;
+                let close_index = Self::find_closing_bracket(tokens, body_end + 2)?;
+                match tokens.get(close_index + 1) {
+                    Some((Token::Ctrl(';'), _)) => Some(close_index + 1),
+                    _ => None,
+                }
+            }
+            _ => {
+                let mut depth: usize = 0;
+                for (index, (token, _)) in tokens.iter().enumerate().skip(start_index) {
+                    match token {
+                        Token::Ctrl('(' | '[' | '{') => depth += 1,
+                        Token::Ctrl(')' | ']' | '}') if depth == 0 => return None,
+                        Token::Ctrl(')' | ']' | '}') => depth -= 1,
+                        Token::Ctrl(';') if depth == 0 => return Some(index),
+                        Token::Preprocessor(_) => return None,
+                        _ => {}
+                    }
+                }
+                None
             }
         }
+    }
 
-        // Check case.
-        if let Some(case) = self.config.variable_case {
-            Self::check_name_case(name, case)?
+    /// Returns index of the bracket that closes the bracket (`(`, `[` or `{`) at the specified index.
+    fn find_closing_bracket(tokens: &[(Token<'_>, Span)], open_index: usize) -> Option<usize> {
+        if !matches!(tokens.get(open_index)?.0, Token::Ctrl('(' | '[' | '{')) {
+            return None;
         }
 
-        // Check prefixes.
-        if _type == Type::Bool && self.config.bool_prefix.is_some() {
-            Self::check_prefix(name, self.config.bool_prefix.as_ref().unwrap())?
+        let mut depth: usize = 0;
+        for (index, (token, _)) in tokens.iter().enumerate().skip(open_index) {
+            match token {
+                Token::Ctrl('(' | '[' | '{') => depth += 1,
+                Token::Ctrl(')' | ']' | '}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(index);
+                    }
+                }
+                _ => {}
+            }
         }
-        if _type == Type::Integer && self.config.int_prefix.is_some() {
-            Self::check_prefix(name, self.config.int_prefix.as_ref().unwrap())?
+
+        None
+    }
+
+    /// Formats spaces around colons before base types of the specified struct and its nested structs.
+    fn format_inheritance_colons(
+        &self,
+        text: &str,
+        info: &StructInfo,
+        edits: &mut Vec<(Span, String)>,
+    ) {
+        if let Some(edit) = self.format_inheritance_colon(text, info) {
+            edits.push(edit);
         }
-        if _type == Type::Float && self.config.float_prefix.is_some() {
-            Self::check_prefix(name, self.config.float_prefix.as_ref().unwrap())?
+        for nested_info in &info.nested {
+            self.format_inheritance_colons(text, nested_info, edits);
         }
+    }
 
-        Ok(())
+    /// Reorders `@param` entries in docs of methods of the specified struct and its nested structs.
+    fn reorder_method_param_docs(text: &str, info: &StructInfo, edits: &mut Vec<(Span, String)>) {
+        for method_info in &info.methods {
+            edits.extend(Self::reorder_param_docs(text, method_info).unwrap_or_default());
+        }
+        for nested_info in &info.nested {
+            Self::reorder_method_param_docs(text, nested_info, edits);
+        }
     }
 
-    fn check_name_case(name: &str, case: Case) -> Result<(), String> {
-        match Self::is_case_different(name, case) {
-            Ok(_) => Ok(()),
-            Err(correct) => Err(format!(
-                "\"{}\" has incorrect case, the correct case is \"{}\"",
-                name, correct
-            )),
+    /// Normalizes spaces around `=` of default argument values of methods of the specified
+    /// struct and its nested structs.
+    fn normalize_method_default_value_spacing(
+        text: &str,
+        tokens: &[(Token<'_>, Span)],
+        info: &StructInfo,
+        edits: &mut Vec<(Span, String)>,
+    ) {
+        for method_info in &info.methods {
+            edits.extend(Self::normalize_default_value_spacing(
+                text,
+                tokens,
+                method_info,
+            ));
+        }
+        for nested_info in &info.nested {
+            Self::normalize_method_default_value_spacing(text, tokens, nested_info, edits);
         }
     }
 
-    /// This function contains repetitive code for checking prefixes.
+    /// Puts exactly one space before and after `=` of default values of arguments of the
+    /// specified function (like `float scale=1.0` is formatted to `float scale = 1.0`).
     ///
     /// # Return
-    /// `Ok` if prefix is correct, otherwise `Err` that contains a meaningful error message
-    /// about wrong prefix.
-    fn check_prefix(name: &str, prefix: &str) -> Result<(), String> {
-        if !name.starts_with(prefix) {
-            return Err(format!(
-                "variable \"{}\" has incorrect prefix, the correct prefix is \"{}\"",
-                name, prefix
-            ));
+    /// Edits that replace the whitespace around `=` (whitespace with line breaks is not changed).
+    fn normalize_default_value_spacing(
+        text: &str,
+        tokens: &[(Token<'_>, Span)],
+        info: &FunctionInfo,
+    ) -> Vec<(Span, String)> {
+        let mut edits = Vec::new();
+
+        for arg in info.args.iter().filter(|arg| arg.has_default_value) {
+            let Some(name_index) = tokens
+                .iter()
+                .position(|(_, span)| span.start == arg.name_span.start)
+            else {
+                continue;
+            };
+
+            // Find `=` after the name (skipping array dimensions and semantics).
+            let mut depth = 0;
+            let mut assign_index = None;
+            for (index, (token, _)) in tokens.iter().enumerate().skip(name_index + 1) {
+                match token {
+                    Token::Ctrl('[') => depth += 1,
+                    Token::Ctrl(']') => depth -= 1,
+                    Token::Op("=") if depth == 0 => {
+                        assign_index = Some(index);
+                        break;
+                    }
+                    Token::Ctrl(',' | ')') if depth == 0 => break,
+                    _ => {}
+                }
+            }
+            let Some(assign_index) = assign_index else {
+                continue;
+            };
+            let (Some((_, before_span)), (_, assign_span), Some((_, after_span))) = (
+                tokens.get(assign_index - 1),
+                &tokens[assign_index],
+                tokens.get(assign_index + 1),
+            ) else {
+                continue;
+            };
+            if Self::is_inside_no_format(text, assign_span.start) {
+                continue;
+            }
+
+            for gap_span in [
+                Span::from(before_span.end..assign_span.start),
+                Span::from(assign_span.end..after_span.start),
+            ] {
+                let gap = &text[gap_span.start..gap_span.end];
+                if gap != " " && gap.chars().all(|c| c == ' ' || c == '\t') {
+                    edits.push((gap_span, " ".to_owned()));
+                }
+            }
         }
 
-        Ok(())
+        edits
     }
 
-    /// Checks if the case of the specified test string is different from the specified case.
+    /// Makes `@param` entries in docs of the specified function follow the order of its arguments.
     ///
     /// # Return
-    /// `Ok` if case is correct, otherwise `Err` that contains the specified string in the correct
-    /// casing.
-    fn is_case_different(test: &str, target_case: Case) -> Result<(), String> {
-        let converted_str = match target_case {
-            Case::Camel => test.to_case(convert_case::Case::Camel),
-            Case::Snake => test.to_case(convert_case::Case::Snake),
-            Case::Pascal => test.to_case(convert_case::Case::Pascal),
-            Case::UpperSnake => test.to_case(convert_case::Case::UpperSnake),
-        };
+    /// `None` if the entries can't be reordered (like when multiple entries are on the same line
+    /// or some entries don't reference an argument), otherwise edits that move the entries
+    /// (empty if the order is correct).
+    fn reorder_param_docs(text: &str, info: &FunctionInfo) -> Option<Vec<(Span, String)>> {
+        let entries = Self::get_param_doc_entries(text, info.docs_span)?;
+        if entries
+            .iter()
+            .any(|(_, span)| Self::is_inside_no_format(text, span.start))
+        {
+            return None;
+        }
 
-        if test != converted_str {
-            return Err(converted_str);
+        // Sort entries by the positions of their arguments.
+        let mut sorted_entries = entries
+            .iter()
+            .map(|(name, span)| {
+                let arg_index = info.args.iter().position(|arg| arg.name == *name)?;
+                Some((arg_index, *span))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        sorted_entries.sort_by_key(|(arg_index, _)| *arg_index);
+
+        Some(
+            entries
+                .iter()
+                .zip(sorted_entries)
+                .filter(|((_, span), (_, new_span))| span != new_span)
+                .map(|((_, span), (_, new_span))| {
+                    (*span, text[new_span.start..new_span.end].to_owned())
+                })
+                .collect(),
+        )
+    }
+
+    /// Looks for `@param` entries in documentation comments at the specified span, an entry
+    /// includes the line with `@param` and the following lines of its description.
+    ///
+    /// # Return
+    /// `None` if some line has multiple entries, otherwise names of the documented arguments
+    /// and spans of the entries (without the last line break).
+    fn get_param_doc_entries(text: &str, docs_span: Span) -> Option<Vec<(&str, Span)>> {
+        let param_keyword = "@param";
+        let docs = text[docs_span.start..docs_span.end].trim_end();
+
+        let mut entries: Vec<(&str, Span)> = Vec::new();
+        let mut is_inside_entry = false;
+        let mut line_start = docs_span.start;
+        for line in docs.split_inclusive('\n') {
+            let line_end = line_start + line.trim_end().len();
+            let line_text = line.trim_matches(|c: char| c.is_whitespace() || c == '*' || c == '/');
+
+            if let Some(position) = line_text.find(param_keyword) {
+                if line_text[position + 1..].contains(param_keyword) {
+                    return None;
+                }
+
+                let name = Self::get_param_doc_name(&line_text[position + param_keyword.len()..])
+                    .unwrap_or_default();
+                entries.push((name, Span::from(line_start..line_end)));
+                is_inside_entry = true;
+            } else if line_text.is_empty() || line_text.starts_with('@') {
+                is_inside_entry = false;
+            } else if is_inside_entry {
+                // Description of the entry continues on this line.
+                if let Some((_, span)) = entries.last_mut() {
+                    *span = Span::from(span.start..line_end);
+                }
+            }
+
+            line_start += line.len();
         }
 
-        Ok(())
+        Some(entries)
     }
 
-    /// Checks that the documentation for the specified function is written for return type and all arguments.
+    /// Formats spaces around the colon before base types of a struct (like `struct Derived : Base`).
     ///
     /// # Return
-    /// `Ok` if docs are correct, otherwise `Err` with a meaningful message about incorrect docs.
-    fn check_function_docs(func_info: &FunctionInfo) -> Result<(), String> {
-        // Make sure docs are not empty.
-        if func_info.docs.is_empty() {
-            return Err(format!(
-                "expected to find documentation for the function \"{}\"",
-                func_info.name
-            ));
+    /// `None` if the text should not be changed, otherwise span of the text between the struct
+    /// name and the first base type with the new text.
+    fn format_inheritance_colon(&self, text: &str, info: &StructInfo) -> Option<(Span, String)> {
+        let (_, first_base_span) = info.bases.first()?;
+        let span = Span::from(info.name_span.end..first_base_span.start);
+        if Self::is_inside_no_format(text, span.start) {
+            return None;
         }
 
-        // Make sure docs are using ASCII characters since we will use `find` on bytes not chars.
-        if !func_info.docs.is_ascii() {
-            return Err(format!(
-                "expected the documentation for the function \"{}\" to only use ASCII characters",
-                func_info.name
-            ));
+        // Make sure there's nothing but the colon (no comments or line breaks).
+        let old_text = &text[span.start..span.end];
+        if old_text.trim_matches([' ', '\t']) != ":" {
+            return None;
         }
 
-        // Check return docs.
-        let return_doc_pos = func_info.docs.find("@return");
-        if func_info.return_type != Type::Void {
-            if return_doc_pos.is_none() {
-                return Err(format!(
-                    "expected to find documentation of the return value for the function \"{}\"",
-                    func_info.name
-                ));
-            }
-        } else if return_doc_pos.is_some() {
-            // Make sure there is no "return" docs (since it's void).
-            return Err(format!(
-                "found documentation of the VOID return value for the function \"{}\"",
-                func_info.name
-            ));
+        let new_text = if self.config.space_before_inheritance_colon {
+            " : "
+        } else {
+            ": "
+        };
+        if old_text == new_text {
+            return None;
         }
 
-        // Collect all args written in the docs.
-        let param_keyword = "@param ";
-        let mut documented_args: Vec<String> = Vec::new();
-        let found_arg_docs: Vec<_> = func_info.docs.match_indices(param_keyword).collect();
-        let docs_as_bytes = func_info.docs.as_bytes();
-        for (pos, _) in found_arg_docs {
-            let mut current_pos = pos + param_keyword.len();
-            let mut arg_name = String::new();
-
-            while current_pos < docs_as_bytes.len() {
-                let _char = docs_as_bytes[current_pos];
-                if _char as char == ' ' {
-                    if arg_name.is_empty() {
-                        current_pos += 1;
-                        continue;
-                    } else {
-                        break;
+        Some((span, new_text.to_owned()))
+    }
+
+    /// Looks for empty statements (like the second `;` in `;;`) and semicolons after bodies
+    /// of functions and blocks (like `void foo() {};`), semicolons after struct, cbuffer, enum
+    /// and interface block definitions and after initializers are kept.
+    ///
+    /// # Return
+    /// Edits that remove the semicolons.
+    fn remove_redundant_semicolons(
+        text: &str,
+        tokens: &[(Token<'_>, Span)],
+    ) -> Vec<(Span, String)> {
+        let mut edits = Vec::new();
+
+        let mut paren_depth: usize = 0; // to skip `for (;;)`
+        let mut prev_token: Option<(usize, &Token<'_>)> = None; // ignoring comments
+
+        for (index, (token, span)) in tokens.iter().enumerate() {
+            match token {
+                Token::Comment(_) => continue,
+                Token::Ctrl('(') => paren_depth += 1,
+                Token::Ctrl(')') => paren_depth = paren_depth.saturating_sub(1),
+                Token::Ctrl(';') if paren_depth == 0 => {
+                    let is_redundant = match prev_token {
+                        None => true,
+                        Some((_, Token::Ctrl(';' | '{'))) => true,
+                        Some((close_index, Token::Ctrl('}'))) => {
+                            Self::is_block_body_end(tokens, close_index)
+                        }
+                        _ => false,
+                    };
+                    if is_redundant && !Self::is_inside_no_format(text, span.start) {
+                        edits.push((Self::get_semicolon_removal_span(text, *span), String::new()));
+                        continue; // the previous token stays the same
                     }
                 }
-
-                arg_name += &(_char as char).to_string();
-                current_pos += 1;
+                _ => {}
             }
 
-            documented_args.push(arg_name);
+            prev_token = Some((index, token));
         }
 
-        // Check argument docs.
-        for info in &func_info.args {
-            if info.is_using_semantic {
-                // Don't require docs for arguments with semantics.
-                continue;
-            }
-            if !documented_args.iter().any(|name| name == info.name) {
-                return Err(format!(
-                    "expected to find documentation for the argument \"{}\" of the function \"{}\"",
-                    info.name, func_info.name
-                ));
+        edits
+    }
+
+    /// Tells if `}` at the specified index closes the body of a function or a statement
+    /// (like `if`) and not a definition of a type or an initializer (that require `;`).
+    fn is_block_body_end(tokens: &[(Token<'_>, Span)], close_brace_index: usize) -> bool {
+        // Find the matching `{`.
+        let mut depth: usize = 0;
+        let mut open_brace_index = None;
+        for index in (0..close_brace_index).rev() {
+            match tokens[index].0 {
+                Token::Ctrl('}') => depth += 1,
+                Token::Ctrl('{') if depth == 0 => {
+                    open_brace_index = Some(index);
+                    break;
+                }
+                Token::Ctrl('{') => depth -= 1,
+                _ => {}
             }
         }
+        let Some(open_brace_index) = open_brace_index else {
+            return false;
+        };
 
-        // Check if there are argument comments that don't reference an actual argument.
-        for doc_arg_name in documented_args {
-            if !func_info.args.iter().any(|info| info.name == doc_arg_name) {
-                return Err(format!(
-                    "found documentation for a non-existing argument \"{}\" of the function \"{}\"",
-                    doc_arg_name, func_info.name
-                ));
+        // Look at the code between the start of the statement and `{` (ignoring code
+        // in parentheses like in `for (int i = 0; i < 3; i++)`).
+        let mut has_parentheses = false;
+        let mut is_definition_or_initializer = false;
+        let mut depth: usize = 0;
+        for (token, _) in tokens[..open_brace_index].iter().rev() {
+            match token {
+                Token::Ctrl(')') => {
+                    has_parentheses = true;
+                    depth += 1;
+                }
+                Token::Ctrl('(') => depth = depth.saturating_sub(1),
+                Token::Ctrl(';' | '{' | '}') | Token::Preprocessor(_) if depth == 0 => break,
+                Token::Op("=") if depth == 0 => is_definition_or_initializer = true,
+                Token::Ident(
+                    "struct" | "class" | "interface" | "enum" | "cbuffer" | "tbuffer" | "namespace"
+                    | "uniform" | "buffer" | "in" | "out",
+                ) if depth == 0 => is_definition_or_initializer = true,
+                _ => {}
             }
         }
 
-        Ok(())
+        has_parentheses && !is_definition_or_initializer
     }
 
-    /// Checks that the documentation for the specified struct is written correctly.
-    ///
-    /// # Return
-    /// `Ok` if docs are correct, otherwise `Err` with a meaningful message about incorrect docs.
-    fn check_struct_docs(struct_info: &StructInfo) -> Result<(), String> {
-        // Make sure docs are not empty.
-        if struct_info.docs.is_empty() {
-            return Err(format!(
-                "expected to find documentation for the struct \"{}\"",
-                struct_info.name
-            ));
+    /// Returns span of the text to remove with the semicolon at the specified span: the whole
+    /// line if the semicolon is the only thing on the line, otherwise the semicolon and spaces
+    /// before it.
+    fn get_semicolon_removal_span(text: &str, span: Span) -> Span {
+        let line_start = text[..span.start]
+            .rfind('\n')
+            .map_or(0, |position| position + 1);
+        let line_end = text[span.end..]
+            .find('\n')
+            .map_or(text.len(), |position| span.end + position + 1);
+
+        let before = &text[line_start..span.start];
+        let after = &text[span.end..line_end];
+        if before.trim().is_empty() && after.trim().is_empty() && line_start > 0 {
+            return Span::from(line_start..line_end);
         }
 
-        Ok(())
+        Span::from(
+            span.start - (before.len() - before.trim_end_matches([' ', '\t']).len())..span.end,
+        )
     }
 
-    /// Checks if the specified text starts with a comment while ignoring any whitespace
-    /// in the beginning.
+    /// Looks for GLSL work group size declarations (like `layout(local_size_x = 8) in;`) and
+    /// formats them to use the same spacing and order of qualifiers (`x`, `y`, `z`).
     ///
-    /// # Examples
-    /// ```
-    /// assert!(is_text_starts_with_comment("   // comment"), true);
-    /// assert!(is_text_starts_with_comment("  3// comment"), false);
-    /// ```
-    fn is_text_starts_with_comment(text: &str) -> bool {
-        text.chars()
-            .filter(|&_char| _char != ' ' && _char != '\t')
-            .collect::<String>()
+    /// # Return
+    /// Edits that replace the declarations with formatted ones.
+    fn normalize_local_size_layouts(
+        text: &str,
+        tokens: &[(Token<'_>, Span)],
+    ) -> Vec<(Span, String)> {
+        let mut edits = Vec::new();
+
+        for (index, (token, span)) in tokens.iter().enumerate() {
+            if *token != Token::Ident("layout")
+                || tokens.get(index + 1).map(|(token, _)| token) != Some(&Token::Ctrl('('))
+            {
+                continue;
+            }
+
+            // Find the closing parenthesis (declarations with comments are not changed).
+            let Some(close_index) = tokens[index + 2..]
+                .iter()
+                .position(|(token, _)| {
+                    matches!(token, Token::Ctrl(')' | '(' | ';') | Token::Comment(_))
+                })
+                .map(|position| index + 2 + position)
+                .filter(|close_index| tokens[*close_index].0 == Token::Ctrl(')'))
+            else {
+                continue;
+            };
+
+            // Make sure this is a declaration of the work group size.
+            let is_input_declaration = matches!(
+                tokens.get(close_index + 1..close_index + 3),
+                Some([(Token::Ident("in"), _), (Token::Ctrl(';'), _)])
+            );
+            let is_local_size = tokens[index + 2..close_index].iter().any(
+                |(token, _)| matches!(token, Token::Ident(name) if name.starts_with("local_size_")),
+            );
+            if !is_input_declaration || !is_local_size {
+                continue;
+            }
+
+            // Format qualifiers like `local_size_x = 8`.
+            let mut qualifiers = Vec::new();
+            for qualifier_tokens in
+                tokens[index + 2..close_index].split(|(token, _)| *token == Token::Ctrl(','))
+            {
+                let [(Token::Ident(name), _), rest @ ..] = qualifier_tokens else {
+                    qualifiers.clear();
+                    break;
+                };
+                let qualifier = match rest {
+                    [] => name.to_string(),
+                    [(Token::Op("="), _), value @ ..] if !value.is_empty() => {
+                        let value_span = Span::from(value[0].1.start..value[value.len() - 1].1.end);
+                        format!(
+                            "{} = {}",
+                            name,
+                            text[value_span.start..value_span.end].trim()
+                        )
+                    }
+                    _ => {
+                        qualifiers.clear();
+                        break;
+                    }
+                };
+                qualifiers.push((*name, qualifier));
+            }
+            if qualifiers.is_empty() {
+                continue;
+            }
+
+            // Sort by axis (other qualifiers like `local_size_x_id` go after their axis).
+            qualifiers.sort_by_key(|(name, _)| {
+                ["local_size_x", "local_size_y", "local_size_z"]
+                    .iter()
+                    .position(|axis| name.starts_with(axis))
+                    .unwrap_or(3)
+            });
+
+            let semicolon_span = tokens[close_index + 2].1;
+            let declaration_span = Span::from(span.start..semicolon_span.end);
+            let declaration = format!(
+                "layout({}) in;",
+                qualifiers
+                    .into_iter()
+                    .map(|(_, qualifier)| qualifier)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            if text[declaration_span.start..declaration_span.end] != declaration
+                && !Self::is_inside_no_format(text, span.start)
+            {
+                edits.push((declaration_span, declaration));
+            }
+        }
+
+        edits
+    }
+
+    /// Looks for qualifiers of declarations that are not in the canonical order (like `in flat`
+    /// in `in flat int id;`, see `QUALIFIER_ORDER`) and sorts them.
+    ///
+    /// # Return
+    /// Edits that replace the qualifiers with sorted ones.
+    fn sort_qualifiers<'src>(text: &str, tokens: &[(Token<'src>, Span)]) -> Vec<(Span, String)> {
+        let mut edits = Vec::new();
+
+        // Returns the qualifier and its position in the canonical order.
+        let get_qualifier = |token: &Token<'src>| match *token {
+            Token::Ident(name) => QUALIFIER_ORDER
+                .iter()
+                .position(|group| group.contains(&name))
+                .map(|rank| (rank, name)),
+            _ => None,
+        };
+
+        let mut index = 0;
+        while index < tokens.len() {
+            // Find qualifiers that go one after another.
+            let qualifiers = tokens[index..]
+                .iter()
+                .map_while(|(token, _)| get_qualifier(token))
+                .collect::<Vec<_>>();
+            if qualifiers.len() < 2 {
+                index += 1;
+                continue;
+            }
+            let span =
+                Span::from(tokens[index].1.start..tokens[index + qualifiers.len() - 1].1.end);
+            index += qualifiers.len();
+
+            let mut sorted_qualifiers = qualifiers.clone();
+            sorted_qualifiers.sort_by_key(|(rank, _)| *rank);
+            if sorted_qualifiers == qualifiers
+                || text[span.start..span.end].contains('\n')
+                || Self::is_inside_no_format(text, span.start)
+            {
+                continue;
+            }
+
+            edits.push((
+                span,
+                sorted_qualifiers
+                    .into_iter()
+                    .map(|(_, name)| name)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ));
+        }
+
+        edits
+    }
+
+    /// Looks for `return` statements and makes sure that there's exactly one space between
+    /// `return` and the returned value and no space before the `;` (like `return (value) ;`
+    /// is formatted to `return (value);` and `return ;` to `return;`).
+    ///
+    /// # Return
+    /// Edits that replace the whitespace.
+    fn normalize_return_spacing(text: &str, tokens: &[(Token<'_>, Span)]) -> Vec<(Span, String)> {
+        let mut edits = Vec::new();
+
+        // Adds an edit that replaces whitespace between two tokens of a line.
+        let mut replace_gap = |start: usize, end: usize, new_text: &str| {
+            let gap = &text[start..end];
+            if gap != new_text
+                && !gap.contains('\n')
+                && gap.chars().all(|c| c == ' ' || c == '\t')
+                && !Self::is_inside_no_format(text, start)
+            {
+                edits.push((Span::from(start..end), new_text.to_owned()));
+            }
+        };
+
+        for (index, (token, span)) in tokens.iter().enumerate() {
+            if *token != Token::Keyword("return") {
+                continue;
+            }
+            let Some((next_token, next_span)) = tokens.get(index + 1) else {
+                continue;
+            };
+
+            if *next_token == Token::Ctrl(';') {
+                replace_gap(span.end, next_span.start, "");
+                continue;
+            }
+            if matches!(next_token, Token::Comment(_)) {
+                continue;
+            }
+            replace_gap(span.end, next_span.start, " ");
+
+            // Find `;` of the statement.
+            let mut depth: usize = 0;
+            let semicolon_index = tokens[index + 1..]
+                .iter()
+                .position(|(token, _)| {
+                    match token {
+                        Token::Ctrl('(' | '[' | '{') => depth += 1,
+                        Token::Ctrl(')' | ']' | '}') => depth = depth.saturating_sub(1),
+                        Token::Ctrl(';') => return depth == 0,
+                        _ => {}
+                    }
+                    false
+                })
+                .map(|position| index + 1 + position);
+            let Some(semicolon_index) = semicolon_index else {
+                continue;
+            };
+
+            let (value_end_token, value_end_span) = &tokens[semicolon_index - 1];
+            if !matches!(value_end_token, Token::Comment(_)) {
+                replace_gap(value_end_span.end, tokens[semicolon_index].1.start, "");
+            }
+        }
+
+        edits
+    }
+
+    /// Looks for declarations of variables, parameters and functions and replaces multiple
+    /// spaces and tabs between the type and the declared name with a single space (like
+    /// `float   value = 1.0;` is formatted to `float value = 1.0;`).
+    ///
+    /// # Return
+    /// Edits that replace the whitespace.
+    fn normalize_declaration_spacing(
+        text: &str,
+        tokens: &[(Token<'_>, Span)],
+    ) -> Vec<(Span, String)> {
+        let mut edits = Vec::new();
+
+        for (index, window) in tokens.windows(3).enumerate() {
+            let [(type_token, type_span), (Token::Ident(_), name_span), (after_name, _)] = window
+            else {
+                continue;
+            };
+
+            let is_type = match type_token {
+                Token::TypeName(_) | Token::Ident(_) => true,
+                Token::Ctrl('>') => Self::is_template_end(tokens, index),
+                _ => false,
+            };
+            let is_declaration = matches!(
+                after_name,
+                Token::Ctrl(';' | ',' | ')' | '[' | ':' | '(') | Token::Op("=")
+            );
+            if !is_type || !is_declaration {
+                continue;
+            }
+
+            let gap = &text[type_span.end..name_span.start];
+            if gap != " "
+                && !gap.is_empty()
+                && gap.chars().all(|c| c == ' ' || c == '\t')
+                && !Self::is_inside_no_format(text, type_span.start)
+            {
+                edits.push((Span::from(type_span.end..name_span.start), " ".to_owned()));
+            }
+        }
+
+        edits
+    }
+
+    /// Looks for documentation comments (that start with `///` or `/**`) placed on the line
+    /// right after some code and adds an empty line before them (comments after `{` and
+    /// after other comments are not changed).
+    ///
+    /// # Return
+    /// Edits that insert the empty lines.
+    fn add_empty_lines_before_doc_comments(
+        text: &str,
+        tokens: &[(Token<'_>, Span)],
+    ) -> Vec<(Span, String)> {
+        let mut edits = Vec::new();
+
+        for (index, (token, span)) in tokens.iter().enumerate().skip(1) {
+            if !matches!(token, Token::Comment(_)) {
+                continue;
+            }
+            let comment = &text[span.start..span.end];
+            if !comment.starts_with("///") && !comment.starts_with("/**") {
+                continue;
+            }
+            if matches!(tokens[index - 1].0, Token::Comment(_) | Token::Ctrl('{')) {
+                continue;
+            }
+
+            // Make sure the comment is on its own line and the previous line is not empty.
+            let line_start = text[..span.start]
+                .rfind('\n')
+                .map_or(0, |position| position + 1);
+            if line_start == 0 || !text[line_start..span.start].trim().is_empty() {
+                continue;
+            }
+            let prev_line = text[..line_start - 1]
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default();
+            if prev_line.trim().is_empty() || Self::is_inside_no_format(text, span.start) {
+                continue;
+            }
+
+            edits.push((Span::from(line_start..line_start), LINE_ENDING.to_owned()));
+        }
+
+        edits
+    }
+
+    /// Looks for brace initializers (like `{0.2, 0.6, 0.2}`) and adds or removes the comma
+    /// after the last element according to the specified rule.
+    ///
+    /// # Return
+    /// Edits that add or remove the commas.
+    fn format_initializer_trailing_commas(
+        text: &str,
+        tokens: &[(Token<'_>, Span)],
+        trailing_comma: TrailingComma,
+    ) -> Vec<(Span, String)> {
+        let mut edits = Vec::new();
+
+        // Whether each open brace starts an initializer.
+        let mut open_braces: Vec<bool> = Vec::new();
+        // Index of the last token that is not a comment.
+        let mut prev_index: Option<usize> = None;
+
+        for (index, (token, span)) in tokens.iter().enumerate() {
+            match token {
+                Token::Comment(_) => continue,
+                Token::Ctrl('{') => {
+                    let inside_initializer = open_braces.last() == Some(&true);
+                    open_braces.push(match prev_index.map(|prev_index| &tokens[prev_index].0) {
+                        Some(Token::Op("=") | Token::Ctrl('(')) => true,
+                        Some(Token::Ctrl(',' | '{')) => inside_initializer,
+                        _ => false,
+                    });
+                }
+                Token::Ctrl('}') => {
+                    let is_initializer = open_braces.pop() == Some(true);
+                    if let (true, Some(last_index)) = (is_initializer, prev_index) {
+                        if !Self::is_inside_no_format(text, span.start) {
+                            edits.extend(Self::get_trailing_comma_edit(
+                                text,
+                                tokens,
+                                last_index,
+                                *span,
+                                trailing_comma,
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            prev_index = Some(index);
+        }
+
+        edits
+    }
+
+    /// Returns an edit that adds or removes the comma after the last element of an initializer.
+    ///
+    /// # Arguments
+    /// - `last_index` Index of the last token (not a comment) before `}` of the initializer.
+    /// - `close_span` Span of `}` of the initializer.
+    fn get_trailing_comma_edit(
+        text: &str,
+        tokens: &[(Token<'_>, Span)],
+        last_index: usize,
+        close_span: Span,
+        trailing_comma: TrailingComma,
+    ) -> Option<(Span, String)> {
+        let (last_token, last_span) = &tokens[last_index];
+        let (has_comma, element_index) = match last_token {
+            Token::Ctrl('{') => return None, // empty initializer
+            Token::Ctrl(',') => (true, last_index.checked_sub(1)?),
+            _ => (false, last_index),
+        };
+
+        // Spans of some tokens (like numbers) include whitespace after them.
+        let element_span = tokens[element_index].1;
+        let element_end =
+            element_span.start + text[element_span.start..element_span.end].trim_end().len();
+
+        let needs_comma = match trailing_comma {
+            TrailingComma::Always => true,
+            TrailingComma::Never => false,
+            TrailingComma::Multiline => text[element_end..close_span.start].contains('\n'),
+        };
+
+        match (has_comma, needs_comma) {
+            (false, true) => Some((Span::from(element_end..element_end), ",".to_owned())),
+            (true, false) => Some((*last_span, String::new())),
+            _ => None,
+        }
+    }
+
+    /// Tells if `>` at the specified index closes a template argument list of a type (like in
+    /// `Texture2D<float4>`) and is not a comparison operator.
+    fn is_template_end(tokens: &[(Token<'_>, Span)], close_index: usize) -> bool {
+        let mut depth: usize = 0;
+        for index in (0..close_index).rev() {
+            match tokens[index].0 {
+                Token::Ctrl('>') => depth += 1,
+                Token::Ctrl('<') if depth == 0 => {
+                    // Template types are written without a space before `<`.
+                    return index > 0
+                        && matches!(tokens[index - 1].0, Token::TypeName(_) | Token::Ident(_))
+                        && tokens[index - 1].1.end == tokens[index].1.start;
+                }
+                Token::Ctrl('<') => depth -= 1,
+                Token::TypeName(_) | Token::Ident(_) | Token::Integer(_) | Token::Ctrl(',') => {}
+                _ => return false,
+            }
+        }
+
+        false
+    }
+
+    /// Tells if the specified offset is located between `NOFORMATBEGIN` and `NOFORMATEND` comments.
+    fn is_inside_no_format(text: &str, offset: usize) -> bool {
+        let text = &text[..offset];
+        match text.rfind(NOFORMAT_BEGIN_COMMENT) {
+            None => false,
+            Some(begin) => match text.rfind(NOFORMAT_END_COMMENT) {
+                None => true,
+                Some(end) => end < begin,
+            },
+        }
+    }
+
+    /// Applies the most simplest formatting rules that do not require
+    /// any prior parsing (no tokens required).
+    ///
+    /// # Return
+    /// `Ok` with formatted code or `Err` with an error.
+    fn apply_simple_rules(&self, content: &str) -> Result<String, FormatError> {
+        let tokens = parser::layout_token_parser()
+            .parse(content)
+            .into_result()
+            .map_err(|errors| {
+                let error = &errors[0];
+                FormatError::ParseError {
+                    span: *error.span(),
+                    location: Location::default(),
+                    reason: error.reason().to_string(),
+                }
+            })?;
+        let tokens = parser::mark_unary_operators(tokens);
+
+        SimpleRulesPrinter::new(self, content).print(tokens)
+    }
+
+    /// Checks complex formatting rules that require prior parsing (tokens required).
+    ///
+    /// # Arguments
+    /// - `complex_tokens` Parsed tokens to check.
+    /// - `warnings` Collects code that was skipped because we were unable to understand it.
+    ///
+    /// # Return
+    /// `Ok` if all rules are satisfied, otherwise `Err` that describes the first violated rule.
+    fn check_complex_rules(
+        &self,
+        text: &str,
+        tokens: &[(Token<'_>, SimpleSpan)],
+        complex_tokens: Vec<(parser::ComplexToken<'_>, SimpleSpan)>,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        // Collect global functions to match declarations with definitions.
+        let functions = complex_tokens
+            .iter()
+            .filter_map(|(token, _)| match token {
+                Function(info) => Some(info),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // Prepare some variables to determine if we are inside of a global scope or inside of some function.
+        let mut is_global_scope = true;
+        let mut is_inside_nolint = false;
+        let mut nolint_begin_span = Span::from(0..0);
+        let mut nolint_section_rules: Option<Vec<&'static str>> = None;
+        let mut scope_nesting_count = 0;
+        let mut is_after_static = false; // like `static const int value`
+
+        // Prepare some variables to look for shadowed names.
+        let mut global_names: Vec<&str> = Vec::new();
+        let mut local_scopes: Vec<Vec<&str>> = Vec::new(); // first scope contains function arguments
+        let mut names_for_next_scope: Vec<&str> = Vec::new(); // like `i` in `for (int i = 0; ...) {`
+        let mut parentheses_nesting_count = 0;
+
+        if self.config.version_directive_first {
+            self.check_version_directive_first(&complex_tokens, warnings)?;
+        }
+
+        let mut token_iter = complex_tokens.iter().peekable();
+        while let Some((complex_token, span)) = token_iter.next() {
+            // Remember if the declaration (if this token is a declaration) is marked as `static`.
+            let is_static = is_after_static;
+            is_after_static = match complex_token {
+                Other(Token::Ident("static")) => true,
+                Other(Token::Ident(_)) => is_after_static,
+                _ => false,
+            };
+
+            // Check for nolint section.
+            if let Other(Token::Comment(text)) = *complex_token {
+                if text.starts_with("NOLINTBEGIN") {
+                    is_inside_nolint = true;
+                    nolint_begin_span = *span;
+                    nolint_section_rules = self.get_nolint_rules(text, *span, warnings)?;
+                } else if text.starts_with("NOLINTEND") {
+                    is_inside_nolint = false;
+                }
+            }
+
+            // Get rules disabled for this token (an empty array if all rules are disabled).
+            let nolint_rules = if is_inside_nolint {
+                nolint_section_rules.clone()
+            }
+            // Check if next token is a nolint.
+            else if let Some((Other(Token::Comment(text)), comment_span)) = token_iter.peek() {
+                if text.starts_with("NOLINT") {
+                    self.get_nolint_rules(text, *comment_span, warnings)?
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            // Skip this token if all rules are disabled, otherwise check it without disabled rules.
+            let nolint_formatter;
+            let formatter = match nolint_rules {
+                None => self,
+                Some(rules) if rules.is_empty() => continue,
+                Some(rules) => {
+                    let mut config = self.config.clone();
+                    for id in rules {
+                        config.disable_rule(id);
+                    }
+                    nolint_formatter = Self {
+                        config,
+                        path_to_shader: self.path_to_shader.clone(),
+                        shader_stage: self.shader_stage,
+                        compact_output: self.compact_output,
+                        lint_only: self.lint_only,
+                    };
+                    &nolint_formatter
+                }
+            };
+
+            match complex_token {
+                VariableDeclaration(variables) => {
+                    let scope = match (is_static, is_global_scope) {
+                        (true, is_global) => VariableScope::Static { is_global },
+                        (false, true) => VariableScope::Global,
+                        (false, false) if parentheses_nesting_count > 0 => {
+                            VariableScope::LoopCounter
+                        }
+                        (false, false) => VariableScope::Local,
+                    };
+                    for (_type, name, name_span) in variables {
+                        formatter.check_variable_name(name, *name_span, *_type, scope, warnings)?;
+
+                        if !formatter.config.warn_on_shadowing {
+                            continue;
+                        }
+                        if is_global_scope {
+                            global_names.push(name);
+                            continue;
+                        }
+
+                        self.check_shadowing(
+                            name,
+                            *name_span,
+                            &global_names,
+                            &local_scopes,
+                            warnings,
+                        )?;
+                        if parentheses_nesting_count > 0 {
+                            names_for_next_scope.push(name);
+                        } else if let Some(scope) = local_scopes.last_mut() {
+                            scope.push(name);
+                        }
+                    }
+                }
+                Struct(info) => {
+                    formatter.check_struct(info, warnings)?;
+
+                    if info.is_push_constant {
+                        formatter.check_push_constant_size(
+                            text,
+                            info.name,
+                            info.name_span,
+                            &info.fields,
+                            warnings,
+                        )?;
+                    }
+
+                    is_global_scope = true;
+
+                    // Check instance name of an interface block.
+                    if let Some(instance) = &info.instance {
+                        formatter.check_variable_name(
+                            instance.name,
+                            instance.name_span,
+                            instance._type,
+                            if is_global_scope {
+                                VariableScope::Global
+                            } else {
+                                VariableScope::Local
+                            },
+                            warnings,
+                        )?;
+                    }
+                }
+                ConstantBuffer(info) => {
+                    formatter.check_constant_buffer(info, warnings)?;
+
+                    if info.is_push_constant {
+                        formatter.check_push_constant_size(
+                            text,
+                            info.name,
+                            info.name_span,
+                            &info.fields,
+                            warnings,
+                        )?;
+                    }
+
+                    is_global_scope = true;
+
+                    // Fields of constant buffers are global variables.
+                    if formatter.config.warn_on_shadowing {
+                        global_names.extend(info.fields.iter().map(|field| field.name));
+                    }
+                }
+                Enum(info) => {
+                    // Check names.
+                    formatter.check_forbidden_identifier(info.name, info.name_span, warnings)?;
+                    for value in &info.values {
+                        formatter.check_forbidden_identifier(
+                            value.name,
+                            value.name_span,
+                            warnings,
+                        )?;
+                    }
+
+                    // Check name case.
+                    if let Some(case) = formatter.config.enum_case {
+                        self.check_name_case(
+                            "EnumCase",
+                            info.name,
+                            info.name_span,
+                            case,
+                            warnings,
+                        )?;
+                    }
+
+                    // Check value case.
+                    if let Some(case) = formatter.config.enum_value_case {
+                        for value in &info.values {
+                            self.check_name_case(
+                                "EnumValueCase",
+                                value.name,
+                                value.name_span,
+                                case,
+                                warnings,
+                            )?;
+                        }
+                    }
+                }
+                Function(info) if info.is_declaration => {
+                    // Declarations don't have a body (we stay in the global scope).
+                    formatter.check_function(info, &functions, warnings)?;
+                }
+                Function(info) => {
+                    is_global_scope = false;
+                    scope_nesting_count = 0;
+
+                    local_scopes = vec![info.args.iter().map(|arg| arg.name).collect()];
+                    names_for_next_scope.clear();
+                    parentheses_nesting_count = 0;
+
+                    formatter.check_function(info, &functions, warnings)?;
+
+                    if formatter.config.max_function_lines.is_some()
+                        || formatter.config.max_nesting_depth.is_some()
+                    {
+                        formatter.check_function_body_size(text, info, *span, tokens, warnings)?;
+                    }
+
+                    if formatter.config.warn_on_uninitialized_variables {
+                        self.check_uninitialized_variables(
+                            *span,
+                            tokens,
+                            &complex_tokens,
+                            warnings,
+                        )?;
+                    }
+
+                    if formatter.config.forbid_unused_variables
+                        || formatter.config.forbid_unused_parameters
+                    {
+                        formatter.check_unused_names(
+                            info,
+                            *span,
+                            tokens,
+                            &complex_tokens,
+                            warnings,
+                        )?;
+                    }
+                }
+                DefaultPrecision(_) => {} // nothing to check
+                Switch { has_default } => {
+                    if formatter.config.require_default_in_switch && !has_default {
+                        self.report(
+                            FormatError::rule_violation(
+                                "RequireDefaultInSwitch",
+                                "switch",
+                                None,
+                                "the switch statement has no `default` label".to_owned(),
+                                *span,
+                            ),
+                            warnings,
+                        )?;
+                    }
+                }
+                Other(token) => {
+                    if !is_global_scope {
+                        match token {
+                            Token::Ctrl('{') => {
+                                local_scopes.push(std::mem::take(&mut names_for_next_scope));
+                            }
+                            Token::Ctrl('}') if local_scopes.len() > 1 => {
+                                local_scopes.pop();
+                            }
+                            Token::Ctrl('(') => parentheses_nesting_count += 1,
+                            Token::Ctrl(')') if parentheses_nesting_count > 0 => {
+                                parentheses_nesting_count -= 1
+                            }
+                            Token::Ctrl(';') if parentheses_nesting_count == 0 => {
+                                // Statement of a loop without braces ended.
+                                names_for_next_scope.clear();
+                            }
+                            _ => {}
+                        }
+
+                        if *token == Token::Ctrl('{') {
+                            scope_nesting_count += 1;
+                        } else if *token == Token::Ctrl('}') {
+                            if scope_nesting_count == 0 {
+                                // Unexpected, we probably have something wrong in other place.
+                                // Skip this brace and continue checking from the global scope.
+                                warnings.push(FormatError::ParseError {
+                                    span: *span,
+                                    location: Location::default(),
+                                    reason: "found '}' but scope nesting counter is already zero"
+                                        .to_owned(),
+                                });
+                                is_global_scope = true;
+                            } else {
+                                scope_nesting_count -= 1;
+                                if scope_nesting_count == 0 {
+                                    is_global_scope = true;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if is_inside_nolint && self.config.validate_nolint_comments {
+            return self.report(
+                FormatError::rule_violation(
+                    "ValidateNolintComments",
+                    "NOLINTBEGIN",
+                    None,
+                    "`NOLINTBEGIN` was found but no matching `NOLINTEND` detected".to_owned(),
+                    nolint_begin_span,
+                ),
+                warnings,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Returns identifiers of rules listed in a `NOLINT` comment (like `NOLINT(VariableCase)`),
+    /// an empty array if the comment disables all rules.
+    ///
+    /// Unknown rules are ignored if `ValidateNolintComments` is disabled (or reported as
+    /// a warning), `None` is returned if only unknown rules are listed.
+    fn get_nolint_rules(
+        &self,
+        comment: &str,
+        span: Span,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<Option<Vec<&'static str>>, FormatError> {
+        let rest = ["NOLINTBEGIN", "NOLINTEND", "NOLINT"]
+            .iter()
+            .find_map(|prefix| comment.strip_prefix(prefix))
+            .unwrap_or_default();
+        let Some(list) = rest.strip_prefix('(') else {
+            return Ok(Some(Vec::new()));
+        };
+        let list = match list.split_once(')') {
+            Some((list, _)) => list,
+            None => {
+                if self.config.validate_nolint_comments {
+                    self.report(
+                        FormatError::rule_violation(
+                            "ValidateNolintComments",
+                            comment,
+                            None,
+                            "expected `)` after the list of rules in the `NOLINT` comment"
+                                .to_owned(),
+                            span,
+                        ),
+                        warnings,
+                    )?;
+                }
+                list
+            }
+        };
+
+        let mut rules = Vec::new();
+        for id in list.split(',').map(str::trim) {
+            match rules::find_rule(id) {
+                Some(rule) => rules.push(rule.id),
+                None if self.config.validate_nolint_comments => self.report(
+                    FormatError::rule_violation(
+                        "ValidateNolintComments",
+                        id,
+                        None,
+                        format!("found unknown rule \"{}\" in the `NOLINT` comment", id),
+                        span,
+                    ),
+                    warnings,
+                )?,
+                None => {}
+            }
+        }
+
+        Ok((!rules.is_empty()).then_some(rules))
+    }
+
+    /// Looks for tokens of a function body (including braces).
+    ///
+    /// # Arguments
+    /// - `span` Span of the function's declaration (without the body).
+    /// - `tokens` All tokens of the file.
+    ///
+    /// # Return
+    /// `None` if the function has no body.
+    fn find_function_body<'a, 'src>(
+        span: Span,
+        tokens: &'a [(Token<'src>, SimpleSpan)],
+    ) -> Option<&'a [(Token<'src>, SimpleSpan)]> {
+        let body_start = tokens
+            .iter()
+            .position(|(_, token_span)| token_span.start >= span.end)?;
+        if tokens[body_start].0 != Token::Ctrl('{') {
+            // Function declaration without a body.
+            return None;
+        }
+
+        let mut nesting_count = 0;
+        let mut body_end = tokens.len();
+        for (i, (token, _)) in tokens.iter().enumerate().skip(body_start) {
+            if *token == Token::Ctrl('{') {
+                nesting_count += 1;
+            } else if *token == Token::Ctrl('}') {
+                nesting_count -= 1;
+                if nesting_count == 0 {
+                    body_end = i + 1;
+                    break;
+                }
+            }
+        }
+
+        Some(&tokens[body_start..body_end])
+    }
+
+    /// Checks that the body of a function is not too long and does not have too much nested blocks.
+    ///
+    /// # Arguments
+    /// - `text` Text that is being checked.
+    /// - `info` Information about the function.
+    /// - `span` Span of the function's declaration (without the body).
+    /// - `tokens` All tokens of the file.
+    ///
+    /// # Return
+    /// `Ok` if all rules are satisfied, otherwise `Err` that describes the first violated rule.
+    fn check_function_body_size(
+        &self,
+        text: &str,
+        info: &FunctionInfo,
+        span: Span,
+        tokens: &[(Token<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        let Some(body) = Self::find_function_body(span, tokens) else {
+            return Ok(());
+        };
+
+        if let Some(max_lines) = self.config.max_function_lines {
+            // Count lines between the braces.
+            let line_count = text
+                .get(body[0].1.end..body[body.len() - 1].1.start)
+                .map(|code| code.matches('\n').count().saturating_sub(1))
+                .unwrap_or_default();
+            if line_count > max_lines {
+                self.report(
+                    FormatError::rule_violation(
+                        "MaxFunctionLines",
+                        info.name,
+                        None,
+                        format!(
+                            "the function has {} lines while the maximum is {}",
+                            line_count, max_lines
+                        ),
+                        info.name_span,
+                    ),
+                    warnings,
+                )?;
+            }
+        }
+
+        if let Some(max_depth) = self.config.max_nesting_depth {
+            // Find the deepest block (not counting the body).
+            let mut depth = 0;
+            let mut max_found_depth = 0;
+            for (token, _) in body {
+                if *token == Token::Ctrl('{') {
+                    depth += 1;
+                    max_found_depth = max_found_depth.max(depth - 1);
+                } else if *token == Token::Ctrl('}') {
+                    depth -= 1;
+                }
+            }
+            if max_found_depth > max_depth {
+                return self.report(
+                    FormatError::rule_violation(
+                        "MaxNestingDepth",
+                        info.name,
+                        None,
+                        format!(
+                            "the function has {} nested blocks while the maximum is {}",
+                            max_found_depth, max_depth
+                        ),
+                        info.name_span,
+                    ),
+                    warnings,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns names (and spans of names) of variables declared in the specified function body.
+    fn collect_local_variables<'src>(
+        body: &[(Token<'_>, SimpleSpan)],
+        complex_tokens: &[(parser::ComplexToken<'src>, SimpleSpan)],
+    ) -> Vec<(&'src str, Span)> {
+        let body_span = Span::from(body[0].1.start..body[body.len() - 1].1.end);
+
+        complex_tokens
+            .iter()
+            .filter(|(_, token_span)| {
+                token_span.start >= body_span.start && token_span.end <= body_span.end
+            })
+            .filter_map(|(token, _)| match token {
+                VariableDeclaration(variables) => Some(variables),
+                _ => None,
+            })
+            .flatten()
+            .map(|(_, name, name_span)| (*name, *name_span))
+            .collect()
+    }
+
+    /// Checks that local variables declared without an initializer are assigned before they
+    /// are read (uses a simple forward scan of the variable's scope).
+    ///
+    /// # Arguments
+    /// - `span` Span of the function's declaration (without the body).
+    /// - `tokens` All tokens of the file.
+    /// - `complex_tokens` All complex tokens of the file.
+    ///
+    /// # Return
+    /// `Ok` if all rules are satisfied, otherwise `Err` that describes the first violated rule.
+    fn check_uninitialized_variables(
+        &self,
+        span: Span,
+        tokens: &[(Token<'_>, SimpleSpan)],
+        complex_tokens: &[(parser::ComplexToken<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        let Some(body) = Self::find_function_body(span, tokens) else {
+            return Ok(());
+        };
+
+        for (name, name_span) in Self::collect_local_variables(body, complex_tokens) {
+            let Some(declaration_index) = body.iter().position(|(_, span)| *span == name_span)
+            else {
+                continue;
+            };
+
+            // Skip array size (like `[4]` in `float values[4] = ...`).
+            let mut i = declaration_index + 1;
+            while body
+                .get(i)
+                .is_some_and(|(token, _)| *token == Token::Ctrl('['))
+            {
+                while body
+                    .get(i)
+                    .is_some_and(|(token, _)| *token != Token::Ctrl(']'))
+                {
+                    i += 1;
+                }
+                i += 1;
+            }
+
+            // Skip initialized variables.
+            if matches!(body.get(i), Some((Token::Op("="), _))) {
+                continue;
+            }
+
+            // Look for the first usage in the scope of the variable.
+            let mut scope_nesting_count = 0;
+            // For each open parenthesis stores whether it's a function call (that might
+            // assign a value to an `out` argument).
+            let mut parentheses: Vec<bool> = Vec::new();
+            for (j, (token, token_span)) in body.iter().enumerate().skip(i) {
+                let previous_token = &body[j - 1].0;
+
+                match token {
+                    Token::Ctrl('{') => scope_nesting_count += 1,
+                    Token::Ctrl('}') => {
+                        if scope_nesting_count == 0 {
+                            // End of the variable's scope.
+                            break;
+                        }
+                        scope_nesting_count -= 1;
+                    }
+                    Token::Ctrl('(') => parentheses.push(matches!(
+                        previous_token,
+                        Token::Ident(name) if !matches!(*name, "if" | "for" | "while" | "switch")
+                    )),
+                    Token::Ctrl(')') => {
+                        parentheses.pop();
+                    }
+                    Token::Ident(used_name)
+                        if *used_name == name && *previous_token != Token::Ctrl('.') =>
+                    {
+                        let next_token = body.get(j + 1).map(|(token, _)| token);
+                        if parentheses.contains(&true)
+                            || matches!(
+                                next_token,
+                                Some(Token::Op("=") | Token::Ctrl('.') | Token::Ctrl('['))
+                            )
+                        {
+                            // Assigned (or might be assigned).
+                            break;
+                        }
+
+                        self.report(
+                            FormatError::rule_violation(
+                                "WarnOnUninitializedVariables",
+                                name,
+                                None,
+                                "the variable is read before a value is assigned to it".to_owned(),
+                                *token_span,
+                            ),
+                            warnings,
+                        )?;
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that local variables and parameters of a function are used in the function body.
+    ///
+    /// # Arguments
+    /// - `info` Information about the function.
+    /// - `span` Span of the function's declaration (without the body).
+    /// - `tokens` All tokens of the file.
+    /// - `complex_tokens` All complex tokens of the file.
+    ///
+    /// # Return
+    /// `Ok` if all rules are satisfied, otherwise `Err` that describes the first violated rule.
+    fn check_unused_names(
+        &self,
+        info: &FunctionInfo,
+        span: Span,
+        tokens: &[(Token<'_>, SimpleSpan)],
+        complex_tokens: &[(parser::ComplexToken<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        let Some(body) = Self::find_function_body(span, tokens) else {
+            return Ok(());
+        };
+        let variables = Self::collect_local_variables(body, complex_tokens);
+
+        // Returns `true` if the name is used somewhere in the body, member names
+        // (like `value` in `data.value`) and names of declared variables are ignored.
+        let is_used = |name: &str, ignore_assignments: bool| {
+            body.iter().enumerate().any(|(i, (token, token_span))| {
+                if *token != Token::Ident(name)
+                    || (i > 0 && body[i - 1].0 == Token::Ctrl('.'))
+                    || variables
+                        .iter()
+                        .any(|(_, name_span)| *name_span == *token_span)
+                {
+                    return false;
+                }
+
+                !ignore_assignments || !matches!(body.get(i + 1), Some((Token::Op("="), _)))
+            })
+        };
+
+        if self.config.forbid_unused_parameters {
+            for arg in &info.args {
+                if arg.semantic.is_none() && !is_used(arg.name, false) {
+                    self.report(
+                        FormatError::rule_violation(
+                            "ForbidUnusedParameters",
+                            arg.name,
+                            None,
+                            "the parameter is not used in the function body".to_owned(),
+                            arg.name_span,
+                        ),
+                        warnings,
+                    )?;
+                }
+            }
+        }
+
+        if self.config.forbid_unused_variables {
+            for (name, name_span) in &variables {
+                if !is_used(name, true) {
+                    self.report(
+                        FormatError::rule_violation(
+                            "ForbidUnusedVariables",
+                            name,
+                            None,
+                            "the variable is declared but never read".to_owned(),
+                            *name_span,
+                        ),
+                        warnings,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a local variable does not use a name of a global variable, a function argument
+    /// or a local variable from an outer scope.
+    ///
+    /// # Arguments
+    /// - `name` Name of the local variable.
+    /// - `span` Span of the name.
+    /// - `global_names` Names of global variables declared so far.
+    /// - `local_scopes` Names of local variables in all scopes (the first one has arguments).
+    fn check_shadowing(
+        &self,
+        name: &str,
+        span: Span,
+        global_names: &[&str],
+        local_scopes: &[Vec<&str>],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        let shadowed = match local_scopes.iter().position(|scope| scope.contains(&name)) {
+            Some(0) => "an argument",
+            Some(_) => "a local variable",
+            None if global_names.contains(&name) => "a global variable",
+            None => return Ok(()),
+        };
+
+        self.report(
+            FormatError::rule_violation(
+                "WarnOnShadowing",
+                name,
+                None,
+                format!("the variable shadows {} with the same name", shadowed),
+                span,
+            ),
+            warnings,
+        )
+    }
+
+    /// Checks docs, name and arguments of a function (or a method).
+    ///
+    /// # Arguments
+    /// - `info` Function to check.
+    /// - `functions` All functions (or methods) of the same scope (including `info`).
+    ///
+    /// # Return
+    /// `Ok` if all rules are satisfied, otherwise `Err` that describes the first violated rule.
+    fn check_function(
+        &self,
+        info: &FunctionInfo,
+        functions: &[&FunctionInfo],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        // Check docs.
+        if self.config.require_docs_on_functions
+            && !self.are_docs_on_other_declaration(info, functions)
+        {
+            self.check_function_docs(info, warnings)?;
+        }
+        if self.config.require_brief_in_docs {
+            self.check_docs_brief(info.name, &info.docs, info.docs_span, warnings)?;
+        }
+        if self.config.require_param_docs_direction {
+            self.check_param_docs_direction(info, warnings)?;
+        }
+
+        // Check name.
+        self.check_forbidden_identifier(info.name, info.name_span, warnings)?;
+        self.check_identifier_length(info.name, info.name_span, warnings)?;
+        if let Some(case) = self.config.function_case {
+            self.check_name_case("FunctionCase", info.name, info.name_span, case, warnings)?;
+        }
+
+        // Check semantics.
+        if self.config.validate_semantics {
+            for arg in &info.args {
+                if let Some(semantic) = arg.semantic {
+                    self.check_semantic(semantic, arg.name_span, warnings)?;
+                }
+            }
+            if let Some(semantic) = info.return_semantic {
+                self.check_semantic(semantic, info.name_span, warnings)?;
+            }
+        }
+
+        // Check compute shader attributes.
+        if self.config.require_numthreads
+            && self.shader_stage == Some(ShaderStage::Compute)
+            && Self::is_entry_point(info)
+            && !info.attributes.contains(&"numthreads")
+        {
+            self.report(FormatError::rule_violation(
+                "RequireNumthreads",
+                info.name,
+                None,
+                format!(
+                    "expected the compute shader entry point \"{}\" to have the `numthreads` attribute",
+                    info.name
+                ),
+                info.name_span,
+            ), warnings)?;
+        }
+
+        // Check entry point name.
+        if let Some(pattern) = &self.config.entry_point_name_pattern {
+            if Self::is_entry_point(info) && !pattern.is_match(info.name) {
+                self.report(
+                    FormatError::rule_violation(
+                        "EntryPointNamePattern",
+                        info.name,
+                        None,
+                        format!(
+                            "the name of the entry point \"{}\" does not match the pattern \"{}\"",
+                            info.name, pattern
+                        ),
+                        info.name_span,
+                    ),
+                    warnings,
+                )?;
+            }
+        }
+
+        // Check args.
+        for arg_info in &info.args {
+            self.check_variable_name(
+                arg_info.name,
+                arg_info.name_span,
+                arg_info._type,
+                VariableScope::Local,
+                warnings,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the specified HLSL semantic is a known system-value semantic (if it starts
+    /// with `SV_`) or one of `CustomSemantics` (if any are specified).
+    ///
+    /// # Arguments
+    /// - `semantic` Semantic to check (like `SV_Target0` or `TEXCOORD1`).
+    /// - `span` Span of the name of the argument, field or function that uses the semantic.
+    fn check_semantic(
+        &self,
+        semantic: &str,
+        span: Span,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        // Semantics are case-insensitive and may have an index (like `0` in `TEXCOORD0`).
+        let name = semantic.trim_end_matches(|_char: char| _char.is_ascii_digit());
+
+        let message = if name
+            .to_ascii_uppercase()
+            .starts_with(SYSTEM_VALUE_SEMANTIC_PREFIX)
+        {
+            if HLSL_SYSTEM_VALUE_SEMANTICS
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(name))
+            {
+                return Ok(());
+            }
+            format!("\"{}\" is not a known system-value semantic", semantic)
+        } else {
+            if self.config.custom_semantics.is_empty()
+                || self
+                    .config
+                    .custom_semantics
+                    .iter()
+                    .any(|custom| custom.eq_ignore_ascii_case(name))
+            {
+                return Ok(());
+            }
+            format!(
+                "\"{}\" is not a system-value semantic and is not listed in `CustomSemantics`",
+                semantic
+            )
+        };
+
+        self.report(
+            FormatError::rule_violation("ValidateSemantics", semantic, None, message, span),
+            warnings,
+        )
+    }
+
+    /// Tells if the specified function is a shader entry point (uses system-value semantics
+    /// like `SV_Position` in arguments or return value).
+    pub fn is_entry_point(info: &FunctionInfo) -> bool {
+        info.args
+            .iter()
+            .filter_map(|arg| arg.semantic)
+            .chain(info.return_semantic)
+            .any(|semantic| {
+                semantic
+                    .to_ascii_uppercase()
+                    .starts_with(SYSTEM_VALUE_SEMANTIC_PREFIX)
+            })
+    }
+
+    /// Checks that `#version` is the first directive in the file and `#extension` directives
+    /// are placed right after it (only comments are allowed in between).
+    ///
+    /// # Return
+    /// `Ok` if directives are placed correctly, otherwise `Err` that describes the misplaced directive.
+    fn check_version_directive_first(
+        &self,
+        complex_tokens: &[(parser::ComplexToken<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        let mut found_code = false; // something other than a comment, `#version` or `#extension`
+
+        for (index, (complex_token, span)) in complex_tokens.iter().enumerate() {
+            let directive = match complex_token {
+                Other(Token::Comment(_)) => continue,
+                Other(Token::Preprocessor(text)) => Self::get_preprocessor_directive_name(text),
+                _ => "",
+            };
+
+            match directive {
+                "version" => {
+                    let is_first = complex_tokens[..index]
+                        .iter()
+                        .all(|(token, _)| matches!(token, Other(Token::Comment(_))));
+                    if !is_first {
+                        self.report(
+                            FormatError::rule_violation(
+                                "VersionDirectiveFirst",
+                                "#version",
+                                None,
+                                "expected `#version` to be the first directive in the file"
+                                    .to_owned(),
+                                *span,
+                            ),
+                            warnings,
+                        )?;
+                    }
+                }
+                "extension" => {
+                    if found_code {
+                        self.report(
+                            FormatError::rule_violation(
+                                "VersionDirectiveFirst",
+                                "#extension",
+                                None,
+                                "expected `#extension` to be placed right after `#version`"
+                                    .to_owned(),
+                                *span,
+                            ),
+                            warnings,
+                        )?;
+                    }
+                }
+                _ => found_code = true,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Detects indentation that is used by most lines of the specified code: tabs or spaces
+    /// (the number of spaces is determined by the most common increase of indentation between
+    /// consecutive lines).
+    ///
+    /// # Return
+    /// `FourSpaces` if the code has no indented lines.
+    fn detect_indentation(content: &str) -> IndentationRule {
+        let mut tab_line_count = 0;
+        let mut space_line_count = 0;
+        let mut two_space_step_count = 0;
+        let mut four_space_step_count = 0;
+
+        let mut prev_width = 0;
+        for line in content.lines() {
+            let code = line.trim_start_matches([' ', '\t']);
+            if code.is_empty() || code.starts_with('*') {
+                continue; // empty lines and lines of block comments like ` * text`
+            }
+            let indentation = &line[..line.len() - code.len()];
+
+            if indentation.starts_with('\t') {
+                tab_line_count += 1;
+                prev_width = 0;
+                continue;
+            }
+
+            let width = indentation.len();
+            if width > 0 {
+                space_line_count += 1;
+            }
+            match width.checked_sub(prev_width) {
+                Some(2) => two_space_step_count += 1,
+                Some(4) => four_space_step_count += 1,
+                _ => {}
+            }
+            prev_width = width;
+        }
+
+        if tab_line_count > space_line_count {
+            IndentationRule::Tab
+        } else if two_space_step_count > four_space_step_count {
+            IndentationRule::TwoSpaces
+        } else {
+            IndentationRule::FourSpaces
+        }
+    }
+
+    /// Detects placement of open braces that is used by most blocks of the specified code:
+    /// on a separate line (`Before`) or at the end of the line with the code (`After`),
+    /// braces of initializers (like `= {`) are not counted.
+    ///
+    /// # Return
+    /// `After` if the code has no blocks or both placements are used equally.
+    fn detect_brace_placement(content: &str) -> NewLineOnOpenBrace {
+        let mut before_count = 0;
+        let mut after_count = 0;
+
+        for line in content.lines() {
+            let code = line.trim();
+            if code.starts_with("//") || code.starts_with('#') {
+                continue;
+            }
+
+            if code.starts_with('{') {
+                before_count += 1;
+            } else if let Some(code_before_brace) = code.strip_suffix('{') {
+                let code_before_brace = code_before_brace.trim_end();
+                if !code_before_brace.ends_with(['=', ',', '{', '(']) {
+                    after_count += 1;
+                }
+            }
+        }
+
+        if before_count > after_count {
+            NewLineOnOpenBrace::Before
+        } else {
+            NewLineOnOpenBrace::After
+        }
+    }
+
+    /// Detects shader stage using names of semantics, attributes, functions and built-in variables
+    /// that are used in the specified code.
+    ///
+    /// # Return
+    /// `None` if no stage-specific names were found or if they belong to different stages (for
+    /// example, when a file contains both vertex and fragment shaders).
+    fn detect_shader_stage(content: &str) -> Option<ShaderStage> {
+        let tokens = parser::token_parser().parse(content).into_output()?;
+
+        let mut found_stages: Vec<ShaderStage> = Vec::new();
+        for (token, _) in &tokens {
+            let Token::Ident(name) = token else {
+                continue;
+            };
+            let Some((_, stage)) = SHADER_STAGE_MARKERS
+                .iter()
+                .find(|(marker, _)| marker == name)
+            else {
+                continue;
+            };
+            if !found_stages.contains(stage) {
+                found_stages.push(*stage);
+            }
+        }
+
+        // Mesh and amplification shaders also use compute shader markers (like `numthreads`).
+        if found_stages.contains(&ShaderStage::Mesh)
+            || found_stages.contains(&ShaderStage::Amplification)
+        {
+            found_stages.retain(|stage| *stage != ShaderStage::Compute);
+        }
+
+        match found_stages.as_slice() {
+            [stage] => Some(*stage),
+            _ => None,
+        }
+    }
+
+    /// Checks that all branches of conditional directives can be active with the values
+    /// of macros from the config.
+    fn check_dead_branches(
+        &self,
+        tokens: &[(Token<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if !self.config.forbid_dead_branches || self.config.defines.is_empty() {
+            return Ok(());
+        }
+
+        match preprocessor::find_dead_branch(tokens, &self.config.defines) {
+            Some((directive, span)) => self.report(
+                FormatError::rule_violation(
+                    "ForbidDeadBranches",
+                    directive,
+                    None,
+                    "this branch is never active with the defines from the config".to_owned(),
+                    span,
+                ),
+                warnings,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that macros are not defined again with different bodies.
+    fn check_macro_redefinitions(
+        &self,
+        text: &str,
+        tokens: &[(Token<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if !self.config.forbid_macro_redefinition {
+            return Ok(());
+        }
+
+        let current_dir = self.path_to_shader.as_ref().and_then(|path| path.parent());
+        match preprocessor::find_macro_redefinition(
+            text,
+            tokens,
+            current_dir,
+            &self.config.include_dirs,
+        ) {
+            Some(redefinition) => self.report(
+                FormatError::rule_violation(
+                    "ForbidMacroRedefinition",
+                    &redefinition.name,
+                    None,
+                    format!(
+                        "macro \"{}\" is defined at {} and redefined at {}",
+                        redefinition.name,
+                        redefinition.first_location,
+                        redefinition.second_location
+                    ),
+                    redefinition.span,
+                ),
+                warnings,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that there are no resources bound to the same slot.
+    fn check_duplicate_bindings(
+        &self,
+        tokens: &[(Token<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if !self.config.forbid_duplicate_bindings {
+            return Ok(());
+        }
+
+        let bindings = bindings::collect_bindings(tokens);
+        match bindings::find_duplicate_binding(&bindings) {
+            Some((binding, duplicate)) => self.report(
+                FormatError::rule_violation(
+                    "ForbidDuplicateBindings",
+                    duplicate.name,
+                    None,
+                    format!(
+                        "the slot {} is already used by \"{}\"",
+                        duplicate.slot, binding.name
+                    ),
+                    duplicate.name_span,
+                ),
+                warnings,
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that GLSL `in`/`out` variables don't share locations and are declared in order of their locations.
+    fn check_locations(
+        &self,
+        text: &str,
+        tokens: &[(Token<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if !self.config.forbid_duplicate_locations && !self.config.require_ordered_locations {
+            return Ok(());
+        }
+
+        let locations = bindings::collect_locations(tokens);
+        let line_index = helpers::LineIndex::new(text);
+        let line_of = |span: SimpleSpan| line_index.line_and_column(span.start).0;
+
+        if self.config.forbid_duplicate_locations {
+            if let Some((location, duplicate)) = bindings::find_duplicate_location(&locations) {
+                self.report(
+                    FormatError::rule_violation(
+                        "ForbidDuplicateLocations",
+                        duplicate.name,
+                        None,
+                        format!(
+                            "the {} location {} is already used by \"{}\" (line {})",
+                            duplicate.direction,
+                            duplicate.location,
+                            location.name,
+                            line_of(location.name_span)
+                        ),
+                        duplicate.name_span,
+                    ),
+                    warnings,
+                )?;
+            }
+        }
+
+        if self.config.require_ordered_locations {
+            if let Some((previous, current)) = bindings::find_unordered_location(&locations) {
+                return self.report(
+                    FormatError::rule_violation(
+                        "RequireOrderedLocations",
+                        current.name,
+                        None,
+                        format!(
+                            "the {} location {} is declared after location {} of \"{}\" (line {})",
+                            current.direction,
+                            current.location,
+                            previous.location,
+                            previous.name,
+                            line_of(previous.name_span)
+                        ),
+                        current.name_span,
+                    ),
+                    warnings,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that indentation of lines does not contain both tabs and spaces (a single space
+    /// after tabs before `*` of a block comment line is allowed).
+    fn check_mixed_indentation(
+        &self,
+        text: &str,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if !self.config.forbid_mixed_indentation {
+            return Ok(());
+        }
+
+        let mut line_start = 0;
+        for line in text.split_inclusive('\n') {
+            let code = line.trim_start_matches([' ', '\t']);
+            let indentation = &line[..line.len() - code.len()];
+
+            let is_comment_alignment =
+                code.starts_with('*') && indentation.trim_start_matches('\t') == " ";
+            if indentation.contains(' ') && indentation.contains('\t') && !is_comment_alignment {
+                self.report(
+                    FormatError::rule_violation(
+                        "ForbidMixedIndentation",
+                        "indentation",
+                        None,
+                        "indentation of the line contains both tabs and spaces".to_owned(),
+                        Span::from(line_start..line_start + indentation.len()),
+                    ),
+                    warnings,
+                )?;
+            }
+
+            line_start += line.len();
+        }
+
+        Ok(())
+    }
+
+    /// Checks that all included files exist and that there are no circular includes.
+    fn check_includes(
+        &self,
+        tokens: &[(Token<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        let current_dir = self.path_to_shader.as_ref().and_then(|path| path.parent());
+
+        if self.config.require_existing_includes {
+            if let Some((span, include_path)) =
+                includes::find_missing_include(tokens, current_dir, &self.config.include_dirs)
+            {
+                self.report(
+                    FormatError::rule_violation(
+                        "RequireExistingIncludes",
+                        include_path,
+                        None,
+                        format!("unable to find the included file \"{}\"", include_path),
+                        span,
+                    ),
+                    warnings,
+                )?;
+            }
+        }
+
+        if self.config.forbid_circular_includes {
+            if let Some(path_to_shader) = &self.path_to_shader {
+                if let Some(span) = includes::find_circular_include(
+                    tokens,
+                    path_to_shader,
+                    &self.config.include_dirs,
+                ) {
+                    return self.report(
+                        FormatError::rule_violation(
+                            "ForbidCircularIncludes",
+                            "#include",
+                            None,
+                            "the included file (directly or indirectly) includes this file"
+                                .to_owned(),
+                            span,
+                        ),
+                        warnings,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that GLSL compute shaders declare the work group size (like `layout(local_size_x = 8) in;`).
+    fn check_local_size(
+        &self,
+        tokens: &[(Token<'_>, Span)],
+        complex_tokens: &[(parser::ComplexToken<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if !self.config.require_local_size || self.shader_stage != Some(ShaderStage::Compute) {
+            return Ok(());
+        }
+
+        // Only check files with the GLSL entry point (HLSL entry points use `numthreads` instead).
+        let Some(main) = complex_tokens.iter().find_map(|(token, _)| match token {
+            Function(info) if info.name == "main" && !info.attributes.contains(&"numthreads") => {
+                Some(info)
+            }
+            _ => None,
+        }) else {
+            return Ok(());
+        };
+
+        let has_local_size = tokens.iter().any(
+            |(token, _)| matches!(token, Token::Ident(name) if name.starts_with("local_size_")),
+        );
+        if !has_local_size {
+            return self.report(FormatError::rule_violation(
+                "RequireLocalSize",
+                main.name,
+                None,
+                "expected the compute shader to declare the work group size (like `layout(local_size_x = 64) in;`)"
+                    .to_owned(),
+                main.name_span,
+            ), warnings);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the default precision of floats is specified before any code that might use it.
+    fn check_default_precision(
+        &self,
+        complex_tokens: &[(parser::ComplexToken<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if !self.config.require_default_precision
+            || self.shader_stage != Some(ShaderStage::Fragment)
+        {
+            return Ok(());
+        }
+
+        for (complex_token, span) in complex_tokens {
+            match complex_token {
+                DefaultPrecision(Type::Float) => return Ok(()),
+                Other(Token::Comment(_)) | Other(Token::Preprocessor(_)) | DefaultPrecision(_) => {}
+                _ => return self.report(
+                    FormatError::rule_violation(
+                        "RequireDefaultPrecision",
+                        "precision",
+                        None,
+                        "expected the default precision of floats to be specified before any code"
+                            .to_owned(),
+                        *span,
+                    ),
+                    warnings,
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks various complex formatting rules on the specified variable.
+    ///
+    /// # Return
+    /// `Ok` if the name is correct (according to the rules), otherwise `Err` that contains
+    /// an error with suggestions according to the rules.
+    fn check_variable_name(
+        &self,
+        mut name: &str,
+        name_span: Span,
+        _type: Type,
+        scope: VariableScope,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        self.check_forbidden_identifier(name, name_span, warnings)?;
+        self.check_identifier_length(name, name_span, warnings)?;
+
+        // Skip GLSL built-ins (like redeclared `gl_Position`).
+        if name.starts_with(GLSL_BUILTIN_PREFIX) {
+            return Ok(());
+        }
+
+        // Check global variable prefix.
+        if let Some(global_prefixes) = &self.config.global_variable_prefix {
+            // TODO: rework this branch into a single one when Rust's #53667 is resolved
+            if scope.is_global() {
+                let Some(name_without_prefix) = Self::strip_any_prefix(name, global_prefixes)
+                else {
+                    let correct_name = global_prefixes[0].to_owned() + name;
+                    return self.report(FormatError::rule_violation(
+                        "GlobalVariablePrefix",
+                        name,
+                        Some(correct_name.clone()),
+                        format!(
+                            "\"{}\" has incorrect prefix because it's a global variable, the correct name is probably \"{}\"",
+                            name, correct_name
+                        ),
+                        name_span,
+                    ), warnings);
+                };
+
+                // Remove global prefix from further checks.
+                name = name_without_prefix;
+            }
+        }
+
+        // Check case.
+        if let Some((rule, case)) = self.get_variable_case(scope) {
+            self.check_name_case(rule, name, name_span, case, warnings)?
+        }
+
+        // Check prefixes.
+        if scope == VariableScope::LoopCounter && self.config.exempt_loop_counters {
+            return Ok(());
+        }
+        let bool_prefix = (self.config.bool_prefix.as_ref())
+            .filter(|_| scope.is_in_prefix_scope(self.config.bool_prefix_scope));
+        let int_prefix = (self.config.int_prefix.as_ref())
+            .filter(|_| scope.is_in_prefix_scope(self.config.int_prefix_scope));
+        let float_prefix = (self.config.float_prefix.as_ref())
+            .filter(|_| scope.is_in_prefix_scope(self.config.float_prefix_scope));
+        if let (Type::Bool, Some(prefix)) = (_type, bool_prefix) {
+            self.check_prefix("BoolPrefix", name, name_span, prefix, warnings)?
+        }
+        if let (Type::Integer, Some(prefix)) = (_type, int_prefix) {
+            self.check_prefix("IntPrefix", name, name_span, prefix, warnings)?
+        }
+        if let (Type::Float, Some(prefix)) = (_type, float_prefix) {
+            self.check_prefix("FloatPrefix", name, name_span, prefix, warnings)?
+        }
+
+        Ok(())
+    }
+
+    /// Returns the case rule (and its name) that applies to variables declared in the specified
+    /// scope, scope-specific rules take priority over `VariableCase`.
+    fn get_variable_case(&self, scope: VariableScope) -> Option<(&'static str, Case)> {
+        let scoped_case = match scope {
+            VariableScope::Global => self
+                .config
+                .global_variable_case
+                .map(|case| ("GlobalVariableCase", case)),
+            VariableScope::Local | VariableScope::LoopCounter => self
+                .config
+                .local_variable_case
+                .map(|case| ("LocalVariableCase", case)),
+            VariableScope::Static { is_global } => {
+                if let Some(case) = self.config.static_variable_case {
+                    return Some(("StaticVariableCase", case));
+                }
+                return self.get_variable_case(if is_global {
+                    VariableScope::Global
+                } else {
+                    VariableScope::Local
+                });
+            }
+            VariableScope::Field | VariableScope::ConstantBufferField => None,
+        };
+
+        scoped_case.or(self.config.variable_case.map(|case| ("VariableCase", case)))
+    }
+
+    /// Checks that the specified declared name is not one of `ForbiddenIdentifiers` and does not
+    /// match `ForbiddenIdentifierPattern`.
+    fn check_forbidden_identifier(
+        &self,
+        name: &str,
+        name_span: Span,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if self
+            .config
+            .forbidden_identifiers
+            .iter()
+            .any(|forbidden| forbidden == name)
+        {
+            return self.report(
+                FormatError::rule_violation(
+                    "ForbiddenIdentifiers",
+                    name,
+                    Some(format!("{}_", name)),
+                    format!("\"{}\" is a forbidden identifier", name),
+                    name_span,
+                ),
+                warnings,
+            );
+        }
+
+        if let Some(pattern) = &self.config.forbidden_identifier_pattern {
+            if pattern.is_match(name) {
+                // Suggest the name without the forbidden parts (if it's still a valid name).
+                let suggestion = pattern.replace_all(name, "").into_owned();
+                let is_valid = suggestion
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && !pattern.is_match(&suggestion);
+                return self.report(
+                    FormatError::rule_violation(
+                        "ForbiddenIdentifierPattern",
+                        name,
+                        is_valid.then_some(suggestion),
+                        format!(
+                            "\"{}\" matches the forbidden identifier pattern \"{}\"",
+                            name, pattern
+                        ),
+                        name_span,
+                    ),
+                    warnings,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that length of the specified declared name is in range of `MinIdentifierLength`
+    /// and `MaxIdentifierLength` (unless the name is in `IdentifierLengthAllowlist`).
+    fn check_identifier_length(
+        &self,
+        name: &str,
+        name_span: Span,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if self
+            .config
+            .identifier_length_allowlist
+            .iter()
+            .any(|allowed| allowed == name)
+        {
+            return Ok(());
+        }
+
+        let length = name.chars().count();
+        if let Some(min_length) = self.config.min_identifier_length {
+            if length < min_length {
+                return self.report(
+                    FormatError::rule_violation(
+                        "MinIdentifierLength",
+                        name,
+                        None,
+                        format!(
+                            "\"{}\" is too short ({} characters), expected at least {} characters",
+                            name, length, min_length
+                        ),
+                        name_span,
+                    ),
+                    warnings,
+                );
+            }
+        }
+        if let Some(max_length) = self.config.max_identifier_length {
+            if length > max_length {
+                return self.report(
+                    FormatError::rule_violation(
+                        "MaxIdentifierLength",
+                        name,
+                        None,
+                        format!(
+                            "\"{}\" is too long ({} characters), expected at most {} characters",
+                            name, length, max_length
+                        ),
+                        name_span,
+                    ),
+                    warnings,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the specified name uses the specified case.
+    ///
+    /// # Return
+    /// `Ok` if case is correct, otherwise `Err` with the violated rule and the name in the correct case.
+    fn check_name_case(
+        &self,
+        rule: &'static str,
+        name: &str,
+        name_span: Span,
+        case: Case,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        // Skip GLSL built-ins (like `gl_PerVertex`).
+        if name.starts_with(GLSL_BUILTIN_PREFIX) {
+            return Ok(());
+        }
+
+        match Self::is_case_different(name, case, &self.config.acronyms) {
+            Ok(_) => Ok(()),
+            Err(correct) => self.report(
+                FormatError::rule_violation(
+                    rule,
+                    name,
+                    Some(correct.clone()),
+                    format!(
+                        "\"{}\" has incorrect case, the correct case is \"{}\"",
+                        name, correct
+                    ),
+                    name_span,
+                ),
+                warnings,
+            ),
+        }
+    }
+
+    /// This function contains repetitive code for checking prefixes.
+    ///
+    /// # Return
+    /// `Ok` if the name starts with one of the allowed prefixes, otherwise `Err` that contains
+    /// a meaningful error message about wrong prefix.
+    fn check_prefix(
+        &self,
+        rule: &'static str,
+        name: &str,
+        name_span: Span,
+        prefixes: &[String],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if Self::strip_any_prefix(name, prefixes).is_none() {
+            let correct_prefix = match prefixes {
+                [prefix] => format!("the correct prefix is \"{}\"", prefix),
+                _ => format!(
+                    "the correct prefix is one of {}",
+                    prefixes
+                        .iter()
+                        .map(|prefix| format!("\"{}\"", prefix))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            };
+            return self.report(
+                FormatError::rule_violation(
+                    rule,
+                    name,
+                    None,
+                    format!(
+                        "variable \"{}\" has incorrect prefix, {}",
+                        name, correct_prefix
+                    ),
+                    name_span,
+                ),
+                warnings,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Removes the longest of the specified prefixes from the name.
+    ///
+    /// # Return
+    /// `None` if the name does not start with any of the prefixes.
+    fn strip_any_prefix<'a>(name: &'a str, prefixes: &[String]) -> Option<&'a str> {
+        prefixes
+            .iter()
+            .filter_map(|prefix| name.strip_prefix(prefix.as_str()))
+            .min_by_key(|name_without_prefix| name_without_prefix.len())
+    }
+
+    /// Checks if the case of the specified test string is different from the specified case.
+    ///
+    /// # Return
+    /// `Ok` if case is correct, otherwise `Err` that contains the specified string in the correct
+    /// casing.
+    fn is_case_different(test: &str, target_case: Case, acronyms: &[String]) -> Result<(), String> {
+        let converted_str = match Self::convert_case_with_acronyms(test, target_case, acronyms) {
+            Some(converted_str) => converted_str,
+            None => match target_case {
+                Case::Camel => test.to_case(convert_case::Case::Camel),
+                Case::Snake => test.to_case(convert_case::Case::Snake),
+                Case::Pascal => test.to_case(convert_case::Case::Pascal),
+                Case::UpperSnake => test.to_case(convert_case::Case::UpperSnake),
+            },
+        };
+
+        if test != converted_str {
+            return Err(converted_str);
+        }
+
+        Ok(())
+    }
+
+    /// Converts the specified name to the specified case while treating the specified acronyms
+    /// (in uppercase) as single words, for example `lightRgbValue` with the acronym `RGB` is
+    /// converted to `lightRGBValue` in camel case.
+    ///
+    /// # Return
+    /// `None` if the name does not contain any of the acronyms.
+    fn convert_case_with_acronyms(name: &str, case: Case, acronyms: &[String]) -> Option<String> {
+        let capitalize = |word: &str| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        };
+
+        // Split the name into words (acronyms are kept as is).
+        let mut words: Vec<(String, bool)> = Vec::new(); // word and whether it's an acronym
+        let mut is_after_acronym = false;
+        let mut text_start = 0;
+        let mut offset = 0;
+        while let Some(_char) = name[offset..].chars().next() {
+            let previous = name[..offset].chars().last();
+            let is_word_start = previous.is_none_or(|c| c == '_') || is_after_acronym;
+            let is_after_lowercase =
+                previous.is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+
+            // Look for the longest acronym that starts here (written as `RGB`, `Rgb` or `rgb`).
+            let acronym = acronyms
+                .iter()
+                .filter(|acronym| {
+                    let Some(text) = name.get(offset..offset + acronym.len()) else {
+                        return false;
+                    };
+                    let mut next_chars = name[offset + acronym.len()..].chars();
+                    let next = next_chars.next();
+                    let is_next_word = next.is_none_or(|c| c == '_' || c.is_ascii_digit());
+
+                    let lowercase = acronym.to_ascii_lowercase();
+                    if text == acronym.as_str() {
+                        // The next word should not continue the uppercase text (like in `AOMAP`).
+                        (is_word_start || is_after_lowercase)
+                            && (is_next_word
+                                || next.is_some_and(|c| c.is_ascii_uppercase())
+                                    && next_chars.next().is_some_and(|c| c.is_ascii_lowercase()))
+                    } else if text == capitalize(&lowercase) {
+                        (is_word_start || is_after_lowercase)
+                            && (is_next_word || next.is_some_and(|c| c.is_ascii_uppercase()))
+                    } else if text == lowercase {
+                        is_word_start
+                            && (is_next_word || next.is_some_and(|c| c.is_ascii_uppercase()))
+                    } else {
+                        false
+                    }
+                })
+                .max_by_key(|acronym| acronym.len());
+
+            let Some(acronym) = acronym else {
+                is_after_acronym = false;
+                offset += _char.len_utf8();
+                continue;
+            };
+
+            words.extend(Self::split_words(&name[text_start..offset]));
+            words.push((acronym.clone(), true));
+            is_after_acronym = true;
+            offset += acronym.len();
+            text_start = offset;
+        }
+        if !words.iter().any(|(_, is_acronym)| *is_acronym) {
+            return None;
+        }
+        words.extend(Self::split_words(&name[text_start..]));
+
+        // Join the words in the specified case.
+        let converted = match case {
+            Case::Snake => words
+                .iter()
+                .map(|(word, _)| word.to_ascii_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Case::UpperSnake => words
+                .iter()
+                .map(|(word, _)| word.to_ascii_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            Case::Camel | Case::Pascal => words
+                .iter()
+                .enumerate()
+                .map(
+                    |(index, (word, is_acronym))| match (index, case, is_acronym) {
+                        (0, Case::Camel, _) => word.to_ascii_lowercase(),
+                        (_, _, true) => word.clone(),
+                        _ => capitalize(word),
+                    },
+                )
+                .collect(),
+        };
+
+        Some(converted)
+    }
+
+    /// Splits the specified text into lowercase words (according to the rules of
+    /// the `convert_case` crate).
+    fn split_words(text: &str) -> Vec<(String, bool)> {
+        text.to_case(convert_case::Case::Snake)
+            .split('_')
+            .filter(|word| !word.is_empty())
+            .map(|word| (word.to_owned(), false))
+            .collect()
+    }
+
+    /// Checks that the documentation for the specified function is written for return type and all arguments.
+    ///
+    /// # Return
+    /// `Ok` if docs are correct, otherwise `Err` with a meaningful message about incorrect docs.
+    fn check_function_docs(
+        &self,
+        func_info: &FunctionInfo,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        // Make sure docs are not empty.
+        if func_info.docs.is_empty() {
+            return self.report(
+                FormatError::rule_violation(
+                    "RequireDocsOnFunctions",
+                    func_info.name,
+                    None,
+                    format!(
+                        "expected to find documentation for the function \"{}\"",
+                        func_info.name
+                    ),
+                    func_info.name_span,
+                ),
+                warnings,
+            );
+        }
+
+        // Check return docs.
+        let return_doc_pos = func_info.docs.find("@return");
+        if func_info.return_type != Type::Void {
+            if return_doc_pos.is_none() {
+                self.report(
+                    FormatError::rule_violation(
+                        "RequireDocsOnFunctions",
+                        func_info.name,
+                        None,
+                        format!(
+                    "expected to find documentation of the return value for the function \"{}\"",
+                    func_info.name
+                ),
+                        func_info.name_span,
+                    ),
+                    warnings,
+                )?;
+            }
+        } else if return_doc_pos.is_some() {
+            // Make sure there is no "return" docs (since it's void).
+            self.report(
+                FormatError::rule_violation(
+                    "RequireDocsOnFunctions",
+                    func_info.name,
+                    None,
+                    format!(
+                        "found documentation of the VOID return value for the function \"{}\"",
+                        func_info.name
+                    ),
+                    func_info.name_span,
+                ),
+                warnings,
+            )?;
+        }
+
+        // Collect all args written in the docs.
+        let param_keyword = "@param ";
+        let mut documented_args: Vec<String> = Vec::new();
+        for (pos, _) in func_info.docs.match_indices(param_keyword) {
+            let arg_name = func_info.docs[pos + param_keyword.len()..]
+                .trim_start_matches(' ')
+                .chars()
+                .take_while(|_char| *_char != ' ')
+                .collect();
+
+            documented_args.push(arg_name);
+        }
+
+        // Check argument docs.
+        for info in &func_info.args {
+            if info.semantic.is_some() {
+                // Don't require docs for arguments with semantics.
+                continue;
+            }
+            if !documented_args.iter().any(|name| name == info.name) {
+                self.report(
+                    FormatError::rule_violation(
+                        "RequireDocsOnFunctions",
+                        info.name,
+                        None,
+                        format!(
+                    "expected to find documentation for the argument \"{}\" of the function \"{}\"",
+                    info.name, func_info.name
+                ),
+                        info.name_span,
+                    ),
+                    warnings,
+                )?;
+            }
+        }
+
+        // Check if there are argument comments that don't reference an actual argument.
+        for doc_arg_name in documented_args {
+            if !func_info.args.iter().any(|info| info.name == doc_arg_name) {
+                self.report(
+                    FormatError::rule_violation(
+                        "RequireDocsOnFunctions",
+                        &doc_arg_name,
+                        None,
+                        format!(
+                    "found documentation for a non-existing argument \"{}\" of the function \"{}\"",
+                    doc_arg_name, func_info.name
+                ),
+                        func_info.name_span,
+                    ),
+                    warnings,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks docs and names of the specified struct, its fields, methods and nested structs.
+    fn check_struct(
+        &self,
+        info: &StructInfo,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        // Anonymous structs (like `struct { float a; } b;`) don't have a name to check.
+        if !info.name.is_empty() {
+            // Check docs.
+            if self.config.require_docs_on_structs {
+                self.check_struct_docs(info, warnings)?;
+            }
+            if self.config.require_brief_in_docs {
+                self.check_docs_brief(info.name, &info.docs, info.docs_span, warnings)?;
+            }
+
+            // Check name.
+            self.check_forbidden_identifier(info.name, info.name_span, warnings)?;
+            if let Some(case) = self.config.struct_case {
+                self.check_name_case("StructCase", info.name, info.name_span, case, warnings)?;
+            }
+        }
+
+        // Check fields.
+        for field_info in &info.fields {
+            self.check_variable_name(
+                field_info.name,
+                field_info.name_span,
+                field_info._type,
+                VariableScope::Field,
+                warnings,
+            )?;
+        }
+
+        // Check field docs.
+        if self.config.require_docs_on_fields {
+            self.check_struct_field_docs(info, warnings)?;
+        }
+
+        // Check field semantics.
+        if self.config.validate_semantics {
+            for field_info in &info.fields {
+                if let Some(semantic) = field_info.semantic {
+                    self.check_semantic(semantic, field_info.name_span, warnings)?;
+                }
+            }
+        }
+
+        // Check methods.
+        let methods = info.methods.iter().collect::<Vec<_>>();
+        for method_info in &info.methods {
+            self.check_function(method_info, &methods, warnings)?;
+        }
+
+        // Check nested structs.
+        for nested_info in &info.nested {
+            self.check_struct(nested_info, warnings)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks names of fields of the specified constant buffer and structs declared in it.
+    fn check_constant_buffer(
+        &self,
+        info: &ConstantBufferInfo,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        self.check_forbidden_identifier(info.name, info.name_span, warnings)?;
+
+        for field_info in &info.fields {
+            // Fields of constant buffers are global variables so without a special prefix
+            // they are checked as global variables.
+            let Some(prefixes) = &self.config.constant_buffer_field_prefix else {
+                self.check_variable_name(
+                    field_info.name,
+                    field_info.name_span,
+                    field_info._type,
+                    VariableScope::Global,
+                    warnings,
+                )?;
+                continue;
+            };
+
+            let Some(name_without_prefix) = Self::strip_any_prefix(field_info.name, prefixes)
+            else {
+                let correct_name = prefixes[0].to_owned() + field_info.name;
+                self.report(FormatError::rule_violation(
+                    "ConstantBufferFieldPrefix",
+                    field_info.name,
+                    Some(correct_name.clone()),
+                    format!(
+                        "\"{}\" has incorrect prefix because it's a field of the constant buffer \"{}\", the correct name is probably \"{}\"",
+                        field_info.name, info.name, correct_name
+                    ),
+                    field_info.name_span,
+                ), warnings)?;
+                continue;
+            };
+
+            // Remove the prefix from further checks.
+            self.check_variable_name(
+                name_without_prefix,
+                field_info.name_span,
+                field_info._type,
+                VariableScope::ConstantBufferField,
+                warnings,
+            )?;
+        }
+
+        // Check nested structs.
+        for nested_info in &info.nested {
+            self.check_struct(nested_info, warnings)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the specified push constant block is not larger than `MaxPushConstantSize`.
+    ///
+    /// # Arguments
+    /// - `text` Text that is being checked.
+    /// - `name` Name of the block.
+    /// - `name_span` Span of the block's name.
+    /// - `fields` Fields of the block.
+    fn check_push_constant_size(
+        &self,
+        text: &str,
+        name: &str,
+        name_span: Span,
+        fields: &[StructField],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        let Some(max_size) = self.config.max_push_constant_size else {
+            return Ok(());
+        };
+
+        let mut size = 0;
+        for field_info in fields {
+            // Blocks with fields of unknown size (like custom structs) are not checked.
+            let Some(field_size) = Self::get_field_size(text, field_info) else {
+                return Ok(());
+            };
+            size += field_size;
+        }
+
+        if size > max_size {
+            return self.report(FormatError::rule_violation(
+                "MaxPushConstantSize",
+                name,
+                None,
+                format!(
+                    "the push constant block \"{}\" takes at least {} bytes while the maximum is {}",
+                    name, size, max_size
+                ),
+                name_span,
+            ), warnings);
+        }
+
+        Ok(())
+    }
+
+    /// Returns size (in bytes, without padding) of the specified field if it uses a scalar,
+    /// vector or matrix type (arrays of such types are also supported if their size is a number).
+    fn get_field_size(text: &str, field_info: &StructField) -> Option<usize> {
+        let mut size =
+            Self::get_type_size(text.get(field_info.type_span.start..field_info.type_span.end)?)?;
+
+        // Multiply by sizes of array dimensions (like `[4]` in `float4 colors[4];`).
+        let mut text_after_name = text.get(field_info.name_span.end..)?;
+        while let Some(text_after_bracket) = text_after_name.trim_start().strip_prefix('[') {
+            let (count, text_after_count) = text_after_bracket.split_once(']')?;
+            size *= count.trim().parse::<usize>().ok()?;
+            text_after_name = text_after_count;
+        }
+
+        Some(size)
+    }
+
+    /// Returns size (in bytes) of the specified scalar, vector or matrix type (like `float`,
+    /// `half3`, `float4x4`, `uint16_t2`, `vec4` or `dmat3x2`).
+    fn get_type_size(type_name: &str) -> Option<usize> {
+        // Parses dimensions of vectors and matrices (like `4` or `3x2`).
+        let parse_component_count = |dimensions: &str| -> Option<usize> {
+            if dimensions.is_empty() {
+                return Some(1);
+            }
+            match dimensions.split_once('x') {
+                Some((rows, columns)) => {
+                    Some(rows.parse::<usize>().ok()? * columns.parse::<usize>().ok()?)
+                }
+                None => dimensions.parse::<usize>().ok(),
+            }
+        };
+
+        // GLSL square matrices (like `mat4`) have a single dimension.
+        for (prefix, scalar_size) in [("mat", 4), ("dmat", 8)] {
+            if let Some(dimensions) = type_name.strip_prefix(prefix) {
+                if let Ok(size) = dimensions.parse::<usize>() {
+                    return Some(size * size * scalar_size);
+                }
+            }
+        }
+
+        // Scalar types and prefixes of vector and matrix types (16-bit HLSL types without
+        // `-enable-16bit-types` and `min16` types take 4 bytes in constant buffers).
+        [
+            ("float16_t", 2),
+            ("float32_t", 4),
+            ("float64_t", 8),
+            ("int16_t", 2),
+            ("int32_t", 4),
+            ("int64_t", 8),
+            ("uint16_t", 2),
+            ("uint32_t", 4),
+            ("uint64_t", 8),
+            ("min16float", 4),
+            ("min10float", 4),
+            ("min16int", 4),
+            ("min12int", 4),
+            ("min16uint", 4),
+            ("float", 4),
+            ("half", 4),
+            ("double", 8),
+            ("int", 4),
+            ("uint", 4),
+            ("dword", 4),
+            ("bool", 4),
+            ("vec", 4),
+            ("ivec", 4),
+            ("uvec", 4),
+            ("bvec", 4),
+            ("dvec", 8),
+            ("mat", 4),
+            ("dmat", 8),
+        ]
+        .into_iter()
+        .find_map(|(prefix, scalar_size)| {
+            let component_count = parse_component_count(type_name.strip_prefix(prefix)?)?;
+            Some(component_count * scalar_size)
+        })
+    }
+
+    /// Checks that the documentation for the specified struct is written correctly.
+    ///
+    /// # Return
+    /// `Ok` if docs are correct, otherwise `Err` with a meaningful message about incorrect docs.
+    fn check_struct_docs(
+        &self,
+        struct_info: &StructInfo,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        // Make sure docs are not empty.
+        if struct_info.docs.is_empty() {
+            return self.report(
+                FormatError::rule_violation(
+                    "RequireDocsOnStructs",
+                    struct_info.name,
+                    None,
+                    format!(
+                        "expected to find documentation for the struct \"{}\"",
+                        struct_info.name
+                    ),
+                    struct_info.name_span,
+                ),
+                warnings,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the first line of the specified docs is a summary (or starts with `@brief`)
+    /// and not a tag like `@param` (empty docs are not checked).
+    ///
+    /// # Arguments
+    /// - `name` Name of the documented function or struct.
+    /// - `docs` Text of the documentation comments.
+    /// - `docs_span` Span of the documentation comments.
+    fn check_docs_brief(
+        &self,
+        name: &str,
+        docs: &str,
+        docs_span: Span,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        // Find the first line with text (ignoring `*` of block comments and `/` of `///`).
+        let Some(first_line) = docs
+            .lines()
+            .map(|line| line.trim_matches(|c: char| c.is_whitespace() || c == '*' || c == '/'))
+            .find(|line| !line.is_empty())
+        else {
+            return Ok(());
+        };
+
+        let has_brief = match first_line.strip_prefix("@brief") {
+            Some(brief) => !brief.trim().is_empty(),
+            None => !first_line.starts_with('@'),
+        };
+        if has_brief {
+            return Ok(());
+        }
+
+        self.report(
+            FormatError::rule_violation(
+                "RequireBriefInDocs",
+                name,
+                None,
+                format!(
+                    "expected the documentation of \"{}\" to start with a summary (or `@brief`)",
+                    name
+                ),
+                docs_span,
+            ),
+            warnings,
+        )
+    }
+
+    /// Returns name of the argument from the text that follows `@param` (like ` value Input
+    /// value.` or `[out] result Result.`).
+    fn get_param_doc_name(text: &str) -> Option<&str> {
+        // Skip the direction (like `[in]`).
+        let text = match text.strip_prefix('[') {
+            Some(rest) => &rest[rest.find(']')? + 1..],
+            None => text,
+        };
+
+        text.split_whitespace().next()
+    }
+
+    /// Returns direction of the argument from the text that follows `@param` (like `out` in
+    /// `[out] result Result.`).
+    fn get_param_doc_direction(text: &str) -> Option<&str> {
+        let text = text.strip_prefix('[')?;
+        Some(&text[..text.find(']')?])
+    }
+
+    /// Tells if docs of the specified function are expected on its other declaration (like on
+    /// the prototype instead of the definition) according to `FunctionDocsPlacement`.
+    fn are_docs_on_other_declaration(
+        &self,
+        info: &FunctionInfo,
+        functions: &[&FunctionInfo],
+    ) -> bool {
+        let requires_docs = match self.config.function_docs_placement {
+            None => return false,
+            Some(FunctionDocsPlacement::Declaration) => info.is_declaration,
+            Some(FunctionDocsPlacement::Definition) => !info.is_declaration,
+        };
+
+        !requires_docs
+            && functions.iter().any(|other| {
+                other.name == info.name
+                    && other.is_declaration != info.is_declaration
+                    && other
+                        .args
+                        .iter()
+                        .map(|arg| arg._type)
+                        .eq(info.args.iter().map(|arg| arg._type))
+            })
+    }
+
+    /// Checks that `@param` entries of `out` and `inout` arguments of the specified function
+    /// specify the direction of the argument.
+    fn check_param_docs_direction(
+        &self,
+        info: &FunctionInfo,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        let param_keyword = "@param";
+        for (position, _) in info.docs.match_indices(param_keyword) {
+            let entry_text = &info.docs[position + param_keyword.len()..];
+            let Some(arg) = Self::get_param_doc_name(entry_text)
+                .and_then(|name| info.args.iter().find(|arg| arg.name == name))
+            else {
+                continue;
+            };
+
+            let (modifier, expected_direction) = if arg.modifiers.contains(&"inout") {
+                ("inout", "in,out")
+            } else if arg.modifiers.contains(&"out") {
+                ("out", "out")
+            } else {
+                continue;
+            };
+
+            let direction = Self::get_param_doc_direction(entry_text).unwrap_or_default();
+            if direction.replace(' ', "") == expected_direction {
+                continue;
+            }
+
+            self.report(FormatError::rule_violation(
+                "RequireParamDocsDirection",
+                arg.name,
+                Some(format!("@param[{}] {}", expected_direction, arg.name)),
+                format!(
+                    "expected the `@param` entry of the {} argument \"{}\" of the function \"{}\" to specify the direction `[{}]`",
+                    modifier, arg.name, info.name, expected_direction
+                ),
+                arg.name_span,
+            ), warnings)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `@param` entries in docs of functions and methods follow the order of
+    /// their arguments.
+    fn check_param_docs_order(
+        &self,
+        text: &str,
+        complex_tokens: &[(parser::ComplexToken<'_>, SimpleSpan)],
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        if !self.config.require_ordered_param_docs {
+            return Ok(());
+        }
+
+        fn check_struct(
+            formatter: &Formatter,
+            text: &str,
+            info: &StructInfo,
+            warnings: &mut Vec<FormatError>,
+        ) -> Result<(), FormatError> {
+            for method_info in &info.methods {
+                formatter.check_function_param_docs_order(text, method_info, warnings)?;
+            }
+            for nested_info in &info.nested {
+                check_struct(formatter, text, nested_info, warnings)?;
+            }
+            Ok(())
+        }
+
+        for (token, _) in complex_tokens {
+            match token {
+                Function(info) => self.check_function_param_docs_order(text, info, warnings)?,
+                Struct(info) => check_struct(self, text, info, warnings)?,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `@param` entries in docs of the specified function follow the order of
+    /// its arguments (when formatting, entries that can be moved are reordered instead).
+    fn check_function_param_docs_order(
+        &self,
+        text: &str,
+        info: &FunctionInfo,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
+        // Collect arguments in the order of their docs.
+        let param_keyword = "@param";
+        let documented_args = info
+            .docs
+            .match_indices(param_keyword)
+            .filter_map(|(position, _)| {
+                Self::get_param_doc_name(&info.docs[position + param_keyword.len()..])
+            })
+            .filter(|name| info.args.iter().any(|arg| arg.name == *name))
+            .collect::<Vec<_>>();
+
+        let expected_order = info
+            .args
+            .iter()
+            .map(|arg| arg.name)
+            .filter(|name| documented_args.contains(name))
+            .collect::<Vec<_>>();
+        if documented_args == expected_order {
+            return Ok(());
+        }
+        if !self.lint_only && Self::reorder_param_docs(text, info).is_some() {
+            return Ok(()); // will be reordered
+        }
+
+        self.report(FormatError::rule_violation(
+            "RequireOrderedParamDocs",
+            info.name,
+            Some(expected_order.join(", ")),
+            format!(
+                "expected `@param` entries of the function \"{}\" to follow the order of its arguments",
+                info.name
+            ),
+            info.docs_span,
+        ), warnings)
+    }
+
+    /// Returns name of the preprocessor directive that the specified text starts with
+    /// (like `ifdef` for `#ifdef FOO` or `endif` for `#  endif`).
+    fn get_preprocessor_directive_name(text: &str) -> &str {
+        let text = text.trim_start_matches('#').trim_start_matches([' ', '\t']);
+        let name_len = text
+            .find(|_char: char| !_char.is_ascii_alphabetic())
+            .unwrap_or(text.len());
+
+        &text[..name_len]
+    }
+
+    /// Returns indentation text for lines that continue arguments of an open parenthesis
+    /// so that they will be aligned with the first argument.
+    ///
+    /// # Arguments
+    /// - `output` Formatted text.
+    /// - `paren_offset` Position of the open parenthesis in the text.
+    ///
+    /// # Return
+    /// `None` if the parenthesis is not on the last line of the text or if it does not have
+    /// any arguments after it on the same line (arguments start on the next line).
+    fn get_paren_alignment(output: &str, paren_offset: usize) -> Option<String> {
+        let text_after_paren = &output[paren_offset + 1..];
+        if text_after_paren.contains('\n') || text_after_paren.trim().is_empty() {
+            return None;
+        }
+
+        let line_start = output[..paren_offset]
+            .rfind('\n')
+            .map_or(0, |offset| offset + 1);
+        let line = &output[line_start..=paren_offset];
+        let line_indentation: String = line
+            .chars()
+            .take_while(|c| *c == ' ' || *c == '\t')
+            .collect();
+
+        Some(line_indentation.clone() + &" ".repeat(line[line_indentation.len()..].chars().count()))
+    }
+
+    /// Tells if `{` that goes after the specified text opens a brace initializer
+    /// (like in `float w[3] = {0.2, 0.6, 0.2};`) and not a block of code.
+    ///
+    /// # Arguments
+    /// - `output` Formatted text before `{`.
+    /// - `inside_initializer` Whether `{` is located inside of another initializer.
+    fn is_initializer_brace_start(output: &str, inside_initializer: bool) -> bool {
+        match output.chars().rev().find(|c| !c.is_whitespace()) {
+            Some('=') | Some('(') => true,
+            Some(',') | Some('{') => inside_initializer,
+            _ => false,
+        }
+    }
+
+    /// Puts each element of a brace initializer that ends the specified text on a separate line
+    /// if the line with the initializer is longer than the specified limit.
+    ///
+    /// # Arguments
+    /// - `output` Formatted text that ends with an initializer.
+    /// - `initializer_start` Position of the initializer's `{` in the text.
+    /// - `nesting_count` Nesting of the line with the initializer.
+    /// - `max_line_length` Maximum length of a line.
+    fn wrap_initializer(
+        &self,
+        output: &mut String,
+        initializer_start: usize,
+        nesting_count: usize,
+        max_line_length: usize,
+    ) {
+        let initializer = &output[initializer_start..];
+        if initializer.contains('\n') || initializer.contains("//") || initializer.contains("/*") {
+            // Already multi-line or has comments that we can't move safely.
+            return;
+        }
+
+        let line_start = output[..initializer_start]
+            .rfind('\n')
+            .map_or(0, |offset| offset + 1);
+        if output[line_start..].chars().count() <= max_line_length {
+            return;
+        }
+
+        let elements = Self::split_initializer_elements(initializer);
+
+        // Put each element on a new line.
+        let indentation_text = self.get_indentation_text();
+        let mut wrapped = String::from("{");
+        for (index, element) in elements.iter().enumerate() {
+            wrapped += LINE_ENDING;
+            wrapped += &indentation_text.repeat(nesting_count + 1);
+            wrapped += element;
+            if index + 1 != elements.len() {
+                wrapped.push(',');
+            }
+        }
+        wrapped += LINE_ENDING;
+        wrapped += &indentation_text.repeat(nesting_count);
+        wrapped.push('}');
+
+        output.truncate(initializer_start);
+        *output += &wrapped;
+    }
+
+    /// Places each row of a matrix initializer (like `float4x4 m = {...};` or `{{...}, {...}}`)
+    /// that ends the specified text on a separate line and aligns elements of rows by columns.
+    ///
+    /// # Arguments
+    /// - `output` Formatted text that ends with an initializer.
+    /// - `initializer_start` Position of the initializer's `{` in the text.
+    /// - `nesting_count` Nesting of the line with the initializer.
+    ///
+    /// # Return
+    /// `false` if the initializer does not look like a matrix and was not changed.
+    fn align_initializer_columns(
+        &self,
+        output: &mut String,
+        initializer_start: usize,
+        nesting_count: usize,
+    ) -> bool {
+        let initializer = &output[initializer_start..];
+        if initializer.contains("//") || initializer.contains("/*") {
+            // Has comments that we can't move safely.
+            return false;
+        }
+
+        let elements = Self::split_initializer_elements(initializer);
+        if elements.len() < 2 {
+            return false;
+        }
+
+        // Find rows.
+        let is_nested = elements.iter().all(|element| element.starts_with('{'));
+        let rows: Vec<Vec<&str>> = if is_nested {
+            // Each nested initializer is a row.
+            elements
+                .iter()
+                .map(|element| Self::split_initializer_elements(element))
+                .collect()
+        } else {
+            // Use size of the matrix type from the declaration.
+            let declaration_start = output[..initializer_start]
+                .rfind([';', '{', '}'])
+                .map_or(0, |offset| offset + 1);
+            let row_length =
+                match Self::get_matrix_row_length(&output[declaration_start..initializer_start]) {
+                    Some(length) => length,
+                    None => return false,
+                };
+            if elements.len() <= row_length || !elements.len().is_multiple_of(row_length) {
+                return false;
+            }
+            elements
+                .chunks(row_length)
+                .map(|row| row.to_vec())
+                .collect()
+        };
+
+        // Calculate width of each column.
+        let mut column_widths: Vec<usize> = Vec::new();
+        for row in &rows {
+            for (column, element) in row.iter().enumerate() {
+                let width = element.chars().count();
+                match column_widths.get_mut(column) {
+                    Some(max_width) => *max_width = (*max_width).max(width),
+                    None => column_widths.push(width),
+                }
+            }
+        }
+
+        // Put each row on a new line.
+        let indentation_text = self.get_indentation_text();
+        let mut aligned = String::from("{");
+        for (row_index, row) in rows.iter().enumerate() {
+            aligned += LINE_ENDING;
+            aligned += &indentation_text.repeat(nesting_count + 1);
+            if is_nested {
+                aligned.push('{');
+            }
+            for (column, element) in row.iter().enumerate() {
+                aligned += element;
+                if column + 1 != row.len() {
+                    aligned.push(',');
+                    aligned += &" ".repeat(column_widths[column] - element.chars().count() + 1);
+                }
+            }
+            if is_nested {
+                aligned.push('}');
+            }
+            if row_index + 1 != rows.len() {
+                aligned.push(',');
+            }
+        }
+        aligned += LINE_ENDING;
+        aligned += &indentation_text.repeat(nesting_count);
+        aligned.push('}');
+
+        output.truncate(initializer_start);
+        *output += &aligned;
+
+        true
+    }
+
+    /// Splits elements of a brace initializer (like `{1, {2, 3}, foo(4, 5)}`) by commas
+    /// that are not inside of nested brackets.
+    fn split_initializer_elements(initializer: &str) -> Vec<&str> {
+        let mut elements = Vec::new();
+        let mut depth: usize = 0;
+        let mut element_start = 1;
+        for (offset, _char) in initializer.char_indices() {
+            match _char {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth = depth.saturating_sub(1),
+                ',' if depth == 1 => {
+                    elements.push(initializer[element_start..offset].trim());
+                    element_start = offset + 1;
+                }
+                _ => {}
+            }
+        }
+        elements.push(initializer[element_start..initializer.len() - 1].trim());
+        elements.retain(|element| !element.is_empty());
+
+        elements
+    }
+
+    /// Looks for a matrix type (like `float4x3` or `mat4`) in the specified declaration and returns
+    /// the number of elements that are written on one line of its initializer (the number of columns
+    /// for HLSL row-major matrices or the number of rows for GLSL column-major matrices).
+    fn get_matrix_row_length(declaration: &str) -> Option<usize> {
+        declaration
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .find_map(|word| {
+                let bytes = word.as_bytes();
+                let length = bytes.len();
+                if length >= 4
+                    && bytes[length - 2] == b'x'
+                    && bytes[length - 1].is_ascii_digit()
+                    && bytes[length - 3].is_ascii_digit()
+                {
+                    // `float4x3`, `half2x2`, `mat3x4` and etc.
+                    return Some((bytes[length - 1] - b'0') as usize);
+                }
+                if let Some(size) = word
+                    .strip_prefix("mat")
+                    .or_else(|| word.strip_prefix("dmat"))
+                {
+                    // `mat4`, `dmat3` and etc.
+                    return size.parse().ok().filter(|size| (2..=4).contains(size));
+                }
+                None
+            })
+    }
+
+    /// Tells if the specified word is a scalar, vector or matrix type (like `float`, `uint3`,
+    /// `half4x4` or `vec3`) that can be used in type constructors and casts.
+    fn is_numeric_type_name(word: &str) -> bool {
+        let is_size = |size: &str, min: u8| {
+            size.len() == 1 && (min..=4).contains(&(size.as_bytes()[0].wrapping_sub(b'0')))
+        };
+
+        // HLSL types like `float`, `float3` or `float3x4`.
+        let is_hlsl_type = HLSL_NUMERIC_TYPE_NAMES.iter().any(|name| {
+            word.strip_prefix(name)
+                .is_some_and(|size| match size.split_once('x') {
+                    Some((rows, columns)) => is_size(rows, 1) && is_size(columns, 1),
+                    None => size.is_empty() || is_size(size, 1),
+                })
+        });
+
+        // GLSL types like `vec3`, `mat4` or `mat4x3`.
+        let is_glsl_type = ["vec", "ivec", "uvec", "bvec", "dvec", "mat", "dmat"]
+            .iter()
+            .any(|name| {
+                word.strip_prefix(name)
+                    .is_some_and(|size| match size.split_once('x') {
+                        Some((rows, columns)) => {
+                            name.ends_with("mat") && is_size(rows, 2) && is_size(columns, 2)
+                        }
+                        None => is_size(size, 2),
+                    })
+            });
+
+        is_hlsl_type || is_glsl_type
+    }
+
+    /// Tells if the specified text starts with an HLSL statement attribute (like `[unroll]` or
+    /// `[unroll(4)]`) that goes after the specified output.
+    fn is_statement_attribute(output: &str, text: &str) -> bool {
+        let Some(text) = text.strip_prefix('[') else {
+            return false;
+        };
+
+        // Check attribute name.
+        let text = text.trim_start_matches([' ', '\t']);
+        let name_len = text
+            .find(|_char: char| !_char.is_ascii_alphanumeric() && _char != '_')
+            .unwrap_or(text.len());
+        if !HLSL_STATEMENT_ATTRIBUTE_NAMES.contains(&&text[..name_len]) {
+            return false;
+        }
+        if !text[name_len..]
+            .trim_start_matches([' ', '\t'])
+            .starts_with([']', '('])
+        {
+            return false;
+        }
+
+        // Make sure this is not an index (like `values[loop]`), attributes start statements.
+        let line_start = output.rfind('\n').map_or(0, |offset| offset + 1);
+        let output = output.trim_end();
+        let last_word = output
+            .rsplit(|_char: char| !_char.is_ascii_alphanumeric() && _char != '_')
+            .next()
+            .unwrap_or_default();
+        output.len() < line_start
+            || output.ends_with([';', '{', '}', ')', ']', ':'])
+            || last_word == "else"
+            || last_word == "do"
+    }
+
+    /// Tells if `(` of a type constructor (like in `float3(x, y, z)`) goes after the specified output.
+    fn is_type_constructor_parenthesis(output: &str) -> bool {
+        let output = output.trim_end_matches([' ', '\t']);
+        let word_start = output
+            .rfind(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .map_or(0, |position| position + 1);
+
+        Self::is_numeric_type_name(&output[word_start..])
+    }
+
+    /// Tells if the parentheses that are closed at the end of the specified output form a cast
+    /// like `(int)`.
+    ///
+    /// # Arguments
+    /// - `output` Formatted text before `)`.
+    /// - `paren_offset` Position of `(` in the output.
+    fn is_cast_parentheses(output: &str, paren_offset: usize) -> bool {
+        if !Self::is_numeric_type_name(output[paren_offset + 1..].trim()) {
+            return false;
+        }
+
+        // Make sure this is not a call like `foo(int)`.
+        !output[..paren_offset]
+            .trim_end_matches([' ', '\t'])
+            .ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == ')' || c == ']')
+    }
+
+    /// Tells if `<` at the beginning of the specified text opens a template argument list
+    /// (like in `Texture2D<float4>`) and not a comparison.
+    ///
+    /// # Arguments
+    /// - `output` Formatted text before `<`.
+    /// - `text` Text that starts with `<`.
+    fn is_template_argument_list_start(output: &str, text: &str) -> bool {
+        // Make sure `<` goes right after a name.
+        let name: Vec<char> = output
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        match name.last() {
+            Some(c) if c.is_ascii_alphabetic() || *c == '_' => {}
+            _ => return false,
+        }
+
+        // Look for a matching `>` on the same line.
+        let mut angle_depth: usize = 0;
+        for check in text.chars() {
+            match check {
+                '<' => angle_depth += 1,
+                '>' => {
+                    angle_depth -= 1;
+                    if angle_depth == 0 {
+                        return true;
+                    }
+                }
+                ',' | ':' | ' ' | '\t' | '_' => {}
+                c if c.is_ascii_alphanumeric() => {}
+                _ => return false,
+            }
+        }
+
+        false
+    }
+
+    /// Checks if the specified text starts with a comment while ignoring any whitespace
+    /// in the beginning.
+    ///
+    /// # Examples
+    /// ```
+    /// assert!(is_text_starts_with_comment("   // comment"), true);
+    /// assert!(is_text_starts_with_comment("  3// comment"), false);
+    /// ```
+    fn is_text_starts_with_comment(text: &str) -> bool {
+        text.chars()
+            .filter(|&_char| _char != ' ' && _char != '\t')
+            .collect::<String>()
             .starts_with("//")
     }
 
@@ -981,17 +5141,929 @@ impl Formatter {
     ///
     /// # Return
     /// `Ok` if docs are correct, otherwise `Err` with a meaningful message about incorrect docs.
-    fn check_struct_field_docs(struct_info: &StructInfo) -> Result<(), String> {
+    fn check_struct_field_docs(
+        &self,
+        struct_info: &StructInfo,
+        warnings: &mut Vec<FormatError>,
+    ) -> Result<(), FormatError> {
         for info in &struct_info.fields {
             // Make sure docs are not empty.
             if info.docs.is_empty() {
-                return Err(format!(
-                    "expected to find documentation for the struct field \"{}\"",
-                    info.name
-                ));
+                self.report(
+                    FormatError::rule_violation(
+                        "RequireDocsOnFields",
+                        info.name,
+                        None,
+                        format!(
+                            "expected to find documentation for the struct field \"{}\"",
+                            info.name
+                        ),
+                        info.name_span,
+                    ),
+                    warnings,
+                )?;
             }
         }
 
         Ok(())
     }
 }
+
+/// Prints layout tokens while applying the simplest formatting rules (see
+/// `Formatter::apply_simple_rules`).
+struct SimpleRulesPrinter<'a> {
+    formatter: &'a Formatter,
+    /// Text that is being formatted.
+    content: &'a str,
+    /// Text of a single indentation level.
+    indentation_text: &'static str,
+    output: String,
+
+    // For nesting.
+    nesting_count: usize,
+
+    // For new lines.
+    consecutive_empty_new_line_count: usize,
+    is_on_new_line: bool,
+    ignore_until_text: bool,
+    stop_ignoring_if_end_of_line: bool,
+
+    // For preprocessor directives.
+    preproc_add_nesting_on_next_line: bool,
+    line_started_with_preprocessor: bool,
+    /// How much conditional directives are currently open.
+    preproc_if_depth: usize,
+
+    /// How much `<` of template argument lists are currently open.
+    template_angle_depth: usize,
+
+    /// Whether a string literal (like in `[RootSignature("...")]`) is open on the current line.
+    inside_string: bool,
+
+    /// How much `?` of ternary expressions wait for their `:` in the current statement.
+    open_ternary_count: usize,
+
+    /// Positions of open parentheses in the output, whether their arguments should be aligned
+    /// and indentation text for lines with their arguments (if known).
+    open_parens: Vec<(usize, bool, Option<String>)>,
+    align_with_parenthesis: bool,
+
+    /// Positions of open braces of initializers like `{1, 2, 3}` in the output.
+    initializer_brace_starts: Vec<usize>,
+
+    /// Number of open parentheses (including the constructor's one) for each open type constructor
+    /// like `float3(x, y, z)`.
+    constructor_paren_depths: Vec<usize>,
+    /// Whether the last printed token (like `)` of a cast) must be followed by a single space
+    /// (`Some(true)`) or by no space (`Some(false)`) if the next token is an operand.
+    space_after_last_token: Option<bool>,
+
+    /// Whether `[` of an open statement attribute (like `[unroll]`) started its line
+    /// (`None` if no statement attribute is open).
+    statement_attribute_starts_line: Option<bool>,
+
+    // For macros.
+    last_non_space_char_is_backslash: bool,
+    prev_line_ended_with_backslash: bool,
+
+    // For code that should not be formatted.
+    inside_no_format: bool,
+    no_format_begin_span: Span,
+}
+
+impl<'a> SimpleRulesPrinter<'a> {
+    fn new(formatter: &'a Formatter, content: &'a str) -> Self {
+        Self {
+            formatter,
+            content,
+            indentation_text: formatter.get_indentation_text(),
+            output: String::with_capacity(content.len()),
+            nesting_count: 0,
+            consecutive_empty_new_line_count: 0,
+            is_on_new_line: true,
+            ignore_until_text: false,
+            stop_ignoring_if_end_of_line: false,
+            preproc_add_nesting_on_next_line: false,
+            line_started_with_preprocessor: false,
+            preproc_if_depth: 0,
+            template_angle_depth: 0,
+            inside_string: false,
+            open_ternary_count: 0,
+            open_parens: Vec::new(),
+            align_with_parenthesis: formatter.config.argument_wrap
+                == Some(ArgumentWrap::AlignWithParenthesis),
+            initializer_brace_starts: Vec::new(),
+            constructor_paren_depths: Vec::new(),
+            space_after_last_token: None,
+            statement_attribute_starts_line: None,
+            last_non_space_char_is_backslash: false,
+            prev_line_ended_with_backslash: false,
+            inside_no_format: false,
+            no_format_begin_span: Span::from(0..0),
+        }
+    }
+
+    /// Prints the specified tokens of the text.
+    ///
+    /// # Return
+    /// `Ok` with formatted code or `Err` with an error.
+    fn print(mut self, tokens: Vec<(LayoutToken<'_>, Span)>) -> Result<String, FormatError> {
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some((token, span)) = tokens.next() {
+            if self.inside_no_format {
+                self.print_unformatted(token, span);
+                continue;
+            }
+
+            let space_after_last_token = self.space_after_last_token.take();
+            if space_after_last_token == Some(true) && Self::is_operand(Some(&token)) {
+                // There was no whitespace after the previous token.
+                self.output.push(' ');
+            }
+
+            match token {
+                LayoutToken::NewLine => self.new_line(),
+                LayoutToken::Whitespace(text) => {
+                    // Indentation and spaces after some characters are added by us.
+                    if !self.is_on_new_line && !self.ignore_until_text {
+                        let next_token = tokens.peek().map(|(token, _)| token);
+                        match space_after_last_token {
+                            Some(add_space) if Self::is_operand(next_token) => {
+                                if add_space {
+                                    self.output.push(' ');
+                                }
+                            }
+                            _ => self.print_whitespace(text, next_token),
+                        }
+                    }
+                }
+                LayoutToken::LineComment(text) => {
+                    self.start_text(span.start);
+                    self.print_line_comment(text, span);
+                }
+                LayoutToken::BlockComment(text) => {
+                    self.start_text(span.start);
+                    self.print_block_comment(text);
+                }
+                LayoutToken::Word(text) => {
+                    self.start_text(span.start);
+                    self.last_non_space_char_is_backslash = false;
+                    self.output += text;
+                }
+                LayoutToken::Punct(_char) => {
+                    self.start_text(span.start);
+                    if _char == '"' && !self.content[..span.start].ends_with('\\') {
+                        self.inside_string = !self.inside_string;
+                    }
+                    self.print_punct(_char, span.start);
+                }
+                LayoutToken::UnaryOperator(_char) => {
+                    self.start_text(span.start);
+                    self.print_punct(_char, span.start);
+
+                    if self.formatter.config.remove_space_after_unary_operators
+                        && !self.inside_string
+                    {
+                        // Skip spaces between the operator and its operand.
+                        self.ignore_until_text = true;
+                        self.stop_ignoring_if_end_of_line = true;
+                    }
+                }
+            }
+        }
+
+        if self.inside_no_format {
+            return Err(FormatError::RuleViolation {
+                rule: "NOFORMAT",
+                name: NOFORMAT_BEGIN_COMMENT.trim().to_owned(),
+                suggestion: None,
+                message: format!(
+                    "{} was found but no matching{} detected",
+                    NOFORMAT_BEGIN_COMMENT, NOFORMAT_END_COMMENT
+                ),
+                span: self.no_format_begin_span,
+                location: Location::default(),
+            });
+        }
+
+        Ok(self.output)
+    }
+
+    /// Copies a token located between `NOFORMATBEGIN` and `NOFORMATEND` comments as is.
+    fn print_unformatted(&mut self, token: LayoutToken<'_>, span: Span) {
+        match token {
+            LayoutToken::NewLine => {
+                self.output.push('\n');
+                self.is_on_new_line = true;
+            }
+            LayoutToken::Whitespace(text) => self.output += text,
+            _ => {
+                if self.is_on_new_line {
+                    self.start_any_line();
+                }
+
+                self.output += &self.content[span.start..span.end];
+
+                if let LayoutToken::LineComment(text) = token {
+                    if text["//".len()..].starts_with(NOFORMAT_END_COMMENT) {
+                        self.inside_no_format = false;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handles a line break of the text.
+    fn new_line(&mut self) {
+        let current_line_is_empty = self.is_on_new_line;
+        self.is_on_new_line = true;
+
+        self.template_angle_depth = 0; // template argument lists are expected to be on one line
+        self.inside_string = false;
+
+        if self.preproc_add_nesting_on_next_line {
+            self.nesting_count += 1;
+            self.preproc_add_nesting_on_next_line = false;
+        }
+
+        if (self.ignore_until_text && !self.stop_ignoring_if_end_of_line)
+            || self.consecutive_empty_new_line_count > self.formatter.config.max_empty_lines
+        {
+            return;
+        }
+
+        // Reset state for new lines.
+        self.ignore_until_text = false;
+        self.stop_ignoring_if_end_of_line = false;
+
+        if current_line_is_empty {
+            // Remove indentation that we added.
+            self.trim_output_end(false);
+        }
+
+        // Add a new line.
+        let alignment = match self.open_parens.last_mut() {
+            Some((paren_offset, true, alignment)) => {
+                if alignment.is_none() {
+                    *alignment = Formatter::get_paren_alignment(&self.output, *paren_offset);
+                }
+                alignment.clone()
+            }
+            _ => None,
+        };
+        self.output += LINE_ENDING;
+        match alignment {
+            Some(alignment) => self.output += &alignment,
+            None => self.output += &self.indentation_text.repeat(self.nesting_count),
+        }
+
+        self.consecutive_empty_new_line_count += 1;
+    }
+
+    /// Prints spaces and tabs between some text of a line.
+    ///
+    /// # Arguments
+    /// - `text` Spaces and tabs (may contain `\r`).
+    /// - `next_token` Token after the whitespace (if any).
+    fn print_whitespace(&mut self, text: &str, next_token: Option<&LayoutToken<'_>>) {
+        // Keep whitespace that is used to align comments and backslashes of macros,
+        // preprocessor directives (like aligned values of `#define`s) and strings as is.
+        let is_alignment = matches!(
+            next_token,
+            Some(LayoutToken::LineComment(_))
+                | Some(LayoutToken::BlockComment(_))
+                | Some(LayoutToken::Punct('\\'))
+                | Some(LayoutToken::NewLine)
+                | None
+        );
+        if !self.formatter.config.collapse_whitespace
+            || is_alignment
+            || self.inside_string
+            || self.line_started_with_preprocessor
+        {
+            self.output.extend(text.chars().filter(|c| *c != '\r'));
+            return;
+        }
+
+        self.output.push(' ');
+    }
+
+    /// Tells if the specified token can start an operand of a cast or an argument of a type
+    /// constructor (like a name, a number or `-`).
+    fn is_operand(token: Option<&LayoutToken<'_>>) -> bool {
+        matches!(
+            token,
+            Some(LayoutToken::Word(_))
+                | Some(LayoutToken::UnaryOperator(_))
+                | Some(LayoutToken::Punct('(' | '-' | '+' | '!' | '~' | '.'))
+        )
+    }
+
+    /// Updates the state when the first text (not a whitespace) of a line is found.
+    fn start_any_line(&mut self) {
+        self.is_on_new_line = false;
+        self.prev_line_ended_with_backslash = self.last_non_space_char_is_backslash;
+        self.ignore_until_text = false;
+        self.stop_ignoring_if_end_of_line = false;
+        self.consecutive_empty_new_line_count = 0;
+    }
+
+    /// Should be called before some text (not a whitespace) that starts at the specified offset
+    /// is printed.
+    fn start_text(&mut self, offset: usize) {
+        if self.is_on_new_line {
+            self.start_any_line();
+            self.start_code_line(&self.content[offset..]);
+        }
+
+        self.ignore_until_text = false;
+        self.stop_ignoring_if_end_of_line = false;
+    }
+
+    /// Handles indentation of a line that starts with the specified text.
+    fn start_code_line(&mut self, text: &str) {
+        let config = &self.formatter.config;
+
+        if !text.starts_with('#') {
+            self.line_started_with_preprocessor = false;
+
+            let starts_with_operator = if config.break_before_binary_operators.is_some() {
+                Formatter::get_binary_operator_at_start(text).is_some()
+            } else {
+                text.starts_with('=')
+            } || (config.spaces_around_ternary_operator
+                && (text.starts_with('?')
+                    || (self.open_ternary_count > 0
+                        && text.starts_with(':')
+                        && !text.starts_with("::"))));
+            if starts_with_operator {
+                // Since this is the first character on the line,
+                // add an additional indentation because this line is probably too long
+                // and was split into 2 lines, example:
+                // int some_long_variable_name
+                //     = ...;
+                self.output += self.indentation_text;
+            }
+
+            return;
+        }
+
+        self.line_started_with_preprocessor = true;
+
+        if !config.indent_preprocessor {
+            // Remove everything until the beginning of the line.
+            self.trim_output_end(false);
+            return;
+        }
+        if !config.preprocessor_if_creates_nesting {
+            return;
+        }
+
+        let directive = Formatter::get_preprocessor_directive_name(text);
+        let is_if = matches!(directive, "if" | "ifdef" | "ifndef");
+        let is_else = matches!(directive, "elif" | "elifdef" | "elifndef" | "else");
+        let is_end = directive == "endif";
+
+        if is_if {
+            self.preproc_if_depth += 1;
+            self.preproc_add_nesting_on_next_line = true;
+        } else if (is_else || is_end) && self.preproc_if_depth > 0 {
+            // Decrease nesting for this line.
+            self.trim_output_end(false);
+            self.nesting_count = self.nesting_count.saturating_sub(1);
+            self.output += &self.indentation_text.repeat(self.nesting_count);
+
+            if is_else {
+                // Code after `#else` is nested again.
+                self.preproc_add_nesting_on_next_line = true;
+            } else {
+                self.preproc_if_depth -= 1;
+            }
+        }
+    }
+
+    /// Prints a comment that starts with `//`.
+    fn print_line_comment(&mut self, text: &str, span: Span) {
+        self.last_non_space_char_is_backslash = false;
+        self.output.extend(text.chars().filter(|c| *c != '\r'));
+
+        // Check if we don't need to format code.
+        if text["//".len()..].starts_with(NOFORMAT_BEGIN_COMMENT) {
+            self.inside_no_format = true;
+
+            // Remember where the comment is (including `//`) to show it in case of an error.
+            self.no_format_begin_span =
+                Span::from(span.start..span.start + "//".len() + NOFORMAT_BEGIN_COMMENT.len());
+        }
+    }
+
+    /// Prints a comment like `/* ... */` and indents its lines.
+    fn print_block_comment(&mut self, text: &str) {
+        self.last_non_space_char_is_backslash = false;
+
+        let mut lines = text.split('\n');
+        if let Some(first_line) = lines.next() {
+            self.output
+                .extend(first_line.chars().filter(|c| *c != '\r'));
+        }
+
+        for line in lines {
+            self.new_line();
+
+            let line = line.trim_start_matches([' ', '\t', '\r']);
+            if line.is_empty() {
+                continue;
+            }
+            self.start_any_line();
+            self.line_started_with_preprocessor = false;
+
+            if line.starts_with('*') {
+                // Add a single space for C-style comments to look good.
+                self.output.push(' ');
+            }
+            self.output.extend(line.chars().filter(|c| *c != '\r'));
+        }
+    }
+
+    /// Prints a punctuation character that is located at the specified offset of the text.
+    fn print_punct(&mut self, _char: char, offset: usize) {
+        let config = &self.formatter.config;
+
+        self.last_non_space_char_is_backslash = _char == '\\';
+
+        if matches!(_char, ';' | '{' | '}') {
+            self.open_ternary_count = 0;
+        }
+
+        if _char == '{'
+            && Formatter::is_initializer_brace_start(
+                &self.output,
+                !self.initializer_brace_starts.is_empty(),
+            )
+        {
+            // Keep initializers like `float w[3] = {0.2, 0.6, 0.2};` inline.
+            self.initializer_brace_starts.push(self.output.len());
+            self.output.push(_char);
+
+            // Wait for text or an end of line.
+            self.ignore_until_text = true;
+            self.stop_ignoring_if_end_of_line = true;
+
+            // Increase nesting if will be on new line.
+            self.nesting_count += 1;
+        } else if _char == '}' && !self.initializer_brace_starts.is_empty() {
+            self.print_initializer_close_brace();
+        } else if _char == '{' {
+            self.print_open_brace();
+        } else if _char == '}' {
+            // Decrease nesting.
+            self.nesting_count = self.nesting_count.saturating_sub(1);
+
+            // Remove everything until text.
+            self.trim_output_end(true);
+
+            let collapse_empty_body = config.empty_body_style == EmptyBodyStyle::Collapsed
+                && !self.line_started_with_preprocessor
+                && self.output.ends_with('{')
+                && self.move_open_brace_to_declaration_line();
+
+            // Don't add a new line if this line was started with `#`
+            // we likelly need to keep the code on the same line.
+            if collapse_empty_body {
+                // Keep `{}` together.
+            } else if !self.line_started_with_preprocessor {
+                // Add a new line.
+                self.output += LINE_ENDING;
+                self.output += &self.indentation_text.repeat(self.nesting_count);
+            } else {
+                self.output.push(' '); // just add a space after text
+            }
+
+            // Copy brace.
+            self.output.push(_char);
+
+            // struct Foo{
+            // Don't insert a new line here, here is an example why:
+            // };
+            // The `;` will be on the new line if we insert one.
+        } else if _char == '[' || _char == '(' {
+            if _char == '('
+                && config.space_before_constructor_parenthesis.is_some()
+                && !self.line_started_with_preprocessor
+                && Formatter::is_type_constructor_parenthesis(&self.output)
+            {
+                self.trim_output_end(false);
+                if config.space_before_constructor_parenthesis == Some(true) {
+                    self.output.push(' ');
+                }
+                self.constructor_paren_depths
+                    .push(self.open_parens.len() + 1);
+            }
+
+            if _char == '['
+                && config.statement_attribute_placement.is_some()
+                && !self.line_started_with_preprocessor
+                && !self.inside_string
+                && Formatter::is_statement_attribute(&self.output, &self.content[offset..])
+            {
+                let line_start = self.output.rfind('\n').map_or(0, |offset| offset + 1);
+                self.statement_attribute_starts_line =
+                    Some(self.output[line_start..].trim().is_empty());
+            }
+
+            if _char == '(' {
+                let align = self.align_with_parenthesis
+                    || (!config.bin_pack_parameters
+                        && Formatter::is_declaration_parenthesis(&self.output));
+                self.open_parens.push((self.output.len(), align, None));
+            }
+
+            self.output.push(_char);
+
+            // Add space if needed.
+            if config.spaces_in_brackets {
+                self.output.push(' ');
+            }
+
+            // Wait for text or an end of line.
+            self.ignore_until_text = true;
+            self.stop_ignoring_if_end_of_line = true;
+
+            // Increase nesting if will be on new line (while inside braces).
+            self.nesting_count += 1;
+        } else if _char == ']' || _char == ')' {
+            if config.keep_user_line_breaks {
+                // Remove spaces before the bracket but keep it on its own line (if it is).
+                self.trim_output_end(false);
+                if self.output.ends_with('\n') {
+                    self.output += &self
+                        .indentation_text
+                        .repeat(self.nesting_count.saturating_sub(1));
+                }
+            } else {
+                // Remove everything until text.
+                self.trim_output_end(true);
+            }
+
+            // Add space if needed.
+            let nothing_in_brackets = self.output.ends_with(['<', '[', '(']);
+            if config.spaces_in_brackets && !nothing_in_brackets {
+                self.output.push(' ');
+            }
+
+            if _char == ')' {
+                if let Some((paren_offset, _, _)) = self.open_parens.last() {
+                    if config.space_after_cast.is_some()
+                        && !self.line_started_with_preprocessor
+                        && Formatter::is_cast_parentheses(&self.output, *paren_offset)
+                    {
+                        self.space_after_last_token = config.space_after_cast;
+                    }
+                }
+                if self.constructor_paren_depths.last() == Some(&self.open_parens.len()) {
+                    self.constructor_paren_depths.pop();
+                }
+            }
+
+            self.output.push(_char);
+
+            if _char == ')' {
+                self.open_parens.pop();
+            }
+
+            // Decrease nesting if will be on new line.
+            self.nesting_count = self.nesting_count.saturating_sub(1);
+
+            if _char == ']' {
+                if let Some(starts_line) = self.statement_attribute_starts_line.take() {
+                    self.place_statement_after_attribute(starts_line, offset);
+                }
+            }
+        } else if _char == '<'
+            && !self.line_started_with_preprocessor
+            && Formatter::is_template_argument_list_start(&self.output, &self.content[offset..])
+        {
+            self.output.push(_char);
+
+            // Add space if needed.
+            if config.spaces_in_angle_brackets {
+                self.output.push(' ');
+            }
+
+            // Skip spaces after `<`.
+            self.ignore_until_text = true;
+            self.stop_ignoring_if_end_of_line = true;
+
+            self.template_angle_depth += 1;
+        } else if _char == '>' && self.template_angle_depth > 0 {
+            // Remove spaces before `>`.
+            self.trim_output_end(false);
+
+            // Add space if needed.
+            if config.spaces_in_angle_brackets && !self.output.ends_with('<') {
+                self.output.push(' ');
+            }
+
+            self.output.push(_char);
+
+            self.template_angle_depth -= 1;
+        } else if config.spaces_around_ternary_operator
+            && !self.line_started_with_preprocessor
+            && !self.inside_string
+            && (_char == '?'
+                || (_char == ':'
+                    && self.open_ternary_count > 0
+                    && !self.content[offset + 1..].starts_with(':')
+                    && !self.content[..offset].ends_with(':')))
+        {
+            if _char == '?' {
+                self.open_ternary_count += 1;
+            } else {
+                self.open_ternary_count -= 1;
+            }
+
+            // Add a single space before the operator (unless it starts a continuation line).
+            let line_start = self.output.rfind('\n').map_or(0, |position| position + 1);
+            if !self.output[line_start..].trim().is_empty() {
+                self.trim_output_end(false);
+                self.output.push(' ');
+            }
+
+            self.output.push(_char);
+            self.space_after_last_token = Some(true);
+        } else if config.remove_spaces_around_member_access
+            && !self.line_started_with_preprocessor
+            && !self.inside_string
+            && (_char == '.'
+                || (_char == ':'
+                    && (self.content[offset + 1..].starts_with(':')
+                        || self.content[..offset].ends_with(':'))))
+        {
+            let is_second_colon = _char == ':' && self.content[..offset].ends_with(':');
+            let has_owner = self.output_ends_with_member_owner();
+            if _char == '.' && !has_owner {
+                // Not a member access (like `.5`).
+                self.output.push(_char);
+                return;
+            }
+
+            if has_owner && !is_second_colon {
+                // Remove spaces before `.` or `::` (unless it starts a continuation line).
+                self.trim_output_end(false);
+            }
+
+            self.output.push(_char);
+
+            if _char == '.' || is_second_colon {
+                // Skip spaces after the operator.
+                self.ignore_until_text = true;
+                self.stop_ignoring_if_end_of_line = true;
+            }
+        } else if _char == ','
+            && self.constructor_paren_depths.last() == Some(&self.open_parens.len())
+        {
+            // Arguments of type constructors are separated by `, `.
+            self.trim_output_end(false);
+            self.output.push(_char);
+            self.space_after_last_token = Some(true);
+        } else {
+            // Just copy the char.
+            self.output.push(_char);
+        }
+    }
+
+    /// Places the statement that follows an HLSL statement attribute (like `[unroll]`) according
+    /// to `StatementAttributePlacement`, should be called after `]` of the attribute was printed.
+    ///
+    /// # Arguments
+    /// - `attribute_starts_line` Whether the attribute is the first text on its line.
+    /// - `offset` Position of the attribute's `]` in the text.
+    fn place_statement_after_attribute(&mut self, attribute_starts_line: bool, offset: usize) {
+        let text_after_attribute = self.content[offset + 1..].trim_start_matches([' ', '\t', '\r']);
+
+        // Keep comments after attributes on their line.
+        if text_after_attribute.starts_with("//") || text_after_attribute.starts_with("/*") {
+            return;
+        }
+
+        match self.formatter.config.statement_attribute_placement {
+            Some(StatementAttributePlacement::SeparateLine) => {
+                if !attribute_starts_line
+                    || text_after_attribute.is_empty()
+                    || text_after_attribute.starts_with('\n')
+                {
+                    return;
+                }
+
+                // Move the statement to the next line.
+                self.new_line();
+            }
+            Some(StatementAttributePlacement::SameLine) => {
+                let statement = text_after_attribute.trim_start();
+                if statement.is_empty()
+                    || statement.starts_with("//")
+                    || statement.starts_with("/*")
+                    || statement.starts_with('#')
+                {
+                    return;
+                }
+
+                // Move the statement to the attribute's line.
+                self.output.push(' ');
+            }
+            None => return,
+        }
+
+        // Skip whitespace (and line breaks) before the statement.
+        self.ignore_until_text = true;
+        self.stop_ignoring_if_end_of_line = false;
+    }
+
+    /// Moves `{` at the end of the output to the end of the previous line with code
+    /// (if it's on its own line) and puts a single space before it.
+    ///
+    /// # Return
+    /// `false` if the brace can't be moved (for example, because the previous line ends with
+    /// a comment).
+    fn move_open_brace_to_declaration_line(&mut self) -> bool {
+        let declaration =
+            self.output[..self.output.len() - 1].trim_end_matches([' ', '\t', '\r', '\n']);
+        let line_start = declaration.rfind('\n').map_or(0, |position| position + 1);
+        let declaration_line = &declaration[line_start..];
+        if declaration_line.trim().is_empty()
+            || declaration_line.contains("//")
+            || declaration_line.trim_start().starts_with('#')
+        {
+            return false;
+        }
+
+        self.output.truncate(declaration.len());
+        self.output += " {";
+
+        true
+    }
+
+    /// Tells if the output ends with something that can have members (like a name, `)` or `]`)
+    /// and not with a number or a keyword.
+    fn output_ends_with_member_owner(&self) -> bool {
+        let output = self.output.trim_end_matches([' ', '\t']);
+        if output.ends_with([')', ']']) {
+            return true;
+        }
+
+        let word_start = output
+            .rfind(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .map_or(0, |position| position + 1);
+        let word = &output[word_start..];
+
+        !word.is_empty()
+            && !word.starts_with(|c: char| c.is_ascii_digit())
+            && !matches!(word, "return" | "case")
+    }
+
+    /// Prints `}` of a brace initializer like `{1, 2, 3}`.
+    fn print_initializer_close_brace(&mut self) {
+        let config = &self.formatter.config;
+
+        // Decrease nesting.
+        self.nesting_count = self.nesting_count.saturating_sub(1);
+
+        // Remove spaces before the brace.
+        self.trim_output_end(false);
+
+        // Indent the brace if it's on a separate line.
+        if self.output.ends_with('\n') {
+            self.output += &self.indentation_text.repeat(self.nesting_count);
+        }
+
+        self.output.push('}');
+
+        let initializer_start = self.initializer_brace_starts.pop().unwrap_or_default();
+
+        // Don't change layout of initializers that were split by the author.
+        if config.keep_user_line_breaks && self.output[initializer_start..].contains('\n') {
+            return;
+        }
+
+        if self.initializer_brace_starts.is_empty()
+            && !(config.align_initializer_columns
+                && self.formatter.align_initializer_columns(
+                    &mut self.output,
+                    initializer_start,
+                    self.nesting_count,
+                ))
+        {
+            if let Some(max_line_length) = config.max_line_length {
+                self.formatter.wrap_initializer(
+                    &mut self.output,
+                    initializer_start,
+                    self.nesting_count,
+                    max_line_length,
+                );
+            }
+        }
+    }
+
+    /// Prints `{` of a scope (not an initializer) according to the brace placement rule.
+    fn print_open_brace(&mut self) {
+        let is_on_own_line = self.formatter.config.keep_user_line_breaks
+            && self.output.trim_end_matches([' ', '\t']).ends_with('\n');
+
+        // Remove everything until text.
+        self.trim_output_end(true);
+        let text_ends_with_backslash = self.output.ends_with('\\');
+
+        match self.formatter.config.new_line_around_braces {
+            // `Auto` is replaced with the detected placement before formatting.
+            NewLineOnOpenBrace::After | NewLineOnOpenBrace::Auto => {
+                if self.prev_line_ended_with_backslash && text_ends_with_backslash {
+                    // Most likelly we got here from this code:
+                    // #define MACRO \
+                    // ...           \
+                    // {
+                    // and now we have:
+                    // #define MACRO \
+                    // ...           \{
+
+                    // Remove backslash and everything until text.
+                    self.output.pop();
+                    self.trim_output_end(true);
+                }
+
+                // Make sure previous line is not a comment otherwise our stuff will be inside of a comment:
+                // struct Foo // comment
+                // {
+                // can become this:
+                // struct Foo // comment {
+                let line_start = self.output.rfind('\n').map_or(0, |offset| offset + 1);
+                let line_before = &self.output[line_start..];
+
+                if is_on_own_line || Formatter::is_text_starts_with_comment(line_before) {
+                    // Just put bracket to a new line.
+                    self.output += LINE_ENDING;
+                    self.output += &self.indentation_text.repeat(self.nesting_count);
+                    self.output.push('{');
+                } else if let Some(comment_start) = line_before.find("//") {
+                    // Put the brace before the comment.
+                    let comment = self.output.split_off(line_start + comment_start);
+                    self.trim_output_end(false);
+                    self.output += " { ";
+                    self.output += &comment;
+                } else {
+                    // Add a space and a brace.
+                    self.output += " {";
+                }
+
+                // Increase nesting.
+                self.nesting_count += 1;
+
+                // Before inserting a new line check if we are inside of a multi-line macro.
+                if !self.prev_line_ended_with_backslash {
+                    // Insert a new line.
+                    self.is_on_new_line = true;
+                    self.output += LINE_ENDING;
+                    self.output += &self.indentation_text.repeat(self.nesting_count);
+                    self.consecutive_empty_new_line_count += 1;
+                }
+            }
+            NewLineOnOpenBrace::Before => {
+                // Before inserting a new line check if we are inside of a multi-line macro.
+                if self.prev_line_ended_with_backslash && !text_ends_with_backslash {
+                    self.output.push('\\');
+                }
+
+                // Insert a new line.
+                self.is_on_new_line = true;
+                self.output += LINE_ENDING;
+                self.output += &self.indentation_text.repeat(self.nesting_count);
+
+                // Add brace.
+                self.output.push('{');
+
+                if self.prev_line_ended_with_backslash {
+                    self.output.push('\\');
+                }
+
+                // Add new line with increased nesting.
+                self.nesting_count += 1;
+                self.output += LINE_ENDING;
+                self.output += &self.indentation_text.repeat(self.nesting_count);
+                self.consecutive_empty_new_line_count += 1;
+            }
+        }
+
+        // Ignore everything until we find some text.
+        self.ignore_until_text = true;
+    }
+
+    /// Removes spaces and tabs (and also line breaks if `including_line_breaks` is `true`)
+    /// from the end of the output.
+    fn trim_output_end(&mut self, including_line_breaks: bool) {
+        let trimmed_len = if including_line_breaks {
+            self.output.trim_end_matches([' ', '\t', '\n', '\r']).len()
+        } else {
+            self.output.trim_end_matches([' ', '\t']).len()
+        };
+        self.output.truncate(trimmed_len);
+    }
+}
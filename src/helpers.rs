@@ -1,21 +1,27 @@
-pub fn span_offset_to_line_and_column(target_offset: usize, file_contents: &str) -> (usize, usize) {
-    let mut line: usize = 1;
-    let mut column: usize = 0;
+/// Positions of line starts in a text, used to convert a lot of offsets to lines and columns
+/// without scanning the text from the beginning for each offset.
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
 
-    for (offset, char) in file_contents.char_indices() {
-        if char == '\n' {
-            line += 1;
-            column = 0;
-        }
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
 
-        column += 1;
+        Self { text, line_starts }
+    }
 
-        if offset != target_offset {
-            continue;
-        }
+    /// Returns line and column (both start from 1) of the character at the specified offset
+    /// (offsets after the last character point to the end of the last line).
+    pub fn line_and_column(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.text.len());
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column = self.text[line_start..offset].chars().count() + 1;
 
-        return (line, column - 1);
+        (line, column)
     }
-
-    (0, 0)
 }
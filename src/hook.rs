@@ -0,0 +1,185 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::{error::FormatError, rules::ShaderStage};
+
+/// Extensions of files that are formatted in the pre-commit hook (in addition to extensions
+/// of stage-specific files like `frag`).
+const SHADER_FILE_EXTENSIONS: [&str; 5] = ["hlsl", "hlsli", "glsl", "fx", "fxh"];
+
+/// Name of the hook file that git runs before creating a commit.
+const PRE_COMMIT_HOOK_NAME: &str = "pre-commit";
+
+/// A file that is added to the git index.
+pub struct StagedFile {
+    /// Path relative to the root of the repository (uses `/` as a separator).
+    pub path: String,
+    /// File mode (like `100644`).
+    mode: String,
+}
+
+/// Runs git with the specified arguments and returns its output.
+///
+/// # Arguments
+/// - `args` Arguments to pass to git.
+/// - `input` Data to write to stdin of git (if needed).
+fn run_git(args: &[&str], input: Option<&[u8]>) -> Result<Vec<u8>, FormatError> {
+    let mut child = Command::new("git")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| FormatError::Io(format!("failed to run git, error: {}", e)))?;
+
+    if let Some(input) = input {
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input).map_err(|e| {
+                FormatError::Io(format!("failed to write to git stdin, error: {}", e))
+            })?;
+        }
+    }
+    drop(child.stdin.take());
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| FormatError::Io(format!("failed to run git, error: {}", e)))?;
+    if !output.status.success() {
+        return Err(FormatError::Io(format!(
+            "\"git {}\" failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Returns the root directory of the current git repository.
+pub fn get_repository_root() -> Result<PathBuf, FormatError> {
+    let output = run_git(&["rev-parse", "--show-toplevel"], None)?;
+    Ok(PathBuf::from(String::from_utf8_lossy(&output).trim()))
+}
+
+/// Tells if the specified file should be formatted by the pre-commit hook.
+pub fn is_shader_file(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            SHADER_FILE_EXTENSIONS.contains(&extension)
+                || ShaderStage::from_file_extension(extension).is_some()
+        })
+}
+
+/// Returns added, copied and modified shader files from the git index.
+pub fn get_staged_shader_files() -> Result<Vec<StagedFile>, FormatError> {
+    let output = run_git(
+        &["diff", "--cached", "--name-only", "--diff-filter=ACM", "-z"],
+        None,
+    )?;
+
+    let mut files = Vec::new();
+    for path in String::from_utf8_lossy(&output).split('\0') {
+        if path.is_empty() || !is_shader_file(path) {
+            continue;
+        }
+
+        // Output looks like "<mode> <hash> <stage>\t<path>".
+        let entry = run_git(&["ls-files", "--stage", "--", path], None)?;
+        let Some(mode) = String::from_utf8_lossy(&entry)
+            .split_whitespace()
+            .next()
+            .map(str::to_owned)
+        else {
+            continue;
+        };
+
+        files.push(StagedFile {
+            path: path.to_owned(),
+            mode,
+        });
+    }
+
+    Ok(files)
+}
+
+/// Returns content of the specified file from the git index (not from the working tree).
+pub fn read_staged_file(file: &StagedFile) -> Result<Vec<u8>, FormatError> {
+    run_git(&["show", &format!(":{}", file.path)], None)
+}
+
+/// Replaces content of the specified file in the git index (the working tree is not changed).
+pub fn stage_file(file: &StagedFile, content: &str) -> Result<(), FormatError> {
+    let hash = run_git(
+        &["hash-object", "-w", "--stdin", "--path", &file.path],
+        Some(content.as_bytes()),
+    )?;
+    let hash = String::from_utf8_lossy(&hash);
+
+    run_git(
+        &[
+            "update-index",
+            "--cacheinfo",
+            &format!("{},{},{}", file.mode, hash.trim(), file.path),
+        ],
+        None,
+    )?;
+
+    Ok(())
+}
+
+/// Writes a pre-commit hook that runs the formatter in the hook mode to the hooks directory
+/// of the current git repository.
+///
+/// # Arguments
+/// - `program` Path to the formatter (or its name if it's in `PATH`).
+/// - `hook_command` Command that formats staged files.
+///
+/// # Return
+/// Path to the created hook.
+pub fn install_hook(program: &str, hook_command: &str) -> Result<PathBuf, FormatError> {
+    let output = run_git(&["rev-parse", "--git-path", "hooks"], None)?;
+    let hooks_dir = PathBuf::from(String::from_utf8_lossy(&output).trim());
+
+    let path_to_hook = hooks_dir.join(PRE_COMMIT_HOOK_NAME);
+    if path_to_hook.exists() {
+        return Err(FormatError::Io(format!(
+            "hook \"{}\" already exists",
+            path_to_hook.display()
+        )));
+    }
+
+    std::fs::create_dir_all(&hooks_dir)
+        .and_then(|_| {
+            std::fs::write(
+                &path_to_hook,
+                format!("#!/bin/sh\nexec \"{}\" {}\n", program, hook_command),
+            )
+        })
+        .map_err(|e| {
+            FormatError::Io(format!(
+                "failed to write the hook \"{}\", error: {}",
+                path_to_hook.display(),
+                e
+            ))
+        })?;
+
+    // Make the hook executable.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path_to_hook, std::fs::Permissions::from_mode(0o755)).map_err(
+            |e| {
+                FormatError::Io(format!(
+                    "failed to make the hook \"{}\" executable, error: {}",
+                    path_to_hook.display(),
+                    e
+                ))
+            },
+        )?;
+    }
+
+    Ok(path_to_hook)
+}
@@ -0,0 +1,253 @@
+use std::path::{Path, PathBuf};
+
+use chumsky::Parser;
+
+use crate::parser::{self, Token, Type};
+
+/// Returns path from an `#include` directive (like `common.hlsl` in `#include "common.hlsl"`).
+pub fn get_include_path(directive: &str) -> Option<&str> {
+    let text = directive
+        .trim_start()
+        .strip_prefix('#')?
+        .trim_start()
+        .strip_prefix("include")?
+        .trim();
+
+    let closing_char = match text.chars().next()? {
+        '"' => '"',
+        '<' => '>',
+        _ => return None,
+    };
+    let text = &text[1..];
+
+    text.find(closing_char).map(|end| &text[..end])
+}
+
+/// Looks for an included file in the directory of the file that includes it and then
+/// in the specified include directories.
+///
+/// # Arguments
+/// - `include_path` Path from an `#include` directive.
+/// - `current_dir` Directory of the file that has the `#include` directive (if known).
+/// - `include_dirs` Directories to look for included files.
+pub fn resolve_include(
+    include_path: &str,
+    current_dir: Option<&Path>,
+    include_dirs: &[PathBuf],
+) -> Option<PathBuf> {
+    current_dir
+        .into_iter()
+        .chain(include_dirs.iter().map(|dir| dir.as_path()))
+        .map(|dir| dir.join(include_path))
+        .find(|path| path.is_file())
+}
+
+/// Collects names of types defined in files included by the specified tokens (including files
+/// that are included by them).
+///
+/// # Arguments
+/// - `tokens` Tokens of a file.
+/// - `current_dir` Directory of the file (if known).
+/// - `include_dirs` Directories to look for included files.
+/// - `visited_files` Files that were already processed (used to skip files that are included
+///   multiple times or have circular includes).
+pub fn collect_included_types(
+    tokens: &[(Token<'_>, parser::Span)],
+    current_dir: Option<&Path>,
+    include_dirs: &[PathBuf],
+    visited_files: &mut Vec<PathBuf>,
+) -> Vec<(String, Type)> {
+    let mut types = Vec::new();
+
+    for (token, _) in tokens {
+        let Token::Preprocessor(directive) = token else {
+            continue;
+        };
+        let Some(include_path) = get_include_path(directive) else {
+            continue;
+        };
+        let Some(path) = resolve_include(include_path, current_dir, include_dirs) else {
+            continue;
+        };
+
+        // Make sure we didn't process this file yet.
+        let path = path.canonicalize().unwrap_or(path);
+        if visited_files.contains(&path) {
+            continue;
+        }
+        visited_files.push(path.clone());
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Some(included_tokens) = parser::token_parser().parse(&content).into_output() else {
+            continue;
+        };
+
+        types.extend(parser::collect_type_names(&included_tokens));
+        types.extend(collect_included_types(
+            &included_tokens,
+            path.parent(),
+            include_dirs,
+            visited_files,
+        ));
+    }
+
+    types
+}
+
+/// Collects paths to files included by the specified tokens (including files that are included
+/// by them).
+///
+/// # Arguments
+/// - `tokens` Tokens of a file.
+/// - `current_dir` Directory of the file (if known).
+/// - `include_dirs` Directories to look for included files.
+/// - `visited_files` Files that were already processed (used to skip files that are included
+///   multiple times or have circular includes), found files are added here.
+///
+/// # Return
+/// Canonical paths to the included files.
+pub fn collect_included_files(
+    tokens: &[(Token<'_>, parser::Span)],
+    current_dir: Option<&Path>,
+    include_dirs: &[PathBuf],
+    visited_files: &mut Vec<PathBuf>,
+) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for (token, _) in tokens {
+        let Token::Preprocessor(directive) = token else {
+            continue;
+        };
+        let Some(path) = get_include_path(directive)
+            .and_then(|include_path| resolve_include(include_path, current_dir, include_dirs))
+        else {
+            continue;
+        };
+
+        // Make sure we didn't process this file yet.
+        let path = path.canonicalize().unwrap_or(path);
+        if visited_files.contains(&path) {
+            continue;
+        }
+        visited_files.push(path.clone());
+        files.push(path.clone());
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Some(included_tokens) = parser::token_parser().parse(&content).into_output() else {
+            continue;
+        };
+
+        files.extend(collect_included_files(
+            &included_tokens,
+            path.parent(),
+            include_dirs,
+            visited_files,
+        ));
+    }
+
+    files
+}
+
+/// Looks for an `#include` directive that includes a file that does not exist.
+///
+/// # Return
+/// Span of the directive and the path from the directive.
+pub fn find_missing_include<'a>(
+    tokens: &[(Token<'a>, parser::Span)],
+    current_dir: Option<&Path>,
+    include_dirs: &[PathBuf],
+) -> Option<(parser::Span, &'a str)> {
+    tokens.iter().find_map(|(token, span)| {
+        let Token::Preprocessor(directive) = token else {
+            return None;
+        };
+        let include_path = get_include_path(directive)?;
+        match resolve_include(include_path, current_dir, include_dirs) {
+            Some(_) => None,
+            None => Some((*span, include_path)),
+        }
+    })
+}
+
+/// Looks for an `#include` directive that includes a file which (directly or indirectly) includes
+/// the specified file.
+///
+/// # Arguments
+/// - `tokens` Tokens of the file.
+/// - `path_to_file` Path to the file.
+/// - `include_dirs` Directories to look for included files.
+///
+/// # Return
+/// Span of the directive.
+pub fn find_circular_include(
+    tokens: &[(Token<'_>, parser::Span)],
+    path_to_file: &Path,
+    include_dirs: &[PathBuf],
+) -> Option<parser::Span> {
+    let path_to_file = path_to_file
+        .canonicalize()
+        .unwrap_or(path_to_file.to_path_buf());
+    let mut visited_files = Vec::new();
+
+    tokens.iter().find_map(|(token, span)| {
+        let Token::Preprocessor(directive) = token else {
+            return None;
+        };
+        let path = resolve_include(
+            get_include_path(directive)?,
+            path_to_file.parent(),
+            include_dirs,
+        )?;
+
+        if is_file_included(&path, &path_to_file, include_dirs, &mut visited_files) {
+            Some(*span)
+        } else {
+            None
+        }
+    })
+}
+
+/// Tells if the specified file is the target file or if it (directly or indirectly) includes
+/// the target file.
+fn is_file_included(
+    path: &Path,
+    target: &Path,
+    include_dirs: &[PathBuf],
+    visited_files: &mut Vec<PathBuf>,
+) -> bool {
+    let path = path.canonicalize().unwrap_or(path.to_path_buf());
+    if path == target {
+        return true;
+    }
+
+    // Make sure we didn't check this file yet.
+    if visited_files.contains(&path) {
+        return false;
+    }
+    visited_files.push(path.clone());
+
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return false;
+    };
+    let Some(tokens) = parser::token_parser().parse(&content).into_output() else {
+        return false;
+    };
+
+    tokens.iter().any(|(token, _)| {
+        let Token::Preprocessor(directive) = token else {
+            return false;
+        };
+        match get_include_path(directive)
+            .and_then(|include_path| resolve_include(include_path, path.parent(), include_dirs))
+        {
+            Some(included_path) => {
+                is_file_included(&included_path, target, include_dirs, visited_files)
+            }
+            None => false,
+        }
+    })
+}
@@ -1,183 +1,1456 @@
 #![deny(warnings)]
 
+use chumsky::{input::Input, Parser};
+use clap::Parser as _;
+use cli::{Cli, Command, EditorSettingsArgs, FileArgs, OutputFormat, ReportArgs, RuleSetArgs};
 use config::Config;
+use error::FormatError;
 use formatter::Formatter;
+use rules::Preset;
 use std::fs;
-use std::io::Write;
+use std::io::{BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
 use std::{fs::File, process::ExitCode};
 
+mod bindings;
+mod call_graph;
+mod cli;
 mod config;
+mod daemon;
+mod error;
 mod formatter;
 mod helpers;
+mod hook;
+mod includes;
 mod parser;
+mod patch;
+mod preprocessor;
+mod progress;
+mod rename;
 mod rules;
+mod stream;
 mod tests;
 
-const PRINT_TOKENS_ARG: &str = "--print-tokens";
-const ONLY_SCAN_ARG: &str = "--only-scan";
+/// Name of the command that formats staged files (used in the pre-commit hook).
+const HOOK_COMMAND: &str = "hook";
 
-fn get_files_in_directory(dir: &Path) -> Vec<PathBuf> {
-    let dir = Path::new(&dir);
+/// Name used instead of a rule name when reporting that formatting is needed
+/// in the compact output format.
+const FORMATTING_NEEDED_RULE_NAME: &str = "Formatting";
 
+/// Defines which files and directories are visited when looking for files in a directory.
+#[derive(Default)]
+struct TraversalOptions {
+    /// Whether to visit symbolic links (each directory is still visited only once).
+    follow_symlinks: bool,
+    /// Whether to visit directories which names start with a dot (like `.git`).
+    include_hidden: bool,
+    /// Maximum depth of subdirectories to visit (files of the specified directory have depth 0).
+    max_depth: Option<usize>,
+}
+
+/// Reason printed when a file is skipped because it took longer than `MaxFormatTime` to format.
+const FORMAT_TIME_EXCEEDED_REASON: &str = "formatting took longer than \"MaxFormatTime\"";
+
+/// Reason printed when a file that is formatted in chunks is skipped because it can't be split.
+const CHUNK_TOO_LARGE_REASON: &str =
+    "failed to split the file into chunks smaller than \"MaxFileSize\" at top-level statements";
+
+/// Error printed when the formatter panicked while formatting a file with `MaxFormatTime`.
+const FORMATTER_PANICKED_MESSAGE: &str = "failed to format the file, the formatter panicked";
+
+/// Maximum number of functions started by `run_with_time_limit` that run at the same time.
+/// Functions that exceeded the time limit keep running in the background (threads can't be
+/// stopped) so when this limit is reached new functions wait for some of them to finish.
+const MAX_TIME_LIMITED_FUNCTIONS: usize = 4;
+
+/// Number of functions started by `run_with_time_limit` that are still running and a condition
+/// variable that is notified when some of them finishes.
+static RUNNING_TIME_LIMITED_FUNCTIONS: (Mutex<usize>, Condvar) = (Mutex::new(0), Condvar::new());
+
+/// Number of bytes at the beginning of a file that are checked to detect binary files.
+const BINARY_SNIFF_LENGTH: usize = 8000;
+
+/// Defines what to do with formatted code.
+#[derive(Clone, Copy, PartialEq)]
+enum FormatMode {
+    /// Write formatted code to files.
+    Write,
+    /// Only check if formatting is needed.
+    Check,
+    /// Print formatted code to stdout.
+    Stdout,
+    /// Print a unified diff of all changes.
+    Patch,
+}
+
+/// Content of a file or a reason why the file was skipped.
+enum FileContent {
+    Text(String),
+    Skipped(&'static str),
+}
+
+/// Reads a text file, large and binary files are skipped.
+///
+/// # Arguments
+/// - `path` Path to the file.
+/// - `max_file_size` If specified, files larger than this size (in bytes) are skipped.
+///
+/// # Return
+/// `Err` if failed to read the file.
+fn read_text_file(path: &Path, max_file_size: Option<usize>) -> std::io::Result<FileContent> {
+    let file_size = fs::metadata(path)?.len();
+    if max_file_size.is_some_and(|max_file_size| file_size > max_file_size as u64) {
+        return Ok(FileContent::Skipped(
+            "the file is larger than \"MaxFileSize\"",
+        ));
+    }
+
+    let bytes = fs::read(path)?;
+    if bytes[..bytes.len().min(BINARY_SNIFF_LENGTH)].contains(&0) {
+        return Ok(FileContent::Skipped("the file seems to be binary"));
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(FileContent::Text(text)),
+        Err(_) => Ok(FileContent::Skipped("the file is not a valid UTF-8 text")),
+    }
+}
+
+fn get_files_in_directory(dir: &Path, options: &TraversalOptions) -> Vec<PathBuf> {
+    let mut visited_dirs = Vec::new();
+    let mut output = Vec::new();
+    collect_files_in_directory(dir, options, 0, &mut visited_dirs, &mut output);
+    output
+}
+
+/// Recursively collects files of the specified directory.
+///
+/// # Arguments
+/// - `dir` Directory to look for files in.
+/// - `options` Defines which files and directories to visit.
+/// - `depth` Depth of the directory relative to the directory the search was started in.
+/// - `visited_dirs` Canonical paths of directories that were already visited (used to avoid cycles).
+/// - `output` Found files.
+fn collect_files_in_directory(
+    dir: &Path,
+    options: &TraversalOptions,
+    depth: usize,
+    visited_dirs: &mut Vec<PathBuf>,
+    output: &mut Vec<PathBuf>,
+) {
     if !dir.is_dir() {
         panic!()
     }
 
+    // Make sure we don't visit the same directory twice (symlinks might create cycles).
+    let canonical_dir = dir.canonicalize().unwrap_or(dir.to_path_buf());
+    if visited_dirs.contains(&canonical_dir) {
+        return;
+    }
+    visited_dirs.push(canonical_dir);
+
     let paths = fs::read_dir(dir).unwrap();
-    let mut output = Vec::new();
 
     for path in paths {
         let path = path.unwrap().path();
 
+        if !options.follow_symlinks && path.is_symlink() {
+            continue;
+        }
+
         if path.is_dir() {
-            let mut result = get_files_in_directory(&path);
-            output.append(&mut result);
+            let is_hidden = path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+            if (is_hidden && !options.include_hidden)
+                || options
+                    .max_depth
+                    .is_some_and(|max_depth| depth >= max_depth)
+            {
+                continue;
+            }
+
+            collect_files_in_directory(&path, options, depth + 1, visited_dirs, output);
             continue;
         }
 
         output.push(path);
     }
+}
 
-    output
+/// Returns the specified path relative to the current directory (if possible) with `/`
+/// as a separator to be used in patches.
+fn get_patch_path(path: &Path) -> String {
+    let current_dir = std::env::current_dir()
+        .and_then(|dir| dir.canonicalize())
+        .unwrap_or_default();
+    let absolute_path = path.canonicalize().unwrap_or(path.to_path_buf());
+
+    match absolute_path.strip_prefix(&current_dir) {
+        Ok(relative_path) => relative_path
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.to_string_lossy().replace('\\', "/"),
+    }
 }
 
-fn main() -> ExitCode {
-    // Make sure a path is specified.
-    if std::env::args().len() == 1 {
-        println!("shader formatter (v{})", env!("CARGO_PKG_VERSION"));
-        println!();
-        println!("expected a path to be specified\n");
-        println!("usage:");
-        println!(
-            "{} <path to file or a directory> <option>",
-            std::env::args().next().unwrap()
-        );
-        println!("\nwhere <option> is one of the following:");
-        println!(
-            "\"{}\" - prints parsed tokens (used for debugging)\n\
-             \"{}\" - only check if formatting is needed or not, don't change the actual file, \
-                returns 0 if no formatting is needed",
-            PRINT_TOKENS_ARG, ONLY_SCAN_ARG
-        );
-        return ExitCode::FAILURE;
+/// Prints all resources that have explicit bindings (registers) in the specified files.
+fn report_bindings(
+    shader_paths: &[PathBuf],
+    as_json: bool,
+    max_file_size: Option<usize>,
+) -> ExitCode {
+    let mut resources = Vec::new();
+
+    for path_to_shader in shader_paths {
+        // Read file.
+        let file_content = match read_text_file(path_to_shader, max_file_size) {
+            Ok(FileContent::Text(v)) => v,
+            Ok(FileContent::Skipped(reason)) => {
+                eprintln!(
+                    "warning: skipped file \"{}\", {}",
+                    path_to_shader.display(),
+                    reason
+                );
+                continue;
+            }
+            Err(e) => {
+                println!("failed to read the file, error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        // Parse tokens.
+        let Some(tokens) = parser::token_parser()
+            .parse(file_content.as_str())
+            .into_output()
+        else {
+            println!("failed to parse the file \"{}\"", path_to_shader.display());
+            return ExitCode::FAILURE;
+        };
+        let tokens = parser::split_template_closing_shifts(tokens);
+
+        let line_index = helpers::LineIndex::new(&file_content);
+        for binding in bindings::collect_bindings(&tokens) {
+            let (line, column) = line_index.line_and_column(binding.name_span.start);
+            let type_name = &file_content[binding.type_span.into_range()];
+
+            if !as_json {
+                println!(
+                    "{}:{}:{}: {} {} {}",
+                    path_to_shader.display(),
+                    line,
+                    column,
+                    type_name,
+                    binding.name,
+                    binding.slot
+                );
+                continue;
+            }
+
+            resources.push(serde_json::json!({
+                "file": path_to_shader.to_string_lossy(),
+                "line": line,
+                "column": column,
+                "type": type_name,
+                "name": binding.name,
+                "registerType": binding.slot.register_type.map(String::from),
+                "binding": binding.slot.index,
+                "space": binding.slot.space,
+            }));
+        }
     }
 
-    // Get path to shaders.
-    let Some(path_to_shader) = std::env::args().nth(1) else {
-        println!("expected a path to be specified");
+    if as_json {
+        println!("{}", serde_json::Value::Array(resources));
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Creates a config file in the current directory.
+fn init_config(preset_name: Option<&str>) -> ExitCode {
+    let preset = match preset_name {
+        Some(name) => match Preset::from_name(name) {
+            Some(preset) => Some(preset),
+            None => {
+                println!("unknown preset \"{}\"", name);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+
+    let current_dir = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            println!("failed to get the current directory, error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match Config::create_file(&current_dir, preset) {
+        Ok(path) => {
+            println!("created config file at {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints description, allowed values, default value and an example of a config rule.
+fn explain_rule(rule_name: Option<&str>) -> ExitCode {
+    let Some(rule) = rule_name.and_then(rules::find_rule) else {
+        match rule_name {
+            Some(name) => println!("unknown rule \"{}\"", name),
+            None => println!("expected a rule name to be specified"),
+        }
+        let names = rules::RULES.iter().map(|rule| rule.id).collect::<Vec<_>>();
+        println!("available rules: {}", names.join(", "));
         return ExitCode::FAILURE;
     };
 
-    // See if we need to print tokens.
-    let print_tokens = if let Some(additional_option) = std::env::args().nth(2) {
-        additional_option == PRINT_TOKENS_ARG
-    } else {
-        false
+    let category = match rule.category {
+        rules::RuleCategory::Formatting => "formatting",
+        rules::RuleCategory::Lint => "lint",
     };
+    println!("{} ({} rule)\n", rule.id, category);
+    println!("{}\n", rule.description);
+    println!("allowed values: {}", rule.allowed_values());
+    println!(
+        "default: {}\n",
+        rule.default.unwrap_or("not checked unless specified")
+    );
+    if rule.example_before.is_none() && rule.example_after.is_none() {
+        println!("example: {} = {}", rule.id, rule.example_value);
+        return ExitCode::SUCCESS;
+    }
+    println!("example ({} = {}):", rule.id, rule.example_value);
+    if let Some(before) = rule.example_before {
+        println!("\nbefore:\n{}", before);
+    }
+    if let Some(after) = rule.example_after {
+        println!("\nafter:\n{}", after);
+    }
 
-    // See if we only need to scan.
-    let only_scan = if let Some(additional_option) = std::env::args().nth(2) {
-        additional_option == ONLY_SCAN_ARG
-    } else {
-        false
+    ExitCode::SUCCESS
+}
+
+/// Prints parsed tokens (or complex tokens like structs and functions if `print_ast` is `true`)
+/// of the specified files as a JSON array, files are not formatted.
+fn print_parsed_files(shader_paths: &[PathBuf], config: &Config, print_ast: bool) -> ExitCode {
+    let mut files = Vec::new();
+
+    for path_to_shader in shader_paths {
+        // Read file.
+        let file_content = match read_text_file(path_to_shader, config.max_file_size) {
+            Ok(FileContent::Text(v)) => v,
+            Ok(FileContent::Skipped(reason)) => {
+                eprintln!(
+                    "warning: skipped file \"{}\", {}",
+                    path_to_shader.display(),
+                    reason
+                );
+                continue;
+            }
+            Err(e) => {
+                println!("failed to read the file, error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        // Parse tokens.
+        let (tokens, errors) = parser::token_parser()
+            .parse(file_content.as_str())
+            .into_output_errors();
+        let mut errors: Vec<_> = errors
+            .iter()
+            .map(|error| {
+                serde_json::json!({
+                    "span": parser::span_to_json(*error.span()),
+                    "reason": error.reason().to_string(),
+                })
+            })
+            .collect();
+        let tokens = parser::split_template_closing_shifts(tokens.unwrap_or_default());
+
+        let line_index = helpers::LineIndex::new(&file_content);
+        let token_to_json = |token: serde_json::Value, span: parser::Span| {
+            let (line, column) = line_index.line_and_column(span.start);
+            serde_json::json!({
+                "line": line,
+                "column": column,
+                "span": parser::span_to_json(span),
+                "token": token,
+            })
+        };
+
+        if !print_ast {
+            files.push(serde_json::json!({
+                "file": path_to_shader.to_string_lossy(),
+                "tokens": tokens
+                    .iter()
+                    .map(|(token, span)| token_to_json(token.to_json(), *span))
+                    .collect::<Vec<_>>(),
+                "errors": errors,
+            }));
+            continue;
+        }
+
+        // Collect custom types defined in this file and in included files.
+        let mut known_types = parser::collect_type_names(&tokens);
+        let mut visited_files = vec![path_to_shader
+            .canonicalize()
+            .unwrap_or(path_to_shader.clone())];
+        known_types.extend(includes::collect_included_types(
+            &tokens,
+            path_to_shader.parent(),
+            &config.include_dirs,
+            &mut visited_files,
+        ));
+
+        // Parse statements.
+        let (complex_tokens, complex_errors) = parser::complex_token_parser(known_types)
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_output_errors();
+        errors.extend(complex_errors.iter().map(|error| {
+            serde_json::json!({
+                "span": parser::span_to_json(*error.span()),
+                "reason": error.reason().to_string(),
+            })
+        }));
+
+        files.push(serde_json::json!({
+            "file": path_to_shader.to_string_lossy(),
+            "complexTokens": complex_tokens
+                .unwrap_or_default()
+                .iter()
+                .map(|(token, span)| token_to_json(token.to_json(), *span))
+                .collect::<Vec<_>>(),
+            "errors": errors,
+        }));
+    }
+
+    println!("{}", serde_json::Value::Array(files));
+
+    ExitCode::SUCCESS
+}
+
+/// Describes why `run_with_time_limit` did not return a result of the function.
+enum TimeLimitError {
+    /// The function took longer than the time limit.
+    Exceeded,
+    /// The function panicked.
+    Panicked,
+}
+
+/// Runs the specified function and waits for it to finish, the waiting is stopped if the function
+/// takes longer than the time limit.
+///
+/// The function keeps running in the background after the time limit is exceeded (until it
+/// finishes or the program exits), if `MAX_TIME_LIMITED_FUNCTIONS` functions are already running
+/// then this call waits for some of them to finish before starting the function.
+///
+/// # Arguments
+/// - `time_limit` Maximum time to wait for (in milliseconds), if not specified the function is
+///   run on the current thread.
+/// - `run` Function to run.
+fn run_with_time_limit<T: Send + 'static>(
+    time_limit: Option<usize>,
+    run: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, TimeLimitError> {
+    let Some(time_limit) = time_limit else {
+        return Ok(run());
+    };
+
+    /// Marks the function as finished when dropped (even if the function panicked).
+    struct RunningFunction;
+    impl Drop for RunningFunction {
+        fn drop(&mut self) {
+            let (running_count, finished) = &RUNNING_TIME_LIMITED_FUNCTIONS;
+            *running_count.lock().unwrap() -= 1;
+            finished.notify_one();
+        }
+    }
+
+    let (running_count, finished) = &RUNNING_TIME_LIMITED_FUNCTIONS;
+    *finished
+        .wait_while(running_count.lock().unwrap(), |count| {
+            *count >= MAX_TIME_LIMITED_FUNCTIONS
+        })
+        .unwrap() += 1;
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _running = RunningFunction;
+        let _ = sender.send(run());
+    });
+
+    match receiver.recv_timeout(Duration::from_millis(time_limit as u64)) {
+        Ok(result) => Ok(result),
+        Err(RecvTimeoutError::Timeout) => Err(TimeLimitError::Exceeded),
+        Err(RecvTimeoutError::Disconnected) => Err(TimeLimitError::Panicked),
+    }
+}
+
+/// How much time processing of a file took.
+struct FileTimings {
+    path: PathBuf,
+    /// Tokenize and parse time (not measured for files that were skipped or formatted in chunks).
+    parsing: Option<(Duration, Duration)>,
+    /// All stages of formatting (including tokenizing, parsing and checks).
+    format: Duration,
+}
+
+/// Measures how much time tokenizing and parsing (of statements) of the specified code takes.
+///
+/// # Return
+/// Tokenize and parse time.
+fn measure_parsing(content: &str) -> (Duration, Duration) {
+    // Tokenize.
+    let start = Instant::now();
+    let tokens = parser::token_parser().parse(content).into_output();
+    let tokenize_time = start.elapsed();
+
+    // Parse statements.
+    let start = Instant::now();
+    if let Some(tokens) = tokens {
+        let tokens = parser::split_template_closing_shifts(tokens);
+        let _ = parser::complex_token_parser(parser::collect_type_names(&tokens))
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_output();
+    }
+    let parse_time = start.elapsed();
+
+    (tokenize_time, parse_time)
+}
+
+/// Prints (to stderr) timings of the files that took the most time to format.
+///
+/// # Arguments
+/// - `timings` Timings of all processed files.
+/// - `count` Maximum number of files to print.
+fn print_slowest_files(mut timings: Vec<FileTimings>, count: usize) {
+    timings.sort_by_key(|timing| std::cmp::Reverse(timing.format));
+
+    eprintln!(
+        "{} slowest of {} processed file(s):",
+        count.min(timings.len()),
+        timings.len()
+    );
+    for timing in timings.iter().take(count) {
+        match timing.parsing {
+            Some((tokenize, parse)) => eprintln!(
+                "{}: tokenize {:.2?}, parse {:.2?}, format {:.2?}",
+                timing.path.display(),
+                tokenize,
+                parse,
+                timing.format
+            ),
+            None => eprintln!("{}: format {:.2?}", timing.path.display(), timing.format),
+        }
+    }
+}
+
+/// Runs the formatter on the specified files (without changing them) and prints how much time
+/// tokenizing, parsing and formatting (all stages including checks) of each file took.
+fn bench_files(shader_paths: &[PathBuf], config: &Config) -> ExitCode {
+    let mut total_tokenize_time = Duration::ZERO;
+    let mut total_parse_time = Duration::ZERO;
+    let mut total_format_time = Duration::ZERO;
+
+    for path_to_shader in shader_paths {
+        // Read file.
+        let file_content = match read_text_file(path_to_shader, config.max_file_size) {
+            Ok(FileContent::Text(v)) => v,
+            Ok(FileContent::Skipped(reason)) => {
+                eprintln!(
+                    "warning: skipped file \"{}\", {}",
+                    path_to_shader.display(),
+                    reason
+                );
+                continue;
+            }
+            Err(e) => {
+                println!("failed to read the file, error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        let (tokenize_time, parse_time) = measure_parsing(&file_content);
+
+        // Format.
+        let mut formatter = Formatter::new(config.clone());
+        formatter.set_path_to_shader(path_to_shader);
+        let start = Instant::now();
+        let result = formatter.format(&file_content, false);
+        let format_time = start.elapsed();
+
+        println!(
+            "{}: tokenize {:.2?}, parse {:.2?}, format {:.2?}{}",
+            path_to_shader.display(),
+            tokenize_time,
+            parse_time,
+            format_time,
+            if result.is_err() {
+                " (formatting failed)"
+            } else {
+                ""
+            }
+        );
+
+        total_tokenize_time += tokenize_time;
+        total_parse_time += parse_time;
+        total_format_time += format_time;
+    }
+
+    println!(
+        "total: tokenize {:.2?}, parse {:.2?}, format {:.2?}",
+        total_tokenize_time, total_parse_time, total_format_time
+    );
+
+    ExitCode::SUCCESS
+}
+
+/// Formats shader files that are added to the git index (not the working tree), stages
+/// the formatted files and prints a summary (used as a git pre-commit hook).
+fn format_staged_files(compact_output: bool) -> ExitCode {
+    let (repository_root, staged_files) = match hook::get_repository_root()
+        .and_then(|root| Ok((root, hook::get_staged_shader_files()?)))
+    {
+        Ok(v) => v,
+        Err(error) => {
+            println!("{}", error);
+            return ExitCode::FAILURE;
+        }
     };
 
-    let specified_path = std::path::PathBuf::from(path_to_shader);
-    let mut shader_paths: Vec<PathBuf> = Vec::new();
+    let mut formatted_count = 0;
+    let mut failed_count = 0;
+    for file in &staged_files {
+        let path_to_shader = repository_root.join(&file.path);
 
-    let mut shader_directory = specified_path.clone();
-    if specified_path.is_file() {
-        shader_paths.push(specified_path.clone());
+        // Read the staged version of the file.
+        let file_content = match hook::read_staged_file(file).map(String::from_utf8) {
+            Ok(Ok(v)) => v,
+            Ok(Err(_)) => {
+                eprintln!(
+                    "warning: skipped file \"{}\", the file is not a valid UTF-8 text",
+                    file.path
+                );
+                continue;
+            }
+            Err(error) => {
+                println!("{}", error);
+                return ExitCode::FAILURE;
+            }
+        };
+
+        // Load config.
+        let config = match Config::get(path_to_shader.parent().unwrap_or(&repository_root)) {
+            Ok(f) => f,
+            Err(error) => {
+                println!("{}", error);
+                return ExitCode::FAILURE;
+            }
+        };
 
+        // Format code.
+        let max_format_time = config.max_format_time;
+        let mut formatter = Formatter::new(config);
+        formatter.set_path_to_shader(&path_to_shader);
+        formatter.set_compact_output(compact_output);
+        let content = file_content.clone();
+        let result =
+            match run_with_time_limit(max_format_time, move || formatter.format(&content, false)) {
+                Ok(result) => result,
+                Err(TimeLimitError::Exceeded) => {
+                    eprintln!(
+                        "warning: skipped file \"{}\", {}",
+                        file.path, FORMAT_TIME_EXCEEDED_REASON
+                    );
+                    continue;
+                }
+                Err(TimeLimitError::Panicked) => {
+                    if compact_output {
+                        println!("{}: {}", file.path, FORMATTER_PANICKED_MESSAGE);
+                    } else {
+                        println!("file \"{}\": {}", file.path, FORMATTER_PANICKED_MESSAGE);
+                    }
+                    failed_count += 1;
+                    continue;
+                }
+            };
+        let output = match result {
+            Ok(o) => o,
+            Err(error) => {
+                if compact_output {
+                    println!("{}", error.to_compact(Path::new(&file.path)));
+                } else {
+                    println!("file \"{}\": {}", file.path, error);
+                }
+                failed_count += 1;
+                continue;
+            }
+        };
+        if output == file_content {
+            continue;
+        }
+
+        // Update the working tree too if it does not have unstaged changes.
+        let has_unstaged_changes =
+            fs::read(&path_to_shader).map_or(true, |content| content != file_content.as_bytes());
+        if !has_unstaged_changes {
+            if let Err(error) = fs::write(&path_to_shader, &output) {
+                println!("failed to write to the file, error: {}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+
+        if let Err(error) = hook::stage_file(file, &output) {
+            println!("{}", error);
+            return ExitCode::FAILURE;
+        }
+        formatted_count += 1;
+    }
+
+    println!(
+        "formatted {} of {} staged shader file(s){}",
+        formatted_count,
+        staged_files.len(),
+        if failed_count > 0 {
+            format!(", {} file(s) need manual changes", failed_count)
+        } else {
+            String::new()
+        }
+    );
+
+    if failed_count > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Creates a git pre-commit hook that runs the formatter on staged files.
+fn install_pre_commit_hook() -> ExitCode {
+    let program = match std::env::current_exe() {
+        Ok(path) => path.to_string_lossy().replace('\\', "/"),
+        Err(e) => {
+            println!("failed to get path to the program, error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match hook::install_hook(&program, HOOK_COMMAND) {
+        Ok(path) => {
+            println!("created pre-commit hook at {}", path.display());
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Loads config for the specified path and collects files to process.
+///
+/// # Return
+/// Config and paths to files.
+fn load_files(args: &FileArgs) -> Result<(Config, Vec<PathBuf>), FormatError> {
+    let mut shader_directory = args.path.clone();
+    if args.path.is_file() {
         // Get directory of this shader file.
-        shader_directory = match specified_path.parent() {
+        shader_directory = match args.path.parent() {
             Some(path) => path.to_path_buf(),
             None => {
-                println!(
+                return Err(FormatError::Io(format!(
                     "failed to get parent directory for file \"{}\"",
-                    specified_path.to_string_lossy()
-                );
-                return ExitCode::FAILURE;
+                    args.path.to_string_lossy()
+                )))
             }
         };
+    }
+
+    // Load config.
+    let config = Config::get(&shader_directory)?;
+
+    let shader_paths = if args.path.is_file() {
+        vec![args.path.clone()]
     } else {
         // Recursively get all shader files from the specified directory.
-        shader_paths = get_files_in_directory(&specified_path);
+        let options = TraversalOptions {
+            follow_symlinks: args.follow_symlinks,
+            include_hidden: args.hidden,
+            max_depth: config.max_depth,
+        };
+        get_files_in_directory(&args.path, &options)
+    };
+
+    Ok((config, shader_paths))
+}
+
+/// Loads config and collects files for the specified path and runs the specified function
+/// on them.
+fn run_on_files(files: &FileArgs, run: impl FnOnce(&[PathBuf], &Config) -> ExitCode) -> ExitCode {
+    match load_files(files) {
+        Ok((config, shader_paths)) => run(&shader_paths, &config),
+        Err(error) => {
+            println!("{}", error);
+            ExitCode::FAILURE
+        }
     }
+}
 
-    // Load config.
-    let config = match Config::get(&shader_directory) {
-        Ok(f) => f,
-        Err(msg) => {
-            println!("{}", msg);
+/// Files to check and files included by them.
+struct FilesWithIncludes {
+    config: Config,
+    /// Paths and contents of the files, included files that are not among the files to check
+    /// are placed after the files to check.
+    contents: Vec<(PathBuf, String)>,
+    /// Number of files to check.
+    checked_count: usize,
+}
+
+/// Loads config for the specified path and reads files to check and files included by them.
+fn read_files_with_includes(files: &FileArgs) -> Result<FilesWithIncludes, FormatError> {
+    let (config, shader_paths) = load_files(files)?;
+
+    // Read files.
+    let mut contents = Vec::new();
+    let mut visited_files = Vec::new();
+    for path_to_shader in shader_paths {
+        match read_text_file(&path_to_shader, config.max_file_size) {
+            Ok(FileContent::Text(text)) => {
+                visited_files.push(
+                    path_to_shader
+                        .canonicalize()
+                        .unwrap_or(path_to_shader.clone()),
+                );
+                contents.push((path_to_shader, text));
+            }
+            Ok(FileContent::Skipped(_)) => {} // large and binary files are not checked
+            Err(e) => {
+                return Err(FormatError::Io(format!(
+                    "failed to read the file, error: {}",
+                    e
+                )))
+            }
+        }
+    }
+    let checked_count = contents.len();
+
+    // Read included files.
+    let mut included_files = Vec::new();
+    for (path_to_shader, text) in &contents {
+        let Some(tokens) = parser::token_parser().parse(text.as_str()).into_output() else {
+            continue;
+        };
+        included_files.extend(includes::collect_included_files(
+            &tokens,
+            path_to_shader.parent(),
+            &config.include_dirs,
+            &mut visited_files,
+        ));
+    }
+    for path in included_files {
+        if let Ok(FileContent::Text(text)) = read_text_file(&path, config.max_file_size) {
+            contents.push((path, text));
+        }
+    }
+
+    Ok(FilesWithIncludes {
+        config,
+        contents,
+        checked_count,
+    })
+}
+
+/// Renames global symbols in the specified files (and in files included by them) to names
+/// suggested by naming rules and prints which files were changed.
+///
+/// # Arguments
+/// - `files` Files to check.
+/// - `dry_run` Only print the renames, don't change the files.
+fn fix_global_names(files: &FileArgs, dry_run: bool) -> Result<(), FormatError> {
+    let FilesWithIncludes {
+        config,
+        mut contents,
+        checked_count,
+    } = read_files_with_includes(files)?;
+
+    let renames = rename::fix_global_names(&mut contents, checked_count, &config);
+
+    let mut changed_files = Vec::new();
+    for rename in &renames {
+        println!(
+            "{} \"{}\" to \"{}\" ({}) in {} file(s):",
+            if dry_run { "would rename" } else { "renamed" },
+            rename.old_name,
+            rename.new_name,
+            rename.rule,
+            rename.affected_files.len()
+        );
+        for file_index in &rename.affected_files {
+            println!("    {}", contents[*file_index].0.display());
+            if !changed_files.contains(file_index) {
+                changed_files.push(*file_index);
+            }
+        }
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+    for file_index in changed_files {
+        let (path, text) = &contents[file_index];
+        fs::write(path, text)
+            .map_err(|e| FormatError::Io(format!("failed to write to the file, error: {}", e)))?;
+    }
+
+    Ok(())
+}
+
+/// Writes names that violate naming rules, names suggested by the rules and locations of
+/// references to the names as a JSON array to the specified file.
+///
+/// # Arguments
+/// - `files` Files to check.
+/// - `output_path` Path to the JSON file to create.
+fn export_renames(files: &FileArgs, output_path: &Path) -> Result<(), FormatError> {
+    let FilesWithIncludes {
+        config,
+        contents,
+        checked_count,
+    } = read_files_with_includes(files)?;
+
+    let suggestions = rename::collect_rename_suggestions(&contents, checked_count, &config);
+    let json = serde_json::Value::Array(
+        suggestions
+            .iter()
+            .map(|suggestion| suggestion.to_json(&contents))
+            .collect(),
+    );
+
+    fs::write(output_path, format!("{:#}\n", json))
+        .map_err(|e| FormatError::Io(format!("failed to write to the file, error: {}", e)))?;
+    println!(
+        "exported {} rename suggestion(s) to {}",
+        suggestions.len(),
+        output_path.display()
+    );
+
+    Ok(())
+}
+
+/// Formats a file in chunks (see `StreamingChunkSize`) without reading the whole file
+/// into memory.
+///
+/// # Arguments
+/// - `path` Path to the file.
+/// - `formatter` Formatter to use.
+/// - `chunk_size` Minimum size of a chunk (in bytes).
+/// - `max_chunk_size` Maximum size of a chunk (in bytes).
+/// - `deadline` Time after which formatting is stopped (the file is not changed then).
+/// - `mode` Defines what to do with the formatted code (patches are not supported).
+fn format_file_in_chunks(
+    path: &Path,
+    formatter: &Formatter,
+    chunk_size: usize,
+    max_chunk_size: usize,
+    deadline: Option<Instant>,
+    mode: FormatMode,
+) -> Result<stream::ChunkedOutput, FormatError> {
+    let io_error = |e: std::io::Error| {
+        FormatError::Io(format!(
+            "failed to process the file \"{}\", error: {}",
+            path.display(),
+            e
+        ))
+    };
+    let input = BufReader::new(File::open(path).map_err(io_error)?);
+
+    match mode {
+        FormatMode::Check | FormatMode::Patch => stream::format_in_chunks(
+            input,
+            &mut std::io::sink(),
+            formatter,
+            chunk_size,
+            max_chunk_size,
+            deadline,
+        ),
+        FormatMode::Stdout => stream::format_in_chunks(
+            input,
+            &mut std::io::stdout().lock(),
+            formatter,
+            chunk_size,
+            max_chunk_size,
+            deadline,
+        ),
+        FormatMode::Write => {
+            // Write to a temporary file and replace the file only if formatting succeeded.
+            let mut temp_path = path.as_os_str().to_owned();
+            temp_path.push(".tmp");
+            let temp_path = PathBuf::from(temp_path);
+
+            let mut output = BufWriter::new(File::create(&temp_path).map_err(io_error)?);
+            let result = stream::format_in_chunks(
+                input,
+                &mut output,
+                formatter,
+                chunk_size,
+                max_chunk_size,
+                deadline,
+            )
+            .and_then(|result| {
+                output.flush().map_err(io_error)?;
+                Ok(result)
+            });
+            drop(output);
+
+            match result {
+                Ok(stream::ChunkedOutput::Formatted(Some(line))) => {
+                    fs::rename(&temp_path, path).map_err(io_error)?;
+                    Ok(stream::ChunkedOutput::Formatted(Some(line)))
+                }
+                result => {
+                    let _ = fs::remove_file(&temp_path);
+                    result
+                }
+            }
+        }
+    }
+}
+
+/// Formats the specified files.
+///
+/// # Arguments
+/// - `files` Files to format.
+/// - `mode` Defines what to do with the formatted code.
+/// - `print_tokens` Whether to print parsed tokens (used for debugging).
+/// - `rule_set` Defines which rules are applied.
+/// - `editor_settings` Rules that take precedence over the config file.
+/// - `report` Defines how results of processing the files are reported.
+fn format_files(
+    files: &FileArgs,
+    mode: FormatMode,
+    print_tokens: bool,
+    rule_set: &RuleSetArgs,
+    editor_settings: &EditorSettingsArgs,
+    report: &ReportArgs,
+) -> ExitCode {
+    let Some(profile_count) = report.profile else {
+        return process_files(
+            files,
+            mode,
+            print_tokens,
+            rule_set,
+            editor_settings,
+            report,
+            None,
+        );
+    };
+
+    // Print the slowest files even if some file failed.
+    let mut timings = Vec::new();
+    let exit_code = process_files(
+        files,
+        mode,
+        print_tokens,
+        rule_set,
+        editor_settings,
+        report,
+        Some(&mut timings),
+    );
+    print_slowest_files(timings, profile_count);
+
+    exit_code
+}
+
+/// Formats the specified files (see `format_files`).
+///
+/// # Arguments
+/// - `timings` If specified, timings of processed files are measured and added here.
+fn process_files(
+    files: &FileArgs,
+    mode: FormatMode,
+    print_tokens: bool,
+    rule_set: &RuleSetArgs,
+    editor_settings: &EditorSettingsArgs,
+    report: &ReportArgs,
+    mut timings: Option<&mut Vec<FileTimings>>,
+) -> ExitCode {
+    let compact_output = report.output_format == OutputFormat::Compact;
+
+    if mode == FormatMode::Stdout && !files.path.is_file() {
+        println!("expected a path to a file when \"--stdout\" is specified");
+        return ExitCode::FAILURE;
+    }
+
+    let (mut config, shader_paths) = match load_files(files) {
+        Ok(v) => v,
+        Err(error) => {
+            println!("{}", error);
             return ExitCode::FAILURE;
         }
     };
+    if rule_set.format_only {
+        config.disable_lint_rules();
+    }
+    config.override_rules(&|config| {
+        if let Some(indentation) = editor_settings.indent {
+            config.indentation = indentation;
+        }
+        if let Some(line_ending) = editor_settings.line_ending {
+            config.line_ending = Some(line_ending);
+        }
+        if let Some(final_newline) = editor_settings.final_newline {
+            config.insert_final_newline = Some(final_newline);
+        }
+    });
+
+    // Formatted files (used to look for unused functions).
+    let mut formatted_files = Vec::new();
+    let report_unused_functions = config.report_unused_functions && files.path.is_dir();
 
+    let mut progress = progress::Progress::new(report.progress, shader_paths.len());
     for path_to_shader in shader_paths {
+        progress.start_file(&path_to_shader);
+
+        // Format huge files in chunks (if enabled) instead of skipping them.
+        let exceeded_file_size = config.max_file_size.filter(|&max_file_size| {
+            fs::metadata(&path_to_shader)
+                .is_ok_and(|metadata| metadata.len() > max_file_size as u64)
+        });
+        if let (Some(chunk_size), Some(max_file_size)) =
+            (config.streaming_chunk_size, exceeded_file_size)
+        {
+            if mode == FormatMode::Patch {
+                progress.clear();
+                eprintln!(
+                    "warning: skipped file \"{}\", files that are formatted in chunks can't be printed as a patch",
+                    path_to_shader.display()
+                );
+                continue;
+            }
+
+            let mut formatter = Formatter::new(config.clone());
+            formatter.set_path_to_shader(&path_to_shader);
+            formatter.set_compact_output(compact_output);
+            formatter.set_lint_only(rule_set.lint_only);
+            let start = Instant::now();
+            let deadline = (config.max_format_time)
+                .map(|time_limit| start + Duration::from_millis(time_limit as u64));
+            let result = format_file_in_chunks(
+                &path_to_shader,
+                &formatter,
+                chunk_size,
+                max_file_size,
+                deadline,
+                mode,
+            );
+            if let Some(timings) = timings.as_deref_mut() {
+                timings.push(FileTimings {
+                    path: path_to_shader.clone(),
+                    parsing: None,
+                    format: start.elapsed(),
+                });
+            }
+            progress.clear();
+            match result {
+                Ok(stream::ChunkedOutput::TimeLimitExceeded) => {
+                    eprintln!(
+                        "warning: skipped file \"{}\", {}",
+                        path_to_shader.display(),
+                        FORMAT_TIME_EXCEEDED_REASON
+                    );
+                }
+                Ok(stream::ChunkedOutput::ChunkTooLarge) => {
+                    eprintln!(
+                        "warning: skipped file \"{}\", {}",
+                        path_to_shader.display(),
+                        CHUNK_TOO_LARGE_REASON
+                    );
+                }
+                Ok(stream::ChunkedOutput::Formatted(Some(line))) if mode == FormatMode::Check => {
+                    if compact_output {
+                        println!(
+                            "{}:{}:1: {}: formatting is needed",
+                            path_to_shader.display(),
+                            line,
+                            FORMATTING_NEEDED_RULE_NAME
+                        );
+                    } else {
+                        println!(
+                            "formatting is needed for file \"{}\" (starting from line {})",
+                            path_to_shader.display(),
+                            line
+                        );
+                    }
+                    return ExitCode::FAILURE;
+                }
+                Ok(_) => {}
+                Err(error) if compact_output => {
+                    println!("{}", error.to_compact(&path_to_shader));
+                    return ExitCode::FAILURE;
+                }
+                Err(error) => {
+                    println!("{}", error);
+                    return ExitCode::FAILURE;
+                }
+            }
+            continue;
+        }
+
         // Read file.
-        let file_content = match std::fs::read_to_string(path_to_shader.clone()) {
-            Ok(v) => v,
+        let file_content = match read_text_file(&path_to_shader, config.max_file_size) {
+            Ok(FileContent::Text(v)) => v,
+            Ok(FileContent::Skipped(reason)) => {
+                progress.clear();
+                eprintln!(
+                    "warning: skipped file \"{}\", {}",
+                    path_to_shader.display(),
+                    reason
+                );
+                continue;
+            }
             Err(e) => {
+                progress.clear();
                 println!("failed to read the file, error: {}", e);
                 return ExitCode::FAILURE;
             }
         };
 
         // Format code.
-        let formatter = Formatter::new(config.clone());
-        let output = match formatter.format(&file_content, print_tokens) {
+        let mut formatter = Formatter::new(config.clone());
+        formatter.set_path_to_shader(&path_to_shader);
+        formatter.set_compact_output(compact_output);
+        formatter.set_lint_only(rule_set.lint_only);
+        let measure_parsing_time = timings.is_some();
+        let content = file_content.clone();
+        let start = Instant::now();
+        let result = run_with_time_limit(config.max_format_time, move || {
+            let (tokenize_time, parse_time) = if measure_parsing_time {
+                measure_parsing(&content)
+            } else {
+                (Duration::ZERO, Duration::ZERO)
+            };
+            let start = Instant::now();
+            let result = formatter.format(&content, print_tokens);
+            (result, tokenize_time, parse_time, start.elapsed())
+        });
+        let elapsed = start.elapsed();
+        if let Some(timings) = timings.as_deref_mut() {
+            let (parsing, format) = match &result {
+                Ok((_, tokenize, parse, format)) => (Some((*tokenize, *parse)), *format),
+                Err(_) => (None, elapsed),
+            };
+            timings.push(FileTimings {
+                path: path_to_shader.clone(),
+                parsing,
+                format,
+            });
+        }
+        progress.clear();
+        let result = match result {
+            Ok((result, ..)) => result,
+            Err(TimeLimitError::Exceeded) => {
+                eprintln!(
+                    "warning: skipped file \"{}\", {}",
+                    path_to_shader.display(),
+                    FORMAT_TIME_EXCEEDED_REASON
+                );
+                continue;
+            }
+            Err(TimeLimitError::Panicked) if compact_output => {
+                println!(
+                    "{}: {}",
+                    path_to_shader.display(),
+                    FORMATTER_PANICKED_MESSAGE
+                );
+                return ExitCode::FAILURE;
+            }
+            Err(TimeLimitError::Panicked) => {
+                println!(
+                    "file \"{}\": {}",
+                    path_to_shader.display(),
+                    FORMATTER_PANICKED_MESSAGE
+                );
+                return ExitCode::FAILURE;
+            }
+        };
+        let output = match result {
             Ok(o) => o,
-            Err(msg) => {
-                println!("{}", msg);
+            Err(error) if compact_output => {
+                println!("{}", error.to_compact(&path_to_shader));
+                return ExitCode::FAILURE;
+            }
+            Err(error) => {
+                println!("{}", error);
                 return ExitCode::FAILURE;
             }
         };
 
-        if only_scan {
-            let diffs = diff::myers::lines(&file_content, &output);
-
-            let mut formatting_needed = false;
-            for diff in &diffs {
-                if let diff::Result::Left(_) = diff {
-                    formatting_needed = true;
-                    break;
-                } else if let diff::Result::Right(_) = diff {
-                    formatting_needed = true;
-                    break;
+        if report_unused_functions {
+            formatted_files.push((path_to_shader.clone(), output.clone()));
+        }
+
+        match mode {
+            FormatMode::Check => {
+                let diffs = diff::myers::lines(&file_content, &output);
+
+                let formatting_needed = diffs
+                    .iter()
+                    .any(|diff| !matches!(diff, diff::Result::Both(..)));
+
+                if formatting_needed && compact_output {
+                    // Point to the first changed line.
+                    let line = diffs
+                        .iter()
+                        .take_while(|diff| matches!(diff, diff::Result::Both(..)))
+                        .count()
+                        + 1;
+                    println!(
+                        "{}:{}:1: {}: formatting is needed",
+                        path_to_shader.display(),
+                        line,
+                        FORMATTING_NEEDED_RULE_NAME
+                    );
+                    return ExitCode::FAILURE;
+                } else if formatting_needed {
+                    println!("formatting is needed, see diff for before and after formatting:");
+                    for diff in diffs {
+                        match diff {
+                            diff::Result::Left(l) => println!("-{}", l),
+                            diff::Result::Both(l, _) => println!(" {}", l),
+                            diff::Result::Right(r) => println!("+{}", r),
+                        }
+                    }
+                    return ExitCode::FAILURE;
                 }
             }
-
-            if formatting_needed {
-                println!("formatting is needed, see diff for before and after formatting:");
-                for diff in diffs {
-                    match diff {
-                        diff::Result::Left(l) => println!("-{}", l),
-                        diff::Result::Both(l, _) => println!(" {}", l),
-                        diff::Result::Right(r) => println!("+{}", r),
+            FormatMode::Patch => {
+                print!(
+                    "{}",
+                    patch::unified_diff(&get_patch_path(&path_to_shader), &file_content, &output)
+                );
+            }
+            FormatMode::Stdout => print!("{}", output),
+            FormatMode::Write => {
+                // Write result to the file.
+                let mut file = match File::create(path_to_shader) {
+                    Ok(f) => f,
+                    Err(error) => {
+                        println!("failed to open the file for writing, error: {}", error);
+                        return ExitCode::FAILURE;
+                    }
+                };
+                match write!(file, "{}", output) {
+                    Ok(_) => {}
+                    Err(error) => {
+                        println!("failed to write to the file, error: {}", error);
+                        return ExitCode::FAILURE;
                     }
                 }
-                return ExitCode::FAILURE;
             }
+        }
+    }
+
+    // Show unused functions.
+    for (path, warning) in call_graph::find_unused_functions(&formatted_files) {
+        if compact_output {
+            println!("{}", warning.to_compact(&path));
         } else {
-            // Write result to the file.
-            let mut file = match File::create(path_to_shader) {
-                Ok(f) => f,
-                Err(error) => {
-                    println!("failed to open the file for writing, error: {}", error);
-                    return ExitCode::FAILURE;
-                }
+            println!("warning: file \"{}\", {}", path.display(), warning);
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Format(args) => {
+            let mode = if args.stdout {
+                FormatMode::Stdout
+            } else if args.emit_patch {
+                FormatMode::Patch
+            } else {
+                FormatMode::Write
             };
-            match write!(file, "{}", output) {
-                Ok(_) => {}
-                Err(error) => {
-                    println!("failed to write to the file, error: {}", error);
+            if let Some(output_path) = &args.export_renames {
+                return match export_renames(&args.files, output_path) {
+                    Ok(()) => ExitCode::SUCCESS,
+                    Err(error) => {
+                        println!("{}", error);
+                        ExitCode::FAILURE
+                    }
+                };
+            }
+            if args.fix_names {
+                if let Err(error) = fix_global_names(&args.files, args.dry_run) {
+                    println!("{}", error);
                     return ExitCode::FAILURE;
                 }
+                if args.dry_run {
+                    return ExitCode::SUCCESS;
+                }
+            }
+            format_files(
+                &args.files,
+                mode,
+                args.print_tokens,
+                &args.rule_set,
+                &args.editor_settings,
+                &args.report,
+            )
+        }
+        Command::Check(args) => format_files(
+            &args.files,
+            FormatMode::Check,
+            false,
+            &args.rule_set,
+            &args.editor_settings,
+            &args.report,
+        ),
+        Command::Init { preset } => init_config(preset.as_deref()),
+        Command::Explain { rule } => explain_rule(rule.as_deref()),
+        Command::DumpConfigSchema => {
+            println!("{:#}", Config::json_schema());
+            ExitCode::SUCCESS
+        }
+        Command::ReportBindings { files, json } => run_on_files(&files, |shader_paths, config| {
+            report_bindings(shader_paths, json, config.max_file_size)
+        }),
+        Command::PrintTokens { files } => run_on_files(&files, |shader_paths, config| {
+            print_parsed_files(shader_paths, config, false)
+        }),
+        Command::PrintAst { files } => run_on_files(&files, |shader_paths, config| {
+            print_parsed_files(shader_paths, config, true)
+        }),
+        Command::Bench { files } => run_on_files(&files, bench_files),
+        Command::Hook { output_format } => {
+            format_staged_files(output_format == OutputFormat::Compact)
+        }
+        Command::InstallHook => install_pre_commit_hook(),
+        Command::Daemon => {
+            match daemon::Daemon::default().run(std::io::stdin().lock(), std::io::stdout()) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("failed to handle requests, error: {}", e);
+                    ExitCode::FAILURE
+                }
             }
         }
     }
-
-    ExitCode::SUCCESS
 }
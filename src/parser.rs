@@ -1,7 +1,13 @@
 use chumsky::{input::ValueInput, prelude::*};
+use serde_json::{json, Value};
 
 pub type Span = SimpleSpan<usize>;
 
+/// Converts a span to a JSON object with byte offsets of the span.
+pub fn span_to_json(span: Span) -> Value {
+    json!({ "start": span.start, "end": span.end })
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Type {
     Void,
@@ -12,6 +18,7 @@ pub enum Type {
     Matrix,
     Array,
     Texture,
+    Buffer,
     Sampler,
     Custom,
 }
@@ -31,26 +38,90 @@ pub enum Token<'src> {
     Other(char),
 }
 
+/// Piece of text used by the formatting pass that runs before the code is parsed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LayoutToken<'src> {
+    /// `\n` (`\r` is a part of `Whitespace`).
+    NewLine,
+    /// Spaces, tabs and `\r`s.
+    Whitespace(&'src str),
+    /// Comment that starts with `//` (including `//`, without the line break).
+    LineComment(&'src str),
+    /// Comment like `/* ... */` (including `/*` and `*/`), may contain line breaks.
+    BlockComment(&'src str),
+    /// Identifier, keyword or a number (without the `.` of floats).
+    Word(&'src str),
+    /// Any ASCII punctuation character.
+    Punct(char),
+    /// `-`, `+`, `!` or `~` used as a unary operator (like in `-x` or `!enabled`).
+    UnaryOperator(char),
+}
+
 impl std::fmt::Display for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
+impl Token<'_> {
+    /// Converts the token to a JSON object with the kind and the value of the token.
+    pub fn to_json(&self) -> Value {
+        let (kind, value) = match self {
+            Token::Bool(value) => ("Bool", json!(value)),
+            Token::Integer(value) => ("Integer", json!(value)),
+            Token::Float(value) => ("Float", json!(value)),
+            Token::Op(value) => ("Op", json!(value)),
+            Token::Ctrl(value) => ("Ctrl", json!(value)),
+            Token::TypeName(value) => ("TypeName", json!(format!("{:?}", value))),
+            Token::Ident(value) => ("Ident", json!(value)),
+            Token::Comment(value) => ("Comment", json!(value)),
+            Token::Keyword(value) => ("Keyword", json!(value)),
+            Token::Preprocessor(value) => ("Preprocessor", json!(value)),
+            Token::Other(value) => ("Other", json!(value)),
+        };
+
+        json!({ "kind": kind, "value": value })
+    }
+}
+
 /// Groups parsed information about a field of a struct.
 #[derive(Clone, Debug, PartialEq)]
 pub struct StructField<'src> {
     pub _type: Type,
+    /// Span of the field's type (like `float4` in `float4 color;`), empty for instances of
+    /// structs declared in place (like `b` in `struct { float a; } b;`).
+    pub type_span: Span,
     pub name: &'src str,
+    pub name_span: Span,
+    /// HLSL semantic of the field (like `SV_Position` in `float4 pos : SV_Position;`).
+    pub semantic: Option<&'src str>,
     pub docs: String,
 }
 
 /// Groups parsed information about a struct.
 #[derive(Clone, Debug, PartialEq)]
 pub struct StructInfo<'src> {
+    /// Name of the struct (empty for anonymous structs like `struct { float a; } b;`).
     pub name: &'src str,
+    pub name_span: Span,
+    /// Names (and spans of names) of base types (like `Base` in `struct Derived : Base`).
+    pub bases: Vec<(&'src str, Span)>,
     pub fields: Vec<StructField<'src>>,
+    /// Member functions (of HLSL structs, classes and interfaces).
+    pub methods: Vec<FunctionInfo<'src>>,
+    /// Structs declared inside of this struct (fields that use them are stored in `fields`).
+    pub nested: Vec<StructInfo<'src>>,
     pub docs: String,
+    /// Span of the documentation comments (empty if there are no docs).
+    pub docs_span: Span,
+    /// Instance name of a GLSL interface block (like `u_lights` in `uniform Lights { ... } u_lights[4];`).
+    pub instance: Option<StructField<'src>>,
+    /// Value of the `binding` qualifier from GLSL `layout(...)` (if specified).
+    pub binding: Option<i64>,
+    /// Value of the `set` qualifier from Vulkan GLSL `layout(...)` (if specified).
+    pub set: Option<i64>,
+    /// Whether this is a Vulkan push constant block (like `layout(push_constant) uniform Constants { ... }`).
+    pub is_push_constant: bool,
 }
 
 /// Groups parsed information about a function argument.
@@ -58,23 +129,92 @@ pub struct StructInfo<'src> {
 pub struct FuncArgument<'src> {
     pub _type: Type,
     pub name: &'src str,
-    pub is_using_semantic: bool,
+    pub name_span: Span,
+    /// HLSL semantic of the argument (like `SV_Position` in `float4 pos : SV_Position`).
+    pub semantic: Option<&'src str>,
+    /// Modifiers specified before the type (like `out` or `inout`).
+    pub modifiers: Vec<&'src str>,
+    /// Sizes of array dimensions (like `[3]` in `VSOut input[3]`), `None` for sizes that are
+    /// not integer literals (like `[MAX_LIGHTS]`), empty if the argument is not an array.
+    pub array_dimensions: Vec<Option<i64>>,
+    /// Whether the argument has a default value (like `float scale = 1.0`).
+    pub has_default_value: bool,
 }
 
 /// Groups parsed information about a function.
 #[derive(Clone, Debug, PartialEq)]
 pub struct FunctionInfo<'src> {
     pub name: &'src str,
+    pub name_span: Span,
     pub args: Vec<FuncArgument<'src>>,
     pub return_type: Type,
+    /// HLSL semantic of the return value (like `SV_Target` in `float4 main() : SV_Target`).
+    pub return_semantic: Option<&'src str>,
+    /// Names of HLSL attributes of the function (like `numthreads` in `[numthreads(8, 8, 1)]`).
+    pub attributes: Vec<&'src str>,
     pub docs: String,
+    /// Span of the documentation comments (empty if there are no docs).
+    pub docs_span: Span,
+    /// Whether this is a declaration without a body (like `float foo(float a);`).
+    pub is_declaration: bool,
+}
+
+/// Groups parsed information about a value of an enum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumValue<'src> {
+    pub name: &'src str,
+    pub name_span: Span,
+    /// Span of the whole value (including docs and initializer).
+    pub span: Span,
+    pub docs: String,
+}
+
+/// Groups parsed information about an enum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumInfo<'src> {
+    pub name: &'src str,
+    pub name_span: Span,
+    pub values: Vec<EnumValue<'src>>,
+    /// Span of the enum body (from `{` to `}`).
+    pub body_span: Span,
+    pub docs: String,
+}
+
+/// Groups parsed information about an HLSL constant buffer (`cbuffer` or `tbuffer`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstantBufferInfo<'src> {
+    pub name: &'src str,
+    pub name_span: Span,
+    /// Fields of the buffer (including fields that use nested structs).
+    pub fields: Vec<StructField<'src>>,
+    /// Structs declared inside of the buffer.
+    pub nested: Vec<StructInfo<'src>>,
+    /// Whether the buffer has the `[[vk::push_constant]]` attribute.
+    pub is_push_constant: bool,
+}
+
+/// A member of a struct/class/interface.
+#[derive(Clone, Debug, PartialEq)]
+enum StructMember<'src> {
+    Fields(Vec<StructField<'src>>),
+    Method(FunctionInfo<'src>),
+    Struct(StructInfo<'src>),
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ComplexToken<'src> {
-    VariableDeclaration(Type, &'src str),
+    /// Type, name and span of the name of every variable declared in a statement.
+    VariableDeclaration(Vec<(Type, &'src str, Span)>),
     Struct(StructInfo<'src>),
+    Enum(EnumInfo<'src>),
+    ConstantBuffer(ConstantBufferInfo<'src>),
     Function(FunctionInfo<'src>),
+    /// Type from a GLSL default precision statement (like `precision mediump float;`).
+    DefaultPrecision(Type),
+    /// Header of a `switch` statement (like `switch (value)`), the body is parsed separately.
+    Switch {
+        has_default: bool,
+    },
     Other(Token<'src>),
 }
 
@@ -84,57 +224,304 @@ impl std::fmt::Display for ComplexToken<'_> {
     }
 }
 
+impl StructField<'_> {
+    /// Converts the field to a JSON object (spans are byte offsets in the parsed text).
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": format!("{:?}", self._type),
+            "typeSpan": span_to_json(self.type_span),
+            "name": self.name,
+            "nameSpan": span_to_json(self.name_span),
+            "semantic": self.semantic,
+            "docs": self.docs,
+        })
+    }
+}
+
+impl StructInfo<'_> {
+    /// Converts the struct to a JSON object (spans are byte offsets in the parsed text).
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "nameSpan": span_to_json(self.name_span),
+            "bases": self
+                .bases
+                .iter()
+                .map(|(name, span)| json!({ "name": name, "span": span_to_json(*span) }))
+                .collect::<Vec<_>>(),
+            "fields": self.fields.iter().map(StructField::to_json).collect::<Vec<_>>(),
+            "methods": self.methods.iter().map(FunctionInfo::to_json).collect::<Vec<_>>(),
+            "nested": self.nested.iter().map(StructInfo::to_json).collect::<Vec<_>>(),
+            "docs": self.docs,
+            "instance": self.instance.as_ref().map(StructField::to_json),
+            "binding": self.binding,
+            "set": self.set,
+            "isPushConstant": self.is_push_constant,
+        })
+    }
+}
+
+impl FuncArgument<'_> {
+    /// Converts the argument to a JSON object (spans are byte offsets in the parsed text).
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": format!("{:?}", self._type),
+            "name": self.name,
+            "nameSpan": span_to_json(self.name_span),
+            "semantic": self.semantic,
+            "modifiers": self.modifiers,
+            "arrayDimensions": self.array_dimensions,
+            "hasDefaultValue": self.has_default_value,
+        })
+    }
+}
+
+impl FunctionInfo<'_> {
+    /// Converts the function to a JSON object (spans are byte offsets in the parsed text).
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "nameSpan": span_to_json(self.name_span),
+            "args": self.args.iter().map(FuncArgument::to_json).collect::<Vec<_>>(),
+            "returnType": format!("{:?}", self.return_type),
+            "returnSemantic": self.return_semantic,
+            "attributes": self.attributes,
+            "docs": self.docs,
+            "isDeclaration": self.is_declaration,
+        })
+    }
+}
+
+impl EnumInfo<'_> {
+    /// Converts the enum to a JSON object (spans are byte offsets in the parsed text).
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "nameSpan": span_to_json(self.name_span),
+            "values": self
+                .values
+                .iter()
+                .map(|value| {
+                    json!({
+                        "name": value.name,
+                        "nameSpan": span_to_json(value.name_span),
+                        "span": span_to_json(value.span),
+                        "docs": value.docs,
+                    })
+                })
+                .collect::<Vec<_>>(),
+            "bodySpan": span_to_json(self.body_span),
+            "docs": self.docs,
+        })
+    }
+}
+
+impl ConstantBufferInfo<'_> {
+    /// Converts the constant buffer to a JSON object (spans are byte offsets in the parsed text).
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "nameSpan": span_to_json(self.name_span),
+            "fields": self.fields.iter().map(StructField::to_json).collect::<Vec<_>>(),
+            "nested": self.nested.iter().map(StructInfo::to_json).collect::<Vec<_>>(),
+            "isPushConstant": self.is_push_constant,
+        })
+    }
+}
+
+impl ComplexToken<'_> {
+    /// Converts the token to a JSON object with the kind of the token and parsed information
+    /// (spans are byte offsets in the parsed text).
+    pub fn to_json(&self) -> Value {
+        match self {
+            ComplexToken::VariableDeclaration(variables) => json!({
+                "kind": "VariableDeclaration",
+                "variables": variables
+                    .iter()
+                    .map(|(_type, name, span)| {
+                        json!({
+                            "type": format!("{:?}", _type),
+                            "name": name,
+                            "nameSpan": span_to_json(*span),
+                        })
+                    })
+                    .collect::<Vec<_>>(),
+            }),
+            ComplexToken::Struct(info) => json!({ "kind": "Struct", "struct": info.to_json() }),
+            ComplexToken::Enum(info) => json!({ "kind": "Enum", "enum": info.to_json() }),
+            ComplexToken::ConstantBuffer(info) => {
+                json!({ "kind": "ConstantBuffer", "constantBuffer": info.to_json() })
+            }
+            ComplexToken::Function(info) => {
+                json!({ "kind": "Function", "function": info.to_json() })
+            }
+            ComplexToken::DefaultPrecision(_type) => json!({
+                "kind": "DefaultPrecision",
+                "type": format!("{:?}", _type),
+            }),
+            ComplexToken::Switch { has_default } => json!({
+                "kind": "Switch",
+                "hasDefault": has_default,
+            }),
+            ComplexToken::Other(token) => json!({ "kind": "Other", "token": token.to_json() }),
+        }
+    }
+}
+
+/// Characters that can be used as suffixes of integer literals (like `3u`).
+const INTEGER_SUFFIXES: [char; 4] = ['u', 'U', 'l', 'L'];
+
+/// Characters that can be used as suffixes of float literals (like `1.0f` or `2.5h`).
+const FLOAT_SUFFIXES: [char; 6] = ['f', 'F', 'h', 'H', 'l', 'L'];
+
+/// Converts text of an integer literal (that may have a sign, a `0x`/`0` prefix and a suffix)
+/// to its value. Values that don't fit are clamped, literals with a `0` prefix that have
+/// non-octal digits (like `08`) are read as decimal.
+fn integer_literal_to_value(literal: &str) -> i64 {
+    let (is_negative, literal) = match literal.strip_prefix('-') {
+        Some(literal) => (true, literal),
+        None => (false, literal),
+    };
+    let digits = literal.trim_end_matches(INTEGER_SUFFIXES);
+
+    let value = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16)
+    } else if digits.len() > 1 && digits.starts_with('0') && !digits.contains(['8', '9']) {
+        u64::from_str_radix(&digits[1..], 8)
+    } else {
+        digits.parse::<u64>()
+    };
+    let value = value.unwrap_or(u64::MAX).min(i64::MAX as u64) as i64;
+
+    if is_negative {
+        -value
+    } else {
+        value
+    }
+}
+
 pub fn token_parser<'src>(
 ) -> impl Parser<'src, &'src str, Vec<(Token<'src>, Span)>, extra::Err<Rich<'src, char, Span>>> {
-    // A parser for integers
+    // A parser for integers (decimal, hexadecimal and octal) with optional suffixes like `u`.
+    // All digits after a leading zero are consumed (even if not octal like in `08`) so that
+    // the literal is not split into multiple tokens.
     let integer = just('-')
         .or_not()
-        .then(text::int(10))
+        .then(
+            just("0x")
+                .or(just("0X"))
+                .then(text::digits(16))
+                .ignored()
+                .or(just('0').then(text::digits(10)).ignored())
+                .or(text::int(10).ignored()),
+        )
+        .then(one_of("uUlL").repeated())
         .then_ignore(just('.').not())
         .to_slice()
-        .map(|value: &str| Token::Integer(value.parse().unwrap()))
+        .map(|value: &str| Token::Integer(integer_literal_to_value(value)))
         .padded();
 
-    // A parser for floats
+    // A parser for floats (including scientific notation) with optional suffixes like `f` or `h`.
+    let exponent = one_of("eE")
+        .then(one_of("+-").or_not())
+        .then(text::digits(10));
     let float = just('-')
         .or_not()
-        .then(text::int(10))
-        .then(just('.'))
-        .then(text::digits(10).or_not())
+        .then(
+            text::digits(10)
+                .then(just('.'))
+                .then(text::digits(10).or_not())
+                .then(exponent.or_not())
+                .ignored()
+                .or(just('.')
+                    .then(text::digits(10))
+                    .then(exponent.or_not())
+                    .ignored())
+                .or(text::digits(10).then(exponent).ignored()),
+        )
+        .then(one_of("fFhHlL").repeated())
         .to_slice()
-        .map(|value: &str| Token::Float(value.parse().unwrap()))
+        .map(|value: &str| {
+            Token::Float(
+                value
+                    .trim_end_matches(FLOAT_SUFFIXES)
+                    .parse()
+                    .unwrap_or_default(),
+            )
+        })
         .padded();
 
-    // A parser for operators
-    let single_char_operator = one_of("+*-/!=%|~&")
-        .repeated()
-        .at_least(1)
-        .to_slice()
-        .map(|s: &str| Token::Op(s));
-    // some special cases that might conflic with other parsers
-    let multi_char_operator = just(">=").or(just("<=")).map(|s: &str| Token::Op(s));
+    // A parser for operators (longer operators are checked first).
+    // Note that `:` of the ternary operator is parsed as a control character because it's also
+    // used for HLSL semantics.
+    let operator = choice((
+        just("<<="),
+        just(">>="),
+        just("&&"),
+        just("||"),
+        just("<<"),
+        just(">>"),
+        just("++"),
+        just("--"),
+        just("+="),
+        just("-="),
+        just("*="),
+        just("/="),
+        just("%="),
+        just("&="),
+        just("|="),
+        just("^="),
+        just("=="),
+        just("!="),
+        just(">="),
+        just("<="),
+    ))
+    .or(one_of("+-*/%=!~&|^?").to_slice())
+    .map(Token::Op);
 
     // A parser for control characters (delimiters, semicolons, etc.)
     let ctrl = one_of("()[]{};,:<>.").map(Token::Ctrl);
 
-    // A parser for identifiers and keywords
-    let ident = text::ascii::ident().map(|ident: &str| match ident {
-        "true" => Token::Bool(true),
-        "false" => Token::Bool(false),
-        "void" => Token::TypeName(Type::Void),
-        "float" | "half" | "double" => Token::TypeName(Type::Float),
-        "int" | "uint" | "dword" => Token::TypeName(Type::Integer),
-        "bool" => Token::TypeName(Type::Bool),
-        "float4" | "vec4" | "float2" | "vec2" | "float3" | "vec3" | "uint4" | "uvec4" | "uint3"
-        | "uvec3" | "uint2" | "uvec2" => Token::TypeName(Type::Vector),
-        "float4x4" | "mat4x4" | "float3x3" | "mat3x3" | "float2x2" | "mat2x2" => {
-            Token::TypeName(Type::Matrix)
-        }
-        "Texture2D" | "sampler2D" => Token::TypeName(Type::Texture),
-        "SamplerState" | "SamplerComparisonState" => Token::TypeName(Type::Sampler),
-        "return" => Token::Keyword("return"),
-        _ => Token::Ident(ident),
-    });
+    // A parser for identifiers and keywords (identifiers can have non-ASCII letters).
+    let ident = any()
+        .filter(|c: &char| c.is_alphabetic() || *c == '_')
+        .then(
+            any()
+                .filter(|c: &char| c.is_alphanumeric() || *c == '_')
+                .repeated(),
+        )
+        .to_slice()
+        .map(|ident: &str| match ident {
+            "true" => Token::Bool(true),
+            "false" => Token::Bool(false),
+            "void" => Token::TypeName(Type::Void),
+            "float" | "half" | "double" => Token::TypeName(Type::Float),
+            "int" | "uint" | "dword" => Token::TypeName(Type::Integer),
+            "bool" => Token::TypeName(Type::Bool),
+            "float4" | "vec4" | "float2" | "vec2" | "float3" | "vec3" | "uint4" | "uvec4"
+            | "uint3" | "uvec3" | "uint2" | "uvec2" => Token::TypeName(Type::Vector),
+            "float4x4" | "mat4x4" | "float3x3" | "mat3x3" | "float2x2" | "mat2x2" => {
+                Token::TypeName(Type::Matrix)
+            }
+            "Texture1D" | "Texture1DArray" | "Texture2D" | "Texture2DArray" | "Texture2DMS"
+            | "Texture3D" | "TextureCube" | "TextureCubeArray" | "RWTexture1D" | "RWTexture2D"
+            | "RWTexture2DArray" | "RWTexture3D" | "sampler2D" => Token::TypeName(Type::Texture),
+            "Buffer"
+            | "RWBuffer"
+            | "StructuredBuffer"
+            | "RWStructuredBuffer"
+            | "ByteAddressBuffer"
+            | "RWByteAddressBuffer"
+            | "AppendStructuredBuffer"
+            | "ConsumeStructuredBuffer"
+            | "ConstantBuffer" => Token::TypeName(Type::Buffer),
+            "SamplerState" | "SamplerComparisonState" => Token::TypeName(Type::Sampler),
+            "return" => Token::Keyword("return"),
+            _ => Token::Ident(ident),
+        });
 
     // Parsers for preprocessor directives.
     let preprocessor_if = just("#if")
@@ -204,8 +591,7 @@ pub fn token_parser<'src>(
         .or(integer)
         .or(comment)
         .or(preprocessor)
-        .or(single_char_operator)
-        .or(multi_char_operator)
+        .or(operator)
         .or(ctrl)
         .or(ident)
         .or(any().map(Token::Other));
@@ -217,161 +603,924 @@ pub fn token_parser<'src>(
         .collect()
 }
 
+/// Returns a parser that splits text into layout tokens, unlike `token_parser` it keeps
+/// whitespace and comments and never fails so that the formatter could print the text back.
+pub fn layout_token_parser<'src>(
+) -> impl Parser<'src, &'src str, Vec<(LayoutToken<'src>, Span)>, extra::Err<Rich<'src, char, Span>>>
+{
+    let new_line = just('\n').to(LayoutToken::NewLine);
+    let whitespace = one_of(" \t\r")
+        .repeated()
+        .at_least(1)
+        .to_slice()
+        .map(LayoutToken::Whitespace);
+    let line_comment = just("//")
+        .then(any().and_is(just('\n').not()).repeated())
+        .to_slice()
+        .map(LayoutToken::LineComment);
+    let block_comment = just("/*")
+        .then(any().and_is(just("*/").not()).repeated())
+        .then(just("*/").or_not())
+        .to_slice()
+        .map(LayoutToken::BlockComment);
+    let word = any()
+        .filter(|c: &char| !c.is_ascii_punctuation() && !matches!(c, ' ' | '\t' | '\r' | '\n'))
+        .repeated()
+        .at_least(1)
+        .to_slice()
+        .map(LayoutToken::Word);
+    let punct = any().map(LayoutToken::Punct);
+
+    choice((
+        new_line,
+        whitespace,
+        line_comment,
+        block_comment,
+        word,
+        punct,
+    ))
+    .map_with(|t, extra| (t, extra.span()))
+    .repeated()
+    .collect()
+}
+
+/// Replaces `-`, `+`, `!` and `~` layout tokens that are used as unary operators (like in `-x`)
+/// with `UnaryOperator` tokens (binary operators, `++`, `--` and compound assignments are not changed).
+pub fn mark_unary_operators(tokens: Vec<(LayoutToken<'_>, Span)>) -> Vec<(LayoutToken<'_>, Span)> {
+    let mut output: Vec<(LayoutToken<'_>, Span)> = Vec::with_capacity(tokens.len());
+    // Whether the last token (except whitespace and comments) ends an operand (like a name or `)`).
+    let mut prev_token_ends_operand = false;
+
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some((token, span)) = tokens.next() {
+        let next_token = tokens.peek().map(|(token, _)| token);
+        let prev_token = output.last().map(|(token, _)| token);
+
+        let token = match token {
+            LayoutToken::Punct(_char @ ('-' | '+' | '!' | '~')) => {
+                // Skip `--`, `++`, `-=`, `!=` and etc. (operands before and after such
+                // operators are handled as if there was no operator).
+                let is_part_of_longer_operator = match (prev_token, next_token) {
+                    (_, Some(LayoutToken::Punct('='))) => true,
+                    (_, Some(LayoutToken::Punct(next))) if *next == _char => {
+                        matches!(_char, '-' | '+')
+                    }
+                    (Some(LayoutToken::Punct(prev)), _) if *prev == _char => {
+                        matches!(_char, '-' | '+')
+                    }
+                    _ => false,
+                };
+                if is_part_of_longer_operator {
+                    output.push((token, span));
+                    continue;
+                }
+
+                if matches!(_char, '!' | '~') || !prev_token_ends_operand {
+                    LayoutToken::UnaryOperator(_char)
+                } else {
+                    token
+                }
+            }
+            _ => token,
+        };
+
+        match token {
+            LayoutToken::Whitespace(_)
+            | LayoutToken::NewLine
+            | LayoutToken::LineComment(_)
+            | LayoutToken::BlockComment(_) => {}
+            LayoutToken::Word(word) => {
+                prev_token_ends_operand = !matches!(word, "return" | "case" | "else" | "do");
+            }
+            LayoutToken::Punct(_char) => {
+                prev_token_ends_operand = matches!(_char, ')' | ']' | '.' | '"');
+            }
+            LayoutToken::UnaryOperator(_) => prev_token_ends_operand = false,
+        }
+
+        output.push((token, span));
+    }
+
+    output
+}
+
+/// Splits `>>` operators that close nested template argument lists (like in
+/// `Buffer<vector<float, 4>>`) into two `>` tokens so that the complex token parser
+/// could parse them.
+pub fn split_template_closing_shifts(tokens: Vec<(Token<'_>, Span)>) -> Vec<(Token<'_>, Span)> {
+    let mut output = Vec::with_capacity(tokens.len());
+    let mut angle_depth: usize = 0;
+    let mut prev_token_is_name = false;
+
+    for (token, span) in tokens {
+        match token {
+            Token::Ctrl('<') if prev_token_is_name => angle_depth += 1,
+            Token::Ctrl('>') => angle_depth = angle_depth.saturating_sub(1),
+            Token::Ctrl(';' | '{' | '}' | '(' | ')') => angle_depth = 0,
+            Token::Op(">>") if angle_depth >= 2 => {
+                angle_depth -= 2;
+                output.push((Token::Ctrl('>'), Span::from(span.start..span.start + 1)));
+                output.push((Token::Ctrl('>'), Span::from(span.start + 1..span.end)));
+                prev_token_is_name = false;
+                continue;
+            }
+            _ => {}
+        }
+
+        prev_token_is_name = matches!(token, Token::Ident(_) | Token::TypeName(_));
+        output.push((token, span));
+    }
+
+    output
+}
+
+/// Looks for names of types defined in the specified tokens (structs, classes, interfaces, enums
+/// and typedefs) and returns them with the type they represent.
+pub fn collect_type_names(tokens: &[(Token<'_>, Span)]) -> Vec<(String, Type)> {
+    let mut types = Vec::new();
+
+    for (index, (token, _)) in tokens.iter().enumerate() {
+        match token {
+            Token::Ident("struct" | "class" | "interface" | "enum") => {
+                // Skip `class` in `enum class`.
+                let mut name_index = index + 1;
+                if let Some((Token::Ident("class" | "struct"), _)) = tokens.get(name_index) {
+                    name_index += 1;
+                }
+                if let Some((Token::Ident(name), _)) = tokens.get(name_index) {
+                    types.push((name.to_string(), Type::Custom));
+                }
+            }
+            Token::Ident("typedef") => {
+                // Find the end of the statement, the name is right before it.
+                let Some(end) = tokens[index..]
+                    .iter()
+                    .position(|(token, _)| *token == Token::Ctrl(';'))
+                else {
+                    continue;
+                };
+                let statement = &tokens[index + 1..index + end];
+                if statement
+                    .iter()
+                    .any(|(token, _)| *token == Token::Ctrl('{'))
+                {
+                    continue; // typedefs of anonymous structs are not supported
+                }
+                let _type = match statement.first() {
+                    Some((Token::TypeName(_type), _)) => *_type,
+                    _ => Type::Custom,
+                };
+                if let Some((Token::Ident(name), _)) = statement.last() {
+                    types.push((name.to_string(), _type));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    types
+}
+
+/// Creates a parser for complex tokens.
+///
+/// # Arguments
+/// - `known_types` Names of custom types (defined in the code or in included files) with the type
+///   they represent, used to detect variable declarations that use these types.
 pub fn complex_token_parser<'src, I>(
+    known_types: Vec<(String, Type)>,
 ) -> impl Parser<'src, I, Vec<(ComplexToken<'src>, Span)>, extra::Err<Rich<'src, Token<'src>>>>
 where
     I: ValueInput<'src, Token = Token<'src>, Span = SimpleSpan>,
 {
     let std_var_type = select! { Token::TypeName(t) => t };
     let ident = select! { Token::Ident(ident) => ident };
+    let spanned_ident = ident.map_with(|name, extra| (name, extra.span()));
     let comment = select! { Token::Comment(c) => c};
     let token = select! { token => token };
 
-    // A parser for GLSL `layout` keyword.
+    // A parser for template argument lists like `<float4>` or `<vector<float, 4> >`.
+    let template_args = recursive(|template_args| {
+        just(Token::Ctrl('<'))
+            .ignore_then(
+                template_args
+                    .or(none_of([
+                        Token::Ctrl('<'),
+                        Token::Ctrl('>'),
+                        Token::Ctrl(';'),
+                        Token::Ctrl('{'),
+                        Token::Ctrl('}'),
+                    ])
+                    .ignored())
+                    .repeated(),
+            )
+            .then_ignore(just(Token::Ctrl('>')))
+            .ignored()
+    });
+
+    // A parser for standard types that may have template arguments like `Texture2D<float4>`.
+    let std_var_type = std_var_type.then_ignore(template_args.clone().or_not());
+
+    // A parser for custom (user) types that may have template arguments.
+    let known_types_copy = known_types.clone();
+    let custom_type = ident
+        .then_ignore(template_args.clone().or_not())
+        .map(move |name| {
+            match known_types_copy
+                .iter()
+                .find(|(known_name, _)| known_name == name)
+            {
+                Some((_, _type)) => *_type,
+                None => Type::Custom,
+            }
+        });
+
+    // A parser for custom types that were defined in the code or in included files.
+    let known_type = ident
+        .try_map(move |name, span| {
+            match known_types
+                .iter()
+                .find(|(known_name, _)| known_name == name)
+            {
+                Some((_, _type)) => Ok(*_type),
+                None => Err(Rich::custom(span, "unknown type")),
+            }
+        })
+        .then_ignore(template_args.clone().or_not());
+
+    // A parser for HLSL 2021 template declarations like `template<typename T>`.
+    let template_declaration = just(Token::Ident("template")).then(template_args.clone());
+
+    // A parser for a single GLSL layout qualifier like `std140`, `binding = 0` or
+    // `local_size_x_id = SIZE_ID` (values that are not integer literals are ignored).
+    let layout_qualifier = ident.then(
+        just(Token::Op("="))
+            .ignore_then(select! { Token::Integer(value) => Some(value) }.or(ident.map(|_| None)))
+            .or_not()
+            .map(Option::flatten),
+    );
+
+    // A parser for GLSL `layout` keyword, returns values of `set` and `binding` (if specified)
+    // and whether the `push_constant` qualifier is specified.
     let layout = just(Token::Ident("layout"))
-        .ignore_then(just(Token::Ctrl('(')))
-        .ignore_then(ident.then(just(Token::Ctrl(','))).or_not()) // for `std140` and etc
-        .ignore_then(just(Token::Ident("binding")))
-        .ignore_then(just(Token::Op("=")))
-        .ignore_then(select! { Token::Integer(ident) => ident })
-        .ignore_then(just(Token::Ctrl(')')));
-
-    // A parser for struct fields.
+        .ignore_then(
+            layout_qualifier
+                .separated_by(just(Token::Ctrl(',')))
+                .collect::<Vec<_>>()
+                .delimited_by(just(Token::Ctrl('(')), just(Token::Ctrl(')'))),
+        )
+        .map(|qualifiers| {
+            let find_value = |qualifier: &str| {
+                qualifiers
+                    .iter()
+                    .find_map(|(name, value)| if *name == qualifier { *value } else { None })
+            };
+            (
+                find_value("set"),
+                find_value("binding"),
+                qualifiers.iter().any(|(name, _)| *name == "push_constant"),
+            )
+        });
+
+    // A parser for GLSL precision qualifiers.
+    let precision_qualifier = just(Token::Ident("highp"))
+        .or(just(Token::Ident("mediump")))
+        .or(just(Token::Ident("lowp")));
+
+    // A parser for GLSL default precision statements like `precision mediump float;`.
+    let default_precision = just(Token::Ident("precision"))
+        .ignore_then(precision_qualifier.clone())
+        .ignore_then(std_var_type.clone())
+        .then_ignore(just(Token::Ctrl(';')))
+        .map(ComplexToken::DefaultPrecision);
+
+    // A parser for GLSL memory qualifiers of buffers.
+    let memory_qualifier = just(Token::Ident("readonly"))
+        .or(just(Token::Ident("writeonly")))
+        .or(just(Token::Ident("coherent")))
+        .or(just(Token::Ident("volatile")))
+        .or(just(Token::Ident("restrict")));
+
+    // A parser for code inside of brackets like `(...)`, `[...]` or `{...}` (can be nested).
+    let brackets = recursive(|brackets| {
+        let inside = brackets
+            .or(none_of([
+                Token::Ctrl('('),
+                Token::Ctrl(')'),
+                Token::Ctrl('['),
+                Token::Ctrl(']'),
+                Token::Ctrl('{'),
+                Token::Ctrl('}'),
+                Token::Ctrl(';'),
+            ])
+            .ignored())
+            .repeated();
+
+        inside
+            .clone()
+            .delimited_by(just(Token::Ctrl('(')), just(Token::Ctrl(')')))
+            .or(inside
+                .clone()
+                .delimited_by(just(Token::Ctrl('[')), just(Token::Ctrl(']'))))
+            .or(inside.delimited_by(just(Token::Ctrl('{')), just(Token::Ctrl('}'))))
+            .ignored()
+    });
+
+    // A parser for a name of a variable with everything that follows it until the next
+    // declarator (like `a[3] : register(t0) = {1, 2, 3}` in `float a[3] = {1, 2, 3}, b;`),
+    // returns the name, whether the variable is an array and HLSL semantic (if specified).
+    let declarator = spanned_ident
+        .then(just(Token::Ctrl('[')).rewind().or_not()) // for arrays
+        .then_ignore(brackets.clone().repeated())
+        .then(
+            just(Token::Ctrl(':'))
+                .ignore_then(
+                    ident.filter(|name: &&str| *name != "register" && *name != "packoffset"),
+                )
+                .or_not(),
+        )
+        .then_ignore(
+            brackets
+                .clone()
+                .or(none_of([
+                    Token::Ctrl(','),
+                    Token::Ctrl(';'),
+                    Token::Ctrl('('),
+                    Token::Ctrl(')'),
+                    Token::Ctrl('['),
+                    Token::Ctrl(']'),
+                    Token::Ctrl('{'),
+                    Token::Ctrl('}'),
+                ])
+                .ignored())
+                .repeated(),
+        )
+        .map(|(((name, name_span), opt_array), semantic)| {
+            (name, name_span, opt_array.is_some(), semantic)
+        });
+
+    // A parser for a list of declarators like `a, b = 1, c[2]`.
+    let declarators = declarator
+        .separated_by(just(Token::Ctrl(',')))
+        .at_least(1)
+        .collect::<Vec<_>>();
+
+    // A parser for struct fields (a single statement can declare multiple fields).
     let field = comment
         .repeated()
         .collect::<Vec<&str>>()
-        .then(std_var_type.or(ident.map(|_| Type::Custom)))
-        .then(ident)
-        .then(just(Token::Ctrl('[')).or_not()) // for arrays
-        .then_ignore(none_of(Token::Ctrl(';')).repeated())
+        .then_ignore(layout.clone().or_not())
+        .then_ignore(precision_qualifier.clone().or_not())
+        .then(
+            std_var_type
+                .clone()
+                .or(custom_type.clone())
+                .map_with(|_type, extra| (_type, extra.span())),
+        )
+        .then(declarators.clone())
         .then_ignore(just(Token::Ctrl(';')))
-        .map(|(((opt_comments, _type), name), opt_array)| {
-            if opt_array.is_some() {
-                StructField {
-                    _type: Type::Array,
-                    name,
-                    docs: opt_comments.concat(),
-                }
-            } else {
-                StructField {
-                    _type,
+        .map(|((opt_comments, (_type, type_span)), declarators)| {
+            let docs = opt_comments.concat();
+            declarators
+                .into_iter()
+                .map(|(name, name_span, is_array, semantic)| StructField {
+                    _type: if is_array { Type::Array } else { _type },
+                    type_span,
                     name,
-                    docs: opt_comments.concat(),
-                }
-            }
+                    name_span,
+                    semantic,
+                    docs: docs.clone(),
+                })
+                .collect::<Vec<_>>()
         });
 
-    // A parser for variable declaration.
-    let variable_declaration = std_var_type
-        .then(ident)
-        .then(just(Token::Ctrl('[')).or_not())
-        .then_ignore(just(Token::Op("=")).or_not())
-        .then_ignore(none_of(Token::Ctrl(';')).repeated())
+    // A parser for variable declaration (a single statement can declare multiple variables).
+    let variable_declaration = precision_qualifier
+        .clone()
+        .or_not()
+        .ignore_then(std_var_type.clone())
+        .or(known_type)
+        .or(ident
+            .then_ignore(template_args.clone())
+            .map(|_| Type::Custom))
+        .then(declarators)
         .then_ignore(just(Token::Ctrl(';')).or_not())
-        .map(|((t, name), opt_array)| {
-            if opt_array.is_some() {
-                ComplexToken::VariableDeclaration(Type::Array, name)
+        .map(|(_type, declarators)| {
+            ComplexToken::VariableDeclaration(
+                declarators
+                    .into_iter()
+                    .map(|(name, name_span, is_array, _)| {
+                        (if is_array { Type::Array } else { _type }, name, name_span)
+                    })
+                    .collect(),
+            )
+        });
+
+    // A parser for instance names of GLSL interface blocks (like `} u_lights[4];`).
+    let struct_instance = spanned_ident
+        .then(
+            just(Token::Ctrl('['))
+                .then_ignore(none_of([Token::Ctrl(']'), Token::Ctrl(';')]).repeated())
+                .then_ignore(just(Token::Ctrl(']')))
+                .or_not(),
+        )
+        .then_ignore(just(Token::Ctrl(';')))
+        .map(|((name, name_span), opt_array)| StructField {
+            _type: if opt_array.is_some() {
+                Type::Array
             } else {
-                ComplexToken::VariableDeclaration(t, name)
-            }
+                Type::Custom
+            },
+            type_span: Span::from(name_span.start..name_span.start),
+            name,
+            name_span,
+            semantic: None,
+            docs: String::new(),
         });
 
-    // A parser for structs.
-    let _struct = comment
+    // A parser for input modifiers for HLSL function arguments (including modifiers of mesh
+    // shader outputs like `out vertices` and primitive types of geometry shader inputs).
+    let argument_modifier = ident
+        .filter(|modifier| {
+            [
+                "in",
+                "out",
+                "inout",
+                "uniform",
+                "const",
+                "vertices",
+                "indices",
+                "primitives",
+                "payload",
+                "point",
+                "line",
+                "triangle",
+                "lineadj",
+                "triangleadj",
+            ]
+            .contains(modifier)
+        })
         .repeated()
-        .collect::<Vec<&str>>()
-        .then_ignore(layout.or_not())
-        .then_ignore(just(Token::Ident("readonly")).or_not())
-        .then_ignore(
-            just(Token::Ident("struct"))
-                .or(just(Token::Ident("uniform")))
-                .or(just(Token::Ident("buffer"))),
-        )
-        .then(ident)
-        .then_ignore(just(Token::Ctrl('{')))
-        .then(field.repeated().collect())
-        .then_ignore(just(Token::Ctrl('}')).or_not())
-        .map(|((opt_comments, name), fields)| {
-            ComplexToken::Struct(StructInfo {
-                name,
-                fields,
-                docs: opt_comments.concat(),
+        .collect::<Vec<_>>()
+        .then_ignore(precision_qualifier.clone().or_not());
+
+    // A parser for a name of a function argument that might be an array (like `input[3]`),
+    // returns the name and sizes of array dimensions.
+    let argument_name = spanned_ident.then(
+        none_of([Token::Ctrl(']'), Token::Ctrl(';')])
+            .repeated()
+            .collect::<Vec<_>>()
+            .delimited_by(just(Token::Ctrl('[')), just(Token::Ctrl(']')))
+            .map(|size_tokens| match size_tokens.as_slice() {
+                [Token::Integer(size)] => Some(*size),
+                _ => None,
             })
-        });
+            .repeated()
+            .collect::<Vec<_>>(),
+    );
 
-    // A parser for input modifiers for HLSL function arguments.
-    let argument_modifier = just(Token::Ident("in"))
-        .or(just(Token::Ident("out")))
-        .or(just(Token::Ident("uniform")))
-        .or_not();
+    // A parser for the end of a function argument with an optional default value (like
+    // `= float2(0.0, 1.0)`), returns whether the default value was specified.
+    let argument_end = just(Token::Op("="))
+        .ignore_then(
+            brackets
+                .clone()
+                .or(none_of([
+                    Token::Ctrl(','),
+                    Token::Ctrl(';'),
+                    Token::Ctrl('('),
+                    Token::Ctrl(')'),
+                    Token::Ctrl('['),
+                    Token::Ctrl(']'),
+                    Token::Ctrl('{'),
+                    Token::Ctrl('}'),
+                ])
+                .ignored())
+                .repeated()
+                .at_least(1),
+        )
+        .or_not()
+        .map(|opt_default| opt_default.is_some())
+        .then_ignore(just(Token::Ctrl(',')).or(just(Token::Ctrl(')'))));
 
     // A parser for function arguments that use HLSL semantics.
     let argument_semantic = argument_modifier
         .clone()
-        .ignore_then(std_var_type)
-        .then(ident)
-        .then_ignore(
-            just(Token::Ctrl(':'))
-                .then_ignore(ident)
-                .then_ignore(just(Token::Ctrl(',')).or(just(Token::Ctrl(')')))),
-        )
-        .map(|(_type, name)| FuncArgument {
-            _type,
-            name,
-            is_using_semantic: true,
-        });
+        .then(std_var_type.clone())
+        .then(argument_name.clone())
+        .then(just(Token::Ctrl(':')).ignore_then(ident))
+        .then(argument_end.clone())
+        .map(
+            |(
+                (((modifiers, _type), ((name, name_span), array_dimensions)), semantic),
+                has_default_value,
+            )| {
+                FuncArgument {
+                    _type: if array_dimensions.is_empty() {
+                        _type
+                    } else {
+                        Type::Array
+                    },
+                    name,
+                    name_span,
+                    semantic: Some(semantic),
+                    modifiers,
+                    array_dimensions,
+                    has_default_value,
+                }
+            },
+        );
 
     // A parser for function arguments with custom (user) type.
     let custom_argument = argument_modifier
         .clone()
-        .ignore_then(ident)
-        .then(ident)
-        .then_ignore(just(Token::Ctrl(',')).or(just(Token::Ctrl(')'))))
-        .map(|(_type, name)| FuncArgument {
-            _type: Type::Custom,
-            name,
-            is_using_semantic: false,
-        });
+        .then(custom_type.clone())
+        .then(argument_name.clone())
+        .then(argument_end.clone())
+        .map(
+            |(((modifiers, _type), ((name, name_span), array_dimensions)), has_default_value)| {
+                FuncArgument {
+                    _type: if array_dimensions.is_empty() {
+                        _type
+                    } else {
+                        Type::Array
+                    },
+                    name,
+                    name_span,
+                    semantic: None,
+                    modifiers,
+                    array_dimensions,
+                    has_default_value,
+                }
+            },
+        );
 
     // A parser for function arguments with standard types.
     let std_argument = argument_modifier
-        .ignore_then(std_var_type)
-        .then(ident)
-        .then_ignore(just(Token::Ctrl(',')).or(just(Token::Ctrl(')'))))
-        .map(|(_type, name)| FuncArgument {
-            _type,
-            name,
-            is_using_semantic: false,
-        });
+        .then(std_var_type.clone())
+        .then(argument_name)
+        .then(argument_end)
+        .map(
+            |(((modifiers, _type), ((name, name_span), array_dimensions)), has_default_value)| {
+                FuncArgument {
+                    _type: if array_dimensions.is_empty() {
+                        _type
+                    } else {
+                        Type::Array
+                    },
+                    name,
+                    name_span,
+                    semantic: None,
+                    modifiers,
+                    array_dimensions,
+                    has_default_value,
+                }
+            },
+        );
 
     // A parser for function arguments.
     let argument = std_argument.or(argument_semantic).or(custom_argument);
 
     // A parser for function return type.
-    let func_return_type = std_var_type.or(ident
-        .and_is(just(Token::Keyword("return")).not())
-        .map(|_| Type::Custom));
+    let func_return_type = precision_qualifier
+        .or_not()
+        .ignore_then(std_var_type.clone())
+        .or(custom_type.and_is(just(Token::Keyword("return")).not()));
 
-    // A parser for functions.
-    let function = comment
+    // A parser for HLSL attributes like `[numthreads(8, 8, 1)]` or `[domain("tri")]`.
+    let attribute = just(Token::Ctrl('['))
+        .ignore_then(ident)
+        .then_ignore(brackets.clone().or_not())
+        .then_ignore(just(Token::Ctrl(']')));
+
+    // A parser for function signatures.
+    let function_info = comment
         .repeated()
         .collect::<Vec<&str>>()
+        .map_with(|comments, extra| (comments, extra.span()))
+        .then(attribute.repeated().collect::<Vec<_>>())
+        .then_ignore(template_declaration.or_not())
         .then(func_return_type)
-        .then(ident)
+        .then(spanned_ident)
         .then_ignore(just(Token::Ctrl('(')))
         .then(argument.clone().repeated().collect())
         .then_ignore(just(Token::Ctrl(')')).or_not())
-        .map(|(((opt_comments, return_type), name), args)| {
-            ComplexToken::Function(FunctionInfo {
+        .then(just(Token::Ctrl(':')).ignore_then(ident).or_not())
+        .map(
+            |(
+                (((((opt_comments, docs_span), attributes), return_type), (name, name_span)), args),
+                return_semantic,
+            )| FunctionInfo {
                 name,
+                name_span,
                 args,
                 return_type,
+                return_semantic,
+                attributes,
+                docs: opt_comments.concat(),
+                docs_span,
+                is_declaration: false,
+            },
+        );
+
+    // A parser for functions (the `;` of declarations is left as a separate token).
+    let function = function_info
+        .clone()
+        .then(just(Token::Ctrl(';')).rewind().or_not())
+        .map(|(info, opt_semicolon)| {
+            ComplexToken::Function(FunctionInfo {
+                is_declaration: opt_semicolon.is_some(),
+                ..info
+            })
+        });
+
+    // A parser for code blocks like `{ ... { ... } ... }`.
+    let block = recursive(|block| {
+        just(Token::Ctrl('{'))
+            .ignore_then(
+                block
+                    .or(none_of([Token::Ctrl('{'), Token::Ctrl('}')]).ignored())
+                    .repeated(),
+            )
+            .then_ignore(just(Token::Ctrl('}')))
+            .ignored()
+    });
+
+    // A parser for the body of a `switch` statement, returns `true` if it has a `default` label
+    // (labels of nested blocks are ignored).
+    let switch_body = just(Token::Ctrl('{'))
+        .ignore_then(
+            block
+                .clone()
+                .to(false)
+                .or(just(Token::Ident("default"))
+                    .then(just(Token::Ctrl(':')))
+                    .to(true))
+                .or(none_of([Token::Ctrl('{'), Token::Ctrl('}')]).to(false))
+                .repeated()
+                .collect::<Vec<_>>(),
+        )
+        .then_ignore(just(Token::Ctrl('}')))
+        .map(|labels| labels.contains(&true));
+
+    // A parser for headers of `switch` statements (the body is only checked for labels and
+    // then parsed as usual).
+    let switch = just(Token::Ident("switch"))
+        .ignore_then(brackets.clone())
+        .ignore_then(switch_body.rewind())
+        .map(|has_default| ComplexToken::Switch { has_default });
+
+    // A parser for methods of structs/classes (with a body) and interfaces (without a body).
+    let method = function_info
+        .then_ignore(
+            none_of([Token::Ctrl('{'), Token::Ctrl('}'), Token::Ctrl(';')]).repeated(), // for semantics
+        )
+        .then(block.clone().to(false).or(just(Token::Ctrl(';')).to(true)))
+        .map(|(info, is_declaration)| FunctionInfo {
+            is_declaration,
+            ..info
+        })
+        .boxed();
+
+    // A parser that is used to recover from members of structs and constant buffers that we
+    // failed to parse (like HLSL 2021 operator overloads), skips the member until the end of
+    // the statement (or until the end of its body) and reports the skipped code as an error.
+    // Comments and preprocessor directives are not skipped (they end the body).
+    let member_code = any().filter(|token: &Token| {
+        !matches!(
+            token,
+            Token::Comment(_)
+                | Token::Preprocessor(_)
+                | Token::Ctrl('{')
+                | Token::Ctrl('}')
+                | Token::Ctrl(';')
+        )
+    });
+    let skipped_member = comment
+        .repeated()
+        .ignore_then(
+            member_code
+                .repeated()
+                .then(just(Token::Ctrl(';')).ignored().or(block.clone()))
+                .ignored()
+                .or(member_code.repeated().at_least(1)),
+        )
+        .validate(|_, extra, emitter| {
+            emitter.emit(Rich::custom(extra.span(), "unable to parse the member"));
+            StructMember::Fields(Vec::new())
+        });
+
+    // A parser for structs (and GLSL interface blocks, HLSL classes and interfaces), nested
+    // structs are parsed as members.
+    let struct_info = recursive(|struct_info| {
+        // A parser for a member of a struct/class/interface.
+        let struct_member = method
+            .map(StructMember::Method)
+            .or(struct_info
+                .then_ignore(just(Token::Ctrl(';')).or_not())
+                .map(StructMember::Struct))
+            .or(field.clone().map(StructMember::Fields))
+            .or(skipped_member.clone())
+            .boxed(); // to keep compilation time reasonable
+
+        comment
+            .repeated()
+            .collect::<Vec<&str>>()
+            .map_with(|comments, extra| (comments, extra.span()))
+            .then(layout.or_not())
+            .then_ignore(memory_qualifier.repeated())
+            .then_ignore(
+                just(Token::Ident("struct"))
+                    .or(just(Token::Ident("uniform")))
+                    .or(just(Token::Ident("buffer")))
+                    .or(just(Token::Ident("in")))
+                    .or(just(Token::Ident("out")))
+                    .or(just(Token::Ident("class")))
+                    .or(just(Token::Ident("interface"))),
+            )
+            .then(
+                // anonymous structs (like `struct { float a; } b;`) have an empty name
+                spanned_ident.or(just(Token::Ctrl('{')).rewind().map_with(|_, extra| {
+                    let span: Span = extra.span();
+                    ("", Span::from(span.start..span.start))
+                })),
+            )
+            .then(
+                // for base structs and interfaces that a class implements
+                just(Token::Ctrl(':'))
+                    .ignore_then(
+                        spanned_ident
+                            .separated_by(just(Token::Ctrl(',')))
+                            .at_least(1)
+                            .collect::<Vec<_>>(),
+                    )
+                    .or_not(),
+            )
+            .then_ignore(just(Token::Ctrl('{')))
+            .then(struct_member.repeated().collect::<Vec<_>>())
+            .then(
+                just(Token::Ctrl('}'))
+                    .ignore_then(struct_instance.clone().or_not())
+                    .or_not(),
+            )
+            .map(
+                |(
+                    (
+                        ((((opt_comments, docs_span), opt_layout), (name, name_span)), opt_bases),
+                        members,
+                    ),
+                    opt_instance,
+                )| {
+                    let mut fields = Vec::new();
+                    let mut methods = Vec::new();
+                    let mut nested = Vec::new();
+                    for member in members {
+                        match member {
+                            StructMember::Fields(new_fields) => fields.extend(new_fields),
+                            StructMember::Method(method) => methods.push(method),
+                            StructMember::Struct(mut info) => {
+                                // A field that uses the nested struct (like `inner` in `struct Inner { ... } inner;`).
+                                if let Some(instance) = info.instance.take() {
+                                    fields.push(instance);
+                                }
+                                nested.push(info);
+                            }
+                        }
+                    }
+
+                    StructInfo {
+                        name,
+                        name_span,
+                        bases: opt_bases.unwrap_or_default(),
+                        fields,
+                        methods,
+                        nested,
+                        docs: opt_comments.concat(),
+                        docs_span,
+                        instance: opt_instance.flatten(),
+                        binding: opt_layout.and_then(|(_, binding, _)| binding),
+                        set: opt_layout.and_then(|(set, _, _)| set),
+                        is_push_constant: opt_layout
+                            .is_some_and(|(_, _, is_push_constant)| is_push_constant),
+                    }
+                },
+            )
+            .boxed()
+    });
+    let _struct = struct_info.clone().map(ComplexToken::Struct);
+
+    // A parser for the `[[vk::push_constant]]` attribute of HLSL constant buffers.
+    let push_constant_attribute = just(Token::Ctrl('['))
+        .then(just(Token::Ctrl('[')))
+        .then(just(Token::Ident("vk")))
+        .then(just(Token::Ctrl(':')))
+        .then(just(Token::Ctrl(':')))
+        .then(just(Token::Ident("push_constant")))
+        .then(just(Token::Ctrl(']')))
+        .then(just(Token::Ctrl(']')));
+
+    // A parser for HLSL constant buffers (like `cbuffer Frame : register(b0) { ... };`).
+    let constant_buffer = push_constant_attribute
+        .or_not()
+        .then_ignore(just(Token::Ident("cbuffer")).or(just(Token::Ident("tbuffer"))))
+        .then(spanned_ident)
+        .then_ignore(
+            just(Token::Ctrl(':'))
+                .then(just(Token::Ident("register")))
+                .then(brackets.clone())
+                .or_not(),
+        )
+        .then_ignore(just(Token::Ctrl('{')))
+        .then(
+            struct_info
+                .then_ignore(just(Token::Ctrl(';')).or_not())
+                .map(StructMember::Struct)
+                .or(field.map(StructMember::Fields))
+                .or(skipped_member)
+                .repeated()
+                .collect::<Vec<_>>(),
+        )
+        .then_ignore(just(Token::Ctrl('}')))
+        .then_ignore(just(Token::Ctrl(';')).or_not())
+        .map(|((opt_push_constant, (name, name_span)), members)| {
+            let mut fields = Vec::new();
+            let mut nested = Vec::new();
+            for member in members {
+                match member {
+                    StructMember::Fields(new_fields) => fields.extend(new_fields),
+                    StructMember::Struct(mut info) => {
+                        if let Some(instance) = info.instance.take() {
+                            fields.push(instance);
+                        }
+                        nested.push(info);
+                    }
+                    StructMember::Method(_) => {} // not allowed in constant buffers
+                }
+            }
+
+            ComplexToken::ConstantBuffer(ConstantBufferInfo {
+                name,
+                name_span,
+                fields,
+                nested,
+                is_push_constant: opt_push_constant.is_some(),
+            })
+        })
+        .boxed();
+
+    // A parser for values of enums.
+    let enum_value = comment
+        .repeated()
+        .collect::<Vec<&str>>()
+        .then(spanned_ident)
+        .then_ignore(
+            just(Token::Op("="))
+                .then(none_of([Token::Ctrl(','), Token::Ctrl('}')]).repeated())
+                .or_not(),
+        )
+        .map_with(|(opt_comments, (name, name_span)), extra| EnumValue {
+            name,
+            name_span,
+            span: extra.span(),
+            docs: opt_comments.concat(),
+        });
+
+    // A parser for enums (including HLSL 2021 `enum class`).
+    let _enum = comment
+        .repeated()
+        .collect::<Vec<&str>>()
+        .then_ignore(just(Token::Ident("enum")))
+        .then_ignore(
+            just(Token::Ident("class"))
+                .or(just(Token::Ident("struct")))
+                .or_not(),
+        )
+        .then(spanned_ident)
+        .then_ignore(just(Token::Ctrl(':')).then(std_var_type).or_not()) // for underlying type
+        .then(
+            enum_value
+                .separated_by(just(Token::Ctrl(',')))
+                .allow_trailing()
+                .collect::<Vec<_>>()
+                .then_ignore(comment.repeated())
+                .delimited_by(just(Token::Ctrl('{')), just(Token::Ctrl('}')))
+                .map_with(|values, extra| (values, extra.span())),
+        )
+        .map(|((opt_comments, (name, name_span)), (values, body_span))| {
+            ComplexToken::Enum(EnumInfo {
+                name,
+                name_span,
+                values,
+                body_span,
                 docs: opt_comments.concat(),
             })
         });
 
     // If non of our parsers from above worked then just pass the token.
-    let output = _struct
+    let output = default_precision
+        .or(_enum)
+        .or(_struct)
+        .or(constant_buffer)
+        .or(switch)
         .or(function)
         .or(variable_declaration)
         .or(token.map(ComplexToken::Other));
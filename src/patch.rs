@@ -0,0 +1,179 @@
+/// Number of unchanged lines to show around changed lines.
+const CONTEXT_LINES: usize = 3;
+
+/// A line of the unified diff.
+#[derive(Clone, Copy, PartialEq)]
+enum DiffLine {
+    /// Index of the line in the old text and the index of the same line in the new text.
+    Unchanged(usize, usize),
+    /// Index of the removed line in the old text and the number of new lines before it.
+    Removed(usize, usize),
+    /// Index of the added line in the new text and the number of old lines before it.
+    Added(usize, usize),
+}
+
+impl DiffLine {
+    /// Returns indices of the line in the old text and in the new text (for removed/added lines
+    /// the index in the other text is the number of lines before it).
+    fn positions(&self) -> (usize, usize) {
+        match *self {
+            DiffLine::Unchanged(old, new) => (old, new),
+            DiffLine::Removed(old, new_before) => (old, new_before),
+            DiffLine::Added(new, old_before) => (old_before, new),
+        }
+    }
+}
+
+/// Creates a unified diff (that can be applied using `git apply`) that changes the old text
+/// of the file to the new text.
+///
+/// # Arguments
+/// - `path` Path to the file relative to the root directory of the patch (uses `/` as a separator).
+/// - `old_text` Current text of the file.
+/// - `new_text` Text after formatting.
+///
+/// # Return
+/// Empty string if texts are equal.
+pub fn unified_diff(path: &str, old_text: &str, new_text: &str) -> String {
+    if old_text == new_text {
+        return String::new();
+    }
+
+    // Line breaks are kept in lines to handle `\r`s and files without a line break at the end.
+    let old_lines: Vec<&str> = old_text.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new_text.split_inclusive('\n').collect();
+
+    // Lines are compared without line breaks, skip items for empty text after the last line break.
+    let mut diff_lines = Vec::new();
+    let mut removed_lines = Vec::new();
+    let mut added_lines = Vec::new();
+    let (mut old_index, mut new_index) = (0, 0);
+    for result in diff::myers::lines(old_text, new_text) {
+        let has_old_line = old_index < old_lines.len();
+        let has_new_line = new_index < new_lines.len();
+        match result {
+            diff::Result::Both(..) if has_old_line && has_new_line => {
+                if old_lines[old_index] == new_lines[new_index] {
+                    push_changes(
+                        &mut diff_lines,
+                        &mut removed_lines,
+                        &mut added_lines,
+                        old_index,
+                        new_index,
+                    );
+                    diff_lines.push(DiffLine::Unchanged(old_index, new_index));
+                } else {
+                    removed_lines.push(old_index);
+                    added_lines.push(new_index);
+                }
+                old_index += 1;
+                new_index += 1;
+            }
+            diff::Result::Left(_) if has_old_line => {
+                removed_lines.push(old_index);
+                old_index += 1;
+            }
+            diff::Result::Right(_) if has_new_line => {
+                added_lines.push(new_index);
+                new_index += 1;
+            }
+            _ => {}
+        }
+    }
+    push_changes(
+        &mut diff_lines,
+        &mut removed_lines,
+        &mut added_lines,
+        old_index,
+        new_index,
+    );
+
+    let mut patch = format!("--- a/{}\n+++ b/{}\n", path, path);
+
+    let mut hunk_start = 0;
+    while let Some(first_change) = diff_lines[hunk_start..]
+        .iter()
+        .position(|line| !matches!(line, DiffLine::Unchanged(..)))
+    {
+        let first_change = hunk_start + first_change;
+
+        // Extend the hunk while the next change is close enough to share context lines.
+        let mut last_change = first_change;
+        for (index, line) in diff_lines.iter().enumerate().skip(first_change + 1) {
+            if index - last_change > CONTEXT_LINES * 2 {
+                break;
+            }
+            if !matches!(line, DiffLine::Unchanged(..)) {
+                last_change = index;
+            }
+        }
+
+        let start = first_change.saturating_sub(CONTEXT_LINES).max(hunk_start);
+        let end = (last_change + 1 + CONTEXT_LINES).min(diff_lines.len());
+        let hunk = &diff_lines[start..end];
+
+        let old_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Added(..)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|line| !matches!(line, DiffLine::Removed(..)))
+            .count();
+        let (old_start, new_start) = hunk[0].positions();
+
+        // Empty ranges point to the line before them.
+        patch += &format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start + usize::from(old_count != 0),
+            old_count,
+            new_start + usize::from(new_count != 0),
+            new_count
+        );
+
+        for line in hunk {
+            let (prefix, text) = match *line {
+                DiffLine::Unchanged(old, _) => (' ', old_lines[old]),
+                DiffLine::Removed(old, _) => ('-', old_lines[old]),
+                DiffLine::Added(new, _) => ('+', new_lines[new]),
+            };
+            patch.push(prefix);
+            patch += text;
+            if !text.ends_with('\n') {
+                patch += "\n\\ No newline at end of file\n";
+            }
+        }
+
+        hunk_start = end;
+    }
+
+    patch
+}
+
+/// Adds a group of changed lines (removed lines go first) to the diff.
+///
+/// # Arguments
+/// - `diff_lines` Lines of the diff.
+/// - `removed_lines` Indices of removed lines in the old text (cleared).
+/// - `added_lines` Indices of added lines in the new text (cleared).
+/// - `old_index` Index of the next line in the old text (after the changes).
+/// - `new_index` Index of the next line in the new text (after the changes).
+fn push_changes(
+    diff_lines: &mut Vec<DiffLine>,
+    removed_lines: &mut Vec<usize>,
+    added_lines: &mut Vec<usize>,
+    old_index: usize,
+    new_index: usize,
+) {
+    let new_lines_before = new_index - added_lines.len();
+    diff_lines.extend(
+        removed_lines
+            .drain(..)
+            .map(|old| DiffLine::Removed(old, new_lines_before)),
+    );
+    diff_lines.extend(
+        added_lines
+            .drain(..)
+            .map(|new| DiffLine::Added(new, old_index)),
+    );
+}
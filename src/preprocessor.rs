@@ -0,0 +1,510 @@
+use std::path::{Path, PathBuf};
+
+use chumsky::Parser;
+
+use crate::{
+    helpers, includes,
+    parser::{self, Span, Token},
+};
+
+/// Looks for a branch of a conditional directive (like `#if`, `#ifdef`, `#elif` or `#else`)
+/// that can never be active with the specified values of macros.
+///
+/// # Arguments
+/// - `tokens` Tokens of the file.
+/// - `defines` Names and values of macros, other macros are considered as unknown.
+///
+/// # Return
+/// Text and span of the directive that starts the dead branch.
+pub fn find_dead_branch<'src>(
+    tokens: &[(Token<'src>, Span)],
+    defines: &[(String, i64)],
+) -> Option<(&'src str, Span)> {
+    // For each opened `#if` stores whether one of its branches is always active.
+    let mut conditionals: Vec<bool> = Vec::new();
+
+    for (token, span) in tokens {
+        let Token::Preprocessor(directive) = token else {
+            continue;
+        };
+        let (keyword, expression) = split_directive(directive);
+
+        let is_dead = match keyword {
+            "if" | "ifdef" | "ifndef" => {
+                let value = match keyword {
+                    "if" => evaluate(expression, defines),
+                    "ifdef" => evaluate_defined(expression.trim(), defines),
+                    _ => evaluate_defined(expression.trim(), defines).map(|value| 1 - value),
+                };
+                conditionals.push(value.is_some_and(|value| value != 0));
+                value == Some(0)
+            }
+            "elif" => {
+                let Some(has_active_branch) = conditionals.last_mut() else {
+                    continue;
+                };
+                let value = evaluate(expression, defines);
+                let is_dead = *has_active_branch || value == Some(0);
+                *has_active_branch |= value.is_some_and(|value| value != 0);
+                is_dead
+            }
+            "else" => match conditionals.last() {
+                Some(has_active_branch) => *has_active_branch,
+                None => continue,
+            },
+            "endif" => {
+                conditionals.pop();
+                false
+            }
+            _ => false,
+        };
+
+        if is_dead {
+            return Some((directive.trim(), *span));
+        }
+    }
+
+    None
+}
+
+/// Splits a directive like `#if A > 0 // comment` to a keyword (`if`) and the rest of the
+/// directive without comments (`A > 0`).
+fn split_directive(directive: &str) -> (&str, &str) {
+    let directive = directive.trim_start().trim_start_matches('#').trim_start();
+    let directive = match directive.find("//") {
+        Some(comment_start) => &directive[..comment_start],
+        None => directive,
+    };
+
+    let keyword_end = directive
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(directive.len());
+
+    (&directive[..keyword_end], &directive[keyword_end..])
+}
+
+/// Returns text of a directive that starts at the specified span including lines that
+/// continue it (like a multi-line macro with `\` at the end of lines).
+fn get_multiline_directive(text: &str, span: Span) -> &str {
+    let mut end = span.start;
+    for line in text[span.start..].split_inclusive('\n') {
+        end += line.len();
+        if !line.trim_end().ends_with('\\') {
+            break;
+        }
+    }
+
+    &text[span.start..end]
+}
+
+/// Returns `1` if the macro is defined, `None` if unknown.
+fn evaluate_defined(name: &str, defines: &[(String, i64)]) -> Option<i64> {
+    if defines.iter().any(|(define, _)| define == name) {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// Evaluates a condition of an `#if`/`#elif` directive.
+///
+/// # Return
+/// `None` if the value is unknown (uses unknown macros or unsupported syntax).
+fn evaluate(expression: &str, defines: &[(String, i64)]) -> Option<i64> {
+    let mut evaluator = ExpressionEvaluator {
+        tokens: tokenize(expression)?,
+        position: 0,
+        defines,
+        is_valid: true,
+    };
+
+    let value = evaluator.parse_or();
+    if !evaluator.is_valid || evaluator.position != evaluator.tokens.len() {
+        return None;
+    }
+
+    value
+}
+
+/// Splits an expression to numbers, identifiers and operators.
+fn tokenize(expression: &str) -> Option<Vec<&str>> {
+    const OPERATORS: [&str; 17] = [
+        "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "*", "/", "%", "(", ")", "~",
+    ];
+
+    let mut tokens = Vec::new();
+    let mut text = expression.trim_start();
+    while !text.is_empty() {
+        let length = if text.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_') {
+            text.find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(text.len())
+        } else {
+            OPERATORS
+                .iter()
+                .find(|operator| text.starts_with(*operator))?
+                .len()
+        };
+
+        tokens.push(&text[..length]);
+        text = text[length..].trim_start();
+    }
+
+    Some(tokens)
+}
+
+/// Evaluates tokens of a preprocessor expression, the value of each expression is `None`
+/// if it's unknown.
+struct ExpressionEvaluator<'a> {
+    tokens: Vec<&'a str>,
+    position: usize,
+    defines: &'a [(String, i64)],
+    /// `false` if found unsupported syntax.
+    is_valid: bool,
+}
+
+impl<'a> ExpressionEvaluator<'a> {
+    /// Returns the next token if it's one of the specified operators.
+    fn next_operator(&mut self, operators: &[&str]) -> Option<&'a str> {
+        let token = *self.tokens.get(self.position)?;
+        if !operators.contains(&token) {
+            return None;
+        }
+
+        self.position += 1;
+        Some(token)
+    }
+
+    fn parse_or(&mut self) -> Option<i64> {
+        let mut value = self.parse_and();
+        while self.next_operator(&["||"]).is_some() {
+            let right = self.parse_and();
+            value = match (value, right) {
+                (Some(left), _) if left != 0 => Some(1),
+                (_, Some(right)) if right != 0 => Some(1),
+                (Some(_), Some(_)) => Some(0),
+                _ => None,
+            };
+        }
+        value
+    }
+
+    fn parse_and(&mut self) -> Option<i64> {
+        let mut value = self.parse_binary(0);
+        while self.next_operator(&["&&"]).is_some() {
+            let right = self.parse_binary(0);
+            value = match (value, right) {
+                (Some(0), _) | (_, Some(0)) => Some(0),
+                (Some(_), Some(_)) => Some(1),
+                _ => None,
+            };
+        }
+        value
+    }
+
+    /// Parses binary operators starting from the specified precedence level.
+    fn parse_binary(&mut self, level: usize) -> Option<i64> {
+        const LEVELS: [&[&str]; 4] = [
+            &["==", "!="],
+            &["<", ">", "<=", ">="],
+            &["+", "-"],
+            &["*", "/", "%"],
+        ];
+
+        let Some(operators) = LEVELS.get(level) else {
+            return self.parse_unary();
+        };
+
+        let mut value = self.parse_binary(level + 1);
+        while let Some(operator) = self.next_operator(operators) {
+            let right = self.parse_binary(level + 1);
+            value = value.zip(right).and_then(|(left, right)| match operator {
+                "==" => Some((left == right) as i64),
+                "!=" => Some((left != right) as i64),
+                "<" => Some((left < right) as i64),
+                ">" => Some((left > right) as i64),
+                "<=" => Some((left <= right) as i64),
+                ">=" => Some((left >= right) as i64),
+                "+" => left.checked_add(right),
+                "-" => left.checked_sub(right),
+                "*" => left.checked_mul(right),
+                "/" => left.checked_div(right),
+                _ => left.checked_rem(right),
+            });
+        }
+        value
+    }
+
+    fn parse_unary(&mut self) -> Option<i64> {
+        match self.next_operator(&["!", "-", "+", "~"]) {
+            Some("!") => self.parse_unary().map(|value| (value == 0) as i64),
+            Some("-") => self.parse_unary().and_then(i64::checked_neg),
+            Some("~") => self.parse_unary().map(|value| !value),
+            Some(_) => self.parse_unary(),
+            None => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Option<i64> {
+        let Some(token) = self.tokens.get(self.position).copied() else {
+            self.is_valid = false;
+            return None;
+        };
+        self.position += 1;
+
+        if token == "(" {
+            let value = self.parse_or();
+            if self.next_operator(&[")"]).is_none() {
+                self.is_valid = false;
+            }
+            return value;
+        }
+
+        if token == "defined" {
+            let has_parentheses = self.next_operator(&["("]).is_some();
+            let Some(name) = self.tokens.get(self.position).copied() else {
+                self.is_valid = false;
+                return None;
+            };
+            self.position += 1;
+            if has_parentheses && self.next_operator(&[")"]).is_none() {
+                self.is_valid = false;
+            }
+            return evaluate_defined(name, self.defines);
+        }
+
+        if token.starts_with(|c: char| c.is_ascii_digit()) {
+            return parse_integer(token);
+        }
+
+        if token.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            return self
+                .defines
+                .iter()
+                .find(|(name, _)| name == token)
+                .map(|(_, value)| *value);
+        }
+
+        self.is_valid = false;
+        None
+    }
+}
+
+/// Parses an integer literal like `10`, `0x1F` or `1u`.
+fn parse_integer(literal: &str) -> Option<i64> {
+    let literal = literal.trim_end_matches(['u', 'U', 'l', 'L']);
+
+    match literal
+        .strip_prefix("0x")
+        .or_else(|| literal.strip_prefix("0X"))
+    {
+        Some(hex) => i64::from_str_radix(hex, 16).ok(),
+        None => literal.parse().ok(),
+    }
+}
+
+/// A macro that was defined again with a different body.
+pub struct MacroRedefinition {
+    pub name: String,
+    /// Span of the second definition (or of the `#include` directive that leads to it).
+    pub span: Span,
+    /// Where the macro was defined first (like `line 2 of file "common.hlsl"`).
+    pub first_location: String,
+    /// Where the macro was defined again.
+    pub second_location: String,
+}
+
+/// Information about a `#define` directive.
+struct MacroDefinition {
+    name: String,
+    /// Parameters and replacement text with normalized whitespace.
+    body: String,
+    location: String,
+    /// IDs of conditional directives (and indices of their branches) that contain the definition.
+    branches: Vec<(usize, usize)>,
+}
+
+/// State of a search for redefined macros.
+struct MacroCollector<'a> {
+    include_dirs: &'a [PathBuf],
+    definitions: Vec<MacroDefinition>,
+    /// IDs of opened conditional directives and indices of their current branches.
+    branches: Vec<(usize, usize)>,
+    conditional_count: usize,
+    visited_files: Vec<PathBuf>,
+}
+
+/// Looks for a macro that is defined twice with different bodies in the specified file
+/// (or in files included by it). Definitions in different branches of a conditional
+/// directive (like `#ifdef A ... #else ... #endif`) are not considered as redefinitions.
+///
+/// # Arguments
+/// - `text` Text of the file.
+/// - `tokens` Tokens of the file.
+/// - `current_dir` Directory of the file (if known).
+/// - `include_dirs` Directories to look for included files.
+pub fn find_macro_redefinition(
+    text: &str,
+    tokens: &[(Token<'_>, Span)],
+    current_dir: Option<&Path>,
+    include_dirs: &[PathBuf],
+) -> Option<MacroRedefinition> {
+    let mut collector = MacroCollector {
+        include_dirs,
+        definitions: Vec::new(),
+        branches: Vec::new(),
+        conditional_count: 0,
+        visited_files: Vec::new(),
+    };
+
+    collector
+        .process_file(text, tokens, None, current_dir)
+        .err()
+}
+
+impl MacroCollector<'_> {
+    /// Collects macro definitions from the specified file.
+    ///
+    /// # Arguments
+    /// - `text` Text of the file.
+    /// - `tokens` Tokens of the file.
+    /// - `path` Path to the file (`None` for the file that is being checked).
+    /// - `current_dir` Directory of the file (if known).
+    fn process_file(
+        &mut self,
+        text: &str,
+        tokens: &[(Token<'_>, Span)],
+        path: Option<&Path>,
+        current_dir: Option<&Path>,
+    ) -> Result<(), MacroRedefinition> {
+        for (token, span) in tokens {
+            let Token::Preprocessor(directive) = token else {
+                continue;
+            };
+
+            if let Some(include_path) = includes::get_include_path(directive) {
+                self.process_include(include_path, current_dir)
+                    .map_err(|redefinition| MacroRedefinition {
+                        span: *span,
+                        ..redefinition
+                    })?;
+                continue;
+            }
+
+            let (keyword, rest) = split_directive(directive);
+            match keyword {
+                "if" | "ifdef" | "ifndef" => {
+                    self.branches.push((self.conditional_count, 0));
+                    self.conditional_count += 1;
+                }
+                "elif" | "else" => {
+                    if let Some((_, branch_index)) = self.branches.last_mut() {
+                        *branch_index += 1;
+                    }
+                }
+                "endif" => {
+                    self.branches.pop();
+                }
+                "undef" => {
+                    let name = rest.trim();
+                    self.definitions
+                        .retain(|definition| definition.name != name);
+                }
+                "define" => {
+                    let (line, _) = helpers::LineIndex::new(text).line_and_column(span.start);
+                    let location = match path {
+                        Some(path) => format!("line {} of file \"{}\"", line, path.display()),
+                        None => format!("line {}", line),
+                    };
+
+                    // Take lines that continue the macro (if it ends with `\`).
+                    let (_, definition) = split_directive(get_multiline_directive(text, *span));
+                    self.add_definition(definition, location, *span)?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collects macro definitions from an included file (if it was not processed yet).
+    fn process_include(
+        &mut self,
+        include_path: &str,
+        current_dir: Option<&Path>,
+    ) -> Result<(), MacroRedefinition> {
+        let Some(path) = includes::resolve_include(include_path, current_dir, self.include_dirs)
+        else {
+            return Ok(());
+        };
+
+        // Make sure we didn't process this file yet.
+        let path = path.canonicalize().unwrap_or(path);
+        if self.visited_files.contains(&path) {
+            return Ok(());
+        }
+        self.visited_files.push(path.clone());
+
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Ok(());
+        };
+        let Some(tokens) = parser::token_parser().parse(&content).into_output() else {
+            return Ok(());
+        };
+
+        self.process_file(&content, &tokens, Some(&path), path.parent())
+    }
+
+    /// Saves a definition of a macro and checks that it does not conflict with previous ones.
+    ///
+    /// # Arguments
+    /// - `definition` Text of the `#define` directive after the keyword.
+    /// - `location` Human-readable location of the directive.
+    /// - `span` Span of the directive.
+    fn add_definition(
+        &mut self,
+        definition: &str,
+        location: String,
+        span: Span,
+    ) -> Result<(), MacroRedefinition> {
+        let definition = definition.trim_start();
+        let name_length = definition
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(definition.len());
+        let name = &definition[..name_length];
+        let body = definition[name_length..]
+            .split_whitespace()
+            .filter(|word| *word != "\\")
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let previous = self.definitions.iter().find(|previous| {
+            previous.name == name
+                && previous.branches.iter().all(|(id, index)| {
+                    self.branches
+                        .iter()
+                        .all(|(other_id, other_index)| id != other_id || index == other_index)
+                })
+        });
+        if let Some(previous) = previous {
+            if previous.body != body {
+                return Err(MacroRedefinition {
+                    name: name.to_owned(),
+                    span,
+                    first_location: previous.location.clone(),
+                    second_location: location,
+                });
+            }
+            return Ok(());
+        }
+
+        self.definitions.push(MacroDefinition {
+            name: name.to_owned(),
+            body,
+            location,
+            branches: self.branches.clone(),
+        });
+
+        Ok(())
+    }
+}
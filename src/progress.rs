@@ -0,0 +1,100 @@
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::cli::ProgressMode;
+
+/// Width (in characters) of the filled part of the progress bar when all files are processed.
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+/// Whether the progress bar is currently printed (and needs to be cleared before other output).
+///
+/// Global because warnings are also printed by the formatter (possibly from another thread).
+static IS_BAR_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// Erases the progress bar (if it's shown), should be called before printing to stderr.
+pub fn clear_bar() {
+    if !IS_BAR_SHOWN.swap(false, Ordering::SeqCst) {
+        return;
+    }
+
+    let mut stderr = std::io::stderr().lock();
+    let _ = write!(stderr, "\r\x1b[K");
+    let _ = stderr.flush();
+}
+
+/// Prints progress of processing multiple files to stderr (so that it does not mix with
+/// formatted code or patches printed to stdout).
+pub struct Progress {
+    /// `None` if progress is not shown.
+    mode: Option<ProgressMode>,
+    total_count: usize,
+    processed_count: usize,
+}
+
+impl Progress {
+    /// Creates a new progress reporter.
+    ///
+    /// # Arguments
+    /// - `mode` How to show progress, `Auto` shows a progress bar if both stdout and stderr are
+    ///   terminals and there are multiple files (otherwise progress is not shown).
+    /// - `total_count` Number of files to process.
+    pub fn new(mode: ProgressMode, total_count: usize) -> Self {
+        let mode = match mode {
+            ProgressMode::Auto => (total_count > 1
+                && std::io::stdout().is_terminal()
+                && std::io::stderr().is_terminal())
+            .then_some(ProgressMode::Bar),
+            ProgressMode::None => None,
+            mode => Some(mode),
+        };
+
+        Self {
+            mode,
+            total_count,
+            processed_count: 0,
+        }
+    }
+
+    /// Reports that processing of the specified file started.
+    pub fn start_file(&mut self, path: &Path) {
+        self.processed_count += 1;
+
+        match self.mode {
+            Some(ProgressMode::Plain) => eprintln!(
+                "[{}/{}] {}",
+                self.processed_count,
+                self.total_count,
+                path.display()
+            ),
+            Some(ProgressMode::Bar) => {
+                let filled_width =
+                    PROGRESS_BAR_WIDTH * self.processed_count / self.total_count.max(1);
+                let mut stderr = std::io::stderr().lock();
+                let _ = write!(
+                    stderr,
+                    "\r\x1b[K[{}{}] {}/{} {}",
+                    "#".repeat(filled_width),
+                    " ".repeat(PROGRESS_BAR_WIDTH.saturating_sub(filled_width)),
+                    self.processed_count,
+                    self.total_count,
+                    path.display()
+                );
+                let _ = stderr.flush();
+                IS_BAR_SHOWN.store(true, Ordering::SeqCst);
+            }
+            _ => {}
+        }
+    }
+
+    /// Erases the progress bar (if it's shown), should be called before printing other output.
+    pub fn clear(&mut self) {
+        clear_bar();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
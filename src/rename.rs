@@ -0,0 +1,375 @@
+use std::path::PathBuf;
+
+use chumsky::{input::Input, Parser};
+use serde_json::{json, Value};
+
+use crate::{
+    config::Config,
+    error::{FormatError, Location},
+    formatter::Formatter,
+    parser::{self, ComplexToken, Span, Token},
+};
+
+/// Maximum number of renames applied while fixing names of a single file (protects from rules
+/// that suggest names which violate other rules in a loop).
+const MAX_RENAMES_PER_FILE: usize = 64;
+
+/// Rename of a global symbol that was suggested by a naming rule.
+pub struct Rename {
+    /// Name of the rule that suggested the new name.
+    pub rule: &'static str,
+    pub old_name: String,
+    pub new_name: String,
+    /// Indices of files (in the specified files) that reference the symbol.
+    pub affected_files: Vec<usize>,
+}
+
+/// Name that violates a naming rule and the name suggested by the rule.
+pub struct RenameSuggestion {
+    /// Name of the rule that suggested the new name.
+    pub rule: &'static str,
+    pub old_name: String,
+    pub new_name: String,
+    /// Whether the name is declared in the global scope (references are looked for in all files).
+    pub is_global: bool,
+    /// Indices of files (in the specified files) and locations of references to the name.
+    pub occurrences: Vec<(usize, Location)>,
+}
+
+impl RenameSuggestion {
+    /// Converts the suggestion to a JSON object.
+    ///
+    /// # Arguments
+    /// - `files` Files that were used to collect the suggestion (used to get paths of occurrences).
+    pub fn to_json(&self, files: &[(PathBuf, String)]) -> Value {
+        json!({
+            "rule": self.rule,
+            "name": self.old_name,
+            "suggestion": self.new_name,
+            "isGlobal": self.is_global,
+            "occurrences": self
+                .occurrences
+                .iter()
+                .map(|(file_index, location)| {
+                    json!({
+                        "file": files[*file_index].0.to_string_lossy(),
+                        "line": location.start_line,
+                        "column": location.start_column,
+                        "endLine": location.end_line,
+                        "endColumn": location.end_column,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Returns names (and spans of names) of symbols declared in the global scope of the specified
+/// code: functions, structs, enums, constant buffers (and their fields) and global variables.
+fn collect_global_names(content: &str) -> Vec<(String, Span)> {
+    let Some(tokens) = parser::token_parser().parse(content).into_output() else {
+        return Vec::new();
+    };
+    let tokens = parser::split_template_closing_shifts(tokens);
+    let Some(complex_tokens) = parser::complex_token_parser(parser::collect_type_names(&tokens))
+        .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+        .into_output()
+    else {
+        return Vec::new();
+    };
+
+    let mut names = Vec::new();
+    let mut nesting_count = 0;
+    for (token, _) in &complex_tokens {
+        match token {
+            ComplexToken::Other(Token::Ctrl('{')) => nesting_count += 1,
+            ComplexToken::Other(Token::Ctrl('}')) => nesting_count -= 1,
+            _ if nesting_count > 0 => {}
+            ComplexToken::VariableDeclaration(variables) => {
+                names.extend(
+                    variables
+                        .iter()
+                        .map(|(_, name, span)| (name.to_string(), *span)),
+                );
+            }
+            ComplexToken::Function(info) => names.push((info.name.to_owned(), info.name_span)),
+            ComplexToken::Struct(info) => {
+                names.push((info.name.to_owned(), info.name_span));
+                names.extend(
+                    (info.instance.iter())
+                        .map(|instance| (instance.name.to_owned(), instance.name_span)),
+                );
+            }
+            ComplexToken::Enum(info) => names.push((info.name.to_owned(), info.name_span)),
+            ComplexToken::ConstantBuffer(info) => {
+                names.push((info.name.to_owned(), info.name_span));
+                names.extend(
+                    info.fields
+                        .iter()
+                        .map(|field| (field.name.to_owned(), field.name_span)),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    names.retain(|(name, _)| !name.is_empty());
+    names
+}
+
+/// Returns spans of all references of the specified name in the code (identifiers except member
+/// accesses like `.name` and words of `#define` directives).
+fn find_references(content: &str, name: &str) -> Vec<Span> {
+    let Some(tokens) = parser::token_parser().parse(content).into_output() else {
+        return Vec::new();
+    };
+
+    let mut spans = Vec::new();
+    for (i, (token, span)) in tokens.iter().enumerate() {
+        match token {
+            Token::Ident(ident) if *ident == name => {
+                let is_member_access = i > 0 && tokens[i - 1].0 == Token::Ctrl('.');
+                if !is_member_access {
+                    spans.push(*span);
+                }
+            }
+            Token::Preprocessor(text) if text.trim_start().starts_with("#define") => {
+                spans.extend(
+                    find_words(&content[span.into_range()], name)
+                        .into_iter()
+                        .map(|range| Span::from(span.start + range.start..span.start + range.end)),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    spans
+}
+
+/// Returns byte ranges of whole words of the text that are equal to the specified name.
+fn find_words(text: &str, name: &str) -> Vec<std::ops::Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut word_start = None;
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        let is_word_char = c.is_alphanumeric() || c == '_';
+        match (word_start, is_word_char) {
+            (None, true) => word_start = Some(i),
+            (Some(start), false) => {
+                if &text[start..i] == name {
+                    ranges.push(start..i);
+                }
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    ranges
+}
+
+/// Replaces all references of the specified name in the code (see `find_references`).
+///
+/// # Return
+/// `None` if the code does not reference the name.
+fn rename_in_text(content: &str, old_name: &str, new_name: &str) -> Option<String> {
+    let references = find_references(content, old_name);
+    if references.is_empty() {
+        return None;
+    }
+
+    let mut output = String::with_capacity(content.len());
+    let mut last_end = 0;
+    for span in references {
+        output.push_str(&content[last_end..span.start]);
+        output.push_str(new_name);
+        last_end = span.end;
+    }
+    output.push_str(&content[last_end..]);
+
+    Some(output)
+}
+
+/// Looks for a rename suggested by a naming rule.
+///
+/// # Arguments
+/// - `error` Error returned by the formatter (in the "lint only" mode so that the span points
+///   to the checked file).
+/// - `content` Checked file.
+///
+/// # Return
+/// Name of the rule, the current name, the suggested name and `true` if the name is declared
+/// in the global scope.
+fn get_suggested_rename(
+    error: &FormatError,
+    content: &str,
+) -> Option<(&'static str, String, String, bool)> {
+    let FormatError::RuleViolation {
+        rule,
+        name,
+        suggestion: Some(suggestion),
+        span,
+        ..
+    } = error
+    else {
+        return None;
+    };
+
+    let tokens = parser::token_parser().parse(content).into_output()?;
+    let old_name = tokens.iter().find_map(|(token, token_span)| match token {
+        Token::Ident(ident) if token_span.start == span.start => Some(ident.to_string()),
+        _ => None,
+    })?;
+
+    // Names are checked without global prefixes (like `g_`) after the prefixes are checked
+    // so the error might only contain the part of the name after the prefix.
+    let prefix = old_name.strip_suffix(name.as_str())?;
+    let new_name = format!("{}{}", prefix, suggestion);
+
+    let is_global = collect_global_names(content)
+        .iter()
+        .any(|(_, name_span)| name_span.start == span.start);
+
+    Some((*rule, old_name, new_name, is_global))
+}
+
+/// Renames global symbols (functions, structs, global variables and others) according to
+/// names suggested by naming rules and updates references in all specified files.
+///
+/// # Arguments
+/// - `files` Paths and contents of the files, only the first `checked_count` files are checked
+///   (other files like included files are only updated).
+/// - `checked_count` Number of files to check.
+/// - `config` Rules to check.
+///
+/// # Return
+/// Applied renames (contents of the files are updated).
+pub fn fix_global_names(
+    files: &mut [(PathBuf, String)],
+    checked_count: usize,
+    config: &Config,
+) -> Vec<Rename> {
+    let mut renames: Vec<Rename> = Vec::new();
+
+    for file_index in 0..checked_count.min(files.len()) {
+        let mut formatter = Formatter::new(config.clone());
+        formatter.set_path_to_shader(&files[file_index].0);
+        formatter.set_lint_only(true);
+
+        for _ in 0..MAX_RENAMES_PER_FILE {
+            let Err(error) = formatter.format(&files[file_index].1, false) else {
+                break;
+            };
+            let Some((rule, old_name, new_name, true)) =
+                get_suggested_rename(&error, &files[file_index].1)
+            else {
+                break;
+            };
+
+            // Don't create conflicts with existing global names.
+            let is_conflicting = files.iter().any(|(_, content)| {
+                collect_global_names(content)
+                    .iter()
+                    .any(|(name, _)| *name == new_name)
+            });
+            if is_conflicting || old_name == new_name {
+                break;
+            }
+
+            let mut affected_files = Vec::new();
+            for (i, (_, content)) in files.iter_mut().enumerate() {
+                if let Some(renamed) = rename_in_text(content, &old_name, &new_name) {
+                    *content = renamed;
+                    affected_files.push(i);
+                }
+            }
+            renames.push(Rename {
+                rule,
+                old_name,
+                new_name,
+                affected_files,
+            });
+        }
+    }
+
+    renames
+}
+
+/// Collects names that violate naming rules (with names suggested by the rules) and references
+/// to them, files are not changed.
+///
+/// # Arguments
+/// - `files` Paths and contents of the files, only the first `checked_count` files are checked
+///   (references to global names are also looked for in other files like included files).
+/// - `checked_count` Number of files to check.
+/// - `config` Rules to check.
+pub fn collect_rename_suggestions(
+    files: &[(PathBuf, String)],
+    checked_count: usize,
+    config: &Config,
+) -> Vec<RenameSuggestion> {
+    let mut suggestions: Vec<RenameSuggestion> = Vec::new();
+
+    for file_index in 0..checked_count.min(files.len()) {
+        // Names that were already reported are renamed here to look for other violations.
+        let mut content = files[file_index].1.clone();
+        let mut file_config = config.clone();
+
+        for _ in 0..MAX_RENAMES_PER_FILE {
+            let mut formatter = Formatter::new(file_config.clone());
+            formatter.set_path_to_shader(&files[file_index].0);
+            formatter.set_lint_only(true);
+
+            let Err(error) = formatter.format(&content, false) else {
+                break;
+            };
+            let Some((rule, old_name, new_name, is_global)) =
+                get_suggested_rename(&error, &content)
+            else {
+                // Skip rules that don't suggest names to look for other violations.
+                match error {
+                    FormatError::RuleViolation { rule, .. } if file_config.disable_rule(rule) => {
+                        continue
+                    }
+                    _ => break,
+                }
+            };
+            let Some(renamed) = rename_in_text(&content, &old_name, &new_name) else {
+                break;
+            };
+            content = renamed;
+
+            // Global names might be declared in multiple files (like function prototypes).
+            let is_reported = suggestions.iter().any(|suggestion| {
+                is_global && suggestion.is_global && suggestion.old_name == old_name
+            });
+            if is_reported {
+                continue;
+            }
+
+            let searched_files = if is_global {
+                0..files.len()
+            } else {
+                file_index..file_index + 1
+            };
+            let occurrences = searched_files
+                .flat_map(|i| {
+                    let text = &files[i].1;
+                    find_references(text, &old_name)
+                        .into_iter()
+                        .map(move |span| (i, Location::new(span, text)))
+                })
+                .collect();
+
+            suggestions.push(RenameSuggestion {
+                rule,
+                old_name,
+                new_name,
+                is_global,
+                occurrences,
+            });
+        }
+    }
+
+    suggestions
+}
@@ -3,12 +3,72 @@ pub enum IndentationRule {
     Tab,
     TwoSpaces,
     FourSpaces,
+    /// Indentation that is used by most lines of the file that is being formatted.
+    Auto,
 }
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum NewLineOnOpenBrace {
     Before,
     After,
+    /// Placement that is used by most braces of the file that is being formatted.
+    Auto,
+}
+
+/// Defines how bodies without code (like `void foo() {}`) are written.
+#[derive(Clone, Copy, PartialEq)]
+pub enum EmptyBodyStyle {
+    /// `{}` on the declaration line.
+    Collapsed,
+    /// Open and closing braces on separate lines.
+    Separate,
+}
+
+/// Defines where HLSL statement attributes (like `[unroll]`) are placed.
+#[derive(Clone, Copy, PartialEq)]
+pub enum StatementAttributePlacement {
+    /// The attribute is on its own line above the statement.
+    SeparateLine,
+    /// The attribute is on the same line as the statement.
+    SameLine,
+}
+
+/// Defines when bodies of statements (like `if` or `for`) must be enclosed in braces.
+#[derive(Clone, Copy, PartialEq)]
+pub enum RequireBraces {
+    /// Braces are required for all bodies.
+    Always,
+    /// Braces are required if the statement with its body is written on multiple lines.
+    MultiLine,
+}
+
+/// Defines whether brace initializer lists (like `{1, 2, 3}`) end with a comma.
+#[derive(Clone, Copy, PartialEq)]
+pub enum TrailingComma {
+    /// The last element is always followed by a comma.
+    Always,
+    /// The last element is never followed by a comma.
+    Never,
+    /// The last element is followed by a comma only if `}` is on a separate line.
+    Multiline,
+}
+
+/// Defines which one of a function declaration (prototype) and its definition must have docs.
+#[derive(Clone, Copy, PartialEq)]
+pub enum FunctionDocsPlacement {
+    /// Docs are required on the declaration (definitions of declared functions don't need docs).
+    Declaration,
+    /// Docs are required on the definition (declarations of defined functions don't need docs).
+    Definition,
+}
+
+/// Defines which variables are checked by a type prefix rule (like `IntPrefix`).
+#[derive(Clone, Copy, PartialEq)]
+pub enum PrefixScope {
+    /// All variables, function arguments and fields are checked.
+    All,
+    /// Only global variables (including fields of constant buffers) are checked.
+    GlobalsOnly,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -18,3 +78,1223 @@ pub enum Case {
     Snake,
     UpperSnake,
 }
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EnumValueLayout {
+    OnePerLine,
+    Packed,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    /// Returns characters that end a line.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ArgumentWrap {
+    AllOnOneLine,
+    AlignWithParenthesis,
+    OnePerLine,
+}
+
+/// Named set of rules that is used as a base for rules from the config file.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Preset {
+    Unreal,
+    Unity,
+    Google,
+    Allman,
+}
+
+impl Preset {
+    /// Names of all presets.
+    pub const NAMES: [&'static str; 4] = ["Unreal", "Unity", "Google", "Allman"];
+
+    /// Returns a preset that uses the specified name (like `Unreal`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Unreal" => Some(Self::Unreal),
+            "Unity" => Some(Self::Unity),
+            "Google" => Some(Self::Google),
+            "Allman" => Some(Self::Allman),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ShaderStage {
+    Vertex,
+    Fragment,
+    Compute,
+    Geometry,
+    Hull,
+    Domain,
+    Mesh,
+    Amplification,
+}
+
+impl ShaderStage {
+    /// Returns a shader stage that uses the specified file extension (like `frag`).
+    pub fn from_file_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "vert" => Some(Self::Vertex),
+            "frag" => Some(Self::Fragment),
+            "comp" => Some(Self::Compute),
+            "geom" => Some(Self::Geometry),
+            "tesc" => Some(Self::Hull),
+            "tese" => Some(Self::Domain),
+            "mesh" => Some(Self::Mesh),
+            "task" => Some(Self::Amplification),
+            _ => None,
+        }
+    }
+
+    /// Names of config sections with stage-specific rules.
+    pub const CONFIG_SECTION_NAMES: [&'static str; 8] = [
+        "Vertex",
+        "Fragment",
+        "Compute",
+        "Geometry",
+        "Hull",
+        "Domain",
+        "Mesh",
+        "Amplification",
+    ];
+
+    /// Returns a shader stage that uses the specified name of a config section (like `Fragment`).
+    pub fn from_config_section_name(name: &str) -> Option<Self> {
+        match name {
+            "Vertex" => Some(Self::Vertex),
+            "Fragment" => Some(Self::Fragment),
+            "Compute" => Some(Self::Compute),
+            "Geometry" => Some(Self::Geometry),
+            "Hull" => Some(Self::Hull),
+            "Domain" => Some(Self::Domain),
+            "Mesh" => Some(Self::Mesh),
+            "Amplification" => Some(Self::Amplification),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of a rule.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RuleCategory {
+    /// Rule that changes the code.
+    Formatting,
+    /// Rule that reports code that needs to be changed manually (or configures such rules).
+    Lint,
+}
+
+/// Defines how violations of a lint rule are reported.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Severity {
+    /// Formatting fails.
+    Error,
+    /// A warning is printed and formatting continues.
+    Warning,
+    /// The rule is not checked.
+    Off,
+}
+
+impl Severity {
+    /// Names of all severities.
+    pub const NAMES: [&'static str; 3] = ["Error", "Warning", "Off"];
+
+    /// Returns a severity that uses the specified name (like `Warning`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Error" => Some(Self::Error),
+            "Warning" => Some(Self::Warning),
+            "Off" => Some(Self::Off),
+            _ => None,
+        }
+    }
+}
+
+/// Type of a value of a rule.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RuleValueType {
+    Bool,
+    UnsignedInteger,
+    /// A string with a single character.
+    Char,
+    /// A string with a regular expression.
+    Regex,
+    /// An array of strings.
+    StringArray,
+    /// A string or a non-empty array of strings.
+    StringOrStringArray,
+    /// One of the specified strings.
+    Enum(&'static [&'static str]),
+}
+
+/// Description of a rule that can be specified in the config file.
+pub struct RuleInfo {
+    /// Stable identifier of the rule, used as a key in the config file, in `NOLINT` comments
+    /// and in reported errors.
+    pub id: &'static str,
+    pub category: RuleCategory,
+    pub description: &'static str,
+    /// Type of values that the rule accepts.
+    pub value_type: RuleValueType,
+    /// Default value (in TOML syntax), `None` if the rule is not checked by default.
+    pub default: Option<&'static str>,
+    /// Value (in TOML syntax) that is used in the example.
+    pub example_value: &'static str,
+    /// Code before the rule (with the example value) is applied, `None` if the rule does not
+    /// look at the code (like limits on files that are formatted).
+    pub example_before: Option<&'static str>,
+    /// Code after the rule (with the example value) is applied, `None` if the code is only
+    /// reported (not changed) or if the rule does not look at the code.
+    pub example_after: Option<&'static str>,
+}
+
+impl RuleInfo {
+    /// Returns human-readable description of values that the rule accepts.
+    pub fn allowed_values(&self) -> String {
+        match self.value_type {
+            RuleValueType::Bool => "true or false".to_owned(),
+            RuleValueType::UnsignedInteger => "unsigned integer".to_owned(),
+            RuleValueType::Char => "single character".to_owned(),
+            RuleValueType::Regex => "regular expression".to_owned(),
+            RuleValueType::StringArray => "array of strings".to_owned(),
+            RuleValueType::StringOrStringArray => "string or array of strings".to_owned(),
+            RuleValueType::Enum(names) => {
+                let names = names
+                    .iter()
+                    .map(|name| format!("\"{}\"", name))
+                    .collect::<Vec<_>>();
+                match names.split_last() {
+                    Some((last, [])) => last.clone(),
+                    Some((last, others)) => format!("{} or {}", others.join(", "), last),
+                    None => String::new(),
+                }
+            }
+        }
+    }
+}
+
+/// Names of case styles.
+const CASE_NAMES: &[&str] = &["Camel", "Pascal", "Snake", "UpperSnake"];
+
+/// All rules that can be specified in the config file.
+pub const RULES: &[RuleInfo] = &[
+    RuleInfo {
+        id: "Indentation",
+        category: RuleCategory::Formatting,
+        description: "Characters that are used to indent lines of code.",
+        value_type: RuleValueType::Enum(&["Tab", "TwoSpaces", "FourSpaces", "Auto"]),
+        default: Some("\"FourSpaces\""),
+        example_value: "\"TwoSpaces\"",
+        example_before: Some("void foo() {\n    return;\n}"),
+        example_after: Some("void foo() {\n  return;\n}"),
+    },
+    RuleInfo {
+        id: "NewLineOnOpenBrace",
+        category: RuleCategory::Formatting,
+        description: "Whether to put a new line before an open brace or after it.",
+        value_type: RuleValueType::Enum(&["Before", "After", "Auto"]),
+        default: Some("\"After\""),
+        example_value: "\"Before\"",
+        example_before: Some("void foo() {\n    return;\n}"),
+        example_after: Some("void foo()\n{\n    return;\n}"),
+    },
+    RuleInfo {
+        id: "EmptyBodyStyle",
+        category: RuleCategory::Formatting,
+        description: "Whether to put braces of empty function, struct and statement bodies on the declaration line (`Collapsed`) or on separate lines (`Separate`).",
+        value_type: RuleValueType::Enum(&["Collapsed", "Separate"]),
+        default: Some("\"Separate\""),
+        example_value: "\"Collapsed\"",
+        example_before: Some("void foo() {\n}"),
+        example_after: Some("void foo() {}"),
+    },
+    RuleInfo {
+        id: "MaxEmptyLines",
+        category: RuleCategory::Formatting,
+        description: "How much consecutive empty lines to keep.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: Some("1"),
+        example_value: "1",
+        example_before: Some("int a;\n\n\n\nint b;"),
+        example_after: Some("int a;\n\nint b;"),
+    },
+    RuleInfo {
+        id: "SpacesInBrackets",
+        category: RuleCategory::Formatting,
+        description: "Whether to add spaces inside of `()` and `[]`.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("foo(param1, values[0]);"),
+        example_after: Some("foo( param1, values[ 0 ] );"),
+    },
+    RuleInfo {
+        id: "SpacesInAngleBrackets",
+        category: RuleCategory::Formatting,
+        description: "Whether to add spaces inside of `<>` of template arguments.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("Texture2D<float4> texture;"),
+        example_after: Some("Texture2D< float4 > texture;"),
+    },
+    RuleInfo {
+        id: "SpaceBeforeInheritanceColon",
+        category: RuleCategory::Formatting,
+        description: "Whether to add a space before the colon that separates a struct name from its base types.",
+        value_type: RuleValueType::Bool,
+        default: Some("true"),
+        example_value: "false",
+        example_before: Some("struct Derived : Base {};"),
+        example_after: Some("struct Derived: Base {};"),
+    },
+    RuleInfo {
+        id: "CollapseWhitespace",
+        category: RuleCategory::Formatting,
+        description: "Whether to replace multiple spaces and tabs between code on a line with a single space (comments, strings, preprocessor directives and spaces before comments are not changed).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("float  value =\tfoo(a,   b);"),
+        example_after: Some("float value = foo(a, b);"),
+    },
+    RuleInfo {
+        id: "RemoveSpaceAfterUnaryOperators",
+        category: RuleCategory::Formatting,
+        description: "Whether to remove spaces between unary operators (`-`, `+`, `!` and `~`) and their operands (spaces around binary operators are not changed).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("bool disabled = ! enabled;\nfloat value = - x - y;"),
+        example_after: Some("bool disabled = !enabled;\nfloat value = -x - y;"),
+    },
+    RuleInfo {
+        id: "SpacesAroundTernaryOperator",
+        category: RuleCategory::Formatting,
+        description: "Whether to put a single space around `?` and `:` of ternary expressions, long lines are split before `?` and `:` (when `MaxLineLength` is specified).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("float value = isEnabled?1.0:0.0;"),
+        example_after: Some("float value = isEnabled ? 1.0 : 0.0;"),
+    },
+    RuleInfo {
+        id: "RemoveSpacesAroundMemberAccess",
+        category: RuleCategory::Formatting,
+        description: "Whether to remove spaces around `.` of member (and swizzle) access and around `::` of namespaces and static members (lines that start with `.` are not changed).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("float3 normal = input . normal . xyz;\nfloat value = Math :: PI;"),
+        example_after: Some("float3 normal = input.normal.xyz;\nfloat value = Math::PI;"),
+    },
+    RuleInfo {
+        id: "RemoveRedundantSemicolons",
+        category: RuleCategory::Formatting,
+        description: "Whether to remove empty statements (like `;;`) and semicolons after function bodies (semicolons after struct, cbuffer and enum definitions are kept).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("void foo() {\n    bar();;\n};"),
+        example_after: Some("void foo() {\n    bar();\n}"),
+    },
+    RuleInfo {
+        id: "NormalizeLocalSizeLayout",
+        category: RuleCategory::Formatting,
+        description: "(GLSL) Whether to normalize spacing and order (x, y, z) of qualifiers in work group size declarations of compute shaders.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("layout(local_size_y=8,local_size_x=8)in;"),
+        example_after: Some("layout(local_size_x = 8, local_size_y = 8) in;"),
+    },
+    RuleInfo {
+        id: "SortQualifiers",
+        category: RuleCategory::Formatting,
+        description: "Whether to sort qualifiers of declarations (invariant, interpolation, auxiliary, storage, `precise`, `const`, precision).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("in flat int id;\nconst static float scale = 2.0;"),
+        example_after: Some("flat in int id;\nstatic const float scale = 2.0;"),
+    },
+    RuleInfo {
+        id: "NormalizeReturnSpacing",
+        category: RuleCategory::Formatting,
+        description: "Whether to put exactly one space between `return` and the returned value and no space before the `;` of `return` statements.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("return(value) ;"),
+        example_after: Some("return (value);"),
+    },
+    RuleInfo {
+        id: "NormalizeDeclarationSpacing",
+        category: RuleCategory::Formatting,
+        description: "Whether to replace multiple spaces and tabs between the type and the name of a declaration with a single space.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("float   value = 1.0;"),
+        example_after: Some("float value = 1.0;"),
+    },
+    RuleInfo {
+        id: "NormalizeDefaultValueSpacing",
+        category: RuleCategory::Formatting,
+        description: "Whether to put exactly one space before and after the `=` of default values of function arguments.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("void foo(float scale=1.0) {}"),
+        example_after: Some("void foo(float scale = 1.0) {}"),
+    },
+    RuleInfo {
+        id: "EmptyLineBeforeDocComments",
+        category: RuleCategory::Formatting,
+        description: "Whether to put an empty line before documentation comments (`///` and `/**`) that follow code.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("float first;\n/** Docs. */\nfloat second;"),
+        example_after: Some("float first;\n\n/** Docs. */\nfloat second;"),
+    },
+    RuleInfo {
+        id: "KeepUserLineBreaks",
+        category: RuleCategory::Formatting,
+        description: "Whether to never join lines that were split by the author (only indentation and spacing of such lines is fixed).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("float value = foo(a,\n        b\n);"),
+        example_after: Some("float value = foo(a,\n    b\n);"),
+    },
+    RuleInfo {
+        id: "BinPackParameters",
+        category: RuleCategory::Formatting,
+        description: "If disabled, parameters of function declarations on lines longer than `MaxLineLength` are placed on separate lines.",
+        value_type: RuleValueType::Bool,
+        default: Some("true"),
+        example_value: "false",
+        example_before: Some("float4 shade(float3 normal, float3 lightDirection, float3 viewDirection) {"),
+        example_after: Some("float4 shade(float3 normal,\n             float3 lightDirection,\n             float3 viewDirection) {"),
+    },
+    RuleInfo {
+        id: "AlignInitializerColumns",
+        category: RuleCategory::Formatting,
+        description: "Whether to place each row of matrix initializers on a separate line and align elements by columns.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("float2x2 m = {1, 0, 10, 1};"),
+        example_after: Some("float2x2 m = {\n    1,  0,\n    10, 1\n};"),
+    },
+    RuleInfo {
+        id: "CommentBannerChar",
+        category: RuleCategory::Formatting,
+        description: "Character that is used in \"section divider\" comments (like `//-----`).",
+        value_type: RuleValueType::Char,
+        default: None,
+        example_value: "\"-\"",
+        example_before: Some("//=========="),
+        example_after: Some("//----------"),
+    },
+    RuleInfo {
+        id: "CommentBannerWidth",
+        category: RuleCategory::Formatting,
+        description: "Width of \"section divider\" comments (only used when `CommentBannerChar` is specified).",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "12",
+        example_before: Some("//-------"),
+        example_after: Some("//----------"),
+    },
+    RuleInfo {
+        id: "IndentPreprocessor",
+        category: RuleCategory::Formatting,
+        description: "Whether preprocessor directives are indented.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("void foo() {\n#ifdef FOO\n    return;\n#endif\n}"),
+        example_after: Some("void foo() {\n    #ifdef FOO\n    return;\n    #endif\n}"),
+    },
+    RuleInfo {
+        id: "PreprocessorIfCreatesNesting",
+        category: RuleCategory::Formatting,
+        description: "Whether `#if`, `#ifdef`, `#elif` and `#else` create nesting (only works with `IndentPreprocessor`).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("#ifdef FOO\n#define BAR\n#endif"),
+        example_after: Some("#ifdef FOO\n    #define BAR\n#endif"),
+    },
+    RuleInfo {
+        id: "RequireDocsOnFunctions",
+        category: RuleCategory::Lint,
+        description: "Whether documentation comments on functions are required.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("int foo(int value) {}"),
+        example_after: Some("/**\n* Function docs.\n*\n* @param value Input value docs.\n*\n* @return Return value docs.\n*/\nint foo(int value) {}"),
+    },
+    RuleInfo {
+        id: "FunctionDocsPlacement",
+        category: RuleCategory::Lint,
+        description: "Which one of a function declaration (prototype) and its definition must have docs when `RequireDocsOnFunctions` is enabled.",
+        value_type: RuleValueType::Enum(&["Declaration", "Definition"]),
+        default: None,
+        example_value: "\"Declaration\"",
+        example_before: Some("/** Docs. */\nvoid foo();\n\nvoid foo() {}"),
+        example_after: Some("/** Docs. */\nvoid foo();\n\nvoid foo() {}"),
+    },
+    RuleInfo {
+        id: "RequireDocsOnStructs",
+        category: RuleCategory::Lint,
+        description: "Whether documentation comments on structs are required.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("struct Light {};"),
+        example_after: Some("/** Light source. */\nstruct Light {};"),
+    },
+    RuleInfo {
+        id: "RequireDocsOnFields",
+        category: RuleCategory::Lint,
+        description: "Whether documentation comments on struct fields are required.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("struct Light {\n    float3 color;\n};"),
+        example_after: Some("struct Light {\n    /** Color in linear space. */\n    float3 color;\n};"),
+    },
+    RuleInfo {
+        id: "RequireOrderedParamDocs",
+        category: RuleCategory::Lint,
+        description: "Whether `@param` entries in function docs must follow the order of the arguments (entries on separate lines are reordered when formatting).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("// @param b Second.\n// @param a First.\nvoid foo(float a, float b) {}"),
+        example_after: Some("// @param a First.\n// @param b Second.\nvoid foo(float a, float b) {}"),
+    },
+    RuleInfo {
+        id: "RequireParamDocsDirection",
+        category: RuleCategory::Lint,
+        description: "Whether `@param` entries of `out`/`inout` arguments must specify the direction (`[out]`/`[in,out]`).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("// @param result Result.\nvoid foo(out float result) {}"),
+        example_after: Some("// @param[out] result Result.\nvoid foo(out float result) {}"),
+    },
+    RuleInfo {
+        id: "RequireBriefInDocs",
+        category: RuleCategory::Lint,
+        description: "Whether the first line of function and struct docs must be a summary (or `@brief`) and not a tag like `@param`.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("/** @param value Input value. */\nvoid foo(float value) {}"),
+        example_after: Some("/**\n * Does something.\n *\n * @param value Input value.\n */\nvoid foo(float value) {}"),
+    },
+    RuleInfo {
+        id: "VersionDirectiveFirst",
+        category: RuleCategory::Lint,
+        description: "(GLSL) Whether `#version` must be the first directive followed by `#extension` directives.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("#define FOO\n#version 450"),
+        example_after: Some("#version 450\n#define FOO"),
+    },
+    RuleInfo {
+        id: "IncludeDirs",
+        category: RuleCategory::Lint,
+        description: "Directories (relative to the config file) to look for included files.",
+        value_type: RuleValueType::StringArray,
+        default: Some("[]"),
+        example_value: "[\"include\"]",
+        example_before: None,
+        example_after: None,
+    },
+    RuleInfo {
+        id: "RequireExistingIncludes",
+        category: RuleCategory::Lint,
+        description: "Whether files included using `#include` must exist.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("#include \"missing.hlsl\""),
+        example_after: Some("#include \"common.hlsl\""),
+    },
+    RuleInfo {
+        id: "ForbidCircularIncludes",
+        category: RuleCategory::Lint,
+        description: "Whether to report included files that (directly or indirectly) include the file being formatted.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("// a.hlsl\n#include \"b.hlsl\" // b.hlsl includes a.hlsl"),
+        example_after: Some("// a.hlsl\n#include \"common.hlsl\""),
+    },
+    RuleInfo {
+        id: "ForbidUnusedVariables",
+        category: RuleCategory::Lint,
+        description: "Whether to report local variables that are never read.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("void foo() {\n    int value = 1;\n}"),
+        example_after: Some("void foo() {\n}"),
+    },
+    RuleInfo {
+        id: "ForbidUnusedParameters",
+        category: RuleCategory::Lint,
+        description: "Whether to report function parameters that are never used.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("int foo(int a, int b) {\n    return a;\n}"),
+        example_after: Some("int foo(int a) {\n    return a;\n}"),
+    },
+    RuleInfo {
+        id: "WarnOnShadowing",
+        category: RuleCategory::Lint,
+        description: "Whether to report local variables that use the name of a parameter or a variable from an outer scope.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("void foo(int value) {\n    int value = 1;\n}"),
+        example_after: Some("void foo(int value) {\n    int otherValue = 1;\n}"),
+    },
+    RuleInfo {
+        id: "WarnOnUninitializedVariables",
+        category: RuleCategory::Lint,
+        description: "Whether to report local variables that are read before a value is assigned to them.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("int foo() {\n    int value;\n    return value;\n}"),
+        example_after: Some("int foo() {\n    int value = 0;\n    return value;\n}"),
+    },
+    RuleInfo {
+        id: "ReportUnusedFunctions",
+        category: RuleCategory::Lint,
+        description: "When formatting a directory, whether to report functions that are not called in any file.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("void unused() {}"),
+        example_after: None,
+    },
+    RuleInfo {
+        id: "ForbidDuplicateBindings",
+        category: RuleCategory::Lint,
+        description: "Whether to report resources that are bound to the same slot.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("Texture2D a : register(t0);\nTexture2D b : register(t0);"),
+        example_after: Some("Texture2D a : register(t0);\nTexture2D b : register(t1);"),
+    },
+    RuleInfo {
+        id: "ForbidDuplicateLocations",
+        category: RuleCategory::Lint,
+        description: "Whether to report GLSL `in`/`out` variables that use the same location.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("layout(location = 0) in vec2 uv;\nlayout(location = 0) in vec3 normal;"),
+        example_after: Some("layout(location = 0) in vec2 uv;\nlayout(location = 1) in vec3 normal;"),
+    },
+    RuleInfo {
+        id: "ForbidMixedIndentation",
+        category: RuleCategory::Lint,
+        description: "Whether to report lines that use both tabs and spaces in their indentation.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("void foo() {\n\t    return;\n}"),
+        example_after: Some("void foo() {\n\treturn;\n}"),
+    },
+    RuleInfo {
+        id: "RequireOrderedLocations",
+        category: RuleCategory::Lint,
+        description: "Whether to require GLSL `in`/`out` variables to be declared in ascending order of their locations.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("layout(location = 1) in vec3 normal;\nlayout(location = 0) in vec2 uv;"),
+        example_after: Some("layout(location = 0) in vec2 uv;\nlayout(location = 1) in vec3 normal;"),
+    },
+    RuleInfo {
+        id: "ForbidMacroRedefinition",
+        category: RuleCategory::Lint,
+        description: "Whether to report macros that are defined again with a different body.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("#define SIZE 1\n#define SIZE 2"),
+        example_after: Some("#define SIZE 1"),
+    },
+    RuleInfo {
+        id: "ForbidDeadBranches",
+        category: RuleCategory::Lint,
+        description: "Whether to report branches of conditional directives that are never active with the values of macros from the `Defines` section.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("#if USE_FOG // USE_FOG = 0\nfloat fogDensity;\n#endif"),
+        example_after: None,
+    },
+    RuleInfo {
+        id: "ValidateNolintComments",
+        category: RuleCategory::Lint,
+        description: "Whether to report `NOLINT` comments with unknown rules or without a matching `NOLINTEND`.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("int value; // NOLINT(VariableCas)"),
+        example_after: Some("int value; // NOLINT(VariableCase)"),
+    },
+    RuleInfo {
+        id: "RequireDefaultInSwitch",
+        category: RuleCategory::Lint,
+        description: "Whether `switch` statements must have a `default` label.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("switch (value) {\n    case 0: break;\n}"),
+        example_after: Some("switch (value) {\n    case 0: break;\n    default: break;\n}"),
+    },
+    RuleInfo {
+        id: "RequireDefaultPrecision",
+        category: RuleCategory::Lint,
+        description: "(GLSL ES) Whether fragment shaders must specify the default precision of floats.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("out vec4 color;"),
+        example_after: Some("precision mediump float;\nout vec4 color;"),
+    },
+    RuleInfo {
+        id: "RequireLocalSize",
+        category: RuleCategory::Lint,
+        description: "(GLSL) Whether compute shaders must declare the work group size (like `layout(local_size_x = 8) in;`).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("void main() {}"),
+        example_after: Some("layout(local_size_x = 64) in;\nvoid main() {}"),
+    },
+    RuleInfo {
+        id: "RequireNumthreads",
+        category: RuleCategory::Lint,
+        description: "(HLSL) Whether entry points of compute shaders must have the `numthreads` attribute.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("void main(uint3 id : SV_DispatchThreadID) {}"),
+        example_after: Some("[numthreads(8, 8, 1)]\nvoid main(uint3 id : SV_DispatchThreadID) {}"),
+    },
+    RuleInfo {
+        id: "ValidateSemantics",
+        category: RuleCategory::Lint,
+        description: "(HLSL) Whether semantics of function arguments, return values and struct fields must be known system-value semantics or one of `CustomSemantics`.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("float4 main() : SV_POSITON {}"),
+        example_after: Some("float4 main() : SV_Position {}"),
+    },
+    RuleInfo {
+        id: "CustomSemantics",
+        category: RuleCategory::Lint,
+        description: "(HLSL) Semantics (without indices) that are allowed by `ValidateSemantics` in addition to system-value semantics (if empty any other semantics are allowed).",
+        value_type: RuleValueType::StringArray,
+        default: Some("[]"),
+        example_value: "[\"TEXCOORD\", \"NORMAL\"]",
+        example_before: Some("float2 uv : TEXCORD0;"),
+        example_after: Some("float2 uv : TEXCOORD0;"),
+    },
+    RuleInfo {
+        id: "VariableCase",
+        category: RuleCategory::Lint,
+        description: "Case style of variables.",
+        value_type: RuleValueType::Enum(CASE_NAMES),
+        default: None,
+        example_value: "\"Camel\"",
+        example_before: Some("int my_value;"),
+        example_after: Some("int myValue;"),
+    },
+    RuleInfo {
+        id: "LocalVariableCase",
+        category: RuleCategory::Lint,
+        description: "Case style of local variables and function arguments (overrides `VariableCase`).",
+        value_type: RuleValueType::Enum(CASE_NAMES),
+        default: None,
+        example_value: "\"Snake\"",
+        example_before: Some("void foo() { int myValue; }"),
+        example_after: Some("void foo() { int my_value; }"),
+    },
+    RuleInfo {
+        id: "GlobalVariableCase",
+        category: RuleCategory::Lint,
+        description: "Case style of global variables (overrides `VariableCase`).",
+        value_type: RuleValueType::Enum(CASE_NAMES),
+        default: None,
+        example_value: "\"Pascal\"",
+        example_before: Some("int myValue;"),
+        example_after: Some("int MyValue;"),
+    },
+    RuleInfo {
+        id: "StaticVariableCase",
+        category: RuleCategory::Lint,
+        description: "Case style of static variables (overrides `GlobalVariableCase`, `LocalVariableCase` and `VariableCase`).",
+        value_type: RuleValueType::Enum(CASE_NAMES),
+        default: None,
+        example_value: "\"UpperSnake\"",
+        example_before: Some("static const int maxCount = 8;"),
+        example_after: Some("static const int MAX_COUNT = 8;"),
+    },
+    RuleInfo {
+        id: "FunctionCase",
+        category: RuleCategory::Lint,
+        description: "Case style of functions and methods.",
+        value_type: RuleValueType::Enum(CASE_NAMES),
+        default: None,
+        example_value: "\"Camel\"",
+        example_before: Some("void do_something() {}"),
+        example_after: Some("void doSomething() {}"),
+    },
+    RuleInfo {
+        id: "EntryPointNamePattern",
+        category: RuleCategory::Lint,
+        description: "Regular expression that names of shader entry points must match.",
+        value_type: RuleValueType::Regex,
+        default: None,
+        example_value: "\"^(VS|PS|CS)Main$\"",
+        example_before: Some("float4 pixel() : SV_Target {}"),
+        example_after: Some("float4 PSMain() : SV_Target {}"),
+    },
+    RuleInfo {
+        id: "ForbiddenIdentifiers",
+        category: RuleCategory::Lint,
+        description: "Names that can't be used in declarations (like project-specific reserved words).",
+        value_type: RuleValueType::StringArray,
+        default: Some("[]"),
+        example_value: "[\"input\", \"output\"]",
+        example_before: Some("float input;"),
+        example_after: Some("float input_;"),
+    },
+    RuleInfo {
+        id: "ForbiddenIdentifierPattern",
+        category: RuleCategory::Lint,
+        description: "Regular expression that names used in declarations must not match.",
+        value_type: RuleValueType::Regex,
+        default: None,
+        example_value: "\"^gl_|__\"",
+        example_before: Some("float my__value;"),
+        example_after: Some("float myvalue;"),
+    },
+    RuleInfo {
+        id: "MinIdentifierLength",
+        category: RuleCategory::Lint,
+        description: "Minimum length of names of variables, arguments, functions and struct fields.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "2",
+        example_before: Some("float w;"),
+        example_after: Some("float weight;"),
+    },
+    RuleInfo {
+        id: "MaxIdentifierLength",
+        category: RuleCategory::Lint,
+        description: "Maximum length of names of variables, arguments, functions and struct fields.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "30",
+        example_before: Some("float weightOfTheCurrentSampleInTheKernel;"),
+        example_after: Some("float sampleWeight;"),
+    },
+    RuleInfo {
+        id: "IdentifierLengthAllowlist",
+        category: RuleCategory::Lint,
+        description: "Names that are allowed regardless of `MinIdentifierLength` and `MaxIdentifierLength`.",
+        value_type: RuleValueType::StringArray,
+        default: Some("[\"i\", \"j\", \"k\", \"n\", \"x\", \"y\", \"z\", \"uv\"]"),
+        example_value: "[\"i\", \"uv\"]",
+        example_before: Some("float2 uv; // allowed even if shorter than `MinIdentifierLength`"),
+        example_after: Some("float2 uv;"),
+    },
+    RuleInfo {
+        id: "StructCase",
+        category: RuleCategory::Lint,
+        description: "Case style of structs, classes and interfaces.",
+        value_type: RuleValueType::Enum(CASE_NAMES),
+        default: None,
+        example_value: "\"Pascal\"",
+        example_before: Some("struct point_light {};"),
+        example_after: Some("struct PointLight {};"),
+    },
+    RuleInfo {
+        id: "EnumCase",
+        category: RuleCategory::Lint,
+        description: "Case style of enums.",
+        value_type: RuleValueType::Enum(CASE_NAMES),
+        default: None,
+        example_value: "\"Pascal\"",
+        example_before: Some("enum light_type {};"),
+        example_after: Some("enum LightType {};"),
+    },
+    RuleInfo {
+        id: "EnumValueCase",
+        category: RuleCategory::Lint,
+        description: "Case style of enum values.",
+        value_type: RuleValueType::Enum(CASE_NAMES),
+        default: None,
+        example_value: "\"UpperSnake\"",
+        example_before: Some("enum LightType { pointLight };"),
+        example_after: Some("enum LightType { POINT_LIGHT };"),
+    },
+    RuleInfo {
+        id: "Acronyms",
+        category: RuleCategory::Lint,
+        description: "Abbreviations that case rules treat as single words written in uppercase (like `RGB` in `lightRGB`).",
+        value_type: RuleValueType::StringArray,
+        default: Some("[]"),
+        example_value: "[\"RGB\", \"UV\"]",
+        example_before: Some("float3 lightRgb;"),
+        example_after: Some("float3 lightRGB;"),
+    },
+    RuleInfo {
+        id: "EnumValueLayout",
+        category: RuleCategory::Formatting,
+        description: "How to place values of enums.",
+        value_type: RuleValueType::Enum(&["OnePerLine", "Packed"]),
+        default: None,
+        example_value: "\"OnePerLine\"",
+        example_before: Some("enum Foo { A, B };"),
+        example_after: Some("enum Foo {\n    A,\n    B\n};"),
+    },
+    RuleInfo {
+        id: "EnumTrailingComma",
+        category: RuleCategory::Formatting,
+        description: "Whether to add a comma after the last enum value (when `EnumValueLayout` is `OnePerLine`).",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("enum Foo {\n    A,\n    B\n};"),
+        example_after: Some("enum Foo {\n    A,\n    B,\n};"),
+    },
+    RuleInfo {
+        id: "MaxLineLength",
+        category: RuleCategory::Formatting,
+        description: "Maximum length of a line.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "30",
+        example_before: Some("float w[3] = {0.2, 0.6, 0.2};"),
+        example_after: Some("float w[3] = {\n    0.2,\n    0.6,\n    0.2\n};"),
+    },
+    RuleInfo {
+        id: "ArgumentWrap",
+        category: RuleCategory::Formatting,
+        description: "How to split arguments of function calls on lines longer than `MaxLineLength`.",
+        value_type: RuleValueType::Enum(&["AllOnOneLine", "AlignWithParenthesis", "OnePerLine"]),
+        default: None,
+        example_value: "\"OnePerLine\"",
+        example_before: Some("float4 color = shade(normal, lightDirection);"),
+        example_after: Some("float4 color = shade(\n    normal,\n    lightDirection);"),
+    },
+    RuleInfo {
+        id: "BreakBeforeBinaryOperators",
+        category: RuleCategory::Formatting,
+        description: "Whether to place binary operators of split expressions at the beginning of continuation lines.",
+        value_type: RuleValueType::Bool,
+        default: None,
+        example_value: "true",
+        example_before: Some("float value = firstValue + secondValue;"),
+        example_after: Some("float value = firstValue\n    + secondValue;"),
+    },
+    RuleInfo {
+        id: "StatementAttributePlacement",
+        category: RuleCategory::Formatting,
+        description: "Where to place HLSL statement attributes like `[unroll]` or `[branch]`.",
+        value_type: RuleValueType::Enum(&["SeparateLine", "SameLine"]),
+        default: None,
+        example_value: "\"SeparateLine\"",
+        example_before: Some("[unroll] for (int i = 0; i < 4; i++) {\n}"),
+        example_after: Some("[unroll]\nfor (int i = 0; i < 4; i++) {\n}"),
+    },
+    RuleInfo {
+        id: "RequireBracesOnIf",
+        category: RuleCategory::Formatting,
+        description: "When to add braces around the body of `if` statements.",
+        value_type: RuleValueType::Enum(&["Always", "MultiLine"]),
+        default: None,
+        example_value: "\"Always\"",
+        example_before: Some("if (enabled) return;"),
+        example_after: Some("if (enabled) {\n    return;\n}"),
+    },
+    RuleInfo {
+        id: "RequireBracesOnElse",
+        category: RuleCategory::Formatting,
+        description: "When to add braces around the body of `else` (`else if` is not affected).",
+        value_type: RuleValueType::Enum(&["Always", "MultiLine"]),
+        default: None,
+        example_value: "\"MultiLine\"",
+        example_before: Some("else\n    return;"),
+        example_after: Some("else {\n    return;\n}"),
+    },
+    RuleInfo {
+        id: "RequireBracesOnFor",
+        category: RuleCategory::Formatting,
+        description: "When to add braces around the body of `for` loops.",
+        value_type: RuleValueType::Enum(&["Always", "MultiLine"]),
+        default: None,
+        example_value: "\"Always\"",
+        example_before: Some("for (int i = 0; i < count; i++) sum += i;"),
+        example_after: Some("for (int i = 0; i < count; i++) {\n    sum += i;\n}"),
+    },
+    RuleInfo {
+        id: "RequireBracesOnWhile",
+        category: RuleCategory::Formatting,
+        description: "When to add braces around the body of `while` loops.",
+        value_type: RuleValueType::Enum(&["Always", "MultiLine"]),
+        default: None,
+        example_value: "\"Always\"",
+        example_before: Some("while (count > 0) count--;"),
+        example_after: Some("while (count > 0) {\n    count--;\n}"),
+    },
+    RuleInfo {
+        id: "TrailingCommaInInitializers",
+        category: RuleCategory::Formatting,
+        description: "Whether brace initializer lists end with a comma after the last element.",
+        value_type: RuleValueType::Enum(&["Always", "Never", "Multiline"]),
+        default: None,
+        example_value: "\"Never\"",
+        example_before: Some("float weights[2] = {0.5, 0.5,};"),
+        example_after: Some("float weights[2] = {0.5, 0.5};"),
+    },
+    RuleInfo {
+        id: "LineEnding",
+        category: RuleCategory::Formatting,
+        description: "Characters that are used to end lines (line endings are not changed unless specified).",
+        value_type: RuleValueType::Enum(&["Lf", "CrLf"]),
+        default: None,
+        example_value: "\"Lf\"",
+        example_before: Some("void foo() {\r\n    return;\r\n}"),
+        example_after: Some("void foo() {\n    return;\n}"),
+    },
+    RuleInfo {
+        id: "InsertFinalNewline",
+        category: RuleCategory::Formatting,
+        description: "Whether a file must end with a line break or must not end with a line break (not checked unless specified).",
+        value_type: RuleValueType::Bool,
+        default: None,
+        example_value: "true",
+        example_before: Some("void foo() {}"),
+        example_after: Some("void foo() {}\n"),
+    },
+    RuleInfo {
+        id: "SpaceBeforeConstructorParenthesis",
+        category: RuleCategory::Formatting,
+        description: "Whether to put a space between the type and `(` of type constructors like `float3(x, y, z)`, arguments of type constructors are separated by `, ` (not checked unless specified).",
+        value_type: RuleValueType::Bool,
+        default: None,
+        example_value: "false",
+        example_before: Some("float3 color = float3 (r,g,b);"),
+        example_after: Some("float3 color = float3(r, g, b);"),
+    },
+    RuleInfo {
+        id: "SpaceAfterCast",
+        category: RuleCategory::Formatting,
+        description: "Whether to put a space after casts to scalar, vector and matrix types like `(int)` (not checked unless specified).",
+        value_type: RuleValueType::Bool,
+        default: None,
+        example_value: "false",
+        example_before: Some("int index = (int) value;"),
+        example_after: Some("int index = (int)value;"),
+    },
+    RuleInfo {
+        id: "MaxFunctionLines",
+        category: RuleCategory::Lint,
+        description: "Maximum number of lines in a function body.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "1",
+        example_before: Some("void foo() {\n    bar();\n    bar();\n}"),
+        example_after: Some("void foo() {\n    bar();\n}"),
+    },
+    RuleInfo {
+        id: "MaxNestingDepth",
+        category: RuleCategory::Lint,
+        description: "Maximum nesting depth of blocks inside of a function body.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "1",
+        example_before: Some("if (a) {\n    if (b) {\n    }\n}"),
+        example_after: Some("if (a && b) {\n}"),
+    },
+    RuleInfo {
+        id: "MaxPushConstantSize",
+        category: RuleCategory::Lint,
+        description: "Maximum size (in bytes, without padding) of push constant blocks.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "128",
+        example_before: Some("layout(push_constant) uniform Constants {\n    mat4 mvp;\n    mat4 model;\n    mat4 view;\n};"),
+        example_after: Some("layout(push_constant) uniform Constants {\n    mat4 mvp;\n    mat4 model;\n};"),
+    },
+    RuleInfo {
+        id: "MaxDepth",
+        category: RuleCategory::Formatting,
+        description: "Maximum depth of subdirectories to look for files in (when formatting a directory).",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "1",
+        example_before: None,
+        example_after: None,
+    },
+    RuleInfo {
+        id: "MaxFileSize",
+        category: RuleCategory::Formatting,
+        description: "If specified, files larger than this size (in bytes) are skipped.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "1024",
+        example_before: None,
+        example_after: None,
+    },
+    RuleInfo {
+        id: "MaxFormatTime",
+        category: RuleCategory::Formatting,
+        description: "Files that take longer than this time (in milliseconds) to format are skipped with a warning.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "5000",
+        example_before: None,
+        example_after: None,
+    },
+    RuleInfo {
+        id: "StreamingChunkSize",
+        category: RuleCategory::Formatting,
+        description: "If specified, files larger than `MaxFileSize` are formatted in chunks of about this size (in bytes, split between top-level statements) instead of being skipped.",
+        value_type: RuleValueType::UnsignedInteger,
+        default: None,
+        example_value: "1048576",
+        example_before: None,
+        example_after: None,
+    },
+    RuleInfo {
+        id: "BoolPrefix",
+        category: RuleCategory::Lint,
+        description: "Required prefix of `bool` variables (or an array of allowed prefixes).",
+        value_type: RuleValueType::StringOrStringArray,
+        default: None,
+        example_value: "\"b\"",
+        example_before: Some("bool enabled;"),
+        example_after: Some("bool bEnabled;"),
+    },
+    RuleInfo {
+        id: "IntPrefix",
+        category: RuleCategory::Lint,
+        description: "Required prefix of integer variables (or an array of allowed prefixes).",
+        value_type: RuleValueType::StringOrStringArray,
+        default: None,
+        example_value: "\"i\"",
+        example_before: Some("int count;"),
+        example_after: Some("int iCount;"),
+    },
+    RuleInfo {
+        id: "FloatPrefix",
+        category: RuleCategory::Lint,
+        description: "Required prefix of floating-point variables (or an array of allowed prefixes).",
+        value_type: RuleValueType::StringOrStringArray,
+        default: None,
+        example_value: "\"f\"",
+        example_before: Some("float weight;"),
+        example_after: Some("float fWeight;"),
+    },
+    RuleInfo {
+        id: "BoolPrefixScope",
+        category: RuleCategory::Lint,
+        description: "Which variables are checked by `BoolPrefix`: all of them or only global variables (including fields of constant buffers).",
+        value_type: RuleValueType::Enum(&["All", "GlobalsOnly"]),
+        default: Some("\"All\""),
+        example_value: "\"GlobalsOnly\"",
+        example_before: Some("bool bEnabled;\n\nvoid foo() { bool bVisible; }"),
+        example_after: Some("bool bEnabled;\n\nvoid foo() { bool visible; }"),
+    },
+    RuleInfo {
+        id: "IntPrefixScope",
+        category: RuleCategory::Lint,
+        description: "Which variables are checked by `IntPrefix`: all of them or only global variables (including fields of constant buffers).",
+        value_type: RuleValueType::Enum(&["All", "GlobalsOnly"]),
+        default: Some("\"All\""),
+        example_value: "\"GlobalsOnly\"",
+        example_before: Some("int iCount;\n\nvoid foo() { int iIndex; }"),
+        example_after: Some("int iCount;\n\nvoid foo() { int index; }"),
+    },
+    RuleInfo {
+        id: "FloatPrefixScope",
+        category: RuleCategory::Lint,
+        description: "Which variables are checked by `FloatPrefix`: all of them or only global variables (including fields of constant buffers).",
+        value_type: RuleValueType::Enum(&["All", "GlobalsOnly"]),
+        default: Some("\"All\""),
+        example_value: "\"GlobalsOnly\"",
+        example_before: Some("float fWeight;\n\nvoid foo() { float fValue; }"),
+        example_after: Some("float fWeight;\n\nvoid foo() { float value; }"),
+    },
+    RuleInfo {
+        id: "ExemptLoopCounters",
+        category: RuleCategory::Lint,
+        description: "Whether variables declared in the header of a `for` loop are not checked by `BoolPrefix`, `IntPrefix` and `FloatPrefix`.",
+        value_type: RuleValueType::Bool,
+        default: Some("false"),
+        example_value: "true",
+        example_before: Some("for (int iIndex = 0; iIndex < 4; iIndex++) {}"),
+        example_after: Some("for (int i = 0; i < 4; i++) {}"),
+    },
+    RuleInfo {
+        id: "GlobalVariablePrefix",
+        category: RuleCategory::Lint,
+        description: "Required prefix of global variables or an array of allowed prefixes (applied before other prefix and case rules).",
+        value_type: RuleValueType::StringOrStringArray,
+        default: None,
+        example_value: "[\"g_\", \"s_\"]",
+        example_before: Some("float time;"),
+        example_after: Some("float g_time;"),
+    },
+    RuleInfo {
+        id: "ConstantBufferFieldPrefix",
+        category: RuleCategory::Lint,
+        description: "Required prefix of fields of constant buffers or an array of allowed prefixes (used instead of `GlobalVariablePrefix` for them).",
+        value_type: RuleValueType::StringOrStringArray,
+        default: None,
+        example_value: "\"cb_\"",
+        example_before: Some("cbuffer Frame { float time; };"),
+        example_after: Some("cbuffer Frame { float cb_time; };"),
+    },
+];
+
+/// Returns description of a rule that uses the specified identifier (like `Indentation`).
+pub fn find_rule(id: &str) -> Option<&'static RuleInfo> {
+    RULES.iter().find(|rule| rule.id == id)
+}
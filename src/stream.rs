@@ -0,0 +1,208 @@
+use std::io::{BufRead, Write};
+use std::time::Instant;
+
+use crate::{
+    error::FormatError,
+    formatter::{Formatter, NOFORMAT_BEGIN_COMMENT, NOFORMAT_END_COMMENT},
+};
+
+/// Tracks nesting of the code that was read so far to find top-level statement boundaries
+/// (places where the code can be split into chunks that are formatted separately).
+#[derive(Default)]
+struct NestingScanner {
+    brace_depth: i32,
+    parentheses_depth: i32,
+    /// Nesting of `#if`/`#ifdef`/`#ifndef` blocks.
+    preprocessor_depth: i32,
+    is_inside_block_comment: bool,
+    /// Whether the last line is a preprocessor directive that continues on the next line.
+    is_inside_directive: bool,
+    /// Whether the last line is between `NOFORMATBEGIN` and `NOFORMATEND` comments.
+    is_inside_no_format: bool,
+    /// Last character of the last line that is not a part of a comment or a whitespace.
+    last_code_char: Option<char>,
+}
+
+impl NestingScanner {
+    /// Updates nesting using the specified line of code.
+    fn scan_line(&mut self, line: &str) {
+        let trimmed_line = line.trim();
+        self.last_code_char = None;
+
+        if line.contains(NOFORMAT_BEGIN_COMMENT) {
+            self.is_inside_no_format = true;
+        } else if line.contains(NOFORMAT_END_COMMENT) {
+            self.is_inside_no_format = false;
+        }
+
+        // Process preprocessor directives.
+        if self.is_inside_directive
+            || (!self.is_inside_block_comment && trimmed_line.starts_with('#'))
+        {
+            if !self.is_inside_directive {
+                let directive = trimmed_line[1..].trim_start();
+                if directive.starts_with("if") {
+                    self.preprocessor_depth += 1;
+                } else if directive.starts_with("endif") {
+                    self.preprocessor_depth -= 1;
+                }
+            }
+            self.is_inside_directive = trimmed_line.ends_with('\\');
+            return;
+        }
+
+        let mut is_inside_string = false;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if self.is_inside_block_comment {
+                if c == '*' && chars.peek() == Some(&'/') {
+                    chars.next();
+                    self.is_inside_block_comment = false;
+                }
+                continue;
+            }
+            if is_inside_string {
+                if c == '\\' {
+                    chars.next();
+                } else if c == '"' {
+                    is_inside_string = false;
+                }
+                continue;
+            }
+
+            match c {
+                '/' if chars.peek() == Some(&'/') => break,
+                '/' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    self.is_inside_block_comment = true;
+                    continue;
+                }
+                '"' => is_inside_string = true,
+                '{' => self.brace_depth += 1,
+                '}' => self.brace_depth -= 1,
+                '(' => self.parentheses_depth += 1,
+                ')' => self.parentheses_depth -= 1,
+                _ => {}
+            }
+            if !c.is_whitespace() {
+                self.last_code_char = Some(c);
+            }
+        }
+    }
+
+    /// Tells if the last scanned line ends a top-level statement (like a global variable
+    /// declaration or a function body) so that the code can be split after this line.
+    fn is_at_statement_boundary(&self) -> bool {
+        self.brace_depth == 0
+            && self.parentheses_depth == 0
+            && self.preprocessor_depth == 0
+            && !self.is_inside_block_comment
+            && !self.is_inside_directive
+            && !self.is_inside_no_format
+            && matches!(self.last_code_char, Some(';') | Some('}'))
+    }
+}
+
+/// Result of formatting code in chunks.
+pub enum ChunkedOutput {
+    /// All chunks were formatted, contains the number of the first line (starting from 1) that
+    /// was changed by formatting (if any).
+    Formatted(Option<usize>),
+    /// Formatting was stopped after some chunk because it took longer than the time limit
+    /// (only a part of the code was written to the output).
+    TimeLimitExceeded,
+    /// Formatting was stopped because no top-level statement boundary was found before the chunk
+    /// reached the maximum size (like in a huge function or after an unclosed `{`), only a part
+    /// of the code was written to the output.
+    ChunkTooLarge,
+}
+
+/// Formats code in chunks (split at top-level statement boundaries) so that memory usage depends
+/// on the size of the chunks instead of the size of the whole code (used for huge generated files).
+///
+/// Rules are checked per chunk so rules that look at multiple statements (like checks for shadowed
+/// global names) don't see statements of other chunks.
+///
+/// # Arguments
+/// - `input` Code to format.
+/// - `output` Formatted code is written here.
+/// - `formatter` Formatter to format chunks with.
+/// - `chunk_size` Minimum size of a chunk (in bytes), a chunk is ended at the first statement
+///   boundary after this size.
+/// - `max_chunk_size` Maximum size of a chunk (in bytes), limits memory usage when the code
+///   can't be split.
+/// - `deadline` Time after which formatting is stopped (checked after each chunk).
+pub fn format_in_chunks(
+    mut input: impl BufRead,
+    output: &mut impl Write,
+    formatter: &Formatter,
+    chunk_size: usize,
+    max_chunk_size: usize,
+    deadline: Option<Instant>,
+) -> Result<ChunkedOutput, FormatError> {
+    let io_error = |e: std::io::Error| {
+        FormatError::Io(format!("failed to format the file in chunks, error: {}", e))
+    };
+
+    let mut scanner = NestingScanner::default();
+    let mut first_changed_line = None;
+    let mut input_line_count = 0;
+    let mut output_line_count = 0;
+    let mut chunk = String::new();
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        let is_end = input.read_line(&mut line).map_err(io_error)? == 0;
+        if !is_end {
+            scanner.scan_line(&line);
+            chunk.push_str(&line);
+            if chunk.len() > max_chunk_size {
+                return Ok(ChunkedOutput::ChunkTooLarge);
+            }
+            if chunk.len() < chunk_size || !scanner.is_at_statement_boundary() {
+                continue;
+            }
+        }
+        if chunk.is_empty() {
+            break;
+        }
+
+        let mut formatted_chunk = formatter
+            .format(&chunk, false)
+            .map_err(|error| error.offset_lines(output_line_count as u32))?;
+
+        // Make sure the next chunk starts on a new line.
+        if !is_end && !formatted_chunk.ends_with('\n') {
+            formatted_chunk.push_str(if chunk.ends_with("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            });
+        }
+
+        if first_changed_line.is_none() && formatted_chunk != chunk {
+            let unchanged_line_count = (chunk.lines())
+                .zip(formatted_chunk.lines())
+                .take_while(|(before, after)| before == after)
+                .count();
+            first_changed_line = Some(input_line_count + unchanged_line_count + 1);
+        }
+
+        output
+            .write_all(formatted_chunk.as_bytes())
+            .map_err(io_error)?;
+        input_line_count += chunk.lines().count();
+        output_line_count += formatted_chunk.lines().count();
+        chunk.clear();
+
+        if is_end {
+            break;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() > deadline) {
+            return Ok(ChunkedOutput::TimeLimitExceeded);
+        }
+    }
+
+    Ok(ChunkedOutput::Formatted(first_changed_line))
+}
@@ -1,11 +1,25 @@
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
+
+    use chumsky::{input::Input, Parser};
+    use regex::Regex;
 
     use crate::{
+        bindings, call_graph,
         config::Config,
-        formatter::{Formatter, CHANGES_REQUIRED_ERR_MSG},
-        rules::{Case, NewLineOnOpenBrace},
+        daemon::Daemon,
+        error::FormatError,
+        formatter::Formatter,
+        helpers, hook,
+        parser::{self, ComplexToken, Token},
+        patch, rename,
+        rules::{
+            self, ArgumentWrap, Case, EmptyBodyStyle, EnumValueLayout, FunctionDocsPlacement,
+            IndentationRule, LineEnding, NewLineOnOpenBrace, PrefixScope, Preset, RequireBraces,
+            Severity, ShaderStage, StatementAttributePlacement, TrailingComma,
+        },
+        stream,
     };
 
     fn get_project_root() -> PathBuf {
@@ -43,17 +57,36 @@ mod tests {
 
         let result = match formatter.format(&input, false) {
             Ok(s) => s,
-            Err(msg) => {
-                panic!("{}", msg);
+            Err(error) => {
+                panic!("{}", error);
             }
         };
 
         assert_eq!(result, output);
     }
 
-    fn test_formatting_fail_success(config: Config, test_dir: &str) {
+    fn check_formatting_is_stable(config: Config, test_dir: &str) {
         let formatter = Formatter::new(config);
 
+        let path_to_output = get_project_root()
+            .join("tests")
+            .join(test_dir)
+            .join("output.hlsl");
+        let output = std::fs::read_to_string(path_to_output).unwrap();
+
+        let result = match formatter.format(&output, false) {
+            Ok(s) => s,
+            Err(error) => {
+                panic!("{}", error);
+            }
+        };
+
+        assert_eq!(result, output);
+    }
+
+    fn test_formatting_fail_success(config: Config, test_dir: &str) {
+        let mut formatter = Formatter::new(config);
+
         let path_to_res = get_project_root().join("tests").join(test_dir);
 
         let mut paths_to_fail = Vec::new();
@@ -112,25 +145,438 @@ mod tests {
 
         // Test fail.
         for path in paths_to_fail {
-            let input = std::fs::read_to_string(path.clone()).unwrap();
+            let input = std::fs::read_to_string(&path).unwrap();
+            formatter.set_path_to_shader(&path);
 
             match formatter.format(&input, false) {
                 Ok(_) => panic!("expected the test to fail (file {})", path.display()),
-                Err(msg) => assert!(msg.starts_with(CHANGES_REQUIRED_ERR_MSG)),
+                Err(error) => assert!(
+                    matches!(error, FormatError::RuleViolation { .. }),
+                    "{}",
+                    error
+                ),
             }
         }
 
         // Test success.
         for path in paths_to_success {
-            let input = std::fs::read_to_string(path).unwrap();
+            let input = std::fs::read_to_string(&path).unwrap();
+            formatter.set_path_to_shader(&path);
 
             match formatter.format(&input, false) {
                 Ok(_) => {}
-                Err(msg) => panic!("{}", msg),
+                Err(error) => panic!("{}", error),
             }
         }
     }
 
+    #[test]
+    fn numeric_literals() {
+        let (tokens, errors) = parser::token_parser()
+            .parse("0xFF 0777 3u 1.0f 1e-5 2.5h .5 -4 1.5e+3F 1.")
+            .into_output_errors();
+
+        assert!(errors.is_empty());
+
+        let tokens: Vec<Token> = tokens.unwrap().into_iter().map(|(t, _)| t).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Integer(255),
+                Token::Integer(511),
+                Token::Integer(3),
+                Token::Float(1.0),
+                Token::Float(1e-5),
+                Token::Float(2.5),
+                Token::Float(0.5),
+                Token::Integer(-4),
+                Token::Float(1500.0),
+                Token::Float(1.0),
+            ]
+        );
+
+        // Leading zeros don't split literals into multiple tokens.
+        let (tokens, errors) = parser::token_parser()
+            .parse("08 09.5 007u")
+            .into_output_errors();
+
+        assert!(errors.is_empty());
+
+        let tokens: Vec<Token> = tokens.unwrap().into_iter().map(|(t, _)| t).collect();
+        assert_eq!(
+            tokens,
+            vec![Token::Integer(8), Token::Float(9.5), Token::Integer(7)]
+        );
+    }
+
+    #[test]
+    fn non_ascii_identifiers() {
+        let (tokens, errors) = parser::token_parser()
+            .parse("float größe = _直径2;")
+            .into_output_errors();
+
+        assert!(errors.is_empty());
+
+        let tokens: Vec<Token> = tokens.unwrap().into_iter().map(|(t, _)| t).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::TypeName(parser::Type::Float),
+                Token::Ident("größe"),
+                Token::Op("="),
+                Token::Ident("_直径2"),
+                Token::Ctrl(';'),
+            ]
+        );
+
+        // Naming rules check the whole name.
+        let mut config = Config::default();
+        config.variable_case = Some(Case::Camel);
+        let formatter = Formatter::new(config);
+        assert!(formatter.format("float größe = 1.0;\n", false).is_ok());
+        match formatter.format("float Größe = 1.0;\n", false) {
+            Err(FormatError::RuleViolation { name, .. }) => assert_eq!(name, "Größe"),
+            result => panic!("expected a rule violation, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn operators() {
+        let (tokens, errors) = parser::token_parser()
+            .parse("a <<= b >>= c && d || e << f >> g++ h-- += -= *= /= %= &= |= ^= == != >= <= ? : !~")
+            .into_output_errors();
+
+        assert!(errors.is_empty());
+
+        let operators: Vec<&str> = tokens
+            .unwrap()
+            .into_iter()
+            .filter_map(|(t, _)| match t {
+                Token::Op(op) => Some(op),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            operators,
+            vec![
+                "<<=", ">>=", "&&", "||", "<<", ">>", "++", "--", "+=", "-=", "*=", "/=", "%=",
+                "&=", "|=", "^=", "==", "!=", ">=", "<=", "?", "!", "~",
+            ]
+        );
+    }
+
+    #[test]
+    fn interface_block_parsing() {
+        let tokens = parser::token_parser()
+            .parse("layout(std140, binding = 3) uniform Lights { vec4 color; } lights[4];")
+            .into_result()
+            .unwrap();
+        let complex_tokens = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_result()
+            .unwrap();
+
+        let info = match &complex_tokens[0].0 {
+            ComplexToken::Struct(info) => info,
+            other => panic!("expected a struct, found {}", other),
+        };
+        assert_eq!(info.name, "Lights");
+        assert_eq!(info.binding, Some(3));
+        assert_eq!(info.fields.len(), 1);
+
+        let instance = info.instance.as_ref().unwrap();
+        assert_eq!(instance.name, "lights");
+        assert_eq!(instance._type, parser::Type::Array);
+    }
+
+    #[test]
+    fn push_constant_parsing() {
+        let tokens = parser::token_parser()
+            .parse(
+                "layout(push_constant) uniform Constants { float time; } constants;\n\
+                 [[vk::push_constant]] cbuffer Frame { float time; };\n\
+                 cbuffer Lights { float intensity; };",
+            )
+            .into_result()
+            .unwrap();
+        let complex_tokens = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_result()
+            .unwrap();
+
+        let info = match &complex_tokens[0].0 {
+            ComplexToken::Struct(info) => info,
+            _ => panic!("expected a struct"),
+        };
+        assert!(info.is_push_constant);
+        assert_eq!(info.fields[0].type_span, (42..47).into());
+
+        let push_constant_flags = complex_tokens[1..]
+            .iter()
+            .map(|(token, _)| match token {
+                ComplexToken::ConstantBuffer(info) => info.is_push_constant,
+                _ => panic!("expected a constant buffer"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(push_constant_flags, vec![true, false]);
+    }
+
+    #[test]
+    fn complex_tokens_to_json() {
+        let code = "float4 main(float2 uv : TEXCOORD0) : SV_Target\n{\n    return 1;\n}";
+        let tokens = parser::token_parser().parse(code).into_result().unwrap();
+        let complex_tokens = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_result()
+            .unwrap();
+
+        assert_eq!(
+            tokens[1].0.to_json(),
+            serde_json::json!({ "kind": "Ident", "value": "main" })
+        );
+        assert_eq!(
+            complex_tokens[0].0.to_json(),
+            serde_json::json!({
+                "kind": "Function",
+                "function": {
+                    "name": "main",
+                    "nameSpan": { "start": 7, "end": 11 },
+                    "args": [{
+                        "type": "Vector",
+                        "name": "uv",
+                        "nameSpan": { "start": 19, "end": 21 },
+                        "semantic": "TEXCOORD0",
+                        "modifiers": [],
+                        "arrayDimensions": [],
+                        "hasDefaultValue": false,
+                    }],
+                    "returnType": "Vector",
+                    "returnSemantic": "SV_Target",
+                    "attributes": [],
+                    "docs": "",
+                    "isDeclaration": false,
+                },
+            })
+        );
+
+        // Locations of tokens.
+        let line_index = helpers::LineIndex::new(code);
+        assert_eq!(line_index.line_and_column(0), (1, 1));
+        assert_eq!(line_index.line_and_column(7), (1, 8));
+        assert_eq!(
+            line_index.line_and_column(code.find("return").unwrap()),
+            (3, 5)
+        );
+    }
+
+    #[test]
+    fn precision_qualifiers_parsing() {
+        let tokens = parser::token_parser()
+            .parse("precision mediump float; lowp vec4 get(in highp vec2 uv) { highp float x; }")
+            .into_result()
+            .unwrap();
+        let complex_tokens = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_result()
+            .unwrap();
+
+        assert_eq!(
+            complex_tokens[0].0,
+            ComplexToken::DefaultPrecision(parser::Type::Float)
+        );
+
+        let info = match &complex_tokens[1].0 {
+            ComplexToken::Function(info) => info,
+            other => panic!("expected a function, found {}", other),
+        };
+        assert_eq!(info.name, "get");
+        assert_eq!(info.return_type, parser::Type::Vector);
+        assert_eq!(info.args[0].name, "uv");
+        assert_eq!(info.args[0]._type, parser::Type::Vector);
+
+        assert!(complex_tokens.iter().any(|(token, _)| *token
+            == ComplexToken::VariableDeclaration(vec![(
+                parser::Type::Float,
+                "x",
+                (71..72).into()
+            )])));
+    }
+
+    #[test]
+    fn vulkan_layout_parsing() {
+        let tokens = parser::token_parser()
+            .parse("layout(set = 1, binding = 2, local_size_x_id = SIZE_ID) buffer Data { uint values[]; };")
+            .into_result()
+            .unwrap();
+        let complex_tokens = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_result()
+            .unwrap();
+
+        let info = match &complex_tokens[0].0 {
+            ComplexToken::Struct(info) => info,
+            other => panic!("expected a struct, found {}", other),
+        };
+        assert_eq!(info.name, "Data");
+        assert_eq!(info.set, Some(1));
+        assert_eq!(info.binding, Some(2));
+    }
+
+    #[test]
+    fn bindings_parsing() {
+        let code = "RWTexture2D<float4> output[2] : register(u1, space2);\n\
+                    layout(std140, set = 1, binding = 3) uniform CameraData {\n\
+                    mat4x4 viewProjection;\n\
+                    } camera;";
+        let tokens = parser::token_parser().parse(code).into_result().unwrap();
+        let tokens = parser::split_template_closing_shifts(tokens);
+
+        let bindings = bindings::collect_bindings(&tokens);
+
+        assert_eq!(bindings.len(), 2);
+        assert_eq!(bindings[0].name, "output");
+        assert_eq!(
+            &code[bindings[0].type_span.into_range()],
+            "RWTexture2D<float4>"
+        );
+        assert_eq!(bindings[0].slot.to_string(), "register(u1, space2)");
+        assert_eq!(bindings[1].name, "CameraData");
+        assert_eq!(&code[bindings[1].type_span.into_range()], "uniform");
+        assert_eq!(bindings[1].slot.to_string(), "set = 1, binding = 3");
+    }
+
+    #[test]
+    fn array_and_template_arguments_parsing() {
+        let tokens = parser::token_parser()
+            .parse("void gs(triangle VSOut input[3], inout TriangleStream<GSOut> stream) {}")
+            .into_result()
+            .unwrap();
+        let complex_tokens = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_result()
+            .unwrap();
+
+        let info = match &complex_tokens[0].0 {
+            ComplexToken::Function(info) => info,
+            other => panic!("expected a function, found {}", other),
+        };
+        assert_eq!(info.args.len(), 2);
+        assert_eq!(info.args[0].name, "input");
+        assert_eq!(info.args[0]._type, parser::Type::Array);
+        assert_eq!(info.args[0].modifiers, vec!["triangle"]);
+        assert_eq!(info.args[0].array_dimensions, vec![Some(3)]);
+        assert_eq!(info.args[1].name, "stream");
+        assert_eq!(info.args[1]._type, parser::Type::Custom);
+        assert_eq!(info.args[1].modifiers, vec!["inout"]);
+        assert!(info.args[1].array_dimensions.is_empty());
+    }
+
+    #[test]
+    fn argument_default_values_parsing() {
+        let tokens = parser::token_parser()
+            .parse("float get(float weights[SIZE][4], in float scale = max(a, b), float bias = 0.5) {}")
+            .into_result()
+            .unwrap();
+        let complex_tokens = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_result()
+            .unwrap();
+
+        let info = match &complex_tokens[0].0 {
+            ComplexToken::Function(info) => info,
+            other => panic!("expected a function, found {}", other),
+        };
+        assert_eq!(info.args.len(), 3);
+        assert_eq!(info.args[0].array_dimensions, vec![None, Some(4)]);
+        assert!(!info.args[0].has_default_value);
+        assert_eq!(info.args[1].name, "scale");
+        assert_eq!(info.args[1].modifiers, vec!["in"]);
+        assert!(info.args[1].has_default_value);
+        assert_eq!(info.args[2].name, "bias");
+        assert!(info.args[2].has_default_value);
+    }
+
+    #[test]
+    fn class_parsing() {
+        let tokens = parser::token_parser()
+            .parse("class Light : ILight { float3 color; float3 get(float3 n) { return color; } };")
+            .into_result()
+            .unwrap();
+        let complex_tokens = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_result()
+            .unwrap();
+
+        let info = match &complex_tokens[0].0 {
+            ComplexToken::Struct(info) => info,
+            other => panic!("expected a class, found {}", other),
+        };
+        assert_eq!(info.name, "Light");
+        assert_eq!(info.bases.len(), 1);
+        assert_eq!(info.bases[0].0, "ILight");
+        assert_eq!(info.fields.len(), 1);
+        assert_eq!(info.methods.len(), 1);
+        assert_eq!(info.methods[0].name, "get");
+        assert_eq!(info.methods[0].args[0].name, "n");
+    }
+
+    #[test]
+    fn nested_struct_parsing() {
+        let tokens = parser::token_parser()
+            .parse("struct Light { struct Range { float min; } range; struct { float a; } b; float c; };")
+            .into_result()
+            .unwrap();
+        let complex_tokens = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_result()
+            .unwrap();
+
+        let info = match &complex_tokens[0].0 {
+            ComplexToken::Struct(info) => info,
+            other => panic!("expected a struct, found {}", other),
+        };
+        assert_eq!(info.name, "Light");
+        assert_eq!(info.fields.len(), 3);
+        assert_eq!(info.fields[0].name, "range");
+        assert_eq!(info.fields[1].name, "b");
+        assert_eq!(info.fields[2].name, "c");
+        assert_eq!(info.nested.len(), 2);
+        assert_eq!(info.nested[0].name, "Range");
+        assert_eq!(info.nested[0].fields[0].name, "min");
+        assert_eq!(info.nested[1].name, ""); // anonymous
+        assert_eq!(info.nested[1].fields[0].name, "a");
+    }
+
+    #[test]
+    fn nested_structs() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.variable_case.is_none());
+        assert!(config.struct_case.is_none());
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+        config.struct_case = Some(Case::Pascal);
+
+        // Test.
+        test_formatting_fail_success(config, "nested_structs");
+    }
+
+    #[test]
+    fn interface_blocks() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.variable_case.is_none());
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+
+        // Test.
+        test_formatting_fail_success(config, "interface_blocks");
+    }
+
     #[test]
     fn default_settings() {
         compare_files_in_directory(Config::default(), "default_settings/general");
@@ -175,6 +621,10 @@ mod tests {
             Config::default(),
             "default_settings/bracket_avoids_comments/3",
         );
+        compare_files_in_directory(
+            Config::default(),
+            "default_settings/bracket_avoids_comments/4",
+        );
     }
 
     #[test]
@@ -250,191 +700,2043 @@ mod tests {
     }
 
     #[test]
-    fn variable_case() {
+    fn spaces_in_angle_brackets() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(config.variable_case.is_none());
+        assert!(!config.spaces_in_angle_brackets);
 
         // Change the setting.
-        config.variable_case = Some(Case::Camel);
+        config.spaces_in_angle_brackets = true;
 
         // Test.
-        test_formatting_fail_success(config, "variable_case");
+        compare_files_in_directory(config, "spaces_in_angle_brackets");
     }
 
     #[test]
-    fn function_case() {
+    fn keep_user_line_breaks() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(config.function_case.is_none());
+        assert!(!config.keep_user_line_breaks);
 
         // Change the setting.
-        config.function_case = Some(Case::Camel);
+        config.keep_user_line_breaks = true;
+        config.enum_value_layout = Some(EnumValueLayout::Packed);
 
         // Test.
-        test_formatting_fail_success(config, "function_case");
+        compare_files_in_directory(config, "keep_user_line_breaks");
     }
 
     #[test]
-    fn struct_case() {
+    fn constructor_and_cast_spacing() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(config.struct_case.is_none());
+        assert!(config.space_before_constructor_parenthesis.is_none());
+        assert!(config.space_after_cast.is_none());
 
         // Change the setting.
-        config.struct_case = Some(Case::Pascal);
+        config.space_before_constructor_parenthesis = Some(false);
+        config.space_after_cast = Some(false);
 
         // Test.
-        test_formatting_fail_success(config, "struct_case");
+        compare_files_in_directory(config, "constructor_and_cast_spacing");
     }
 
     #[test]
-    fn variable_prefix() {
+    fn unary_operator_spacing() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(config.bool_prefix.is_none());
-        assert!(config.int_prefix.is_none());
-        assert!(config.float_prefix.is_none());
+        assert!(!config.remove_space_after_unary_operators);
 
         // Change the setting.
-        config.bool_prefix = Some(String::from("b"));
-        config.int_prefix = Some(String::from("i"));
-        config.float_prefix = Some(String::from("f"));
+        config.remove_space_after_unary_operators = true;
 
         // Test.
-        test_formatting_fail_success(config.clone(), "variable_prefix/bool");
-        test_formatting_fail_success(config.clone(), "variable_prefix/int");
-        test_formatting_fail_success(config, "variable_prefix/float");
+        compare_files_in_directory(config, "unary_operator_spacing");
     }
 
     #[test]
-    fn global_variable_prefix() {
+    fn ternary_operator() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(config.global_variable_prefix.is_none());
-        assert!(config.int_prefix.is_none());
-        assert!(config.variable_case.is_none());
+        assert!(!config.spaces_around_ternary_operator);
 
         // Change the setting.
-        config.global_variable_prefix = Some(String::from("g_"));
-        config.int_prefix = Some(String::from("i"));
-        config.variable_case = Some(Case::Camel);
+        config.spaces_around_ternary_operator = true;
+        config.max_line_length = Some(60);
 
         // Test.
-        test_formatting_fail_success(config, "global_variable_prefix");
+        compare_files_in_directory(config, "ternary_operator");
     }
 
     #[test]
-    fn require_docs_on_functions() {
+    fn member_access_spacing() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(!config.require_docs_on_functions);
+        assert!(!config.remove_spaces_around_member_access);
 
         // Change the setting.
-        config.require_docs_on_functions = true;
+        config.remove_spaces_around_member_access = true;
 
         // Test.
-        test_formatting_fail_success(config, "require_docs_on_functions");
+        compare_files_in_directory(config, "member_access_spacing");
     }
 
     #[test]
-    fn require_docs_on_structs() {
+    fn redundant_semicolons() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(!config.require_docs_on_structs);
+        assert!(!config.remove_redundant_semicolons);
 
         // Change the setting.
-        config.require_docs_on_structs = true;
+        config.remove_redundant_semicolons = true;
 
         // Test.
-        test_formatting_fail_success(config, "require_docs_on_structs");
+        compare_files_in_directory(config, "redundant_semicolons");
     }
 
     #[test]
-    fn indent_preprocessor() {
+    fn empty_body_style() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(!config.indent_preprocessor);
+        assert!(config.empty_body_style == EmptyBodyStyle::Separate);
 
         // Change the setting.
-        config.indent_preprocessor = true;
+        config.empty_body_style = EmptyBodyStyle::Collapsed;
 
         // Test.
-        compare_files_in_directory(config, "indent_preprocessor");
+        compare_files_in_directory(config, "empty_body_style");
     }
 
     #[test]
-    fn preprocessor_if_creates_nesting() {
+    fn normalize_local_size_layout() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(!config.indent_preprocessor);
-        assert!(!config.preprocessor_if_creates_nesting);
+        assert!(!config.normalize_local_size_layout);
 
         // Change the setting.
-        config.indent_preprocessor = true;
-        config.preprocessor_if_creates_nesting = true;
+        config.normalize_local_size_layout = true;
 
         // Test.
-        compare_files_in_directory(config.clone(), "preprocessor_if_creates_nesting/1");
-        compare_files_in_directory(config, "preprocessor_if_creates_nesting/2");
+        compare_files_in_directory(config, "normalize_local_size_layout");
     }
 
     #[test]
-    fn nolint() {
+    fn sort_qualifiers() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(config.variable_case.is_none());
-        assert!(config.struct_case.is_none());
+        assert!(!config.sort_qualifiers);
 
         // Change the setting.
-        config.variable_case = Some(Case::Camel);
-        config.struct_case = Some(Case::Pascal);
+        config.sort_qualifiers = true;
 
         // Test.
-        test_formatting_fail_success(config, "nolint");
+        compare_files_in_directory(config, "sort_qualifiers");
     }
 
     #[test]
-    fn noformat() {
+    fn normalize_return_spacing() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.normalize_return_spacing);
+
+        // Change the setting.
+        config.normalize_return_spacing = true;
+
         // Test.
-        compare_files_in_directory(Config::default(), "noformat/1");
-        compare_files_in_directory(Config::default(), "noformat/2");
-        compare_files_in_directory(Config::default(), "noformat/3");
-        test_formatting_fail_success(Config::default(), "noformat/no_matching_end")
+        compare_files_in_directory(config, "normalize_return_spacing");
     }
 
     #[test]
-    fn require_docs_on_fields() {
+    fn normalize_declaration_spacing() {
         let mut config = Config::default();
 
         // Make sure default config uses other setting.
-        assert!(!config.require_docs_on_fields);
+        assert!(!config.normalize_declaration_spacing);
 
         // Change the setting.
-        config.require_docs_on_fields = true;
+        config.normalize_declaration_spacing = true;
 
         // Test.
-        test_formatting_fail_success(config, "require_docs_on_fields");
+        compare_files_in_directory(config, "normalize_declaration_spacing");
     }
 
     #[test]
-    fn empty_lines_no_indent() {
+    fn normalize_default_value_spacing() {
         let mut config = Config::default();
 
-        config.indent_preprocessor = true;
-        config.preprocessor_if_creates_nesting = true;
+        // Make sure default config uses other setting.
+        assert!(!config.normalize_default_value_spacing);
+
+        // Change the setting.
+        config.normalize_default_value_spacing = true;
 
         // Test.
-        compare_files_in_directory(config, "empty_lines_no_indent");
+        compare_files_in_directory(config, "default_value_spacing");
+    }
+
+    #[test]
+    fn indentation_auto() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.indentation == IndentationRule::FourSpaces);
+
+        // Change the setting.
+        config.indentation = IndentationRule::Auto;
+
+        // Test.
+        for test_dir in [
+            "indentation_auto/tab",
+            "indentation_auto/two_spaces",
+            "indentation_auto/four_spaces",
+        ] {
+            compare_files_in_directory(config.clone(), test_dir);
+        }
+    }
+
+    #[test]
+    fn new_line_on_open_brace_auto() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.new_line_around_braces == NewLineOnOpenBrace::After);
+
+        // Change the setting.
+        config.new_line_around_braces = NewLineOnOpenBrace::Auto;
+
+        // Test.
+        for test_dir in [
+            "new_line_on_open_brace_auto/before",
+            "new_line_on_open_brace_auto/after",
+        ] {
+            compare_files_in_directory(config.clone(), test_dir);
+        }
+    }
+
+    #[test]
+    fn empty_line_before_doc_comments() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.empty_line_before_doc_comments);
+
+        // Change the setting.
+        config.empty_line_before_doc_comments = true;
+
+        // Test.
+        compare_files_in_directory(config.clone(), "empty_line_before_doc_comments");
+
+        // Make sure that formatting the result again does not change it.
+        check_formatting_is_stable(config, "empty_line_before_doc_comments");
+    }
+
+    #[test]
+    fn collapse_whitespace() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.collapse_whitespace);
+
+        // Change the setting.
+        config.collapse_whitespace = true;
+
+        // Test.
+        compare_files_in_directory(config, "collapse_whitespace");
+    }
+
+    #[test]
+    fn template_types() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.variable_case.is_none());
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+
+        // Test.
+        test_formatting_fail_success(config, "template_types");
+    }
+
+    #[test]
+    fn variable_case() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.variable_case.is_none());
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+
+        // Test.
+        test_formatting_fail_success(config, "variable_case");
+    }
+
+    #[test]
+    fn function_case() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.function_case.is_none());
+
+        // Change the setting.
+        config.function_case = Some(Case::Camel);
+
+        // Test.
+        test_formatting_fail_success(config, "function_case");
+    }
+
+    #[test]
+    fn struct_case() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.struct_case.is_none());
+
+        // Change the setting.
+        config.struct_case = Some(Case::Pascal);
+
+        // Test.
+        test_formatting_fail_success(config, "struct_case");
+    }
+
+    #[test]
+    fn variable_prefix() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.bool_prefix.is_none());
+        assert!(config.int_prefix.is_none());
+        assert!(config.float_prefix.is_none());
+
+        // Change the setting.
+        config.bool_prefix = Some(vec![String::from("b")]);
+        config.int_prefix = Some(vec![String::from("i")]);
+        config.float_prefix = Some(vec![String::from("f")]);
+
+        // Test.
+        test_formatting_fail_success(config.clone(), "variable_prefix/bool");
+        test_formatting_fail_success(config.clone(), "variable_prefix/int");
+        test_formatting_fail_success(config, "variable_prefix/float");
+    }
+
+    #[test]
+    fn max_push_constant_size() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.max_push_constant_size.is_none());
+
+        // Change the setting.
+        config.max_push_constant_size = Some(128);
+
+        // Test.
+        test_formatting_fail_success(config, "max_push_constant_size");
+    }
+
+    #[test]
+    fn global_variable_prefix() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.global_variable_prefix.is_none());
+        assert!(config.int_prefix.is_none());
+        assert!(config.variable_case.is_none());
+
+        // Change the setting.
+        config.global_variable_prefix = Some(vec![String::from("g_")]);
+        config.int_prefix = Some(vec![String::from("i")]);
+        config.variable_case = Some(Case::Camel);
+
+        // Test.
+        test_formatting_fail_success(config, "global_variable_prefix");
+    }
+
+    #[test]
+    fn acronyms() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.acronyms.is_empty());
+
+        // Change the setting.
+        config.acronyms = ["RGB", "UV", "AO", "SSAO"].map(str::to_owned).to_vec();
+        config.variable_case = Some(Case::Camel);
+        config.struct_case = Some(Case::Pascal);
+        config.enum_value_case = Some(Case::UpperSnake);
+
+        // Test.
+        test_formatting_fail_success(config, "acronyms");
+    }
+
+    #[test]
+    fn exempt_loop_counters() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.exempt_loop_counters);
+
+        // Change the setting.
+        config.exempt_loop_counters = true;
+        config.int_prefix = Some(vec!["i".to_owned()]);
+
+        // Test.
+        test_formatting_fail_success(config, "loop_counters");
+    }
+
+    #[test]
+    fn scoped_variable_case() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.local_variable_case.is_none());
+        assert!(config.global_variable_case.is_none());
+        assert!(config.static_variable_case.is_none());
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+        config.global_variable_case = Some(Case::Pascal);
+        config.static_variable_case = Some(Case::UpperSnake);
+
+        // Test.
+        test_formatting_fail_success(config, "scoped_variable_case");
+    }
+
+    #[test]
+    fn forbidden_identifiers() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.forbidden_identifiers.is_empty());
+        assert!(config.forbidden_identifier_pattern.is_none());
+
+        // Change the setting.
+        config.forbidden_identifiers = vec![String::from("input"), String::from("output")];
+        config.forbidden_identifier_pattern = Some(Regex::new("^gl_|__").unwrap());
+
+        // Test.
+        test_formatting_fail_success(config, "forbidden_identifiers");
+    }
+
+    #[test]
+    fn identifier_length() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.min_identifier_length.is_none());
+        assert!(config.max_identifier_length.is_none());
+        assert!(config
+            .identifier_length_allowlist
+            .contains(&String::from("uv")));
+
+        // Change the setting.
+        config.min_identifier_length = Some(2);
+        config.max_identifier_length = Some(16);
+
+        // Test.
+        test_formatting_fail_success(config, "identifier_length");
+    }
+
+    #[test]
+    fn multiple_variable_prefixes() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.global_variable_prefix.is_none());
+        assert!(config.bool_prefix.is_none());
+
+        // Change the setting.
+        config.global_variable_prefix = Some(vec![
+            String::from("g_"),
+            String::from("s_"),
+            String::from("c_"),
+        ]);
+        config.bool_prefix = Some(vec![String::from("b"), String::from("is")]);
+
+        // Test.
+        test_formatting_fail_success(config, "multiple_variable_prefixes");
+    }
+
+    #[test]
+    fn prefix_scope() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.int_prefix_scope == PrefixScope::All);
+        assert!(config.float_prefix_scope == PrefixScope::All);
+
+        // Change the setting.
+        config.bool_prefix = Some(vec![String::from("b")]);
+        config.int_prefix = Some(vec![String::from("i")]);
+        config.float_prefix = Some(vec![String::from("f")]);
+        config.int_prefix_scope = PrefixScope::GlobalsOnly;
+        config.float_prefix_scope = PrefixScope::GlobalsOnly;
+
+        // Test.
+        test_formatting_fail_success(config, "prefix_scope");
+    }
+
+    #[test]
+    fn constant_buffer_field_prefix() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.constant_buffer_field_prefix.is_none());
+        assert!(config.global_variable_prefix.is_none());
+        assert!(config.variable_case.is_none());
+
+        // Change the setting.
+        config.constant_buffer_field_prefix = Some(vec![String::from("cb_")]);
+        config.global_variable_prefix = Some(vec![String::from("g_")]);
+        config.variable_case = Some(Case::Camel);
+
+        // Test.
+        test_formatting_fail_success(config, "constant_buffer_field_prefix");
+    }
+
+    #[test]
+    fn require_docs_on_functions() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_docs_on_functions);
+
+        // Change the setting.
+        config.require_docs_on_functions = true;
+
+        // Test.
+        test_formatting_fail_success(config, "require_docs_on_functions");
+    }
+
+    #[test]
+    fn function_docs_placement() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.function_docs_placement.is_none());
+
+        // Change the setting.
+        config.require_docs_on_functions = true;
+
+        // Test.
+        for (placement, test_dir) in [
+            (
+                FunctionDocsPlacement::Declaration,
+                "function_docs_placement/declaration",
+            ),
+            (
+                FunctionDocsPlacement::Definition,
+                "function_docs_placement/definition",
+            ),
+        ] {
+            config.function_docs_placement = Some(placement);
+            test_formatting_fail_success(config.clone(), test_dir);
+        }
+    }
+
+    #[test]
+    fn require_docs_on_structs() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_docs_on_structs);
+
+        // Change the setting.
+        config.require_docs_on_structs = true;
+
+        // Test.
+        test_formatting_fail_success(config, "require_docs_on_structs");
+    }
+
+    #[test]
+    fn indent_preprocessor() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.indent_preprocessor);
+
+        // Change the setting.
+        config.indent_preprocessor = true;
+
+        // Test.
+        compare_files_in_directory(config, "indent_preprocessor");
+    }
+
+    #[test]
+    fn preprocessor_if_creates_nesting() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.indent_preprocessor);
+        assert!(!config.preprocessor_if_creates_nesting);
+
+        // Change the setting.
+        config.indent_preprocessor = true;
+        config.preprocessor_if_creates_nesting = true;
+
+        // Test.
+        compare_files_in_directory(config.clone(), "preprocessor_if_creates_nesting/1");
+        compare_files_in_directory(config.clone(), "preprocessor_if_creates_nesting/2");
+        compare_files_in_directory(config.clone(), "preprocessor_if_creates_nesting/3");
+
+        // Directives don't create nesting if they are not indented.
+        config.indent_preprocessor = false;
+        compare_files_in_directory(config, "preprocessor_if_creates_nesting/4");
+    }
+
+    #[test]
+    fn nolint() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.variable_case.is_none());
+        assert!(config.struct_case.is_none());
+        assert!(!config.validate_nolint_comments);
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+        config.struct_case = Some(Case::Pascal);
+        config.validate_nolint_comments = true;
+
+        // Test.
+        test_formatting_fail_success(config.clone(), "nolint");
+
+        // Errors in `NOLINT` comments are reported by a rule from the registry.
+        let unknown_rule = "int WrOnG_cAsE1 = 1; // NOLINT(UnknownRule)\n";
+        let no_end = "// NOLINTBEGIN\nint WrOnG_cAsE1 = 1;\n";
+        for code in [unknown_rule, no_end] {
+            assert!(matches!(
+                Formatter::new(config.clone()).format(code, false),
+                Err(FormatError::RuleViolation {
+                    rule: "ValidateNolintComments",
+                    ..
+                })
+            ));
+        }
+
+        // Unknown rules are ignored if the comments are not validated.
+        config.validate_nolint_comments = false;
+        let formatter = Formatter::new(config);
+        assert!(matches!(
+            formatter.format(unknown_rule, false),
+            Err(FormatError::RuleViolation {
+                rule: "VariableCase",
+                ..
+            })
+        ));
+        assert!(formatter.format(no_end, false).is_ok());
+    }
+
+    #[test]
+    fn noformat() {
+        // Test.
+        compare_files_in_directory(Config::default(), "noformat/1");
+        compare_files_in_directory(Config::default(), "noformat/2");
+        compare_files_in_directory(Config::default(), "noformat/3");
+        test_formatting_fail_success(Config::default(), "noformat/no_matching_end")
+    }
+
+    #[test]
+    fn ordered_param_docs() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_ordered_param_docs);
+
+        // Change the setting.
+        config.require_ordered_param_docs = true;
+
+        // Test.
+        compare_files_in_directory(config.clone(), "ordered_param_docs");
+        test_formatting_fail_success(config.clone(), "ordered_param_docs");
+
+        // Reordered entries start where an empty line is inserted.
+        config.empty_line_before_doc_comments = true;
+        compare_files_in_directory(config.clone(), "ordered_param_docs/empty_line_before_docs");
+        check_formatting_is_stable(config, "ordered_param_docs/empty_line_before_docs");
+    }
+
+    #[test]
+    fn param_docs_direction() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_param_docs_direction);
+
+        // Change the setting.
+        config.require_param_docs_direction = true;
+
+        // Test.
+        test_formatting_fail_success(config, "param_docs_direction");
+    }
+
+    #[test]
+    fn require_brief_in_docs() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_brief_in_docs);
+
+        // Change the setting.
+        config.require_brief_in_docs = true;
+
+        // Test.
+        test_formatting_fail_success(config, "require_brief_in_docs");
+    }
+
+    #[test]
+    fn require_docs_on_fields() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_docs_on_fields);
+
+        // Change the setting.
+        config.require_docs_on_fields = true;
+
+        // Test.
+        test_formatting_fail_success(config, "require_docs_on_fields");
+    }
+
+    #[test]
+    fn parse_error_recovery() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.variable_case.is_none());
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+
+        // Test.
+        test_formatting_fail_success(config, "parse_error_recovery");
+
+        // Make sure the unparsable member is reported and skipped.
+        let tokens = parser::token_parser()
+            .parse("struct Light { float color; Light operator+(Light other) { return other; } float size; };")
+            .into_result()
+            .unwrap();
+        let (complex_tokens, errors) = parser::complex_token_parser(Vec::new())
+            .parse(tokens.spanned((tokens.len()..tokens.len()).into()))
+            .into_output_errors();
+        assert_eq!(errors.len(), 1);
+
+        let complex_tokens = complex_tokens.unwrap();
+        let info = match &complex_tokens[0].0 {
+            ComplexToken::Struct(info) => info,
+            other => panic!("expected a struct, found {}", other),
+        };
+        assert_eq!(info.fields.len(), 2);
+        assert_eq!(info.fields[1].name, "size");
+    }
+
+    #[test]
+    fn version_directive_first() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.version_directive_first);
+
+        // Change the setting.
+        config.version_directive_first = true;
+
+        // Test.
+        test_formatting_fail_success(config, "version_directive_first");
+    }
+
+    #[test]
+    fn require_local_size() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_local_size);
+
+        // Change the setting.
+        config.require_local_size = true;
+
+        // Test.
+        test_formatting_fail_success(config, "require_local_size");
+    }
+
+    #[test]
+    fn validate_semantics() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.validate_semantics);
+        assert!(config.custom_semantics.is_empty());
+
+        // Change the setting.
+        config.validate_semantics = true;
+        config.custom_semantics = vec![String::from("TEXCOORD"), String::from("COLOR")];
+
+        // Test.
+        test_formatting_fail_success(config, "validate_semantics");
+    }
+
+    #[test]
+    fn require_default_precision() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_default_precision);
+
+        // Change the setting.
+        config.require_default_precision = true;
+
+        // Test.
+        test_formatting_fail_success(config, "require_default_precision");
+    }
+
+    #[test]
+    fn vulkan_glsl() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.variable_case.is_none());
+        assert!(config.struct_case.is_none());
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+        config.struct_case = Some(Case::Pascal);
+
+        // Test.
+        test_formatting_fail_success(config, "vulkan_glsl");
+    }
+
+    #[test]
+    fn shader_stage_attributes() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.function_case.is_none());
+        assert!(!config.require_docs_on_functions);
+
+        // Change the setting.
+        config.function_case = Some(Case::Camel);
+        config.require_docs_on_functions = true;
+
+        // Test.
+        test_formatting_fail_success(config, "shader_stage_attributes");
+    }
+
+    #[test]
+    fn geometry_shader_arguments() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.variable_case.is_none());
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+
+        // Test.
+        test_formatting_fail_success(config, "geometry_shader_arguments");
+    }
+
+    #[test]
+    fn space_before_inheritance_colon() {
+        let mut config = Config::default();
+
+        // Make sure default config uses this setting.
+        assert!(config.space_before_inheritance_colon);
+        config.require_docs_on_structs = true;
+
+        // Test.
+        compare_files_in_directory(config.clone(), "space_before_inheritance_colon/enabled");
+
+        // Change the setting.
+        config.space_before_inheritance_colon = false;
+        compare_files_in_directory(config.clone(), "space_before_inheritance_colon/disabled");
+        check_formatting_is_stable(config, "space_before_inheritance_colon/disabled");
+    }
+
+    #[test]
+    fn comment_banners() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.comment_banner_char.is_none());
+        assert!(config.comment_banner_width.is_none());
+
+        // Change the setting.
+        config.comment_banner_char = Some('-');
+        config.comment_banner_width = Some(32);
+
+        // Test.
+        compare_files_in_directory(config.clone(), "comment_banners");
+        check_formatting_is_stable(config, "comment_banners");
+    }
+
+    #[test]
+    fn entry_point_name_pattern() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.entry_point_name_pattern.is_none());
+
+        // Change the setting.
+        config.entry_point_name_pattern = Some(Regex::new("^(VS|PS|CS)Main$").unwrap());
+
+        // Test.
+        test_formatting_fail_success(config, "entry_point_name_pattern");
+    }
+
+    #[test]
+    fn shader_stage_rules() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_docs_on_functions);
+        assert!(!config.require_numthreads);
+
+        // Change the setting.
+        let mut compute_config = config.clone();
+        compute_config.require_docs_on_functions = true;
+        compute_config.require_numthreads = true;
+        config
+            .stage_configs
+            .push((ShaderStage::Compute, compute_config));
+
+        // Test.
+        test_formatting_fail_success(config.clone(), "shader_stage_rules");
+
+        // Stage from the file extension is used instead of the detected one.
+        assert_eq!(
+            ShaderStage::from_file_extension("comp"),
+            Some(ShaderStage::Compute)
+        );
+        let mut formatter = Formatter::new(config);
+        let code = "void helper(uint3 id) {\n}\n";
+        assert!(formatter.format(code, false).is_ok());
+        formatter.set_path_to_shader(Path::new("shader.comp"));
+        assert!(formatter.format(code, false).is_err());
+    }
+
+    #[test]
+    fn include_dirs() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.include_dirs.is_empty());
+        assert!(config.variable_case.is_none());
+        assert!(config.float_prefix.is_none());
+
+        // Change the setting.
+        config.include_dirs = vec![get_project_root()
+            .join("tests")
+            .join("include_dirs")
+            .join("include")];
+        config.variable_case = Some(Case::Camel);
+        config.float_prefix = Some(vec!["f".to_owned()]);
+
+        // Test.
+        test_formatting_fail_success(config, "include_dirs");
+    }
+
+    #[test]
+    fn include_checks() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_existing_includes);
+        assert!(!config.forbid_circular_includes);
+
+        // Change the setting.
+        config.include_dirs = vec![get_project_root()
+            .join("tests")
+            .join("include_checks")
+            .join("include")];
+        config.require_existing_includes = true;
+        config.forbid_circular_includes = true;
+
+        // Test.
+        test_formatting_fail_success(config, "include_checks");
+    }
+
+    #[test]
+    fn unused_names() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.forbid_unused_variables);
+        assert!(!config.forbid_unused_parameters);
+
+        // Change the setting.
+        config.forbid_unused_variables = true;
+        config.forbid_unused_parameters = true;
+
+        // Test.
+        test_formatting_fail_success(config, "unused_names");
+    }
+
+    #[test]
+    fn unused_functions() {
+        let path_to_res = get_project_root().join("tests").join("unused_functions");
+
+        let files = ["common.hlsli", "main.hlsl"]
+            .into_iter()
+            .map(|name| {
+                let path = path_to_res.join(name);
+                let content = std::fs::read_to_string(&path).unwrap();
+                (path, content)
+            })
+            .collect::<Vec<_>>();
+
+        let unused_functions = call_graph::find_unused_functions(&files);
+
+        assert_eq!(unused_functions.len(), 1);
+        let (path, error) = &unused_functions[0];
+        assert_eq!(path, &path_to_res.join("common.hlsli"));
+        match error {
+            FormatError::RuleViolation { name, .. } => assert_eq!(name, "unusedHelper"),
+            _ => panic!("unexpected error {}", error),
+        }
+    }
+
+    #[test]
+    fn fix_global_names() {
+        let path_to_res = get_project_root().join("tests").join("fix_global_names");
+
+        let mut files = ["common.hlsli", "main.hlsl"]
+            .into_iter()
+            .map(|name| {
+                let path = path_to_res.join(name);
+                let content = std::fs::read_to_string(&path).unwrap();
+                (path, content)
+            })
+            .collect::<Vec<_>>();
+
+        let mut config = Config::default();
+        config.function_case = Some(Case::Camel);
+        config.variable_case = Some(Case::Camel);
+        config.global_variable_prefix = Some(vec![String::from("g_")]);
+
+        // Only the first file is checked but references in both files are updated.
+        let renames = rename::fix_global_names(&mut files, 1, &config);
+
+        let renames = renames
+            .iter()
+            .map(|rename| (rename.old_name.as_str(), rename.new_name.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            renames,
+            [
+                ("g_light_power", "g_lightPower"),
+                ("compute_light", "computeLight")
+            ]
+        );
+        for ((_, content), output_name) in files
+            .iter()
+            .zip(["common_output.hlsli", "main_output.hlsl"])
+        {
+            let output = std::fs::read_to_string(path_to_res.join(output_name)).unwrap();
+            assert_eq!(content, &output);
+        }
+    }
+
+    #[test]
+    fn rename_suggestions() {
+        let path_to_res = get_project_root().join("tests").join("fix_global_names");
+
+        let files = ["common.hlsli", "main.hlsl"]
+            .into_iter()
+            .map(|name| {
+                let path = path_to_res.join(name);
+                let content = std::fs::read_to_string(&path).unwrap();
+                (path, content)
+            })
+            .collect::<Vec<_>>();
+
+        let mut config = Config::default();
+        config.function_case = Some(Case::Camel);
+        config.variable_case = Some(Case::Camel);
+        config.global_variable_prefix = Some(vec![String::from("g_")]);
+
+        let suggestions = rename::collect_rename_suggestions(&files, 1, &config);
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].old_name, "g_light_power");
+        assert_eq!(suggestions[0].new_name, "g_lightPower");
+        assert_eq!(suggestions[0].occurrences.len(), 2);
+        assert_eq!(suggestions[1].old_name, "compute_light");
+        assert_eq!(suggestions[1].new_name, "computeLight");
+        let occurrences = suggestions[1]
+            .occurrences
+            .iter()
+            .map(|(file_index, location)| (*file_index, location.start_line))
+            .collect::<Vec<_>>();
+        assert_eq!(occurrences, [(0, 3), (1, 2), (1, 6)]);
+
+        // Files are not changed.
+        assert_eq!(
+            files[0].1,
+            std::fs::read_to_string(path_to_res.join("common.hlsli")).unwrap()
+        );
+    }
+
+    #[test]
+    fn chunked_formatting() {
+        let path_to_input = get_project_root()
+            .join("tests")
+            .join("chunked_formatting")
+            .join("input.hlsl");
+        let input = std::fs::read_to_string(path_to_input).unwrap();
+        let formatter = Formatter::new(Config::default());
+        let expected_output = formatter.format(&input, false).unwrap();
+
+        // Chunks of any size (from one top-level statement per chunk to the whole file) give
+        // the same result as the whole file (`NOFORMAT` sections are not split).
+        for chunk_size in [1, 64, 256, input.len()] {
+            let mut output = Vec::new();
+            let result = stream::format_in_chunks(
+                input.as_bytes(),
+                &mut output,
+                &formatter,
+                chunk_size,
+                input.len(),
+                None,
+            )
+            .unwrap();
+
+            assert_eq!(String::from_utf8(output).unwrap(), expected_output);
+            assert!(matches!(result, stream::ChunkedOutput::Formatted(Some(2))));
+        }
+
+        // Formatting is stopped after the first chunk if the time limit is exceeded.
+        let mut output = Vec::new();
+        let result = stream::format_in_chunks(
+            input.as_bytes(),
+            &mut output,
+            &formatter,
+            1,
+            input.len(),
+            Some(std::time::Instant::now()),
+        )
+        .unwrap();
+        assert!(matches!(result, stream::ChunkedOutput::TimeLimitExceeded));
+        assert!(output.len() < input.len());
+
+        // Formatting is stopped if the code can't be split into chunks of the maximum size.
+        let unclosed_block = "void foo() {\n    float a = 1.0;\n    float b = 2.0;\n";
+        let result = stream::format_in_chunks(
+            unclosed_block.as_bytes(),
+            &mut std::io::sink(),
+            &formatter,
+            1,
+            32,
+            None,
+        )
+        .unwrap();
+        assert!(matches!(result, stream::ChunkedOutput::ChunkTooLarge));
+    }
+
+    #[test]
+    fn shadowing() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.warn_on_shadowing);
+
+        // Change the setting.
+        config.warn_on_shadowing = true;
+
+        // Test.
+        test_formatting_fail_success(config, "shadowing");
+    }
+
+    #[test]
+    fn function_size() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.max_function_lines.is_none());
+        assert!(config.max_nesting_depth.is_none());
+
+        // Change the setting.
+        config.max_function_lines = Some(7);
+        config.max_nesting_depth = Some(2);
+
+        // Test.
+        test_formatting_fail_success(config, "function_size");
+    }
+
+    #[test]
+    fn duplicate_bindings() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.forbid_duplicate_bindings);
+
+        // Change the setting.
+        config.forbid_duplicate_bindings = true;
+
+        // Test.
+        test_formatting_fail_success(config, "duplicate_bindings");
+    }
+
+    #[test]
+    fn duplicate_locations() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.forbid_duplicate_locations);
+
+        // Change the setting.
+        config.forbid_duplicate_locations = true;
+
+        // Test.
+        test_formatting_fail_success(config, "duplicate_locations");
+    }
+
+    #[test]
+    fn ordered_locations() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_ordered_locations);
+
+        // Change the setting.
+        config.require_ordered_locations = true;
+
+        // Test.
+        test_formatting_fail_success(config, "ordered_locations");
+    }
+
+    #[test]
+    fn dead_branches() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.defines.is_empty());
+        assert!(!config.forbid_dead_branches);
+
+        // Change the setting.
+        config.forbid_dead_branches = true;
+        config.defines = vec![
+            ("USE_FOG".to_owned(), 0),
+            ("USE_SHADOWS".to_owned(), 1),
+            ("QUALITY".to_owned(), 2),
+        ];
+
+        // Test.
+        test_formatting_fail_success(config.clone(), "dead_branches");
+
+        // Dead branches are not reported if the rule is disabled.
+        let path = get_project_root().join("tests/dead_branches/fail1.hlsl");
+        let code = std::fs::read_to_string(path).unwrap();
+        assert!(matches!(
+            Formatter::new(config.clone()).format(&code, false),
+            Err(FormatError::RuleViolation {
+                rule: "ForbidDeadBranches",
+                ..
+            })
+        ));
+        config.forbid_dead_branches = false;
+        assert!(Formatter::new(config).format(&code, false).is_ok());
+    }
+
+    #[test]
+    fn macro_redefinition() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.forbid_macro_redefinition);
+
+        // Change the setting.
+        config.include_dirs = vec![get_project_root()
+            .join("tests")
+            .join("macro_redefinition")
+            .join("include")];
+        config.forbid_macro_redefinition = true;
+
+        // Test.
+        test_formatting_fail_success(config, "macro_redefinition");
+    }
+
+    #[test]
+    fn require_default_in_switch() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.require_default_in_switch);
+
+        // Change the setting.
+        config.require_default_in_switch = true;
+
+        // Test.
+        test_formatting_fail_success(config, "require_default_in_switch");
+    }
+
+    #[test]
+    fn uninitialized_variables() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.warn_on_uninitialized_variables);
+
+        // Change the setting.
+        config.warn_on_uninitialized_variables = true;
+
+        // Test.
+        test_formatting_fail_success(config, "uninitialized_variables");
+    }
+
+    #[test]
+    fn presets() {
+        let config = Config::get(&get_project_root().join("tests").join("presets")).unwrap();
+
+        // Rules from the preset.
+        assert!(config.indentation == IndentationRule::TwoSpaces);
+        assert!(config.variable_case == Some(Case::Snake));
+
+        // Rules from the file override the preset.
+        assert_eq!(config.max_line_length, Some(120));
+
+        let config = Config::from_preset(Preset::Unreal);
+        assert!(config.new_line_around_braces == NewLineOnOpenBrace::Before);
+        assert_eq!(config.bool_prefix, Some(vec!["b".to_owned()]));
+    }
+
+    #[test]
+    fn init_config() {
+        let dir = std::env::temp_dir().join("shader-formatter-init-config");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Create a config file from a preset.
+        let path = Config::create_file(&dir, Some(Preset::Google)).unwrap();
+        assert!(Config::create_file(&dir, None).is_err()); // don't overwrite existing files
+
+        // Make sure the file is valid and has values of the preset.
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("NewLineOnOpenBrace = \"After\""));
+        assert!(content.contains("# BoolPrefix = "));
+        let config = Config::get(&dir).unwrap();
+        let preset = Config::from_preset(Preset::Google);
+        assert!(config.indentation == preset.indentation);
+        assert_eq!(config.max_line_length, preset.max_line_length);
+        assert!(config.argument_wrap == preset.argument_wrap);
+        assert!(config.variable_case == preset.variable_case);
+        assert!(config.bool_prefix.is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn json_and_yaml_configs() {
+        for test_dir in ["json_config", "yaml_config"] {
+            let dir = get_project_root().join("tests").join(test_dir);
+            let config = Config::get(&dir).unwrap();
+
+            assert!(config.indentation == IndentationRule::Tab); // from the preset
+            assert_eq!(config.max_line_length, Some(120));
+            assert_eq!(config.include_dirs, vec![dir.join("include")]);
+            assert_eq!(config.defines, vec![("USE_SHADOWS".to_owned(), 1)]);
+            assert!(config.severity("VariableCase") == Severity::Warning);
+            assert!(
+                config
+                    .get_stage_config(Some(ShaderStage::Fragment))
+                    .require_default_precision
+            );
+        }
+    }
+
+    #[test]
+    fn stage_include_dirs() {
+        // Load the config using a relative path.
+        let dir = Path::new("tests").join("stage_include_dirs");
+        let config = Config::get(&dir).unwrap();
+
+        // Inherited directories are not joined with the config directory again.
+        let fragment_config = config.get_stage_config(Some(ShaderStage::Fragment));
+        assert!(fragment_config.require_default_precision);
+        assert_eq!(fragment_config.include_dirs, vec![dir.join("include")]);
+        assert_eq!(
+            config
+                .get_stage_config(Some(ShaderStage::Compute))
+                .include_dirs,
+            vec![dir.join("compute_include")]
+        );
+    }
+
+    #[test]
+    fn config_schema() {
+        let schema = Config::json_schema();
+        let properties = &schema["properties"];
+
+        for rule in rules::RULES {
+            assert!(properties[rule.id].is_object(), "{}", rule.id);
+            assert!(properties["Fragment"]["properties"][rule.id].is_object());
+            if rule.default.is_some() {
+                assert!(!properties[rule.id]["default"].is_null(), "{}", rule.id);
+            }
+        }
+        assert_eq!(properties["Indentation"]["default"], "FourSpaces");
+        assert_eq!(properties["VariableCase"]["enum"][1], "Pascal");
+
+        // Make sure names in the schema are accepted by the config.
+        for name in Preset::NAMES {
+            assert!(Preset::from_name(name).is_some());
+        }
+        for name in Severity::NAMES {
+            assert!(Severity::from_name(name).is_some());
+        }
+        for name in ShaderStage::CONFIG_SECTION_NAMES {
+            assert!(ShaderStage::from_config_section_name(name).is_some());
+        }
+    }
+
+    #[test]
+    fn directory_traversal() {
+        let dir = std::env::temp_dir().join("shader-formatter-directory-traversal");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::create_dir_all(dir.join("sub").join("deeper")).unwrap();
+        for file in ["a.hlsl", ".git/b.hlsl", "sub/c.hlsl", "sub/deeper/d.hlsl"] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&dir, dir.join("sub").join("loop")).unwrap();
+
+        let get_files = |options: crate::TraversalOptions| {
+            let mut files: Vec<String> = crate::get_files_in_directory(&dir, &options)
+                .iter()
+                .map(|path| {
+                    path.strip_prefix(&dir)
+                        .unwrap()
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                })
+                .collect();
+            files.sort();
+            files
+        };
+
+        // Hidden directories and symlinks are skipped by default.
+        assert_eq!(
+            get_files(crate::TraversalOptions::default()),
+            ["a.hlsl", "sub/c.hlsl", "sub/deeper/d.hlsl"]
+        );
+
+        assert_eq!(
+            get_files(crate::TraversalOptions {
+                include_hidden: true,
+                ..Default::default()
+            }),
+            [".git/b.hlsl", "a.hlsl", "sub/c.hlsl", "sub/deeper/d.hlsl"]
+        );
+
+        assert_eq!(
+            get_files(crate::TraversalOptions {
+                max_depth: Some(1),
+                ..Default::default()
+            }),
+            ["a.hlsl", "sub/c.hlsl"]
+        );
+
+        // Symlink cycles are not followed.
+        assert_eq!(
+            get_files(crate::TraversalOptions {
+                follow_symlinks: true,
+                ..Default::default()
+            }),
+            ["a.hlsl", "sub/c.hlsl", "sub/deeper/d.hlsl"]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn large_and_binary_files() {
+        let dir = std::env::temp_dir().join("shader-formatter-large-and-binary-files");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let read = |name: &str, content: &[u8]| {
+            let path = dir.join(name);
+            std::fs::write(&path, content).unwrap();
+            crate::read_text_file(&path, Some(16)).unwrap()
+        };
+
+        assert!(matches!(
+            read("text.hlsl", b"int a = 1;\n"),
+            crate::FileContent::Text(text) if text == "int a = 1;\n"
+        ));
+        assert!(matches!(
+            read("large.hlsl", &[b' '; 17]),
+            crate::FileContent::Skipped(_)
+        ));
+
+        // There is no size limit by default.
+        assert!(Config::default().max_file_size.is_none());
+        assert!(matches!(
+            crate::read_text_file(&dir.join("large.hlsl"), None).unwrap(),
+            crate::FileContent::Text(_)
+        ));
+        assert!(matches!(
+            read("binary.hlsl", b"DXBC\0\0\x01"),
+            crate::FileContent::Skipped(_)
+        ));
+        assert!(matches!(
+            read("latin1.hlsl", b"// caf\xe9"),
+            crate::FileContent::Skipped(_)
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn severity() {
+        let config = Config::get(&get_project_root().join("tests").join("severity")).unwrap();
+        assert!(config.severity("VariableCase") == Severity::Warning);
+        assert!(config.severity("RequireDocsOnStructs") == Severity::Error);
+        assert!(config.struct_case.is_none()); // disabled
+
+        let formatter = Formatter::new(config);
+
+        // Warnings and disabled rules don't fail formatting.
+        let code = "/** Docs. */\nstruct wrong_case {\n    int WrOnG_cAsE;\n};\n";
+        assert_eq!(formatter.format(code, false), Ok(code.to_owned()));
+
+        // Errors still fail formatting.
+        let code = "struct wrong_case {\n    int WrOnG_cAsE;\n};\n";
+        assert!(matches!(
+            formatter.format(code, false),
+            Err(FormatError::RuleViolation {
+                rule: "RequireDocsOnStructs",
+                ..
+            })
+        ));
+
+        // All violations of a rule with `Warning` severity are reported (in a single pass,
+        // skipped code is reported once).
+        let code = "/** Docs. */\nstruct Light {\n    float first_value;\n    Light operator+(Light other) { return other; }\n    float second_value;\n};\n";
+        let mut warnings = Vec::new();
+        assert!(formatter
+            .format_collecting_warnings(code, false, &mut warnings)
+            .is_ok());
+        let names = warnings
+            .iter()
+            .map(|warning| match warning {
+                FormatError::RuleViolation { rule, name, .. } => format!("{}: {}", rule, name),
+                _ => "ParseError".to_owned(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "ParseError",
+                "VariableCase: first_value",
+                "VariableCase: second_value"
+            ]
+        );
+    }
+
+    #[test]
+    fn compact_error_output() {
+        let mut config = Config::default();
+        config.require_docs_on_structs = true;
+        let formatter = Formatter::new(config);
+
+        let error = formatter
+            .format("int a;\n\nstruct Foo {\n    int a;\n};\n", false)
+            .unwrap_err();
+        assert_eq!(
+            error.to_compact(std::path::Path::new("shaders/a.hlsl")),
+            "shaders/a.hlsl:3:8: RequireDocsOnStructs: \
+             expected to find documentation for the struct \"Foo\""
+        );
+
+        // Columns on the first line start from 1 too.
+        let error = formatter
+            .format("struct Foo {\n    int a;\n};\n", false)
+            .unwrap_err();
+        assert_eq!(
+            error.to_compact(std::path::Path::new("shaders/a.hlsl")),
+            "shaders/a.hlsl:1:8: RequireDocsOnStructs: \
+             expected to find documentation for the struct \"Foo\""
+        );
+
+        // Errors at the end of the file point to the end of the last line.
+        let text = "int a;\nint b";
+        let error = FormatError::rule_violation(
+            "RequireDocsOnStructs",
+            "Foo",
+            None,
+            "test".to_owned(),
+            parser::Span::from(text.len()..text.len()),
+        )
+        .locate(text);
+        assert_eq!(
+            error.to_compact(std::path::Path::new("shaders/a.hlsl")),
+            "shaders/a.hlsl:2:6: RequireDocsOnStructs: test"
+        );
+    }
+
+    #[test]
+    fn hook_shader_files() {
+        assert!(hook::is_shader_file("shaders/light.hlsl"));
+        assert!(hook::is_shader_file("shaders/light.frag"));
+        assert!(!hook::is_shader_file("src/main.rs"));
+        assert!(!hook::is_shader_file("README"));
+    }
+
+    #[test]
+    fn daemon_requests() {
+        let mut daemon = Daemon::default();
+
+        let (response, shutdown) = daemon.handle_request(
+            r#"{"jsonrpc":"2.0","id":1,"method":"format","params":{"path":"a.hlsl","content":"int a;"}}"#,
+        );
+        assert!(!shutdown);
+        assert!(response.unwrap()["result"]["output"].is_string());
+
+        // Invalid requests.
+        let (response, _) = daemon.handle_request("{");
+        assert_eq!(response.unwrap()["error"]["code"], -32700);
+        let (response, _) = daemon.handle_request(r#"{"id":2,"method":"format","params":{}}"#);
+        assert_eq!(response.unwrap()["error"]["code"], -32602);
+
+        // Notifications don't have responses.
+        let (response, shutdown) = daemon.handle_request(r#"{"method":"shutdown"}"#);
+        assert!(response.is_none());
+        assert!(shutdown);
+    }
+
+    #[test]
+    fn lint_only_and_format_only() {
+        let mut config = Config::default();
+        config.require_docs_on_structs = true;
+
+        // Lint only: code is not changed but lint rules are checked.
+        let mut formatter = Formatter::new(config.clone());
+        formatter.set_lint_only(true);
+        let code = "/** Docs. */\nstruct Foo {\nint a;\n};\n";
+        assert_eq!(formatter.format(code, false), Ok(code.to_owned()));
+        assert!(matches!(
+            formatter.format("struct Foo {\nint a;\n};\n", false),
+            Err(FormatError::RuleViolation {
+                rule: "RequireDocsOnStructs",
+                ..
+            })
+        ));
+
+        // Format only: lint rules are not checked.
+        config.disable_lint_rules();
+        let formatter = Formatter::new(config);
+        assert_eq!(
+            formatter.format("struct Foo {\nint a;\n};\n", false),
+            Ok("struct Foo {\n    int a;\n};\n".to_owned())
+        );
+    }
+
+    #[test]
+    fn mixed_indentation() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.forbid_mixed_indentation);
+
+        // Change the setting.
+        config.forbid_mixed_indentation = true;
+
+        // Test (formatting fixes indentation so only check lint rules).
+        let mut formatter = Formatter::new(config);
+        formatter.set_lint_only(true);
+        let path_to_res = get_project_root().join("tests").join("mixed_indentation");
+        for (file_name, must_fail) in [
+            ("fail1.hlsl", true),
+            ("fail2.hlsl", true),
+            ("success1.hlsl", false),
+        ] {
+            let code = std::fs::read_to_string(path_to_res.join(file_name)).unwrap();
+            let result = formatter.format(&code, false);
+            assert_eq!(
+                matches!(
+                    result,
+                    Err(FormatError::RuleViolation {
+                        rule: "ForbidMixedIndentation",
+                        ..
+                    })
+                ),
+                must_fail,
+                "{}",
+                file_name
+            );
+        }
+    }
+
+    #[test]
+    fn line_endings() {
+        let mut config = Config::default();
+        config.line_ending = Some(LineEnding::CrLf);
+        config.insert_final_newline = Some(true);
+        let formatter = Formatter::new(config.clone());
+        assert_eq!(
+            formatter.format("struct Foo {\nint a;\r\n};", false),
+            Ok("struct Foo {\r\n    int a;\r\n};\r\n".to_owned())
+        );
+
+        config.line_ending = Some(LineEnding::Lf);
+        config.insert_final_newline = Some(false);
+        let formatter = Formatter::new(config);
+        assert_eq!(
+            formatter.format("struct Foo {\r\nint a;\r\n};\r\n\r\n", false),
+            Ok("struct Foo {\n    int a;\n};".to_owned())
+        );
+    }
+
+    #[test]
+    fn rules_registry() {
+        let default_config = Config::default();
+        let mut example_config = String::new();
+
+        for rule in rules::RULES {
+            // Make sure defaults in the registry match the default config.
+            assert_eq!(
+                default_config.rule_value(rule.id).as_deref(),
+                rule.default,
+                "{}",
+                rule.id
+            );
+            assert!(rules::find_rule(rule.id).is_some());
+            assert!(
+                !rule.example_before.is_some_and(str::is_empty)
+                    && !rule.example_after.is_some_and(str::is_empty),
+                "{}",
+                rule.id
+            );
+
+            example_config += &format!("{} = {}\n", rule.id, rule.example_value);
+        }
+
+        // Make sure example values are accepted by the config.
+        let dir = std::env::temp_dir().join("shader-formatter-rules-registry");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("shader-formatter.toml"), example_config).unwrap();
+        let config = Config::get(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let config = config.unwrap();
+        for rule in rules::RULES {
+            if rule.id == "IncludeDirs" {
+                continue; // paths are made relative to the config file
+            }
+            assert_eq!(
+                config.rule_value(rule.id).as_deref(),
+                Some(rule.example_value),
+                "{}",
+                rule.id
+            );
+        }
+    }
+
+    #[test]
+    fn methods() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.function_case.is_none());
+        assert!(!config.require_docs_on_functions);
+
+        // Change the setting.
+        config.function_case = Some(Case::Camel);
+        config.require_docs_on_functions = true;
+
+        // Test.
+        test_formatting_fail_success(config, "methods");
+    }
+
+    #[test]
+    fn enum_case() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.enum_case.is_none());
+        assert!(config.enum_value_case.is_none());
+
+        // Change the setting.
+        config.enum_case = Some(Case::Pascal);
+        config.enum_value_case = Some(Case::UpperSnake);
+
+        // Test.
+        test_formatting_fail_success(config, "enum_case");
+    }
+
+    #[test]
+    fn enum_value_layout() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.enum_value_layout.is_none());
+        assert!(!config.enum_trailing_comma);
+
+        // Change the setting.
+        config.enum_value_layout = Some(EnumValueLayout::OnePerLine);
+        config.enum_trailing_comma = true;
+
+        // Test.
+        compare_files_in_directory(config.clone(), "enum_value_layout/one_per_line");
+
+        config.enum_value_layout = Some(EnumValueLayout::Packed);
+        compare_files_in_directory(config, "enum_value_layout/packed");
+    }
+
+    #[test]
+    fn multiple_declarators() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.variable_case.is_none());
+
+        // Change the setting.
+        config.variable_case = Some(Case::Camel);
+
+        // Test.
+        test_formatting_fail_success(config, "multiple_declarators");
+    }
+
+    #[test]
+    fn brace_initializers() {
+        let mut config = Config::default();
+
+        // Test default settings.
+        compare_files_in_directory(config.clone(), "brace_initializers/1");
+
+        // Make sure default config uses other setting.
+        assert!(config.max_line_length.is_none());
+
+        // Change the setting.
+        config.max_line_length = Some(60);
+
+        // Test.
+        compare_files_in_directory(config, "brace_initializers/2");
+    }
+
+    #[test]
+    fn align_initializer_columns() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(!config.align_initializer_columns);
+
+        // Change the setting.
+        config.align_initializer_columns = true;
+
+        // Test.
+        compare_files_in_directory(config, "align_initializer_columns");
+    }
+
+    #[test]
+    fn argument_wrap() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.argument_wrap.is_none());
+
+        // Change the setting.
+        config.max_line_length = Some(100);
+
+        for (style, test_dir) in [
+            (ArgumentWrap::AllOnOneLine, "argument_wrap/all_on_one_line"),
+            (
+                ArgumentWrap::AlignWithParenthesis,
+                "argument_wrap/align_with_parenthesis",
+            ),
+            (ArgumentWrap::OnePerLine, "argument_wrap/one_per_line"),
+        ] {
+            config.argument_wrap = Some(style);
+
+            // Test.
+            compare_files_in_directory(config.clone(), test_dir);
+
+            // Make sure that formatting the result again does not change it.
+            check_formatting_is_stable(config.clone(), test_dir);
+        }
+    }
+
+    #[test]
+    fn bin_pack_parameters() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.bin_pack_parameters);
+
+        // Change the setting.
+        config.max_line_length = Some(80);
+        config.bin_pack_parameters = false;
+
+        // Test.
+        compare_files_in_directory(config.clone(), "bin_pack_parameters");
+        check_formatting_is_stable(config, "bin_pack_parameters");
+    }
+
+    #[test]
+    fn break_before_binary_operators() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.break_before_binary_operators.is_none());
+
+        // Change the setting.
+        config.max_line_length = Some(60);
+
+        for (break_before, test_dir) in [
+            (true, "break_before_binary_operators/before"),
+            (false, "break_before_binary_operators/after"),
+        ] {
+            config.break_before_binary_operators = Some(break_before);
+
+            // Test.
+            compare_files_in_directory(config.clone(), test_dir);
+
+            // Make sure that formatting the result again does not change it.
+            check_formatting_is_stable(config.clone(), test_dir);
+        }
+    }
+
+    #[test]
+    fn require_braces() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.require_braces_on_if.is_none());
+        assert!(config.require_braces_on_else.is_none());
+        assert!(config.require_braces_on_for.is_none());
+        assert!(config.require_braces_on_while.is_none());
+
+        for (rule, new_line_on_open_brace, test_dir) in [
+            (
+                RequireBraces::Always,
+                NewLineOnOpenBrace::After,
+                "require_braces/always",
+            ),
+            (
+                RequireBraces::MultiLine,
+                NewLineOnOpenBrace::After,
+                "require_braces/multi_line",
+            ),
+            (
+                RequireBraces::Always,
+                NewLineOnOpenBrace::Before,
+                "require_braces/new_line_before",
+            ),
+        ] {
+            // Change the setting.
+            config.require_braces_on_if = Some(rule);
+            config.require_braces_on_else = Some(rule);
+            config.require_braces_on_for = Some(rule);
+            config.require_braces_on_while = Some(rule);
+            config.new_line_around_braces = new_line_on_open_brace;
+
+            // Test.
+            compare_files_in_directory(config.clone(), test_dir);
+
+            // Make sure that formatting the result again does not change it.
+            check_formatting_is_stable(config.clone(), test_dir);
+        }
+    }
+
+    #[test]
+    fn trailing_comma_in_initializers() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.trailing_comma_in_initializers.is_none());
+
+        // Keep the multi-line initializer.
+        config.keep_user_line_breaks = true;
+
+        for (trailing_comma, test_dir) in [
+            (
+                TrailingComma::Always,
+                "trailing_comma_in_initializers/always",
+            ),
+            (TrailingComma::Never, "trailing_comma_in_initializers/never"),
+            (
+                TrailingComma::Multiline,
+                "trailing_comma_in_initializers/multiline",
+            ),
+        ] {
+            // Change the setting.
+            config.trailing_comma_in_initializers = Some(trailing_comma);
+
+            // Test.
+            compare_files_in_directory(config.clone(), test_dir);
+
+            // Make sure that formatting the result again does not change it.
+            check_formatting_is_stable(config.clone(), test_dir);
+        }
+    }
+
+    #[test]
+    fn statement_attribute_placement() {
+        let mut config = Config::default();
+
+        // Make sure default config uses other setting.
+        assert!(config.statement_attribute_placement.is_none());
+
+        for (placement, test_dir) in [
+            (
+                StatementAttributePlacement::SeparateLine,
+                "statement_attribute_placement/separate_line",
+            ),
+            (
+                StatementAttributePlacement::SameLine,
+                "statement_attribute_placement/same_line",
+            ),
+        ] {
+            // Change the setting.
+            config.statement_attribute_placement = Some(placement);
+
+            // Test.
+            compare_files_in_directory(config.clone(), test_dir);
+
+            // Make sure that formatting the result again does not change it.
+            check_formatting_is_stable(config.clone(), test_dir);
+        }
+    }
+
+    #[test]
+    fn empty_lines_no_indent() {
+        let mut config = Config::default();
+
+        config.indent_preprocessor = true;
+        config.preprocessor_if_creates_nesting = true;
+
+        // Test.
+        compare_files_in_directory(config, "empty_lines_no_indent");
+    }
+
+    #[test]
+    fn unified_diff() {
+        assert_eq!(patch::unified_diff("a.hlsl", "int a;\n", "int a;\n"), "");
+
+        let old_text = "1\n2\n3\n4\nint  a;\n5\n6\n7\n8\n9\n10\n11\nint  b;";
+        let new_text = "1\n2\n3\n4\nint a;\n5\n6\n7\n8\n9\n10\n11\nint b;\n";
+        assert_eq!(
+            patch::unified_diff("shaders/a.hlsl", old_text, new_text),
+            "--- a/shaders/a.hlsl\n\
+             +++ b/shaders/a.hlsl\n\
+             @@ -2,7 +2,7 @@\n 2\n 3\n 4\n-int  a;\n+int a;\n 5\n 6\n 7\n\
+             @@ -10,4 +10,4 @@\n 9\n 10\n 11\n-int  b;\n\\ No newline at end of file\n+int b;\n"
+        );
+
+        // Changed line breaks.
+        assert_eq!(
+            patch::unified_diff("a.hlsl", "int a;\r\n", "int a;\n"),
+            "--- a/a.hlsl\n+++ b/a.hlsl\n@@ -1,1 +1,1 @@\n-int a;\r\n+int a;\n"
+        );
     }
 }
@@ -0,0 +1,3 @@
+struct RgbColor {
+    float value;
+};
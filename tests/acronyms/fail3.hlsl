@@ -0,0 +1,3 @@
+void foo() {
+    float ssao_value = 1.0;
+}
@@ -0,0 +1,14 @@
+struct RGBColor {
+    float lightRGB;
+    float aoValue;
+};
+
+enum Mode {
+    MODE_AOMAP,
+    MODE_SSAO
+};
+
+void foo() {
+    float rgbColor = 1.0;
+    float ssaoRGBScale = 1.0;
+}
@@ -0,0 +1,27 @@
+static const float4x4 identity = {
+    1.0,  0.0,  0.0, 0.0,
+    0.0,  1.0,  0.0, 0.0,
+    0.0,  0.0,  1.0, 0.0,
+    10.5, -2.0, 0.0, 1.0
+};
+
+static const float2x3 m = {
+    1,  2,  3,
+    40, 50, 60
+};
+
+static const float2 offsets[3] = {
+    {0.0,   1.0},
+    {-10.0, 0.0},
+    {0.5,   -0.25}
+};
+
+static const float weights[3] = {0.2, 0.6, 0.2};
+
+void foo() {
+    float3x3 rotation = {
+        cos(a), -sin(a), 0.0,
+        sin(a), cos(a),  0.0,
+        0.0,    0.0,     1.0
+    };
+}
@@ -0,0 +1,13 @@
+void foo() {
+    float3 color = lerp(computeAmbientLight(normal, position),
+                        computeDirectLight(normal, lightDirection),
+                        factor);
+    if (someCondition) {
+        return;
+    }
+}
+
+float4 main(float4 position : SV_Position, float2 uv : TEXCOORD0, float3 normal : NORMAL0) : SV_Target {
+    // lerp(computeAmbientLight(normal, position), computeDirectLight(normal, lightDirection), f);
+    return float4(0.0, 0.0, 0.0, 1.0);
+}
@@ -0,0 +1,9 @@
+float4 main(float4 position : SV_Position,
+            float2 uv : TEXCOORD0,
+            float3 normal : NORMAL0) : SV_Target {
+    return float4(0.0, 0.0, 0.0, 1.0);
+}
+
+float foo(float a, float b) {
+    return computeSomething(someLongArgumentName, anotherLongArgumentName, yetAnotherArgument);
+}
@@ -0,0 +1,14 @@
+static const float weights[3] = {0.2, 0.6, 0.2};
+static const float2 offsets[2] = { {0.0, 1.0}, {1.0, 0.0} };
+
+static const int values[4] = {
+1, 2,
+        3, 4
+    };
+
+void foo() {
+    float3 colors[2] = {float3(1.0, 0.0, 0.0), float3(0.0, 1.0, 0.0)};
+    if (colors[0].x > 0.0) {
+        return;
+    }
+}
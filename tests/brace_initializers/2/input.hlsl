@@ -0,0 +1,6 @@
+static const float weights[3] = {0.2, 0.6, 0.2};
+static const float2 offsets[4] = {{0.0, 1.0}, {1.0, 0.0}, {0.0, -1.0}, {-1.0, 0.0}};
+
+void foo() {
+    float3 colors[2] = {float3(1.0, 0.0, 0.0), float3(0.0, 1.0, 0.0)};
+}
@@ -0,0 +1,12 @@
+void foo() {
+    bool isVisible = someValue > 0.0 &&
+        otherValue < 1.0;
+    float sum = first +
+        second +
+        third;
+    float result = computeSomething(a, b) * scaleFactor +
+        offsetValue * anotherFactor - somethingElse;
+    // first + second + third + fourth + fifth + sixth + seventh + eighth + ninth + tenth
+    int i = 0; // comment
+    i++;
+}
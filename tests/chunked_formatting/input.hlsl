@@ -0,0 +1,43 @@
+/** Docs. */
+float computeA(float value)
+{
+        float result = value * 2.0;
+  return result;
+}
+
+float globalValueA = 1.0;
+#if defined(FOO)
+int helperA(int x) { return x; }
+#endif
+/* block
+   comment; */
+/** Docs. */
+float computeB(float value)
+{
+        float result = value * 2.0;
+  return result;
+}
+
+float globalValueB = 1.0;
+// NOFORMATBEGIN
+float   keptA   = 1.0;
+float   keptB   = 2.0;
+// NOFORMATEND
+#if defined(FOO)
+int helperB(int x) { return x; }
+#endif
+/* block
+   comment; */
+/** Docs. */
+float computeC(float value)
+{
+        float result = value * 2.0;
+  return result;
+}
+
+float globalValueC = 1.0;
+#if defined(FOO)
+int helperC(int x) { return x; }
+#endif
+/* block
+   comment; */
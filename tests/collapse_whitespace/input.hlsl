@@ -0,0 +1,13 @@
+#define MAX_LIGHTS    16
+#define LIGHT_SIZE    4
+
+[RootSignature("RootFlags(ALLOW_INPUT_ASSEMBLER_INPUT_LAYOUT),  CBV(b0)")]
+float4 main(float4  position :   SV_Position) : SV_Target
+{
+    float  value    =	1.0;  // aligned comment
+    float  other    =	2.0;  // aligned comment
+
+    value = foo(value,     other)  *   2.0;
+
+    return float4(value,  other,   0.0, 1.0);
+}
@@ -0,0 +1,13 @@
+//------------------------------
+// Lighting.
+//------------------------------
+
+float getValue(float input) {
+    //--------------------------
+    float result = input;
+    // ---- not a banner
+    //--
+
+    //--------------------------
+    return result;
+}
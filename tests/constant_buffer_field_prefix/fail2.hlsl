@@ -0,0 +1,5 @@
+cbuffer Frame : register(b0)
+{
+    float cb_time;
+    float cb_delta_time;
+};
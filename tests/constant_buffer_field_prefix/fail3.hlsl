@@ -0,0 +1,6 @@
+float g_fallbackTime;
+
+tbuffer Lights : register(t0)
+{
+    float g_lightCount;
+};
@@ -0,0 +1,22 @@
+float g_fallbackTime;
+
+cbuffer Frame : register(b0)
+{
+    float cb_time;
+    float cb_deltaTime : packoffset(c1);
+    struct
+    {
+        float intensity;
+    } cb_sun;
+};
+
+tbuffer Lights
+{
+    float cb_lightCount;
+};
+
+float main() : SV_Target
+{
+    float localTime = cb_time + g_fallbackTime;
+    return localTime;
+}
@@ -0,0 +1,12 @@
+float4 main(float4 position : SV_Position) : SV_Target
+{
+    float3 color = float3 (position.x,position.y ,0.5);
+    int index = (int) position.z;
+    uint mask = (uint)(index) & 0xFF;
+    float scale = (float) -position.w;
+
+    // Function calls are not changed.
+    float value = foo (index,mask);
+
+    return float4 (normalize(color,scale), (float)mask);
+}
@@ -0,0 +1,5 @@
+#if USE_FOG
+float getFog(float distance) {
+    return distance;
+}
+#endif
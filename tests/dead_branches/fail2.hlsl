@@ -0,0 +1,5 @@
+#ifdef USE_SHADOWS
+static const float shadowBias = 0.01;
+#else
+static const float shadowBias = 0.0;
+#endif
@@ -0,0 +1,7 @@
+#if QUALITY > 1 && defined(USE_SHADOWS)
+static const int sampleCount = 16;
+#elif QUALITY == 1
+static const int sampleCount = 4;
+#else
+static const int sampleCount = 1;
+#endif
@@ -0,0 +1,3 @@
+#ifndef USE_SHADOWS
+static const float shadowBias = 0.0;
+#endif
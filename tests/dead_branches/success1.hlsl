@@ -0,0 +1,20 @@
+#ifndef COMMON_HLSLI
+#define COMMON_HLSLI
+
+#if USE_FOG || USE_SHADOWS
+static const float shadowBias = 0.01;
+#endif
+
+#if !USE_FOG && (QUALITY + 1) * 2 >= 6 && OTHER_MACRO
+static const int sampleCount = 16;
+#elif UNKNOWN_MACRO
+static const int sampleCount = 4;
+#else
+static const int sampleCount = 1;
+#endif
+
+#ifdef USE_SHADOWS // comment
+static const float shadowStrength = 1.0;
+#endif
+
+#endif
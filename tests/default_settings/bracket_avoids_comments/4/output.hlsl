@@ -0,0 +1,8 @@
+/* Plain comment with code: if (a) { b(); } */
+void foo() {
+    if (true) { // some comment
+        /* multi-line
+        comment { */
+        bar();
+    }
+}
@@ -0,0 +1,16 @@
+float get(float scale=1.0, float bias  =  0.5, float weights[4]	= {1.0, 1.0, 1.0, 1.0}) {
+    float result  =  scale + bias;
+    return result;
+}
+
+struct Light {
+    float getIntensity(float distance, float falloff=2.0) {
+        return distance * falloff;
+    }
+};
+
+// NOFORMATBEGIN
+float keep(float scale=1.0) {
+    return scale;
+}
+// NOFORMATEND
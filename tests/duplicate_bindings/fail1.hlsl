@@ -0,0 +1,2 @@
+Texture2D diffuseTexture : register(t0);
+Texture2D normalTexture : register(t0);
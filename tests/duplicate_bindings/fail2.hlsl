@@ -0,0 +1,2 @@
+Texture2D textures[4] : register(t1, space2);
+Texture2D otherTexture : register(T1, space2);
@@ -0,0 +1,7 @@
+#version 450
+
+layout(set = 1, binding = 0) uniform sampler2D diffuseTexture;
+layout(binding = 1, set = 1) uniform CameraData {
+    mat4x4 viewProjection;
+} camera;
+layout(set = 1, binding = 0) uniform sampler2D normalTexture;
@@ -0,0 +1,9 @@
+cbuffer FrameData : register(b0) {
+    float4x4 viewProjection;
+};
+
+Texture2D diffuseTexture : register(t0);
+Texture2D normalTexture : register(t0, space1);
+Texture2D textures[4] : register(t1);
+SamplerState linearSampler : register(s0);
+RWTexture2D<float4> output : register(u0);
@@ -0,0 +1,9 @@
+#version 450
+
+layout(set = 0, binding = 0) uniform sampler2D diffuseTexture;
+layout(set = 1, binding = 0) uniform CameraData {
+    mat4x4 viewProjection;
+} camera;
+layout(binding = 1) uniform sampler2D normalTexture;
+layout(location = 0) in vec2 inUv;
+layout(location = 0) out vec4 outColor;
@@ -0,0 +1,5 @@
+#version 450
+
+layout(location = 0) in float inDepth;
+layout(location = 1) in float inAlpha;
+layout(location = 0) in float inFade;
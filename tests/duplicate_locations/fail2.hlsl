@@ -0,0 +1,4 @@
+#version 450
+
+layout(location = 0) out float outColor;
+layout(location = 0) flat out uint outIndex;
@@ -0,0 +1,6 @@
+#version 450
+
+layout(location = 2) in VertexData {
+    float depth;
+} vertexData;
+layout(location = 2) in float inAlpha;
@@ -0,0 +1,7 @@
+#version 450
+
+layout(location = 0) in float inDepth;
+layout(location = 1) in float inAlpha;
+layout(location = 0) out float outColor;
+layout(location = 1) flat out uint outIndex;
+layout(set = 0, binding = 0) uniform sampler diffuseSampler;
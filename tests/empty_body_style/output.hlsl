@@ -0,0 +1,18 @@
+struct Empty {};
+
+interface ILight {};
+
+void doNothing() { // intentionally empty
+}
+
+void update() {}
+
+void main() {
+    for (int i = 0; i < 3; i++) {}
+
+    if (isEnabled) {
+        // TODO
+    }
+
+    float weights[2] = {};
+}
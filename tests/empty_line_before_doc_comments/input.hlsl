@@ -0,0 +1,30 @@
+#define COUNT 4
+/** Light docs. */
+struct Light {
+    /// Intensity docs.
+    float intensity;
+    /// Color docs.
+    /// Second line.
+    float color;
+
+    /// Radius docs.
+    float radius;
+};
+// Regular comment.
+/**
+ * Foo docs.
+ */
+void foo() {
+    float value;
+    // Not docs.
+    value = 1.0;
+}
+/// Bar docs.
+void bar() {
+}
+
+// NOFORMATBEGIN
+float kept;
+/// Kept docs.
+float keptDocs;
+// NOFORMATEND
@@ -0,0 +1,23 @@
+struct VertexOut {
+    float4 position : SV_Position;
+    float2 uv : TEXCOORD0;
+};
+
+VertexOut VSMain(uint vertexId : SV_VertexID) {
+    VertexOut output;
+    output.position = float4(0.0, 0.0, 0.0, 1.0);
+    output.uv = float2(0.0, 0.0);
+    return output;
+}
+
+float4 PSMain(VertexOut input) : SV_Target {
+    return calculateColor(input.uv);
+}
+
+float4 calculateColor(float2 uv) {
+    return float4(uv, 0.0, 1.0);
+}
+
+[numthreads(8, 8, 1)]
+void CSMain(uint3 threadId : SV_DispatchThreadID) {
+}
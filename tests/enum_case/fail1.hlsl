@@ -0,0 +1,4 @@
+enum light_type {
+    DIRECTIONAL,
+    POINT,
+};
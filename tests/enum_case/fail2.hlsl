@@ -0,0 +1 @@
+enum class BlendMode : uint { OPAQUE, alphaBlend };
@@ -0,0 +1,5 @@
+enum LightType {
+    // Sun.
+    DIRECTIONAL,
+    Point = 1
+};
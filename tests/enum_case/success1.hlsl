@@ -0,0 +1,7 @@
+enum LightType {
+    DIRECTIONAL,
+    POINT = 1,
+    SPOT_LIGHT = 1 << 2,
+};
+
+enum class BlendMode : uint { OPAQUE, ALPHA_BLEND };
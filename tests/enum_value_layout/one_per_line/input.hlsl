@@ -0,0 +1,16 @@
+enum LightType { DIRECTIONAL, POINT = 1, SPOT = 2 };
+
+struct Foo {
+    int value;
+};
+
+enum class BlendMode : uint {
+    // Opaque.
+    OPAQUE,
+    ALPHA_BLEND,
+    ADDITIVE
+};
+
+// NOFORMATBEGIN
+enum Packed { A, B };
+// NOFORMATEND
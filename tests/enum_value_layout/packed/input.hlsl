@@ -0,0 +1,11 @@
+enum LightType {
+    DIRECTIONAL,
+    POINT = 1,
+    SPOT = 2,
+};
+
+enum class BlendMode : uint {
+    // Opaque.
+    OPAQUE,
+    ALPHA_BLEND
+};
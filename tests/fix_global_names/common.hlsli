@@ -0,0 +1,7 @@
+float g_light_power = 1.0;
+
+float compute_light(float value)
+{
+    float result = value;
+    return result * g_light_power;
+}
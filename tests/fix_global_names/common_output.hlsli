@@ -0,0 +1,7 @@
+float g_lightPower = 1.0;
+
+float computeLight(float value)
+{
+    float result = value;
+    return result * g_lightPower;
+}
@@ -0,0 +1,7 @@
+#include "common.hlsli"
+#define SCALE(x) compute_light(x)
+
+float mainPass(float input)
+{
+    return compute_light(input) + SCALE(input);
+}
@@ -0,0 +1,7 @@
+#include "common.hlsli"
+#define SCALE(x) computeLight(x)
+
+float mainPass(float input)
+{
+    return computeLight(input) + SCALE(input);
+}
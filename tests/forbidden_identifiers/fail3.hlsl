@@ -0,0 +1,3 @@
+struct Light {
+    float my__value;
+};
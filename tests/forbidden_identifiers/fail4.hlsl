@@ -0,0 +1,3 @@
+float get(float gl_value) {
+    return gl_value;
+}
@@ -0,0 +1,4 @@
+enum Mode {
+    MODE__FAST,
+    MODE_SLOW
+};
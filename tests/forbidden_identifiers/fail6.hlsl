@@ -0,0 +1,3 @@
+cbuffer __Frame {
+    float time;
+};
@@ -0,0 +1,17 @@
+struct Light {
+    float my_value;
+};
+
+cbuffer Frame {
+    float time;
+};
+
+enum Mode {
+    MODE_FAST,
+    MODE_SLOW
+};
+
+float get(float inputs, float value) {
+    float outputValue = inputs * value;
+    return outputValue;
+}
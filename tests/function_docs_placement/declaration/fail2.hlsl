@@ -0,0 +1,3 @@
+void scale(inout float value) {
+    value *= 2.0;
+}
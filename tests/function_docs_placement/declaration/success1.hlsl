@@ -0,0 +1,19 @@
+/**
+ * Scales the value.
+ *
+ * @param value Value to scale.
+ */
+void scale(inout float value);
+
+/**
+ * Resets the value.
+ *
+ * @param value Value to reset.
+ */
+void reset(out float value) {
+    value = 0.0;
+}
+
+void scale(inout float value) {
+    value *= 2.0;
+}
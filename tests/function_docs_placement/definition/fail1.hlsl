@@ -0,0 +1,10 @@
+/**
+ * Scales the value.
+ *
+ * @param value Value to scale.
+ */
+void scale(inout float value);
+
+void scale(inout float value) {
+    value *= 2.0;
+}
@@ -0,0 +1 @@
+void scale(inout float value);
@@ -0,0 +1,10 @@
+void scale(inout float value);
+
+/**
+ * Scales the value.
+ *
+ * @param value Value to scale.
+ */
+void scale(inout float value) {
+    value *= 2.0;
+}
@@ -0,0 +1,10 @@
+float getValue(float input) {
+    float result = input;
+    result *= 2.0;
+    result += 1.0;
+    result /= 3.0;
+    result -= 4.0;
+    result *= 5.0;
+    result += 6.0;
+    return result;
+}
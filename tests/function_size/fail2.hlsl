@@ -0,0 +1,10 @@
+float getValue(float input) {
+    if (input > 0.0) {
+        if (input > 1.0) {
+            if (input > 2.0) {
+                return 2.0;
+            }
+        }
+    }
+    return 0.0;
+}
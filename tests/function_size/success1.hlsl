@@ -0,0 +1,11 @@
+float getValue(float input) {
+    float result = input;
+    if (input > 0.0) {
+        if (input > 1.0) {
+            result = 1.0;
+        }
+    }
+    return result;
+}
+
+float getOtherValue(float input) { return input; }
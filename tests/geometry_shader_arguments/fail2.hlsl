@@ -0,0 +1,16 @@
+struct VertexOut {
+    float4 position : SV_Position;
+};
+
+[maxvertexcount(3)]
+void geometryMain(triangle VertexOut input[3], inout TriangleStream<VertexOut> stream) {
+    for (uint i = 0; i < 3; i++) {
+        stream.Append(input[i]);
+    }
+}
+
+[numthreads(32, 1, 1)]
+[outputtopology("triangle")]
+void meshMain(uint threadId : SV_GroupThreadID, out vertices VertexOut verts[64], out indices uint3 Tris[126]) {
+    SetMeshOutputCounts(64, 126);
+}
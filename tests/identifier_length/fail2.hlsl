@@ -0,0 +1,3 @@
+float get(float a) {
+    return a;
+}
@@ -0,0 +1,3 @@
+struct Light {
+    float intensityOfTheLight;
+};
@@ -0,0 +1,3 @@
+void foo() {
+    float weightOfTheCurrentSample = 1.0;
+}
@@ -0,0 +1,11 @@
+struct Light {
+    float intensity;
+};
+
+float get(float2 uv, float scale) {
+    float result = 0.0;
+    for (int i = 0; i < 4; i++) {
+        result += uv.x * scale;
+    }
+    return result;
+}
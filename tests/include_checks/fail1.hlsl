@@ -0,0 +1,5 @@
+#include "math.hlsli"
+#include "missing.hlsli"
+
+void main() {
+}
@@ -0,0 +1,4 @@
+#include "include/lighting.hlsli"
+
+void main() {
+}
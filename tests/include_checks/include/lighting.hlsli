@@ -0,0 +1,5 @@
+#include "shared.hlsli"
+
+float3 calculateLighting(float3 normal) {
+    return normal;
+}
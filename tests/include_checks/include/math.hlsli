@@ -0,0 +1,3 @@
+float square(float value) {
+    return value * value;
+}
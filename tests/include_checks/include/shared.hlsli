@@ -0,0 +1,3 @@
+#include "../fail2.hlsl"
+
+static const float PI = 3.14159;
@@ -0,0 +1,6 @@
+#include "math.hlsli"
+#include "include/math.hlsli"
+#include <math.hlsli>
+
+void main() {
+}
@@ -0,0 +1,4 @@
+#include "success1.hlsl"
+
+void main() {
+}
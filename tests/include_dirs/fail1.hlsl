@@ -0,0 +1,9 @@
+#include "types.hlsli"
+
+Light Main_Light;
+
+float4 calculateColor(float3 normal) {
+    Weight fWeight = 0.5;
+    Light light = Main_Light;
+    return float4(light.color * fWeight, 1.0);
+}
@@ -0,0 +1,9 @@
+#include "types.hlsli"
+
+Light mainLight;
+
+float4 calculateColor(float3 normal) {
+    Weight weight = 0.5;
+    Light light = mainLight;
+    return float4(light.color * weight, 1.0);
+}
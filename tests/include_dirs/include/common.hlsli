@@ -0,0 +1,3 @@
+#include "types.hlsli"
+
+typedef float Weight;
@@ -0,0 +1,6 @@
+#include "common.hlsli"
+
+struct Light {
+    float3 color;
+    float intensity;
+};
@@ -0,0 +1,10 @@
+struct Light {
+	float intensity;
+};
+
+void foo(float value) {
+	if (value > 0.5) {
+		value = 0.5;
+	}
+	value = 1.0;
+}
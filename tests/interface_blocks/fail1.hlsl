@@ -0,0 +1,3 @@
+layout(std140, binding = 2) uniform Lights {
+    vec4 lightColor;
+} u_lights[4];
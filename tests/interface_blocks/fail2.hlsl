@@ -0,0 +1,4 @@
+layout(std140, binding = 2) uniform Lights {
+    vec4 lightColor;
+    layout(offset = 16) vec4 light_position;
+} lights[4];
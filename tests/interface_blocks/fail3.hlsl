@@ -0,0 +1,3 @@
+layout(set = 0, binding = 1) restrict readonly buffer Data {
+    float some_values[];
+} data;
@@ -0,0 +1,12 @@
+layout(std140, binding = 2) uniform Lights {
+    vec4 lightColor;
+    layout(offset = 16) vec4 lightPosition;
+} lights[4];
+
+layout(set = 0, binding = 1) restrict readonly buffer Data {
+    float someValues[];
+} data;
+
+uniform Camera {
+    mat4 viewMatrix;
+};
@@ -0,0 +1,23 @@
+float4 shade(float3 normal,
+      float3 lightDirection
+)
+{
+float value = foo(a,
+        b
+    );
+    float w[3] = {
+        0.2, 0.6,
+   0.2
+    };
+    if (value > 0.5)
+    {
+        return float4(normal,
+            value);
+    }
+    return float4(lightDirection, 1.0);
+}
+
+enum Mode {
+    A,
+    B
+};
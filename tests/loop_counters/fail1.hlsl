@@ -0,0 +1,4 @@
+void foo()
+{
+    int count = 0;
+}
@@ -0,0 +1,7 @@
+void foo()
+{
+    for (int i = 0; i < 4; i++)
+    {
+        int value = i;
+    }
+}
@@ -0,0 +1,11 @@
+void foo()
+{
+    int iCount = 0;
+    for (int i = 0; i < 4; i++)
+    {
+        for (int j = 0; j < i; j++)
+        {
+            iCount += j;
+        }
+    }
+}
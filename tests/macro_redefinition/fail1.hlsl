@@ -0,0 +1,2 @@
+#define SAMPLE_COUNT 4
+#define SAMPLE_COUNT 8
@@ -0,0 +1,3 @@
+#define PI 3.14
+
+#include "constants.hlsli"
@@ -0,0 +1,2 @@
+#define SQUARE(x) x * x
+#include "include/constants.hlsli"
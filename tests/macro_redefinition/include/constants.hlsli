@@ -0,0 +1,7 @@
+#ifndef CONSTANTS_HLSLI
+#define CONSTANTS_HLSLI
+
+#define PI 3.14159
+#define SQUARE(x) ((x) * (x))
+
+#endif
@@ -0,0 +1,18 @@
+#include "constants.hlsli"
+#include "include/constants.hlsli"
+
+#define PI   3.14159
+#define SQUARE(x) \
+    ((x) * (x))
+
+#ifdef USE_HIGH_QUALITY
+#define SAMPLE_COUNT 16
+#elif defined(USE_MEDIUM_QUALITY)
+#define SAMPLE_COUNT 8
+#else
+#define SAMPLE_COUNT 4
+#endif
+
+#define BIAS 0.1
+#undef BIAS
+#define BIAS 0.2
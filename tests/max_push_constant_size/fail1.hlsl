@@ -0,0 +1,5 @@
+layout(push_constant) uniform Constants
+{
+    float values[32];
+    float extra;
+} constants;
@@ -0,0 +1,5 @@
+[[vk::push_constant]] cbuffer Constants
+{
+    float values[31];
+    double extra;
+};
@@ -0,0 +1,11 @@
+layout(push_constant) uniform Constants
+{
+    float values[30];
+    uint count;
+    bool enabled;
+} constants;
+
+layout(binding = 1) uniform Lights
+{
+    float intensities[64];
+} lights;
@@ -0,0 +1,15 @@
+struct Light
+{
+    float intensity;
+};
+
+[[vk::push_constant]] cbuffer Constants
+{
+    float values[32];
+    Light light;
+};
+
+cbuffer Frame
+{
+    float weights[64];
+};
@@ -0,0 +1,13 @@
+namespace Lighting {
+    static const float AMBIENT = 0.1;
+}
+
+float4 main(VertexOutput input) : SV_Target {
+    float3 normal = normalize(input.normal.xyz);
+    float ambient = Lighting::AMBIENT;
+    float scale = 1. + .5 * input.color[0].x;
+    float3 color = colorTexture.Sample(linearSampler, input.uv).rgb;
+    float3 tint = input.tint.rgb;
+
+    return float4(color * ambient * scale + tint, 1.0);
+}
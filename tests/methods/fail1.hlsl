@@ -0,0 +1,9 @@
+// Light interface.
+interface ILight {
+    // Calculates light.
+    //
+    // @param normal Surface normal.
+    //
+    // @return Light color.
+    float3 CalculateLight(float3 normal);
+};
@@ -0,0 +1,9 @@
+// Material.
+struct Material {
+    // Diffuse color.
+    float3 diffuse;
+
+    float3 getDiffuse() {
+        return diffuse;
+    }
+};
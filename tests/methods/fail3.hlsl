@@ -0,0 +1,14 @@
+// Directional light.
+class DirectionalLight {
+    // Light color.
+    float3 color;
+
+    // Calculates light.
+    //
+    // @param normal Surface normal.
+    //
+    // @return Light color.
+    float3 calculate_light(float3 normal) {
+        return color;
+    }
+};
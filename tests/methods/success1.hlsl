@@ -0,0 +1,40 @@
+// Light interface.
+interface ILight {
+    // Calculates light.
+    //
+    // @param normal Surface normal.
+    //
+    // @return Light color.
+    float3 calculateLight(float3 normal);
+};
+
+// Directional light.
+class DirectionalLight : ILight {
+    // Light color.
+    float3 color;
+
+    // Calculates light.
+    //
+    // @param normal Surface normal.
+    //
+    // @return Light color.
+    float3 calculateLight(float3 normal) {
+        if (normal.x > 0) {
+            return color;
+        }
+        return color * 0.5;
+    }
+};
+
+// Material.
+struct Material {
+    // Diffuse color.
+    float3 diffuse;
+
+    // Returns diffuse color.
+    //
+    // @return Diffuse color.
+    float3 getDiffuse() {
+        return diffuse;
+    }
+};
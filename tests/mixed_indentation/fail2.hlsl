@@ -0,0 +1,3 @@
+void foo(float value) {
+  	value = 1.0;
+}
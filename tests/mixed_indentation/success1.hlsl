@@ -0,0 +1,6 @@
+/**
+ * Foo docs.
+ */
+void foo(float value) {
+    value = 1.0;
+}
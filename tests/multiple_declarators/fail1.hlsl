@@ -0,0 +1 @@
+float a, b_value;
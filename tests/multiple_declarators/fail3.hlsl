@@ -0,0 +1 @@
+float weights[3] = {0.2, 0.6, 0.2}, bad_name;
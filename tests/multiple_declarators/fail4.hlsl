@@ -0,0 +1,3 @@
+struct Foo {
+    float x, y_value;
+};
@@ -0,0 +1,3 @@
+void foo() {
+    int i = max(1, 2), other_value = 3;
+}
@@ -0,0 +1,13 @@
+float a, bValue, cValue;
+int i = 0, j = max(1, 2);
+float weights[3] = {0.2, 0.6, 0.2}, other;
+
+struct Foo {
+    float x, y, z;
+};
+
+void foo() {
+    for (int i = 0, j = 1; i < 10; i++) {
+        float first = 1.0, second = first * 2.0;
+    }
+}
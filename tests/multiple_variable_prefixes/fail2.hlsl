@@ -0,0 +1,3 @@
+void foo() {
+    bool enabled = true;
+}
@@ -0,0 +1 @@
+bool g_enabled;
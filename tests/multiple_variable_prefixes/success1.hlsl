@@ -0,0 +1,8 @@
+float g_time;
+static float s_scale;
+bool g_bEnabled;
+bool c_isVisible;
+
+void foo(bool bFlag) {
+    bool isEnabled = bFlag;
+}
@@ -0,0 +1,9 @@
+struct Light
+{
+    struct Attenuation
+    {
+        float constant_term;
+    } attenuation;
+
+    float color;
+};
@@ -0,0 +1,7 @@
+cbuffer Constants : register(b0)
+{
+    struct
+    {
+        float light_color;
+    } light;
+};
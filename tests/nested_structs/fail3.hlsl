@@ -0,0 +1,9 @@
+struct Light
+{
+    struct attenuation_info
+    {
+        float constantTerm;
+    } attenuation;
+
+    float color;
+};
@@ -0,0 +1,22 @@
+struct Light
+{
+    struct Attenuation
+    {
+        float constantTerm;
+    } attenuation;
+
+    struct
+    {
+        float range;
+    } bounds;
+
+    float color;
+};
+
+cbuffer Constants : register(b0)
+{
+    struct
+    {
+        float lightColor;
+    } light;
+};
@@ -0,0 +1,13 @@
+struct Light {
+    float intensity;
+};
+
+void foo(float value) {
+    float weights[2] = {
+        0.5, 0.5
+    };
+    if (value > 0.5)
+    {
+        value = 0.5;
+    }
+}
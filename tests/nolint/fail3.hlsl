@@ -0,0 +1 @@
+int WrOnG_cAsE1 = 1; // NOLINT(StructCase): wrong rule
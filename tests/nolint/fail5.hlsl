@@ -0,0 +1,3 @@
+// NOLINTBEGIN(StructCase)
+int WrOnG_cAsE1 = 1;
+// NOLINTEND
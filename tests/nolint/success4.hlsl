@@ -0,0 +1,5 @@
+int WrOnG_cAsE1 = 1; // NOLINT(StructCase, VariableCase): wrong case
+
+// NOLINTBEGIN(VariableCase)
+int WrOnG_cAsE2 = 1;
+// NOLINTEND
@@ -0,0 +1,18 @@
+struct Light {
+    float intensity;
+    bool enabled;
+};
+
+static const float scale = 2.0;
+Texture<float> diffuseTexture;
+Light lights[4];
+
+float getValue(float value, Light light) {
+    float result = value;
+    bool isBigger = value >  result;
+    return result;
+}
+
+// NOFORMATBEGIN
+float   kept;
+// NOFORMATEND
@@ -0,0 +1,13 @@
+#version 450
+
+layout(local_size_x = 8, local_size_y = 8, local_size_z = 1) in;
+layout(local_size_x = 4) in;
+layout(local_size_x = 64, local_size_z_id = 2) in; // comment
+layout(local_size_x = 64 /* x */) in;
+layout(std430, binding = 0) buffer Data {
+    float values[];
+} data;
+layout(location = 0) in vec2 uv;
+
+void main() {
+}
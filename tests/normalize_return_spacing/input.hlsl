@@ -0,0 +1,25 @@
+float getValue(float value, float scale) {
+    if (value > 0.5) {
+        return(value) ;
+    }
+
+    return    value * scale  ;
+}
+
+void foo() {
+    return ;
+}
+
+float bar(float value) {
+    return	-value;
+}
+
+float baz(float value) {
+    return /* comment */ value ;
+}
+
+// NOFORMATBEGIN
+float kept(float value) {
+    return(value) ;
+}
+// NOFORMATEND
@@ -0,0 +1,4 @@
+#version 450
+
+layout(location = 1) in float inAlpha;
+layout(location = 0) in float inDepth;
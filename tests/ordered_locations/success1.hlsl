@@ -0,0 +1,6 @@
+#version 450
+
+layout(location = 0) in float inDepth;
+layout(location = 0) out float outColor;
+layout(location = 2) in float inAlpha;
+layout(location = 1) out float outAlpha;
@@ -0,0 +1,6 @@
+float3 g_color;
+/// @param b Second value.
+/// @param a First value.
+float foo(float a, float b) {
+    return a + b;
+}
@@ -0,0 +1,7 @@
+float3 g_color;
+
+/// @param a First value.
+/// @param b Second value.
+float foo(float a, float b) {
+    return a + b;
+}
@@ -0,0 +1,3 @@
+/** @param b B. @param a A. */
+void foo(float a, float b) {
+}
@@ -0,0 +1,6 @@
+// NOFORMATBEGIN
+// @param b B.
+// @param a A.
+void foo(float a, float b) {
+}
+// NOFORMATEND
@@ -0,0 +1,7 @@
+struct Light {
+    // @param b B.
+    // @param a A.
+    // @param c C.
+    void foo(float a, float b) {
+    }
+};
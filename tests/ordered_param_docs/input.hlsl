@@ -0,0 +1,18 @@
+/**
+ * Foo.
+ *
+ * @param b Second value,
+ * continues here.
+ * @param a First value.
+ *
+ * @return Sum.
+ */
+float foo(float a, float b) {
+    return a + b;
+}
+
+// Bar.
+// @param[out] result Result.
+// @param value Value.
+void bar(float value, out float result) {
+}
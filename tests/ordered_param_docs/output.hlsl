@@ -0,0 +1,18 @@
+/**
+ * Foo.
+ *
+ * @param a First value.
+ * @param b Second value,
+ * continues here.
+ *
+ * @return Sum.
+ */
+float foo(float a, float b) {
+    return a + b;
+}
+
+// Bar.
+// @param value Value.
+// @param[out] result Result.
+void bar(float value, out float result) {
+}
@@ -0,0 +1,7 @@
+/** @param a A. @param b B. */
+void foo(float a, float b) {
+}
+
+// @param value Value.
+void bar(float value, float other) {
+}
@@ -0,0 +1,9 @@
+/**
+ * Computes the result.
+ *
+ * @param value Input value.
+ * @param result Output value.
+ */
+void foo(float value, out float result) {
+    result = value;
+}
@@ -0,0 +1,8 @@
+/**
+ * Scales the value.
+ *
+ * @param[out] value Value to scale.
+ */
+void scale(inout float value) {
+    value *= 2.0;
+}
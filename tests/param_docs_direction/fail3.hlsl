@@ -0,0 +1,10 @@
+struct Light {
+    /**
+     * Returns color of the light.
+     *
+     * @param[in] color Output color.
+     */
+    void getColor(out float color) {
+        color = 1.0;
+    }
+};
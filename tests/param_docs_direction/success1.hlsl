@@ -0,0 +1,18 @@
+/**
+ * Computes the result.
+ *
+ * @param[in] value Input value.
+ * @param scale Scale.
+ * @param[out] result Output value.
+ * @param[in,out] counter Counter to increment.
+ * @param[in, out] sum Sum to update.
+ */
+void foo(float value, float scale, out float result, inout float counter, inout float sum) {
+    result = value * scale;
+    counter += 1.0;
+    sum += result;
+}
+
+void bar(out float result) {
+    result = 1.0;
+}
@@ -0,0 +1,4 @@
+void foo(int iValue);
+}
+
+int BadName = 1;
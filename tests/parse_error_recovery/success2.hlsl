@@ -0,0 +1,11 @@
+struct Light {
+    float color;
+
+    Light operator+(Light other) {
+        Light result;
+        result.color = color + other.color;
+        return result;
+    }
+
+    float goodName;
+};
@@ -0,0 +1 @@
+int count = 0;
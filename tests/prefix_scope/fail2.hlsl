@@ -0,0 +1,4 @@
+cbuffer Settings
+{
+    float weight;
+};
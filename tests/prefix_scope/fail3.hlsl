@@ -0,0 +1,4 @@
+void foo()
+{
+    bool visible = true;
+}
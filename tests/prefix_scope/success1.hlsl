@@ -0,0 +1,20 @@
+int iCount = 0;
+static const int iMaxCount = 8;
+
+cbuffer Settings
+{
+    float fWeight;
+};
+
+struct Light
+{
+    int index;
+    float intensity;
+};
+
+void foo(int index, float weight)
+{
+    int localCount = index;
+    float value = weight;
+    bool bVisible = true;
+}
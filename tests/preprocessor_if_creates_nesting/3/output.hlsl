@@ -0,0 +1,17 @@
+#endif
+void foo() {
+    # ifndef FOO
+        int a = 1;
+        #  if BAR
+            int b = 2;
+        #elifdef BAZ
+            int b = 3;
+        #endif
+    #else
+        int a = 2;
+    #endif
+    /**
+    #if NOT_A_DIRECTIVE
+     */
+    int c = 3;
+}
@@ -0,0 +1,33 @@
+struct Light
+{
+    float3 color;;
+};
+
+cbuffer Constants : register(b0)
+{
+    float intensity;
+};
+
+static const float weights[3] = {0.25, 0.5, 0.25};
+
+float getWeight(int index)
+{
+    return weights[index];
+};
+
+float4 main(float4 position : SV_Position) : SV_Target
+{
+    float sum = 0.0;;
+    for (int i = 0; i < 3; i++)
+    {
+        sum += getWeight(i);
+    };
+    ;
+
+    for (;;)
+    {
+        break;
+    }
+
+    return float4(sum * intensity, 0.0, 0.0, 1.0);
+}
@@ -0,0 +1,44 @@
+void foo(int count)
+{
+    if (count > 0)
+    {
+        count = 0;
+    }
+    else if (count < 0)
+    {
+        count = 1; // negative
+    }
+    else
+    {
+        count = 2;
+    }
+    for (int i = 0; i < count; i++)
+    {
+        sum += i;
+    }
+    for (int i = 0; i < count; i++)
+    {
+        if (i > 0)
+        {
+            sum += i;
+        }
+    }
+    while (count > 0)
+    {
+        count--;
+    }
+    do
+    {
+        count++;
+    } while (count < 0);
+    if (count > 0)
+    {
+        count = 0;
+    }
+}
+
+// NOFORMATBEGIN
+void bar(int count) {
+    if (count > 0) count = 0;
+}
+// NOFORMATEND
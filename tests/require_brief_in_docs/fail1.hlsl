@@ -0,0 +1,5 @@
+/**
+ * @param value Input value.
+ */
+void foo(float value) {
+}
@@ -0,0 +1,4 @@
+// @return Value.
+float foo() {
+    return 1.0;
+}
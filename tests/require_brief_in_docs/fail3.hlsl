@@ -0,0 +1,4 @@
+/** @brief */
+struct Light {
+    float intensity;
+};
@@ -0,0 +1,5 @@
+struct Light {
+    /// @param value Input value.
+    void foo(float value) {
+    }
+};
@@ -0,0 +1,21 @@
+/**
+ * Does something.
+ *
+ * @param value Input value.
+ */
+void foo(float value) {
+}
+
+/// @brief Returns something.
+/// @return Value.
+float bar() {
+    return 1.0;
+}
+
+// Light source.
+struct Light {
+    float intensity;
+};
+
+void undocumented() {
+}
@@ -0,0 +1,9 @@
+float getValue(int index) {
+    switch (index) {
+    case 0:
+        return 1.0;
+    case 1:
+        return 2.0;
+    }
+    return 0.0;
+}
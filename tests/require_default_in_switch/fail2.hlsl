@@ -0,0 +1,14 @@
+float getValue(int index, int mode) {
+    switch (index) {
+    case 0: {
+        switch (mode) {
+        case 0:
+            return 0.5;
+        }
+        break;
+    }
+    default:
+        return 2.0;
+    }
+    return 0.0;
+}
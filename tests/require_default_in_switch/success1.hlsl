@@ -0,0 +1,20 @@
+float getValue(int index, int mode) {
+    float result = 0.0;
+    switch (getIndex(index, mode)) {
+    case 0: {
+        switch (mode) {
+        case 0:
+            result = 0.5;
+            break;
+        default:
+            result = 1.0;
+            break;
+        }
+        break;
+    }
+    default:
+        result = 2.0;
+        break;
+    }
+    return result;
+}
@@ -0,0 +1,15 @@
+#version 300 es
+
+in highp vec2 v_uv;
+
+precision mediump float;
+
+out vec4 fragColor;
+
+void main() {
+    if (v_uv.x < 0.0) {
+        discard;
+    }
+
+    fragColor = vec4(v_uv, 0.0, 1.0);
+}
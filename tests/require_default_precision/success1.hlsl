@@ -0,0 +1,17 @@
+#version 100
+
+// Default precision.
+precision mediump float;
+precision lowp sampler2D;
+
+varying highp vec2 v_uv;
+uniform sampler2D u_texture;
+
+lowp vec4 sampleColor(in highp vec2 uv) {
+    return texture2D(u_texture, uv);
+}
+
+void main() {
+    mediump float alpha = 1.0;
+    gl_FragColor = sampleColor(v_uv) * alpha;
+}
@@ -0,0 +1,9 @@
+#version 100
+
+attribute vec3 a_position;
+varying highp vec2 v_uv;
+
+void main() {
+    v_uv = a_position.xy;
+    gl_Position = vec4(a_position, 1.0);
+}
@@ -0,0 +1,8 @@
+/**
+ * ライトの強さを計算します。
+ *
+ * @return 減衰係数。
+ */
+float attenuation(float distance) {
+    return 1.0 / (distance * distance);
+}
@@ -0,0 +1,11 @@
+/**
+ * ライトの強さを計算します。
+ *
+ * @param distance ライトまでの距離。
+ * @param intensity 光の強さ。
+ *
+ * @return 減衰係数。
+ */
+float attenuation(float distance, float intensity) {
+    return intensity / (distance * distance);
+}
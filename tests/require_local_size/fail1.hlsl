@@ -0,0 +1,11 @@
+#version 450
+
+layout(std430, binding = 0) buffer Data
+{
+    float values[];
+} data;
+
+void main()
+{
+    data.values[gl_GlobalInvocationID.x] = 0.0;
+}
@@ -0,0 +1,13 @@
+#version 450
+
+layout(local_size_x = 64) in;
+
+layout(std430, binding = 0) buffer Data
+{
+    float values[];
+} data;
+
+void main()
+{
+    data.values[gl_GlobalInvocationID.x] = 0.0;
+}
@@ -0,0 +1,7 @@
+RWStructuredBuffer<float> values : register(u0);
+
+[numthreads(64, 1, 1)]
+void main(uint id : SV_DispatchThreadID)
+{
+    values[id] = 0.0;
+}
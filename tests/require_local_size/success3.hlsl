@@ -0,0 +1,4 @@
+uint getInvocationIndex()
+{
+    return gl_GlobalInvocationID.x;
+}
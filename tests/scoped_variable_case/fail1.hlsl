@@ -0,0 +1 @@
+float globalValue = 1.0;
@@ -0,0 +1,3 @@
+void foo(float InputValue)
+{
+}
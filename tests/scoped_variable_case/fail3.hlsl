@@ -0,0 +1,4 @@
+void foo()
+{
+    float LocalValue = 1.0;
+}
@@ -0,0 +1 @@
+static const int MaxCount = 8;
@@ -0,0 +1,4 @@
+void foo()
+{
+    static int callCount = 0;
+}
@@ -0,0 +1,14 @@
+static const int MAX_COUNT = 8;
+float GlobalValue = 1.0;
+
+struct Light
+{
+    float intensity;
+};
+
+void foo(float inputValue)
+{
+    static int CALL_COUNT = 0;
+    float localValue = inputValue;
+    Light light;
+}
@@ -0,0 +1,46 @@
+struct ControlPoint {
+    float3 position : POSITION;
+};
+
+struct PatchConstants {
+    float edges[3] : SV_TessFactor;
+    float inside : SV_InsideTessFactor;
+};
+
+// Calculates tessellation factors.
+//
+// @param patch   Input patch.
+// @param patchId ID of the patch.
+//
+// @return Tessellation factors.
+PatchConstants calculateConstants(InputPatch<ControlPoint, 3> patch, uint patchId : SV_PrimitiveID) {
+    PatchConstants output;
+    return output;
+}
+
+// Hull shader.
+//
+// @param patch   Input patch.
+// @param pointId ID of the control point.
+//
+// @return Control point.
+[domain("tri")]
+[partitioning("fractional_odd")]
+[outputtopology("triangle_cw")]
+[outputcontrolpoints(3)]
+[patchconstantfunc("calculateConstants")]
+ControlPoint HullMain(InputPatch<ControlPoint, 3> patch, uint pointId : SV_OutputControlPointID) {
+    return patch[pointId];
+}
+
+// Domain shader.
+//
+// @param constants Tessellation factors.
+// @param location  Location in the patch.
+// @param patch     Output patch of the hull shader.
+//
+// @return Position.
+[domain("tri")]
+float4 domainMain(PatchConstants constants, float3 location : SV_DomainLocation, const OutputPatch<ControlPoint, 3> patch) : SV_Position {
+    return float4(patch[0].position, 1.0);
+}
@@ -0,0 +1,10 @@
+RWTexture2D<float4> output;
+
+/**
+ * Clears the output texture.
+ *
+ * @param threadId ID of the thread.
+ */
+void CSMain(uint3 threadId : SV_DispatchThreadID) {
+    output[threadId.xy] = float4(0.0, 0.0, 0.0, 1.0);
+}
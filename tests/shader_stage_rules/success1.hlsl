@@ -0,0 +1,11 @@
+RWTexture2D<float4> output;
+
+/**
+ * Clears the output texture.
+ *
+ * @param threadId ID of the thread.
+ */
+[numthreads(8, 8, 1)]
+void CSMain(uint3 threadId : SV_DispatchThreadID) {
+    output[threadId.xy] = float4(0.0, 0.0, 0.0, 1.0);
+}
@@ -0,0 +1,3 @@
+float4 PSMain(float4 position : SV_Position) : SV_Target {
+    return float4(1.0, 1.0, 1.0, 1.0);
+}
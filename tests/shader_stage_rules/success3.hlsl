@@ -0,0 +1,10 @@
+float4 VSMain(uint vertexId : SV_VertexID) : SV_Position {
+    return float4(0.0, 0.0, 0.0, 1.0);
+}
+
+float4 PSMain(float4 position : SV_Position) : SV_Target {
+    return float4(1.0, 1.0, 1.0, 1.0);
+}
+
+void helper(uint3 id) {
+}
@@ -0,0 +1,4 @@
+float getValue(float value) {
+    float value = 1.0;
+    return value;
+}
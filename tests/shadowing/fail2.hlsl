@@ -0,0 +1,8 @@
+float getValue(float input) {
+    float result = input;
+    if (input > 0.0) {
+        float result = 2.0;
+        return result;
+    }
+    return result;
+}
@@ -0,0 +1,6 @@
+static const float scale = 2.0;
+
+float getValue(float input) {
+    float scale = 3.0;
+    return input * scale;
+}
@@ -0,0 +1,9 @@
+float getValue(float input) {
+    float result = input;
+    for (int i = 0; i < 4; i++) {
+        for (int i = 0; i < 4; i++) {
+            result += input;
+        }
+    }
+    return result;
+}
@@ -0,0 +1,28 @@
+static const float scale = 2.0;
+
+float getValue(float input) {
+    float result = input * scale;
+
+    for (int i = 0; i < 4; i++) {
+        float value = result;
+        result += value;
+    }
+
+    for (int i = 0; i < 4; i++) {
+        float value = result;
+        result -= value;
+    }
+
+    for (int j = 0; j < 4; j++)
+        result += input;
+
+    for (int j = 0; j < 4; j++)
+        result += input;
+
+    return result;
+}
+
+float getOtherValue(float input) {
+    float result = input;
+    return result;
+}
@@ -0,0 +1,18 @@
+in flat int id;
+out flat noperspective float depth;
+centroid in smooth float weight;
+const static float scale = 2.0;
+precise static float value;
+uniform highp float time;
+mediump const float ratio = 1.0;
+layout(location = 0) in flat int index;
+invariant out float position;
+groupshared const float shared_values[64];
+
+void foo(const in float a, inout float b) {
+    const float c = a;
+}
+
+// NOFORMATBEGIN
+in flat int kept;
+// NOFORMATEND
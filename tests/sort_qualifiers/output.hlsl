@@ -0,0 +1,18 @@
+flat in int id;
+flat noperspective out float depth;
+smooth centroid in float weight;
+static const float scale = 2.0;
+static precise float value;
+uniform highp float time;
+const mediump float ratio = 1.0;
+layout(location = 0) flat in int index;
+invariant out float position;
+groupshared const float shared_values[64];
+
+void foo(in const float a, inout float b) {
+    const float c = a;
+}
+
+// NOFORMATBEGIN
+in flat int kept;
+// NOFORMATEND
@@ -0,0 +1,20 @@
+/** Base struct. */
+struct Base {
+    float3 position;
+};
+
+/** Derived struct. */
+struct Derived: Base {
+    float3 normal;
+};
+
+/** Light. */
+class PointLight: ILight, IShadowCaster {
+    float3 color;
+};
+
+// NOFORMATBEGIN
+struct Other:Base {
+    float value;
+};
+// NOFORMATEND
@@ -0,0 +1,11 @@
+Texture2D< float4 > gAlbedo : register(t0);
+RWStructuredBuffer< Light > gLights : register(u0);
+Buffer< vector< float, 4 > > gColors;
+
+template< typename T >
+T foo(T value, T other) {
+    if (value < other && other > 0) {
+        return static_cast< T >(value);
+    }
+    return other;
+}
@@ -0,0 +1,26 @@
+void main() {
+    [unroll] for (uint i = 0; i < count; i++) {
+        foo();
+    }
+
+    [branch] if (a) {
+        foo();
+    }
+    else [branch] if (b) {
+        foo();
+    }
+
+    [loop] // keep this comment
+    while (a) {
+        foo();
+    }
+
+    [unroll(4)] for (uint i = 0; i < count; i++) foo();
+
+    switch (a) {
+        [forcecase] case 0: break;
+    }
+
+    values[loop] = 1;
+    [flatten] if (a) foo();
+}
@@ -0,0 +1 @@
+Texture2D<float4> albedo_texture : register(t0);
@@ -0,0 +1 @@
+Buffer<vector<float, 4>> light_colors;
@@ -0,0 +1,4 @@
+template<typename T>
+T foo(T some_value) {
+    return some_value;
+}
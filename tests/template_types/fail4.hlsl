@@ -0,0 +1,3 @@
+float4 foo(StructuredBuffer<Light> light_buffer) {
+    return float4(1.0, 1.0, 1.0, 1.0);
+}
@@ -0,0 +1,11 @@
+Texture2D<float4> albedoTexture : register(t0);
+RWStructuredBuffer<Light> lights : register(u0);
+Buffer<vector<float, 4>> lightColors;
+
+template<typename T>
+T foo(T someValue, StructuredBuffer<Light> lightBuffer) {
+    if (someValue < 1 && someValue > 0) {
+        return static_cast<T>(someValue);
+    }
+    return someValue;
+}
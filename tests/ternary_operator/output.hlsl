@@ -0,0 +1,16 @@
+float4 main(float4 position : SV_Position) : SV_Target {
+    float scale = isEnabled ? 1.0 : 0.0;
+    float shift = (isEnabled ? position.x : position.y) * 2;
+    float3 color = isEnabled
+        ? computeLighting(position)
+        : computeAmbient(position);
+    float bias = isEnabled
+        ? 0.5
+        : Constants::defaultBias;
+
+    switch (index) {
+        case 1: return position;
+    }
+
+    return float4(color * scale, shift + bias);
+}
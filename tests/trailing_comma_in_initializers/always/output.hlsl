@@ -0,0 +1,23 @@
+static const float weights[3] = {first, second, third,};
+static const float offsets[2] = {first, second,};
+static const float values[2][2] = {{first, second,}, {third, fourth,},};
+static const float multiline[3] = {
+    first,
+    second,
+    third, // last
+};
+static const float empty[0] = {};
+
+struct Light {
+    float intensity;
+};
+
+void foo(float value) {
+    if (value > first) {
+        value = second;
+    }
+}
+
+// NOFORMATBEGIN
+static const float kept[2] = {first, second};
+// NOFORMATEND
@@ -0,0 +1,15 @@
+float4 main(float4 position : SV_Position) : SV_Target
+{
+    bool isVisible = ! isHidden;
+    float offset = - position.x - position.y;
+    float delta = offset - - position.z;
+    int mask = index-- - ~ flags;
+    mask -= - 1;
+
+    if (! (isVisible != isHidden))
+    {
+        return - position;
+    }
+
+    return float4(- offset, + delta, 1e-5, -(offset));
+}
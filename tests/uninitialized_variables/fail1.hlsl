@@ -0,0 +1,4 @@
+float getValue(float input) {
+    float result;
+    return result + input;
+}
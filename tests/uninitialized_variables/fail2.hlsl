@@ -0,0 +1,8 @@
+float getValue(float input) {
+    float result;
+    if (input > 0.0) {
+        input = result;
+    }
+    result = input;
+    return result;
+}
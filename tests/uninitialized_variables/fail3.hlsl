@@ -0,0 +1,5 @@
+float getValue(float input) {
+    float values[2];
+    float3 color = float3(values, 0.0);
+    return input;
+}
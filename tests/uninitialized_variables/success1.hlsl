@@ -0,0 +1,33 @@
+struct Data {
+    float value;
+};
+
+float getValue(Texture2D tex, float input) {
+    float result;
+    result = input;
+
+    float values[2] = {1.0, 2.0};
+    result += values[0];
+
+    float scale;
+    if (input > 0.0) {
+        scale = 2.0;
+    } else {
+        scale = 1.0;
+    }
+    result *= scale;
+
+    uint width, height;
+    tex.GetDimensions(width, height);
+    result += width + height;
+
+    Data data;
+    data.value = result;
+
+    {
+        float temp;
+    }
+    float temp = 1.0;
+
+    return data.value + temp;
+}
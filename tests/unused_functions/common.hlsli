@@ -0,0 +1,17 @@
+#define SQUARE(x) square(x)
+
+float square(float value) {
+    return value * value;
+}
+
+float cube(float value) {
+    return value * value * value;
+}
+
+float getLength(float2 value) {
+    return sqrt(value.x * value.x + value.y * value.y);
+}
+
+float unusedHelper(float value) {
+    return value;
+}
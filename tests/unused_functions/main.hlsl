@@ -0,0 +1,9 @@
+#include "common.hlsli"
+
+float getValue(float value) {
+    return SQUARE(value) + cube(value);
+}
+
+float4 main(float4 position : SV_Position) : SV_Target {
+    return float4(getValue(position.x), getLength(position.xy), 0.0, 1.0);
+}
@@ -0,0 +1,4 @@
+float getValue(float input) {
+    float unused = 1.0;
+    return input;
+}
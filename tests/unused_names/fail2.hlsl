@@ -0,0 +1,5 @@
+float getValue(float input) {
+    float result;
+    result = input * 2.0;
+    return input;
+}
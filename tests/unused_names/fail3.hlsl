@@ -0,0 +1,3 @@
+float getValue(float input, float scale) {
+    return input;
+}
@@ -0,0 +1,29 @@
+struct Data {
+    float value;
+};
+
+// Declaration without a body.
+float getValue(float input, float scale);
+
+float getValue(float input, float scale) {
+    float result = input;
+    result *= scale;
+
+    Data data;
+    data.value = result;
+
+    for (int i = 0; i < 4; i++) {
+        float value = data.value;
+        result += value;
+    }
+
+    return result;
+}
+
+void setValue(out float output, float input) {
+    output = input;
+}
+
+[numthreads(8, 8, 1)]
+void main(uint3 id : SV_DispatchThreadID) {
+}
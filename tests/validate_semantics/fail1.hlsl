@@ -0,0 +1,4 @@
+struct PixelOutput
+{
+    float depth : SV_DEPT;
+};
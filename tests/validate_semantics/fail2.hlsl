@@ -0,0 +1,4 @@
+float main(uint id : SV_VertexIDD) : SV_Position
+{
+    return id;
+}
@@ -0,0 +1,4 @@
+float main(uint id : SV_VertexID) : SV_POSITON
+{
+    return id;
+}
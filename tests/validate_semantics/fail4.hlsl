@@ -0,0 +1,5 @@
+struct VertexOutput
+{
+    float position : SV_Position;
+    float uv : TEXCORD0;
+};
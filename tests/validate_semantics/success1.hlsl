@@ -0,0 +1,19 @@
+Texture2D colorTexture : register(t0);
+
+cbuffer Constants : register(b0)
+{
+    float scale : packoffset(c0);
+};
+
+struct VertexOutput
+{
+    float position : SV_POSITION;
+    float uv : TEXCOORD0;
+    float tint : Color;
+    float weight;
+};
+
+float main(VertexOutput input, bool isFrontFace : SV_IsFrontFace) : SV_Target0
+{
+    return input.weight;
+}
@@ -0,0 +1,2 @@
+uniform vec4 color;
+#version 450
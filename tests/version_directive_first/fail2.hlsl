@@ -0,0 +1,5 @@
+#version 450
+
+uniform vec4 color;
+
+#extension GL_EXT_nonuniform_qualifier : enable
@@ -0,0 +1,2 @@
+#extension GL_EXT_nonuniform_qualifier : enable
+#version 450
@@ -0,0 +1,7 @@
+// Some comment.
+#version 450
+// Extensions.
+#extension GL_EXT_nonuniform_qualifier : enable
+#extension GL_EXT_scalar_block_layout : enable
+
+uniform vec4 color;
@@ -0,0 +1,22 @@
+#version 450
+#extension GL_EXT_nonuniform_qualifier : enable
+
+layout(set = 0, binding = 1) uniform sampler2D textures[];
+layout(set = 1, binding = 0) uniform CameraData {
+    mat4x4 viewProjection;
+} camera;
+layout(push_constant) uniform PushConstants {
+    uint textureIndex;
+} constants;
+layout(constant_id = 0) const int sampleCount = 4;
+layout(location = 0) in vec2 inUv;
+layout(location = 0) out vec4 outColor;
+
+out gl_PerVertex {
+    vec4 gl_Position;
+};
+
+void main() {
+    uint texture_index = nonuniformEXT(constants.textureIndex);
+    outColor = texture(textures[nonuniformEXT(texture_index)], inUv) * float(gl_FragCoord.x);
+}